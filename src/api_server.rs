@@ -0,0 +1,256 @@
+use anyhow::Result;
+use clap::Parser;
+use log::{info, warn};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+
+use crate::create_token::{TokenCreator, LAMPORTS_PER_SOL};
+use crate::parser::{vanity_pool_summary, Args, CreateArgs};
+use crate::read_only::{ensure_writable, handle_admin_request, is_read_only};
+use crate::secret_backends::hex_encode;
+use crate::secure_credentials::SecureApiKey;
+use crate::triggers::{read_request, respond, HttpRequest, LaunchDraft};
+use crate::vanity_address::VanityConfig;
+use crate::wallets::WalletSpec;
+
+/// Secret holding the key API clients must send, or comma-separated `operator:key` entries
+pub const API_KEY_VAR: &str = "LAUNCHER_API_KEY";
+pub const API_KEY_HEADER: &str = "x-api-key";
+/// Launches remembered for `GET /tokens/<id>`; the oldest are forgotten first
+const MAX_TRACKED_LAUNCHES: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LaunchState {
+    Queued,
+    Running,
+    Confirmed,
+    /// Ended without a confirmed launch, e.g. in `DRY_RUN`
+    Finished,
+    Failed,
+}
+
+/// A launch requested through `POST /tokens`
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiLaunch {
+    pub id: u64,
+    pub status: LaunchState,
+    pub operator: String,
+    pub mint: Option<String>,
+    pub signature: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Launch parameters waiting for the launch loop
+pub struct QueuedLaunch {
+    pub id: u64,
+    pub create: CreateArgs,
+}
+
+/// State of API launches, shared by the server and the loop running them
+#[derive(Clone, Default)]
+pub struct ApiLaunches {
+    launches: Arc<Mutex<BTreeMap<u64, ApiLaunch>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl ApiLaunches {
+    fn add(&self, operator: &str) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let mut launches = self.launches.lock().unwrap();
+        launches.insert(id, ApiLaunch { id, status: LaunchState::Queued, operator: operator.to_string(), mint: None, signature: None, error: None });
+        while launches.len() > MAX_TRACKED_LAUNCHES {
+            launches.pop_first();
+        }
+        id
+    }
+
+    pub fn update(&self, id: u64, update: impl FnOnce(&mut ApiLaunch)) {
+        if let Some(launch) = self.launches.lock().unwrap().get_mut(&id) {
+            update(launch);
+        }
+    }
+
+    pub fn get(&self, id: u64) -> Option<ApiLaunch> {
+        self.launches.lock().unwrap().get(&id).cloned()
+    }
+}
+
+/// Who sent an API request, as told by the key they used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiCaller {
+    /// Name of the key's `operator:key` entry, or `api-<key_id>` for an unnamed key
+    pub operator: String,
+    /// First bytes of the key's SHA-256, safe to log
+    pub key_id: String,
+}
+
+/// Accept one of the `keys` in `X-Api-Key` or as a bearer token
+pub fn check_api_key(keys: &str, headers: &HashMap<String, String>) -> Result<ApiCaller> {
+    let provided = headers.get(API_KEY_HEADER).map(String::as_str)
+        .or_else(|| headers.get("authorization").and_then(|value| value.strip_prefix("Bearer ")))
+        .ok_or_else(|| anyhow::anyhow!("Missing API key"))?;
+    // Comparing digests keeps the comparison time independent of how much of the key matched
+    let digest = Sha256::digest(provided.trim().as_bytes());
+    let key_id = hex_encode(&digest[..4]);
+    keys.split(',').map(str::trim).filter(|entry| !entry.is_empty()).find_map(|entry| {
+        let (operator, key) = match entry.split_once(':') {
+            Some((operator, key)) => (operator.trim().to_string(), key.trim()),
+            None => (format!("api-{}", key_id), entry),
+        };
+        (Sha256::digest(key.as_bytes()) == digest).then(|| ApiCaller { operator, key_id: key_id.clone() })
+    }).ok_or_else(|| anyhow::anyhow!("Invalid API key"))
+}
+
+/// Launch arguments for a draft sent by an API client, which must not reach files on this host
+pub fn draft_to_create_args(draft: &LaunchDraft) -> Result<CreateArgs> {
+    if draft.image.is_some() {
+        return Err(anyhow::anyhow!("Local image paths are not accepted over the API; use image_url"));
+    }
+    if let Some(wallet) = &draft.wallet {
+        if let WalletSpec::File(_) = wallet.parse()? {
+            return Err(anyhow::anyhow!("Only registered wallet names are accepted over the API"));
+        }
+    }
+    let argv = std::iter::once("pumpfun-launcher".to_string()).chain(draft.to_args());
+    Args::try_parse_from(argv).map(|args| args.create).map_err(|e| anyhow::anyhow!("{}", e.to_string().trim()))
+}
+
+struct ApiServer {
+    api_key: SecureApiKey,
+    launches: ApiLaunches,
+    queue: mpsc::Sender<QueuedLaunch>,
+}
+
+impl ApiServer {
+    async fn handle(&self, request: HttpRequest) -> (&'static str, serde_json::Value) {
+        let (path, query) = request.path.split_once('?').unwrap_or((request.path.as_str(), ""));
+        match path {
+            "/healthz" => return ("200 OK", serde_json::json!({ "status": "ok", "read_only": is_read_only() })),
+            "/admin/read-only" => return handle_admin_request(&request.method, &request.headers, &request.body),
+            _ => {}
+        }
+        let caller = match check_api_key(self.api_key.expose_secret(), &request.headers) {
+            Ok(caller) => caller,
+            Err(e) => return ("401 Unauthorized", serde_json::json!({ "message": e.to_string() })),
+        };
+        match (request.method.as_str(), path) {
+            ("POST", "/tokens") => self.create_token(&request.body, caller).await,
+            ("GET", "/vanity/status") => match vanity_pool_summary(&VanityConfig::from_env()) {
+                Ok(summary) => ("200 OK", summary),
+                Err(e) => ("500 Internal Server Error", serde_json::json!({ "message": e.to_string() })),
+            },
+            ("GET", "/wallet/balance") => self.wallet_balance(query).await,
+            ("GET", path) if path.starts_with("/tokens/") => {
+                match path["/tokens/".len()..].parse().ok().and_then(|id| self.launches.get(id)) {
+                    Some(launch) => ("200 OK", serde_json::json!(launch)),
+                    None => ("404 Not Found", serde_json::json!({ "message": "Unknown launch" })),
+                }
+            }
+            (_, "/tokens" | "/vanity/status" | "/wallet/balance") => ("405 Method Not Allowed", serde_json::json!({ "message": "Method not allowed" })),
+            _ => ("404 Not Found", serde_json::json!({ "message": "Unknown path" })),
+        }
+    }
+
+    /// Queue a launch of the draft in the body, attributed to `caller`; poll `GET /tokens/<id>` for the result
+    async fn create_token(&self, body: &[u8], caller: ApiCaller) -> (&'static str, serde_json::Value) {
+        if let Err(e) = ensure_writable("queue launches") {
+            return ("503 Service Unavailable", serde_json::json!({ "message": e.to_string() }));
+        }
+        let draft: LaunchDraft = match serde_json::from_slice(body) {
+            Ok(draft) => draft,
+            Err(e) => return ("400 Bad Request", serde_json::json!({ "message": format!("Body is not a launch draft: {}", e) })),
+        };
+        let mut create = match draft_to_create_args(&draft) {
+            Ok(create) => create,
+            Err(e) => return ("422 Unprocessable Entity", serde_json::json!({ "message": e.to_string() })),
+        };
+        let id = self.launches.add(&caller.operator);
+        info!("API launch {} queued by {} (key {})", id, caller.operator, caller.key_id);
+        create.operator = Some(caller.operator);
+        create.api_key_id = Some(caller.key_id);
+        if self.queue.send(QueuedLaunch { id, create }).await.is_err() {
+            self.launches.update(id, |launch| launch.status = LaunchState::Failed);
+            return ("503 Service Unavailable", serde_json::json!({ "message": "Launch queue closed" }));
+        }
+        ("202 Accepted", serde_json::json!(self.launches.get(id)))
+    }
+
+    /// Balance of `?wallet=<name>`, or of the `PRIVATE_KEY` payer
+    async fn wallet_balance(&self, query: &str) -> (&'static str, serde_json::Value) {
+        let wallet = url::form_urlencoded::parse(query.as_bytes()).find(|(key, _)| key == "wallet").map(|(_, value)| value.into_owned());
+        let wallet: Option<WalletSpec> = match wallet.map(|w| w.parse()).transpose() {
+            Ok(Some(WalletSpec::File(_))) => return ("400 Bad Request", serde_json::json!({ "message": "Only registered wallet names are accepted" })),
+            Ok(wallet) => wallet,
+            Err(e) => return ("400 Bad Request", serde_json::json!({ "message": e.to_string() })),
+        };
+        let creator = match TokenCreator::with_wallet(wallet.as_ref()) {
+            Ok(creator) => creator,
+            Err(e) => return ("400 Bad Request", serde_json::json!({ "message": e.to_string() })),
+        };
+        match creator.payer_balance().await {
+            Ok(lamports) => ("200 OK", serde_json::json!({
+                "wallet": creator.get_wallet_address().to_string(),
+                "balance_lamports": lamports,
+                "balance_sol": lamports as f64 / LAMPORTS_PER_SOL,
+            })),
+            Err(e) => ("502 Bad Gateway", serde_json::json!({ "message": format!("RPC error: {}", e) })),
+        }
+    }
+}
+
+/// Serve the launcher API on `listen_addr`; accepted launches go to `queue` and are tracked in `launches`
+pub async fn serve_api(listen_addr: &str, api_key: SecureApiKey, launches: ApiLaunches, queue: mpsc::Sender<QueuedLaunch>) -> Result<()> {
+    let listener = TcpListener::bind(listen_addr).await?;
+    info!("Serving the launcher API on {}", listen_addr);
+    let server = Arc::new(ApiServer { api_key, launches, queue });
+    loop {
+        let (mut stream, peer) = listener.accept().await?;
+        let server = server.clone();
+        tokio::spawn(async move {
+            let (status, body) = match read_request(&mut stream).await {
+                Ok(request) => server.handle(request).await,
+                Err(e) => ("400 Bad Request", serde_json::json!({ "message": e.to_string() })),
+            };
+            if !status.starts_with('2') {
+                warn!("{} from {}: {}", status, peer, body["message"]);
+            }
+            respond(&mut stream, status, &body).await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_key_and_draft_checks() {
+        let headers = |name: &str, value: &str| HashMap::from([(name.to_string(), value.to_string())]);
+        assert!(check_api_key("k3y-for-tests", &headers(API_KEY_HEADER, "k3y-for-tests")).is_ok());
+        assert!(check_api_key("k3y-for-tests", &headers("authorization", "Bearer k3y-for-tests")).is_ok());
+        assert!(check_api_key("k3y-for-tests", &headers(API_KEY_HEADER, "k3y-for-test")).is_err());
+        assert!(check_api_key("k3y-for-tests", &HashMap::new()).is_err());
+        let caller = check_api_key("alice:k3y-a, bob:k3y-b", &headers(API_KEY_HEADER, "k3y-b")).unwrap();
+        assert_eq!(caller.operator, "bob");
+        assert_eq!(caller.key_id.len(), 8);
+        assert!(check_api_key("k3y-for-tests", &headers(API_KEY_HEADER, "k3y-for-tests")).unwrap().operator.starts_with("api-"));
+        assert!(check_api_key("alice:k3y-a", &headers(API_KEY_HEADER, "alice:k3y-a")).is_err());
+
+        let draft = LaunchDraft { symbol: Some("MCAT".to_string()), name: Some("Moon Cat".to_string()), ..Default::default() };
+        assert_eq!(draft_to_create_args(&draft).unwrap().symbol.as_deref(), Some("MCAT"));
+        assert!(draft_to_create_args(&LaunchDraft { image: Some("/etc/passwd".to_string()), ..draft.clone() }).is_err());
+        assert!(draft_to_create_args(&LaunchDraft { wallet: Some("keys/dev.json".to_string()), ..draft.clone() }).is_err());
+        assert!(draft_to_create_args(&LaunchDraft { wallet: Some("dev".to_string()), ..draft }).is_ok());
+
+        let launches = ApiLaunches::default();
+        let id = launches.add("alice");
+        launches.update(id, |launch| launch.status = LaunchState::Running);
+        assert_eq!(launches.get(id).unwrap().status, LaunchState::Running);
+    }
+}
@@ -0,0 +1,198 @@
+use anyhow::Result;
+use log::{info, warn};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::launch_dir::LaunchDir;
+use crate::launch_export::google_access_token;
+use crate::secret_backends::{amz_dates, aws_credentials, hex_encode, sign_v4, SigV4Request};
+
+const GCS_SCOPE: &str = "https://www.googleapis.com/auth/devstorage.read_write";
+const GCS_UPLOAD_URL: &str = "https://storage.googleapis.com/upload/storage/v1/b";
+
+/// Bucket that launch directories are copied to
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArtifactStore {
+    /// Signed with the AWS credentials the secrets backend uses; `endpoint` selects an
+    /// S3-compatible service (path-style requests)
+    S3 { bucket: String, prefix: String, region: String, endpoint: Option<String> },
+    /// Written as the Google service account in `credentials_path`
+    Gcs { bucket: String, prefix: String, credentials_path: String },
+}
+
+impl ArtifactStore {
+    /// `s3://bucket/prefix` or `gs://bucket/prefix` from `LAUNCH_ARTIFACTS_BUCKET`; S3 takes its region
+    /// from `AWS_REGION` and an optional `LAUNCH_ARTIFACTS_S3_ENDPOINT`, GCS its key from
+    /// `GOOGLE_SERVICE_ACCOUNT_FILE`. `None` when no bucket is configured.
+    pub fn from_env() -> Result<Option<Self>> {
+        let Ok(location) = std::env::var("LAUNCH_ARTIFACTS_BUCKET") else {
+            return Ok(None);
+        };
+        let (scheme, rest) = location.trim().split_once("://")
+            .ok_or_else(|| anyhow::anyhow!("LAUNCH_ARTIFACTS_BUCKET must be s3://<bucket>[/prefix] or gs://<bucket>[/prefix]"))?;
+        let (bucket, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+        if bucket.is_empty() {
+            return Err(anyhow::anyhow!("LAUNCH_ARTIFACTS_BUCKET names no bucket"));
+        }
+        let (bucket, prefix) = (bucket.to_string(), prefix.trim_matches('/').to_string());
+        match scheme {
+            "s3" => Ok(Some(Self::S3 {
+                bucket,
+                prefix,
+                region: std::env::var("AWS_REGION").or_else(|_| std::env::var("AWS_DEFAULT_REGION"))
+                    .map_err(|_| anyhow::anyhow!("Set AWS_REGION for S3 artifact uploads"))?,
+                endpoint: std::env::var("LAUNCH_ARTIFACTS_S3_ENDPOINT").ok().map(|url| url.trim_end_matches('/').to_string()),
+            })),
+            "gs" => Ok(Some(Self::Gcs {
+                bucket,
+                prefix,
+                credentials_path: std::env::var("GOOGLE_SERVICE_ACCOUNT_FILE")
+                    .map_err(|_| anyhow::anyhow!("Set GOOGLE_SERVICE_ACCOUNT_FILE for GCS artifact uploads"))?,
+            })),
+            other => Err(anyhow::anyhow!("Unsupported artifact store '{}' (expected s3 or gs)", other)),
+        }
+    }
+
+    /// Object key of `file` in launch directory `dir`: `<prefix>/<dir name>/<file name>`
+    pub fn object_key(&self, dir: &LaunchDir, file: &Path) -> String {
+        let prefix = match self {
+            Self::S3 { prefix, .. } | Self::Gcs { prefix, .. } => prefix,
+        };
+        let name = |path: &Path| path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        [prefix.clone(), name(dir.path()), name(file)].into_iter().filter(|part| !part.is_empty()).collect::<Vec<_>>().join("/")
+    }
+
+    /// Copy every file of `dir` to the bucket. Failures are logged, never returned: the launch already happened.
+    pub async fn upload_launch_dir(&self, dir: &LaunchDir) {
+        let files = match std::fs::read_dir(dir.path()) {
+            Ok(entries) => entries.filter_map(|entry| entry.ok().map(|e| e.path())).filter(|path| path.is_file()).collect::<Vec<_>>(),
+            Err(e) => {
+                warn!("Cannot read launch directory {}: {}", dir.path().display(), e);
+                return;
+            }
+        };
+        let mut uploaded = 0;
+        for file in &files {
+            if self.upload_file(dir, file).await {
+                uploaded += 1;
+            }
+        }
+        info!("Uploaded {} of {} launch artifacts from {}", uploaded, files.len(), dir.path().display());
+    }
+
+    /// Copy one file of `dir`, e.g. a report written after the launch; returns whether it was stored
+    pub async fn upload_file(&self, dir: &LaunchDir, file: &Path) -> bool {
+        let key = self.object_key(dir, file);
+        let result = async {
+            let body = std::fs::read(file)?;
+            let client = reqwest::Client::builder().timeout(Duration::from_secs(30)).build()?;
+            match self {
+                Self::S3 { bucket, region, endpoint, .. } => put_s3_object(&client, bucket, region, endpoint.as_deref(), &key, body).await,
+                Self::Gcs { bucket, credentials_path, .. } => put_gcs_object(&client, bucket, credentials_path, &key, body).await,
+            }
+        }.await;
+        if let Err(e) = &result {
+            warn!("Failed to upload {} to {}: {}", file.display(), key, e);
+        }
+        result.is_ok()
+    }
+}
+
+/// Percent-encode an object key for the request path, keeping `/` between segments
+pub fn encode_object_key(key: &str) -> String {
+    key.split('/').map(|segment| {
+        segment.bytes().map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        }).collect::<String>()
+    }).collect::<Vec<_>>().join("/")
+}
+
+/// PUT `body` as `key`, signed with AWS Signature Version 4
+async fn put_s3_object(client: &reqwest::Client, bucket: &str, region: &str, endpoint: Option<&str>, key: &str, body: Vec<u8>) -> Result<()> {
+    let credentials = aws_credentials(client).await?;
+    let (url, host, path) = match endpoint {
+        Some(endpoint) => {
+            let host = url::Url::parse(endpoint)?.host_str().map(str::to_string)
+                .ok_or_else(|| anyhow::anyhow!("LAUNCH_ARTIFACTS_S3_ENDPOINT has no host"))?;
+            let path = format!("/{}/{}", bucket, encode_object_key(key));
+            (format!("{}{}", endpoint, path), host, path)
+        }
+        None => {
+            let host = format!("{}.s3.{}.amazonaws.com", bucket, region);
+            let path = format!("/{}", encode_object_key(key));
+            (format!("https://{}{}", host, path), host, path)
+        }
+    };
+    let payload_hash = hex_encode(&Sha256::digest(&body));
+    let (_, amz_date) = amz_dates(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs());
+
+    let mut headers = vec![
+        ("host", host),
+        ("x-amz-content-sha256", payload_hash.clone()),
+        ("x-amz-date", amz_date.clone()),
+    ];
+    if let Some(token) = &credentials.session_token {
+        headers.push(("x-amz-security-token", token.clone()));
+    }
+    let authorization = sign_v4(&SigV4Request {
+        method: "PUT",
+        path: &path,
+        headers,
+        payload_hash: &payload_hash,
+        region,
+        service: "s3",
+        amz_date: &amz_date,
+    }, &credentials);
+
+    let mut request = client.put(url)
+        .header("X-Amz-Content-Sha256", &payload_hash)
+        .header("X-Amz-Date", &amz_date)
+        .header("Authorization", authorization)
+        .body(body);
+    if let Some(token) = &credentials.session_token {
+        request = request.header("X-Amz-Security-Token", token);
+    }
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        return Err(anyhow::anyhow!("S3 PutObject failed: {} {}", status, response.text().await.unwrap_or_default()));
+    }
+    Ok(())
+}
+
+/// Upload `body` as `key` with a simple media upload
+async fn put_gcs_object(client: &reqwest::Client, bucket: &str, credentials_path: &str, key: &str, body: Vec<u8>) -> Result<()> {
+    let token = google_access_token(client, credentials_path, GCS_SCOPE).await?;
+    let mut url = url::Url::parse(GCS_UPLOAD_URL)?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("Invalid GCS upload URL"))?
+        .extend([bucket, "o"]);
+    url.query_pairs_mut().append_pair("uploadType", "media").append_pair("name", key);
+    client.post(url)
+        .bearer_auth(token)
+        .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+        .body(body)
+        .send().await?
+        .error_for_status()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_object_keys() {
+        let root = std::env::temp_dir().join(format!("artifact_upload_test_{}", std::process::id()));
+        let dir = LaunchDir::create(&root, "MCAT", UNIX_EPOCH + Duration::from_secs(1_760_000_000)).unwrap();
+        let store = ArtifactStore::Gcs { bucket: "records".to_string(), prefix: "runner-7".to_string(), credentials_path: String::new() };
+        assert_eq!(store.object_key(&dir, &dir.manifest_path()), "runner-7/1760000000-MCAT/manifest.json");
+        let store = ArtifactStore::S3 { bucket: "records".to_string(), prefix: String::new(), region: "us-east-1".to_string(), endpoint: None };
+        assert_eq!(store.object_key(&dir, &dir.report_path()), "1760000000-MCAT/report.json");
+
+        assert_eq!(encode_object_key("launches/1760000000-M CAT/image (1).png"), "launches/1760000000-M%20CAT/image%20%281%29.png");
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,213 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use solana_sdk::hash::hashv;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Hash used as `prev_hash` for the first entry of a new audit log
+pub const GENESIS_HASH: &str = "genesis";
+
+// Serializes appends within the process so the hash chain stays linear
+static AUDIT_LOG_LOCK: Mutex<()> = Mutex::new(());
+
+/// A spending action about to be recorded in the audit log
+#[derive(Debug, Clone, Default)]
+pub struct AuditEvent {
+    /// What the transaction was for, e.g. "create_token"
+    pub purpose: String,
+    pub signature: String,
+    pub payer: String,
+    pub mint: Option<String>,
+    /// Lamports spent by the payer, when known
+    pub amount_lamports: Option<u64>,
+    /// Raw tokens bought or sold by a trade
+    pub token_amount: Option<u64>,
+    /// Least lamports a sell accepts
+    pub min_sol_output: Option<u64>,
+    /// "sent", "confirmed", "failed", ...
+    pub status: String,
+    pub operator: Option<String>,
+    pub api_key_id: Option<String>,
+}
+
+/// One line of the audit log, chained to the previous entry by `prev_hash`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub seq: u64,
+    pub timestamp: u64,
+    pub purpose: String,
+    pub signature: String,
+    pub payer: String,
+    pub mint: Option<String>,
+    pub amount_lamports: Option<u64>,
+    // Left out when absent so entries written before trades recorded them still hash the same
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_amount: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_sol_output: Option<u64>,
+    pub status: String,
+    pub operator: Option<String>,
+    pub api_key_id: Option<String>,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+impl AuditEntry {
+    /// Hash over the previous hash and every field except `hash` itself
+    fn compute_hash(&self) -> Result<String> {
+        let mut unhashed = self.clone();
+        unhashed.hash = String::new();
+        let body = serde_json::to_vec(&unhashed)?;
+        Ok(hashv(&[self.prev_hash.as_bytes(), &body]).to_string())
+    }
+}
+
+/// Append-only, hash-chained audit log of signed and sent transactions.
+///
+/// Separate from application logs; any edit or deletion of an earlier line breaks the chain
+/// and is reported by [`AuditLog::verify`].
+pub struct AuditLog {
+    path: String,
+}
+
+impl AuditLog {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Audit log configured through `AUDIT_LOG_PATH`, if any
+    pub fn from_env() -> Option<Self> {
+        std::env::var("AUDIT_LOG_PATH").ok()
+            .filter(|path| !path.trim().is_empty())
+            .map(Self::new)
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    fn last_entry(&self) -> Result<Option<AuditEntry>> {
+        if !Path::new(&self.path).exists() {
+            return Ok(None);
+        }
+        let reader = BufReader::new(File::open(&self.path)?);
+        let mut last = None;
+        for line in reader.lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                last = Some(line);
+            }
+        }
+        match last {
+            Some(line) => Ok(Some(serde_json::from_str(&line)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Append an event, chaining it to the last entry in the file
+    pub fn append(&self, event: AuditEvent) -> Result<AuditEntry> {
+        let _guard = AUDIT_LOG_LOCK.lock().unwrap();
+
+        let (seq, prev_hash) = match self.last_entry()? {
+            Some(last) => (last.seq + 1, last.hash),
+            None => (0, GENESIS_HASH.to_string()),
+        };
+
+        let mut entry = AuditEntry {
+            seq,
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            purpose: event.purpose,
+            signature: event.signature,
+            payer: event.payer,
+            mint: event.mint,
+            amount_lamports: event.amount_lamports,
+            token_amount: event.token_amount,
+            min_sol_output: event.min_sol_output,
+            status: event.status,
+            operator: event.operator,
+            api_key_id: event.api_key_id,
+            prev_hash,
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash()?;
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+        file.sync_all()?;
+
+        Ok(entry)
+    }
+
+    /// Walk the whole file and check every entry's hash and link. Returns the number of entries.
+    pub fn verify(&self) -> Result<u64> {
+        let reader = BufReader::new(File::open(&self.path)?);
+        let mut expected_prev = GENESIS_HASH.to_string();
+        let mut count = 0u64;
+
+        for (line_number, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry: AuditEntry = serde_json::from_str(&line)
+                .map_err(|e| anyhow::anyhow!("Line {}: unreadable audit entry: {}", line_number + 1, e))?;
+            if entry.seq != count {
+                return Err(anyhow::anyhow!("Line {}: expected seq {}, found {}", line_number + 1, count, entry.seq));
+            }
+            if entry.prev_hash != expected_prev {
+                return Err(anyhow::anyhow!("Line {}: chain broken (prev_hash does not match previous entry)", line_number + 1));
+            }
+            if entry.compute_hash()? != entry.hash {
+                return Err(anyhow::anyhow!("Line {}: entry hash mismatch (entry was modified)", line_number + 1));
+            }
+            expected_prev = entry.hash;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tampering_breaks_chain() {
+        let path = std::env::temp_dir().join(format!("audit_test_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let log = AuditLog::new(path.to_string_lossy().to_string());
+
+        for i in 0..3 {
+            log.append(AuditEvent {
+                purpose: "create_token".to_string(),
+                signature: format!("sig{}", i),
+                payer: "payer".to_string(),
+                status: "sent".to_string(),
+                ..Default::default()
+            }).unwrap();
+        }
+        assert_eq!(log.verify().unwrap(), 3);
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("token_amount"));
+        let sell = log.append(AuditEvent {
+            purpose: "sell".to_string(),
+            signature: "sig3".to_string(),
+            payer: "payer".to_string(),
+            token_amount: Some(1_000_000),
+            min_sol_output: Some(29_000),
+            status: "confirmed".to_string(),
+            ..Default::default()
+        }).unwrap();
+        assert_eq!((sell.token_amount, sell.min_sol_output), (Some(1_000_000), Some(29_000)));
+        assert_eq!(log.verify().unwrap(), 4);
+
+        let contents = std::fs::read_to_string(&path).unwrap().replace("sig1", "sigX");
+        std::fs::write(&path, contents).unwrap();
+        assert!(log.verify().is_err());
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,122 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::metadata_update::MetadataAccount;
+
+// SPL Token `Mint` layout: COption<Pubkey> mint authority, supply, decimals, is_initialized, COption<Pubkey> freeze authority
+const MINT_LEN: usize = 82;
+const FREEZE_AUTHORITY_OFFSET: usize = 46;
+
+/// Who controls a launched token, and anything that differs from what the pump.fun program leaves behind
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AuthorityReport {
+    pub mint: String,
+    /// `None` once revoked (pump.fun does so when the curve migrates)
+    pub mint_authority: Option<String>,
+    pub freeze_authority: Option<String>,
+    pub update_authority: String,
+    pub metadata_mutable: bool,
+    /// Empty when every authority is in the expected state
+    pub anomalies: Vec<String>,
+}
+
+impl AuthorityReport {
+    pub fn is_clean(&self) -> bool {
+        self.anomalies.is_empty()
+    }
+}
+
+/// Mint and freeze authorities of an SPL Token mint account
+pub fn parse_mint_authorities(data: &[u8]) -> Result<(Option<Pubkey>, Option<Pubkey>)> {
+    if data.len() < MINT_LEN {
+        return Err(anyhow::anyhow!("Not a token mint: {} bytes of account data", data.len()));
+    }
+    let coption = |offset: usize| match u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap()) {
+        0 => Ok(None),
+        1 => Ok(Some(Pubkey::new_from_array(data[offset + 4..offset + 36].try_into().unwrap()))),
+        tag => Err(anyhow::anyhow!("Malformed mint account: option tag {}", tag)),
+    };
+    Ok((coption(0)?, coption(FREEZE_AUTHORITY_OFFSET)?))
+}
+
+/// Compare the authorities of `mint` with those of a pump.fun launch: the mint authority is the
+/// program's PDA (or revoked), there is no freeze authority and the PDA owns the metadata.
+pub fn check_authorities(
+    mint: &Pubkey,
+    mint_authority: Option<Pubkey>,
+    freeze_authority: Option<Pubkey>,
+    metadata: &MetadataAccount,
+    program_authority: &Pubkey,
+) -> AuthorityReport {
+    let mut anomalies = Vec::new();
+    if let Some(authority) = mint_authority.filter(|authority| authority != program_authority) {
+        anomalies.push(format!("Mint authority is {}, not the pump.fun PDA {}: more tokens can be minted", authority, program_authority));
+    }
+    if let Some(authority) = freeze_authority {
+        anomalies.push(format!("Freeze authority is set to {}: holder accounts can be frozen", authority));
+    }
+    if metadata.update_authority != *program_authority {
+        anomalies.push(format!("Metadata update authority is {}, not the pump.fun PDA {}", metadata.update_authority, program_authority));
+    }
+    if metadata.mint != *mint {
+        anomalies.push(format!("Metadata account describes mint {}, not {}", metadata.mint, mint));
+    }
+    AuthorityReport {
+        mint: mint.to_string(),
+        mint_authority: mint_authority.map(|authority| authority.to_string()),
+        freeze_authority: freeze_authority.map(|authority| authority.to_string()),
+        update_authority: metadata.update_authority.to_string(),
+        metadata_mutable: metadata.is_mutable,
+        anomalies,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata_update::parse_metadata_account;
+
+    fn mint_account(mint_authority: Option<Pubkey>, freeze_authority: Option<Pubkey>) -> Vec<u8> {
+        let mut data = Vec::new();
+        for authority in [mint_authority, freeze_authority] {
+            data.extend_from_slice(&(authority.is_some() as u32).to_le_bytes());
+            data.extend_from_slice(authority.unwrap_or_default().as_ref());
+            if data.len() == 36 {
+                data.extend_from_slice(&1_000_000_000_000_000u64.to_le_bytes());
+                data.extend_from_slice(&[6, 1]);
+            }
+        }
+        data
+    }
+
+    fn metadata_account(update_authority: &Pubkey, mint: &Pubkey) -> MetadataAccount {
+        let mut data = vec![4];
+        data.extend_from_slice(update_authority.as_ref());
+        data.extend_from_slice(mint.as_ref());
+        for field in ["Moon Cat", "MCAT", "https://ipfs.io/ipfs/x"] {
+            data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            data.extend_from_slice(field.as_bytes());
+        }
+        data.extend_from_slice(&[0, 0, 0, 0, 0]);
+        parse_metadata_account(&data).unwrap()
+    }
+
+    #[test]
+    fn test_flags_unexpected_authorities() {
+        let (mint, pda, stranger) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        assert_eq!(parse_mint_authorities(&mint_account(Some(pda), None)).unwrap(), (Some(pda), None));
+        assert!(parse_mint_authorities(&[0; 40]).is_err());
+
+        let metadata = metadata_account(&pda, &mint);
+        let launched = check_authorities(&mint, Some(pda), None, &metadata, &pda);
+        assert!(launched.is_clean());
+        assert_eq!(launched.update_authority, pda.to_string());
+        assert!(check_authorities(&mint, None, None, &metadata, &pda).is_clean());
+
+        let (minted, frozen) = parse_mint_authorities(&mint_account(Some(stranger), Some(stranger))).unwrap();
+        let report = check_authorities(&mint, minted, frozen, &metadata_account(&stranger, &Pubkey::new_unique()), &pda);
+        assert_eq!(report.anomalies.len(), 4);
+        assert!(report.anomalies[1].contains("frozen"));
+    }
+}
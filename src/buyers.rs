@@ -0,0 +1,216 @@
+use anyhow::Result;
+use log::info;
+use serde::Serialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+/// Fewer prior transactions than this marks a buyer wallet as fresh
+pub const FRESH_WALLET_MAX_PRIOR_TXS: usize = 3;
+const SIGNATURE_PAGE_LIMIT: usize = 1000;
+
+/// A buy of the mint found in the bonding curve's history
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Buy {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub buyer: String,
+    /// Raw token amount received
+    pub tokens: u64,
+    /// Lamports the buyer's balance dropped by (buy cost plus fees)
+    pub sol_lamports: u64,
+    /// Reasons the buy looks like sniping/bundling, e.g. "same_slot_as_create"
+    pub flags: Vec<String>,
+}
+
+/// Every signature for `address`, oldest first. Fails when there are more than `max_signatures`,
+/// since the oldest entries can only be reached by paging through all newer ones.
+pub async fn fetch_address_history(rpc: &RpcClient, address: &Pubkey, max_signatures: usize) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+    let mut signatures = Vec::new();
+    let mut before = None;
+    loop {
+        let page = rpc.get_signatures_for_address_with_config(address, GetConfirmedSignaturesForAddress2Config {
+            before,
+            limit: Some(SIGNATURE_PAGE_LIMIT),
+            ..Default::default()
+        }).await?;
+        let done = page.len() < SIGNATURE_PAGE_LIMIT;
+        before = page.last().map(|s| Signature::from_str(&s.signature)).transpose()?;
+        signatures.extend(page);
+        if done {
+            break;
+        }
+        if signatures.len() >= max_signatures {
+            return Err(anyhow::anyhow!("{} has more than {} transactions; raise the signature limit", address, max_signatures));
+        }
+    }
+    signatures.reverse();
+    Ok(signatures)
+}
+
+/// `getTransaction` as raw JSON (json encoding, v0 transactions supported)
+pub async fn fetch_transaction_json(rpc: &RpcClient, signature: &str) -> Result<serde_json::Value> {
+    Ok(rpc.send(
+        RpcRequest::GetTransaction,
+        serde_json::json!([signature, { "encoding": "json", "maxSupportedTransactionVersion": 0, "commitment": "confirmed" }]),
+    ).await?)
+}
+
+/// All account keys of a json-encoded transaction, including lookup-table addresses
+pub fn transaction_account_keys(tx: &serde_json::Value) -> Vec<String> {
+    let mut keys: Vec<String> = tx["transaction"]["message"]["accountKeys"].as_array()
+        .map(|keys| keys.iter().filter_map(|k| k.as_str().map(String::from)).collect())
+        .unwrap_or_default();
+    for kind in ["writable", "readonly"] {
+        if let Some(loaded) = tx["meta"]["loadedAddresses"][kind].as_array() {
+            keys.extend(loaded.iter().filter_map(|k| k.as_str().map(String::from)));
+        }
+    }
+    keys
+}
+
+/// (buyer, tokens received, lamports spent) for every owner whose `mint` balance increased
+pub fn parse_buys(tx: &serde_json::Value, mint: &str) -> Vec<(String, u64, u64)> {
+    let meta = &tx["meta"];
+    if !meta["err"].is_null() {
+        return Vec::new();
+    }
+    let balances = |field: &str| -> HashMap<String, u64> {
+        let mut per_owner = HashMap::new();
+        for balance in meta[field].as_array().into_iter().flatten() {
+            if balance["mint"].as_str() != Some(mint) {
+                continue;
+            }
+            let (Some(owner), Some(amount)) = (
+                balance["owner"].as_str(),
+                balance["uiTokenAmount"]["amount"].as_str().and_then(|a| a.parse::<u64>().ok()),
+            ) else { continue };
+            *per_owner.entry(owner.to_string()).or_insert(0u64) += amount;
+        }
+        per_owner
+    };
+    let pre = balances("preTokenBalances");
+    let post = balances("postTokenBalances");
+    let keys = transaction_account_keys(tx);
+    let lamports = |field: &str, index: usize| meta[field][index].as_u64().unwrap_or(0);
+
+    let mut buys: Vec<(String, u64, u64)> = post.iter()
+        .filter_map(|(owner, after)| {
+            let received = after.checked_sub(*pre.get(owner).unwrap_or(&0)).filter(|r| *r > 0)?;
+            let spent = keys.iter().position(|k| k == owner)
+                .map(|i| lamports("preBalances", i).saturating_sub(lamports("postBalances", i)))
+                .unwrap_or(0);
+            Some((owner.clone(), received, spent))
+        })
+        .collect();
+    buys.sort();
+    buys
+}
+
+/// Flag buys in the creation slot and slots where several wallets bought together
+pub fn flag_buys(buys: &mut [Buy], create_slot: u64) {
+    let mut buyers_per_slot: HashMap<u64, HashSet<&str>> = HashMap::new();
+    for buy in buys.iter() {
+        buyers_per_slot.entry(buy.slot).or_default().insert(&buy.buyer);
+    }
+    let bundled_slots: HashSet<u64> = buyers_per_slot.into_iter()
+        .filter(|(_, buyers)| buyers.len() > 1)
+        .map(|(slot, _)| slot)
+        .collect();
+
+    for buy in buys.iter_mut() {
+        if buy.slot == create_slot {
+            buy.flags.push("same_slot_as_create".to_string());
+        }
+        if bundled_slots.contains(&buy.slot) {
+            buy.flags.push("same_slot_multi_wallet".to_string());
+        }
+    }
+}
+
+/// The first `count` buys of `mint` after creation, flagged for sniping indicators
+pub async fn first_buyers(rpc: &RpcClient, mint: &Pubkey, bonding_curve: &Pubkey, count: usize, max_signatures: usize) -> Result<Vec<Buy>> {
+    let history = fetch_address_history(rpc, bonding_curve, max_signatures).await?;
+    let create_slot = history.first()
+        .ok_or_else(|| anyhow::anyhow!("Bonding curve {} has no transactions", bonding_curve))?
+        .slot;
+    info!("Scanning {} bonding curve transactions (creation slot {})", history.len(), create_slot);
+
+    let mint_str = mint.to_string();
+    let curve_str = bonding_curve.to_string();
+    let mut buys = Vec::new();
+    for entry in history.iter().filter(|s| s.err.is_none()) {
+        let tx = fetch_transaction_json(rpc, &entry.signature).await?;
+        // The curve's own balance rises on sells; those are not buys
+        for (buyer, tokens, sol_lamports) in parse_buys(&tx, &mint_str).into_iter().filter(|(b, _, _)| *b != curve_str) {
+            buys.push(Buy {
+                signature: entry.signature.clone(),
+                slot: entry.slot,
+                block_time: entry.block_time,
+                buyer,
+                tokens,
+                sol_lamports,
+                flags: Vec::new(),
+            });
+        }
+        if buys.len() >= count {
+            break;
+        }
+    }
+    buys.truncate(count);
+    flag_buys(&mut buys, create_slot);
+
+    // Wallets with almost no history before their buy were likely created for the launch
+    for buy in buys.iter_mut() {
+        let prior = rpc.get_signatures_for_address_with_config(&Pubkey::from_str(&buy.buyer)?, GetConfirmedSignaturesForAddress2Config {
+            before: Some(Signature::from_str(&buy.signature)?),
+            limit: Some(FRESH_WALLET_MAX_PRIOR_TXS),
+            ..Default::default()
+        }).await?;
+        if prior.len() < FRESH_WALLET_MAX_PRIOR_TXS {
+            buy.flags.push("fresh_wallet".to_string());
+        }
+    }
+    Ok(buys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_flag_buys() {
+        let tx = serde_json::json!({
+            "transaction": { "message": { "accountKeys": ["buyer1", "curve"] } },
+            "meta": {
+                "err": null,
+                "preBalances": [2_000_000_000u64, 0],
+                "postBalances": [1_000_000_000u64, 0],
+                "preTokenBalances": [
+                    { "mint": "MINT", "owner": "curve", "uiTokenAmount": { "amount": "1000" } }
+                ],
+                "postTokenBalances": [
+                    { "mint": "MINT", "owner": "curve", "uiTokenAmount": { "amount": "400" } },
+                    { "mint": "MINT", "owner": "buyer1", "uiTokenAmount": { "amount": "600" } }
+                ]
+            }
+        });
+        assert_eq!(parse_buys(&tx, "MINT"), vec![("buyer1".to_string(), 600, 1_000_000_000)]);
+        assert!(parse_buys(&tx, "OTHER").is_empty());
+
+        let buy = |buyer: &str, slot| Buy {
+            signature: String::new(), slot, block_time: None, buyer: buyer.to_string(),
+            tokens: 1, sol_lamports: 1, flags: Vec::new(),
+        };
+        let mut buys = vec![buy("a", 10), buy("b", 11), buy("c", 11), buy("d", 12)];
+        flag_buys(&mut buys, 10);
+        assert_eq!(buys[0].flags, vec!["same_slot_as_create"]);
+        assert_eq!(buys[1].flags, vec!["same_slot_multi_wallet"]);
+        assert!(buys[3].flags.is_empty());
+    }
+}
@@ -0,0 +1,73 @@
+use anyhow::Result;
+use log::warn;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::notifications::Attachment;
+
+/// Market cap of a watched bonding curve at one point of the watch session
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChartPoint {
+    pub secs: f64,
+    pub market_cap_lamports: u64,
+}
+
+/// Whether milestone and end-of-watch notifications carry a chart (`NOTIFY_CHARTS=true`)
+pub fn charts_enabled() -> bool {
+    std::env::var("NOTIFY_CHARTS").is_ok_and(|v| v.trim().eq_ignore_ascii_case("true"))
+}
+
+/// PNG sparkline of the market cap over the session. Price moves with market cap on a bonding
+/// curve, so one line shows both.
+#[cfg(feature = "chart-snapshots")]
+pub fn render_sparkline(points: &[ChartPoint]) -> Result<Vec<u8>> {
+    use plotters::prelude::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+    const CHART_SIZE: (u32, u32) = (600, 200);
+
+    if points.len() < 2 {
+        return Err(anyhow::anyhow!("A chart needs at least two points, got {}", points.len()));
+    }
+    let max_secs = points.iter().map(|p| p.secs).fold(1.0, f64::max);
+    let mcap = |point: &ChartPoint| point.market_cap_lamports as f64 / crate::create_token::LAMPORTS_PER_SOL;
+    let (low, high) = points.iter().map(mcap).fold((f64::MAX, f64::MIN), |(lo, hi), v| (lo.min(v), hi.max(v)));
+    let padding = ((high - low) * 0.1).max(high * 0.01).max(f64::EPSILON);
+
+    // The bitmap encoder writes to a path, so render to a temporary file and read it back
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.subsec_nanos()).unwrap_or(0);
+    let path = std::env::temp_dir().join(format!("launch-chart-{}-{}.png", std::process::id(), nanos));
+    let draw = || -> Result<()> {
+        let root = BitMapBackend::new(&path, CHART_SIZE).into_drawing_area();
+        root.fill(&WHITE).map_err(|e| anyhow::anyhow!("{}", e))?;
+        let mut chart = ChartBuilder::on(&root)
+            .margin(8)
+            .build_cartesian_2d(0.0..max_secs, (low - padding)..(high + padding))
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        let color = if mcap(&points[points.len() - 1]) >= mcap(&points[0]) { GREEN } else { RED };
+        chart.draw_series(LineSeries::new(points.iter().map(|p| (p.secs, mcap(p))), color.stroke_width(2)))
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        root.present().map_err(|e| anyhow::anyhow!("{}", e))?;
+        Ok(())
+    };
+    let result = draw().and_then(|_| Ok(std::fs::read(&path)?));
+    let _ = std::fs::remove_file(&path);
+    result
+}
+
+#[cfg(not(feature = "chart-snapshots"))]
+pub fn render_sparkline(_points: &[ChartPoint]) -> Result<Vec<u8>> {
+    Err(anyhow::anyhow!("charts require building with --features chart-snapshots"))
+}
+
+/// Chart of `points` to attach to a notification about `mint`, when charts are enabled and it renders
+pub fn chart_attachment(mint: &Pubkey, points: &[ChartPoint]) -> Option<Attachment> {
+    if !charts_enabled() {
+        return None;
+    }
+    match render_sparkline(points) {
+        Ok(data) => Some(Attachment { filename: format!("{}.png", mint), content_type: "image/png".to_string(), data }),
+        Err(e) => {
+            warn!("Sending the notification without a chart: {}", e);
+            None
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use anyhow::Result;
+use std::env;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+/// Local `solana-test-validator` RPC and websocket endpoints (default ports)
+pub const LOCALNET_RPC_URL: &str = "http://127.0.0.1:8899";
+pub const LOCALNET_WS_URL: &str = "ws://127.0.0.1:8900";
+
+// Cluster chosen with `--cluster`, consulted before `SOLANA_CLUSTER`
+static CLUSTER_OVERRIDE: RwLock<Option<SolanaCluster>> = RwLock::new(None);
+
+/// Well-known cluster that replaces the `HELIUS_API_KEY` RPC endpoint when selected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SolanaCluster {
+    Mainnet,
+    Devnet,
+    Localnet,
+}
+
+impl FromStr for SolanaCluster {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "mainnet" | "mainnet-beta" => Ok(Self::Mainnet),
+            "devnet" => Ok(Self::Devnet),
+            "localnet" | "localhost" => Ok(Self::Localnet),
+            other => Err(anyhow::anyhow!("Unknown cluster '{}' (expected mainnet, devnet or localnet)", other)),
+        }
+    }
+}
+
+impl SolanaCluster {
+    pub fn rpc_url(&self) -> &'static str {
+        match self {
+            Self::Mainnet => "https://api.mainnet-beta.solana.com",
+            Self::Devnet => "https://api.devnet.solana.com",
+            Self::Localnet => LOCALNET_RPC_URL,
+        }
+    }
+
+    pub fn ws_url(&self) -> &'static str {
+        match self {
+            Self::Mainnet => "wss://api.mainnet-beta.solana.com",
+            Self::Devnet => "wss://api.devnet.solana.com",
+            Self::Localnet => LOCALNET_WS_URL,
+        }
+    }
+}
+
+/// Select `cluster` for the rest of the process
+pub fn set_cluster(cluster: SolanaCluster) {
+    *CLUSTER_OVERRIDE.write().unwrap() = Some(cluster);
+}
+
+/// Cluster from `--cluster` or `SOLANA_CLUSTER`, if any
+pub fn selected_cluster() -> Result<Option<SolanaCluster>> {
+    if let Some(cluster) = *CLUSTER_OVERRIDE.read().unwrap() {
+        return Ok(Some(cluster));
+    }
+    env::var("SOLANA_CLUSTER").ok()
+        .filter(|v| !v.trim().is_empty())
+        .map(|v| v.parse())
+        .transpose()
+}
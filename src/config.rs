@@ -0,0 +1,174 @@
+use anyhow::Result;
+use log::info;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// Config file read from the working directory unless `--config` or `LAUNCHER_CONFIG` is set
+pub const DEFAULT_CONFIG_FILE: &str = "launcher.toml";
+
+/// Settings of each `launcher.toml` table and the environment variable each one stands for
+const CONFIG_KEYS: &[(&str, &str, &str)] = &[
+    ("rpc", "url", "HELIUS_API_KEY"),
+    ("rpc", "fallback_urls", "RPC_FALLBACK_URLS"),
+    ("rpc", "ws_url", "RPC_WS_URL"),
+    ("rpc", "cluster", "SOLANA_CLUSTER"),
+    ("rpc", "confirmation", "LAUNCH_CONFIRMATION"),
+    ("vanity", "enabled", "VANITY_ENABLED"),
+    ("vanity", "prefix", "VANITY_PREFIX"),
+    ("vanity", "suffix", "VANITY_SUFFIX"),
+    ("vanity", "ignore_case", "VANITY_IGNORE_CASE"),
+    ("vanity", "pool_sizes", "VANITY_POOL_SIZES"),
+    ("fees", "priority_fee", "PRIORITY_FEE"),
+    ("fees", "compute_unit_limit", "COMPUTE_UNIT_LIMIT"),
+    ("fees", "priority_fee_percentile", "PRIORITY_FEE_PERCENTILE"),
+    ("fees", "priority_fee_max_microlamports", "PRIORITY_FEE_MAX_MICROLAMPORTS"),
+    ("fees", "jito_tip_sol", "JITO_TIP_SOL"),
+    ("token", "image", "TOKEN_IMAGE"),
+    ("token", "show_name", "METADATA_SHOW_NAME"),
+    ("token", "created_on", "METADATA_CREATED_ON"),
+    ("token", "twitter", "METADATA_TWITTER"),
+    ("token", "telegram", "METADATA_TELEGRAM"),
+    ("token", "website", "METADATA_WEBSITE"),
+];
+
+// Variables set from the config file, so configuration problems can name it as their source
+static CONFIG_FILE_VARS: RwLock<Option<HashSet<String>>> = RwLock::new(None);
+
+/// Environment variables described by a config file, after applying `profile`.
+///
+/// Top-level tables hold the defaults and `[profiles.<name>]` tables override them key by key.
+/// `[env]` sets any other variable by name.
+pub fn parse_config(text: &str, profile: Option<&str>) -> Result<BTreeMap<String, String>> {
+    let root: toml::Value = toml::from_str(text)?;
+    let root = root.as_table().ok_or_else(|| anyhow::anyhow!("The config file must be a table"))?;
+    let mut vars = BTreeMap::new();
+    collect_vars(root, "", &mut vars)?;
+
+    let profiles = match root.get("profiles") {
+        Some(profiles) => profiles.as_table().ok_or_else(|| anyhow::anyhow!("[profiles] must be a table of profiles"))?.clone(),
+        None => Default::default(),
+    };
+    if let Some(name) = profile {
+        let overrides = profiles.get(name).and_then(toml::Value::as_table).ok_or_else(|| anyhow::anyhow!(
+            "Profile '{}' is not defined (available: {})",
+            name, profiles.keys().cloned().collect::<Vec<_>>().join(", ")
+        ))?;
+        collect_vars(overrides, &format!("profiles.{}.", name), &mut vars)?;
+    }
+    Ok(vars)
+}
+
+fn collect_vars(tables: &toml::value::Table, path: &str, vars: &mut BTreeMap<String, String>) -> Result<()> {
+    for (section, table) in tables {
+        if section == "profiles" && path.is_empty() {
+            continue;
+        }
+        let table = table.as_table().ok_or_else(|| anyhow::anyhow!("{}{} must be a table", path, section))?;
+        for (key, value) in table {
+            let var = if section == "env" {
+                key.clone()
+            } else {
+                CONFIG_KEYS.iter()
+                    .find(|(s, k, _)| s == section && k == key)
+                    .map(|(_, _, var)| var.to_string())
+                    .ok_or_else(|| anyhow::anyhow!("Unknown setting {}{}.{}", path, section, key))?
+            };
+            let value = env_value(value).ok_or_else(|| anyhow::anyhow!("{}{}.{} must be a string, number, boolean or list of them", path, section, key))?;
+            vars.insert(var, value);
+        }
+    }
+    Ok(())
+}
+
+/// A scalar as the environment would spell it; lists become comma-separated
+fn env_value(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(s) => Some(s.clone()),
+        toml::Value::Integer(n) => Some(n.to_string()),
+        toml::Value::Float(n) => Some(n.to_string()),
+        toml::Value::Boolean(b) => Some(b.to_string()),
+        toml::Value::Array(items) => items.iter()
+            .map(|item| if item.is_array() { None } else { env_value(item) })
+            .collect::<Option<Vec<_>>>()
+            .map(|items| items.join(",")),
+        _ => None,
+    }
+}
+
+/// Load the config file and set each of its variables that is not already set, so flags beat the
+/// environment and `.env`, which beat the profile, which beats the file's top-level tables.
+///
+/// `path` (or `LAUNCHER_CONFIG`) must exist; the default `launcher.toml` is optional.
+pub fn load_config_file(path: Option<&Path>, profile: Option<&str>) -> Result<()> {
+    let explicit = path.map(Path::to_path_buf).or_else(|| std::env::var("LAUNCHER_CONFIG").ok().map(PathBuf::from));
+    let path = explicit.clone().unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_FILE));
+    let profile = profile.map(str::to_string).or_else(|| std::env::var("LAUNCHER_PROFILE").ok());
+    if explicit.is_none() && !path.exists() {
+        if let Some(profile) = profile {
+            return Err(anyhow::anyhow!("Profile '{}' selected but {} does not exist", profile, path.display()));
+        }
+        return Ok(());
+    }
+    let text = std::fs::read_to_string(&path)
+        .map_err(|e| anyhow::anyhow!("Cannot read config file {}: {}", path.display(), e))?;
+    let vars = parse_config(&text, profile.as_deref()).map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?;
+
+    let mut applied = HashSet::new();
+    for (var, value) in vars {
+        if std::env::var_os(&var).is_none() {
+            std::env::set_var(&var, value);
+            applied.insert(var);
+        }
+    }
+    info!("Loaded {} setting(s) from {}{}", applied.len(), path.display(),
+          profile.map(|p| format!(" (profile {})", p)).unwrap_or_default());
+    *CONFIG_FILE_VARS.write().unwrap() = Some(applied);
+    Ok(())
+}
+
+/// Whether `var` was set from the config file
+pub fn is_config_file_var(var: &str) -> bool {
+    CONFIG_FILE_VARS.read().unwrap().as_ref().is_some_and(|vars| vars.contains(var))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles_override_defaults() {
+        let text = r#"
+            [rpc]
+            url = "https://mainnet.helius-rpc.com/?api-key=k"
+            fallback_urls = ["https://a.example", "https://b.example"]
+
+            [fees]
+            priority_fee = "auto"
+            compute_unit_limit = 250000
+
+            [token]
+            twitter = "https://x.com/team"
+
+            [env]
+            AUDIT_LOG_PATH = "audit.jsonl"
+
+            [profiles.devnet]
+            rpc = { cluster = "devnet" }
+            vanity = { enabled = false }
+        "#;
+        let vars = parse_config(text, None).unwrap();
+        assert_eq!(vars["RPC_FALLBACK_URLS"], "https://a.example,https://b.example");
+        assert_eq!(vars["COMPUTE_UNIT_LIMIT"], "250000");
+        assert_eq!(vars["METADATA_TWITTER"], "https://x.com/team");
+        assert_eq!(vars["AUDIT_LOG_PATH"], "audit.jsonl");
+        assert!(!vars.contains_key("SOLANA_CLUSTER"));
+
+        let devnet = parse_config(text, Some("devnet")).unwrap();
+        assert_eq!((devnet["SOLANA_CLUSTER"].as_str(), devnet["VANITY_ENABLED"].as_str()), ("devnet", "false"));
+        assert_eq!(devnet["PRIORITY_FEE"], "auto");
+
+        assert!(parse_config(text, Some("mainnet")).is_err());
+        assert!(parse_config("[fees]\npriorty_fee = 5", None).unwrap_err().to_string().contains("fees.priorty_fee"));
+    }
+}
@@ -0,0 +1,407 @@
+use log::error;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair};
+use std::collections::HashSet;
+use std::env;
+use std::fmt;
+use std::path::Path;
+
+use crate::artifact_upload::ArtifactStore;
+use crate::market_data::MarketDataSource;
+use crate::cluster::{SolanaCluster, selected_cluster};
+use crate::config::is_config_file_var;
+use crate::create_token::{parse_link_url, ProgramVersion};
+use crate::jito::BlockEngine;
+use crate::keypair_cipher::KeypairCipher;
+use crate::launch_rate::LaunchCooldown;
+use crate::media::parse_upload_filename;
+use crate::metadata_hosts::FallbackHost;
+use crate::mint_monitor::MintMonitor;
+use crate::name_collision::threshold_from_env;
+use crate::parser::{Args, CreateArgs};
+use crate::price_feed::PriceFeed;
+use crate::priority_fee::ComputeBudget;
+use crate::secure_credentials::{SecureApiKey, SecurePrivateKey, is_provided_secret};
+use crate::time_lock::parse_timestamp;
+use crate::vanity_address::{PoolSizePolicy, VanityPattern};
+use crate::wallet_guard::WalletGuard;
+
+/// How to supply the payer key and RPC endpoint when they are missing
+pub const PRIVATE_KEY_HINT: &str = "set it in .env, pass --keypair, pipe it with --private-key-stdin or configure SECRETS_BACKEND";
+pub const RPC_URL_HINT: &str = "set the RPC URL in .env or pass --cluster";
+
+/// Optional settings whose format is checked whenever they are set
+const CHECKED_VARS: &[&str] = &[
+    "SOLANA_CLUSTER", "RPC_WS_URL", "LAUNCH_CONFIRMATION",
+    "PUMP_PROGRAM_ID", "MPL_TOKEN_METADATA_PROGRAM_ID", "PUMP_PROGRAM_VERSION",
+    "PUMP_SEED_GLOBAL", "PUMP_SEED_MINT_AUTHORITY", "PUMP_SEED_BONDING_CURVE",
+    "PUMP_SEED_METADATA", "PUMP_SEED_EVENT_AUTHORITY", "PUMP_SEED_CREATOR_VAULT",
+    "NONCE_ACCOUNT", "FEE_RECIPIENT_CACHE_SECS", "WALLET_GUARD_WAIT_SECS", "MINT_MONITOR_SECS",
+    "BALANCE_SAFETY_MARGIN_SOL", "PRIORITY_FEE_WARN_SOL", "HEALTH_MIN_BALANCE_SOL", "JITO_TIP_SOL", "JITO_BLOCK_ENGINE", "PRIORITY_FEE", "COMPUTE_UNIT_LIMIT", "PRIORITY_FEE_PERCENTILE", "PRIORITY_FEE_MAX_MICROLAMPORTS", "TOPUP_MAX_SOL", "BUY_PRICE_TOLERANCE_BPS",
+    "NAME_COLLISION_HOURS", "NAME_COLLISION_THRESHOLD", "VANITY_PROGRESS_SECS", "VANITY_COUNTER_FLUSH", "VANITY_WATCHDOG_MINUTES", "VANITY_POOL_SIZES", "VANITY_PREFIX", "VANITY_SUFFIX", "LAUNCH_COOLDOWN_MINUTES", "LAUNCH_COOLDOWN_MAX_LAUNCHES", "LAUNCH_NOT_BEFORE", "MAX_CLOCK_SKEW_SECS", "RPC_ENDPOINT_COOLDOWN_SECS", "RPC_FALLBACK_URLS",
+    "DRY_RUN", "VERIFY_PINS", "REPIN_TO_PINATA", "COMMIT_METADATA_HASH", "AUTO_TOPUP", "SKIP_PREFLIGHT_CHECKS", "VANITY_ENABLED", "VANITY_IGNORE_CASE", "METADATA_SHOW_NAME", "JUPITER_FALLBACK", "LAUNCHER_READ_ONLY", "NOTIFY_CHARTS",
+    "WALLET_ACTIVITY_GUARD", "MINT_MONITOR", "SOL_PRICE_FEED", "VANITY_FLEET_SECRET", "VANITY_STORE_SECRET", "LAUNCH_KEYPAIR_SECRET",
+    "NOTIFY_WEBHOOK_URL", "NOTIFY_DISCORD_WEBHOOK_URL", "SOL_PRICE_URL", "PUMP_LIMITS_URL", "PUSHGATEWAY_URL",
+    "PUMP_FRONTEND_API_URL", "LOCALNET_CLONE_URL", "HELIUS_WEBHOOK_TARGET_URL", "VAULT_ADDR", "JUPITER_API_URL",
+    "EXPORT_CSV_WEBHOOK_URL", "IPFS_API_URL", "IPFS_GATEWAY_URL", "PINATA_GATEWAY_URL",
+    "METADATA_FALLBACK_HOSTS", "METADATA_UPLOAD_FILENAME", "PUMPFUN_UPLOAD_ATTEMPTS",
+    "METADATA_CREATED_ON", "METADATA_TWITTER", "METADATA_TELEGRAM", "METADATA_WEBSITE",
+    "LAUNCH_ARTIFACTS_BUCKET", "LAUNCH_ARTIFACTS_S3_ENDPOINT",
+    "MARKET_DATA_SOURCE", "MARKET_DATA_URL", "MARKET_DATA_TTL_SECS",
+];
+
+/// Where an offending value came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    Flag(String),
+    Env,
+    /// Set in the `.env` file loaded at startup
+    EnvFile,
+    /// Installed from a secrets backend, stdin or a file descriptor
+    SecretsBackend,
+    /// Set from `launcher.toml` (see `--config`)
+    ConfigFile,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Flag(flag) => write!(f, "flag {}", flag),
+            Self::Env => write!(f, "env"),
+            Self::EnvFile => write!(f, ".env file"),
+            Self::SecretsBackend => write!(f, "secrets backend"),
+            Self::ConfigFile => write!(f, "config file"),
+        }
+    }
+}
+
+/// A single configuration problem
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// A required setting is not set anywhere
+    Missing { key: String, hint: String },
+    /// A setting is present but unusable
+    Invalid { key: String, source: ConfigSource, reason: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { key, hint } => write!(f, "{} is not set ({})", key, hint),
+            Self::Invalid { key, source, reason } => write!(f, "{} ({}): {}", key, source, reason),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Every problem found by `validate_config`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigErrors(pub Vec<ConfigError>);
+
+impl fmt::Display for ConfigErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} configuration problem(s):", self.0.len())?;
+        for problem in &self.0 {
+            write!(f, "\n  - {}", problem)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigErrors {}
+
+/// Check the format of one optional setting
+pub fn check_var(key: &str, value: &str) -> Result<(), String> {
+    let value = value.trim();
+    match key {
+        "SOLANA_CLUSTER" => value.parse::<SolanaCluster>().map(drop).map_err(|e| e.to_string()),
+        "RPC_WS_URL" => check_url(value, &["ws", "wss"]),
+        "LAUNCH_CONFIRMATION" => value.to_lowercase().parse::<CommitmentConfig>().map(drop)
+            .map_err(|_| "expected processed, confirmed or finalized".to_string()),
+        "PUMP_PROGRAM_ID" | "MPL_TOKEN_METADATA_PROGRAM_ID" | "NONCE_ACCOUNT" => value.parse::<Pubkey>().map(drop)
+            .map_err(|e| format!("not a base58 pubkey: {}", e)),
+        "PUMP_PROGRAM_VERSION" if value.eq_ignore_ascii_case("auto") => Ok(()),
+        "PUMP_PROGRAM_VERSION" => value.parse::<ProgramVersion>().map(drop).map_err(|e| e.to_string()),
+        seed if seed.starts_with("PUMP_SEED_") => match value.len() {
+            1..=32 => Ok(()),
+            len => Err(format!("PDA seeds must be between 1 and 32 bytes, got {}", len)),
+        },
+        "LAUNCH_NOT_BEFORE" => check_loader(parse_timestamp(value)),
+        "FEE_RECIPIENT_CACHE_SECS" | "WALLET_GUARD_WAIT_SECS" | "MINT_MONITOR_SECS" | "MAX_CLOCK_SKEW_SECS" | "RPC_ENDPOINT_COOLDOWN_SECS" | "MARKET_DATA_TTL_SECS" => value.parse::<u64>().map(drop)
+            .map_err(|_| format!("expected a whole number of seconds, got '{}'", value)),
+        "NAME_COLLISION_HOURS" => value.parse::<u64>().map(drop)
+            .map_err(|_| format!("expected a whole number of hours, got '{}'", value)),
+        "NAME_COLLISION_THRESHOLD" => check_loader(threshold_from_env()),
+        "VANITY_PROGRESS_SECS" => value.parse::<u64>().map(drop)
+            .map_err(|_| format!("expected a whole number of seconds, got '{}'", value)),
+        "VANITY_WATCHDOG_MINUTES" => value.parse::<u64>().map(drop)
+            .map_err(|_| format!("expected a whole number of minutes, got '{}'", value)),
+        "VANITY_POOL_SIZES" => check_loader(PoolSizePolicy::parse(value)),
+        "VANITY_PREFIX" | "VANITY_SUFFIX" => {
+            let ignore_case = env::var("VANITY_IGNORE_CASE").is_ok_and(|v| v.trim().eq_ignore_ascii_case("true"));
+            check_loader(VanityPattern::new("", value, ignore_case))
+        }
+        "VANITY_COUNTER_FLUSH" => match value.parse::<u64>() {
+            Ok(attempts) if attempts > 0 => Ok(()),
+            _ => Err(format!("expected a positive number of attempts, got '{}'", value)),
+        },
+        "METADATA_FALLBACK_HOSTS" => check_loader(FallbackHost::chain_from_env()),
+        "RPC_FALLBACK_URLS" => value.split(',').map(str::trim).filter(|url| !url.is_empty())
+            .try_for_each(|url| check_url(url, &["http", "https"])),
+        "METADATA_UPLOAD_FILENAME" => check_loader(parse_upload_filename(value)),
+        "PUMPFUN_UPLOAD_ATTEMPTS" => match value.parse::<u32>() {
+            Ok(attempts) if attempts > 0 => Ok(()),
+            _ => Err(format!("expected a positive number of attempts, got '{}'", value)),
+        },
+        "LAUNCH_COOLDOWN_MINUTES" | "LAUNCH_COOLDOWN_MAX_LAUNCHES" => check_loader(LaunchCooldown::from_env()),
+        "JITO_TIP_SOL" => match value.parse::<f64>() {
+            Ok(sol) if sol.is_finite() && sol > 0.0 => Ok(()),
+            _ => Err(format!("expected a positive SOL amount, got '{}'", value)),
+        },
+        "JITO_BLOCK_ENGINE" => value.parse::<BlockEngine>().map(drop).map_err(|e| e.to_string()),
+        "PRIORITY_FEE" | "COMPUTE_UNIT_LIMIT" | "PRIORITY_FEE_PERCENTILE" | "PRIORITY_FEE_MAX_MICROLAMPORTS" => check_loader(ComputeBudget::resolve(None, None)),
+        "BUY_PRICE_TOLERANCE_BPS" => value.parse::<u64>().map(drop)
+            .map_err(|_| format!("expected a whole number of basis points, got '{}'", value)),
+        "BALANCE_SAFETY_MARGIN_SOL" | "TOPUP_MAX_SOL" | "PRIORITY_FEE_WARN_SOL" | "HEALTH_MIN_BALANCE_SOL" => match value.parse::<f64>() {
+            Ok(sol) if sol.is_finite() && sol >= 0.0 => Ok(()),
+            _ => Err(format!("expected a non-negative SOL amount, got '{}'", value)),
+        },
+        "DRY_RUN" | "VERIFY_PINS" | "REPIN_TO_PINATA" | "COMMIT_METADATA_HASH" | "AUTO_TOPUP" | "SKIP_PREFLIGHT_CHECKS" | "VANITY_ENABLED" | "VANITY_IGNORE_CASE" | "METADATA_SHOW_NAME" | "JUPITER_FALLBACK" | "LAUNCHER_READ_ONLY" | "NOTIFY_CHARTS" => {
+            match value.to_lowercase().as_str() {
+                "true" | "false" => Ok(()),
+                _ => Err(format!("expected true or false, got '{}'", value)),
+            }
+        }
+        "WALLET_ACTIVITY_GUARD" => check_loader(WalletGuard::from_env()),
+        "MINT_MONITOR" => value.parse::<MintMonitor>().map(drop).map_err(|e| e.to_string()),
+        "SOL_PRICE_FEED" => check_loader(PriceFeed::from_env()),
+        "LAUNCH_ARTIFACTS_BUCKET" => check_loader(ArtifactStore::from_env()),
+        "MARKET_DATA_SOURCE" => check_loader(MarketDataSource::from_env()),
+        "METADATA_CREATED_ON" | "METADATA_TWITTER" | "METADATA_TELEGRAM" | "METADATA_WEBSITE" => check_loader(parse_link_url(value)),
+        "VANITY_FLEET_SECRET" | "VANITY_STORE_SECRET" | "LAUNCH_KEYPAIR_SECRET" => check_loader(KeypairCipher::from_key(key, value)),
+        _ => check_url(value, &["http", "https"]),
+    }
+}
+
+fn check_loader<T>(result: anyhow::Result<T>) -> Result<(), String> {
+    result.map(drop).map_err(|e| e.to_string())
+}
+
+fn check_url(value: &str, schemes: &[&str]) -> Result<(), String> {
+    let url = url::Url::parse(value).map_err(|e| format!("malformed URL: {}", e))?;
+    if !schemes.contains(&url.scheme()) {
+        return Err(format!("expected a {} URL, got scheme '{}'", schemes.join("/"), url.scheme()));
+    }
+    Ok(())
+}
+
+fn check_keypair(var_name: &str) -> Result<(), String> {
+    let bytes = SecurePrivateKey::load(var_name)
+        .and_then(|key| key.to_bytes())
+        .map_err(|e| e.to_string())?;
+    Keypair::try_from(&bytes[..])
+        .map(drop)
+        .map_err(|_| format!("expected a 64-byte keypair, got {} bytes", bytes.len()))
+}
+
+/// Keys listed in the `.env` file; dotenv never overrides the environment, but a listed key is almost always set there
+fn env_file_keys() -> HashSet<String> {
+    dotenv::dotenv().ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|contents| contents.lines()
+            .filter_map(|line| line.trim().trim_start_matches("export ").split_once('='))
+            .map(|(key, _)| key.trim().to_string())
+            .filter(|key| !key.starts_with('#'))
+            .collect())
+        .unwrap_or_default()
+}
+
+fn source_from(key: &str, env_file_keys: &HashSet<String>) -> ConfigSource {
+    if is_provided_secret(key) {
+        ConfigSource::SecretsBackend
+    } else if env_file_keys.contains(key) {
+        ConfigSource::EnvFile
+    } else if is_config_file_var(key) {
+        ConfigSource::ConfigFile
+    } else {
+        ConfigSource::Env
+    }
+}
+
+/// Where the current value of `key` came from
+pub fn config_source(key: &str) -> ConfigSource {
+    source_from(key, &env_file_keys())
+}
+
+impl ConfigError {
+    /// `key` is set but unusable
+    pub fn invalid(key: &str, reason: impl fmt::Display) -> Self {
+        Self::Invalid { key: key.to_string(), source: config_source(key), reason: reason.to_string() }
+    }
+
+    /// `key` is required but not set
+    pub fn missing(key: &str, hint: &str) -> Self {
+        Self::Missing { key: key.to_string(), hint: hint.to_string() }
+    }
+}
+
+/// Collects problems instead of stopping at the first one
+struct Checker {
+    env_file_keys: HashSet<String>,
+    private_key_flag: Option<&'static str>,
+    problems: Vec<ConfigError>,
+}
+
+impl Checker {
+    fn new(args: &Args) -> Self {
+        let private_key_flag = if args.keypair.is_some() {
+            Some("--keypair")
+        } else if args.private_key_stdin {
+            Some("--private-key-stdin")
+        } else {
+            None
+        };
+        Self { env_file_keys: env_file_keys(), private_key_flag, problems: Vec::new() }
+    }
+
+    fn source(&self, key: &str) -> ConfigSource {
+        if let (Some(flag), "PRIVATE_KEY") = (self.private_key_flag, key) {
+            ConfigSource::Flag(flag.to_string())
+        } else {
+            source_from(key, &self.env_file_keys)
+        }
+    }
+
+    fn is_set(&self, key: &str) -> bool {
+        is_provided_secret(key) || env::var(key).is_ok()
+    }
+
+    fn invalid(&mut self, key: &str, reason: String) {
+        let source = self.source(key);
+        self.problems.push(ConfigError::Invalid { key: key.to_string(), source, reason });
+    }
+
+    fn missing(&mut self, key: &str, hint: &str) {
+        self.problems.push(ConfigError::missing(key, hint));
+    }
+
+    fn check_flag(&mut self, flag: &str, result: Result<(), String>) {
+        if let Err(reason) = result {
+            self.problems.push(ConfigError::Invalid {
+                key: flag.trim_start_matches('-').to_string(),
+                source: ConfigSource::Flag(flag.to_string()),
+                reason,
+            });
+        }
+    }
+
+    fn check_create_args(&mut self, create: &CreateArgs) {
+        if let Some(image) = &create.image {
+            let exists = Path::new(image).is_file();
+            self.check_flag("--image", if exists { Ok(()) } else { Err(format!("file '{}' does not exist", image)) });
+        }
+        if let Some(url) = &create.image_url {
+            self.check_flag("--image-url", check_url(url, &["http", "https"]));
+        }
+        for (flag, part) in [("--vanity-prefix", &create.vanity_prefix), ("--vanity-suffix", &create.vanity_suffix)] {
+            if let Some(part) = part {
+                self.check_flag(flag, check_loader(VanityPattern::new("", part, create.vanity_ignore_case)));
+            }
+        }
+        if create.compute_units.is_some() {
+            self.check_flag("--compute-units", check_loader(ComputeBudget::resolve(create.priority_fee, create.compute_units)));
+        }
+    }
+}
+
+/// Validate the environment, `.env`, provider secrets and flags for `args`, reporting every problem together
+pub fn validate_config(args: &Args) -> Result<(), ConfigErrors> {
+    let mut checker = Checker::new(args);
+
+    for &key in CHECKED_VARS {
+        if let Ok(value) = env::var(key) {
+            if let Err(reason) = check_var(key, &value) {
+                checker.invalid(key, reason);
+            }
+        }
+    }
+
+    // Launches cannot start without a wallet and an RPC endpoint; other commands load them lazily
+    let launching = args.is_launch();
+    let wallet = args.create_args().and_then(|create| create.wallet.as_ref());
+    if let Some(wallet) = wallet {
+        if let Err(e) = wallet.load() {
+            checker.problems.push(e);
+        }
+    } else if checker.is_set("PRIVATE_KEY") {
+        if let Err(reason) = check_keypair("PRIVATE_KEY") {
+            checker.invalid("PRIVATE_KEY", reason);
+        }
+    } else if launching {
+        checker.missing("PRIVATE_KEY", PRIVATE_KEY_HINT);
+    }
+    let cluster_selected = selected_cluster().ok().flatten().is_some();
+    if checker.is_set("HELIUS_API_KEY") && !cluster_selected {
+        let rpc_url = SecureApiKey::load("HELIUS_API_KEY")
+            .map(|key| key.expose_secret().trim().to_string())
+            .unwrap_or_default();
+        if let Err(reason) = check_url(&rpc_url, &["http", "https"]) {
+            checker.invalid("HELIUS_API_KEY", format!("{} (expected the full RPC URL)", reason));
+        }
+    } else if launching && !cluster_selected {
+        checker.missing("HELIUS_API_KEY", RPC_URL_HINT);
+    }
+    if env::var("AUTO_TOPUP").is_ok_and(|v| v.trim().eq_ignore_ascii_case("true")) {
+        if !checker.is_set("FUNDING_PRIVATE_KEY") {
+            checker.missing("FUNDING_PRIVATE_KEY", "required when AUTO_TOPUP=true");
+        } else if let Err(reason) = check_keypair("FUNDING_PRIVATE_KEY") {
+            checker.invalid("FUNDING_PRIVATE_KEY", reason);
+        }
+    }
+
+    if let Some(create) = args.create_args() {
+        checker.check_create_args(create);
+    }
+
+    if checker.problems.is_empty() {
+        Ok(())
+    } else {
+        Err(ConfigErrors(checker.problems))
+    }
+}
+
+/// Log every configuration problem and exit, or return when the configuration is usable
+pub fn validate_config_or_exit(args: &Args) {
+    if let Err(errors) = validate_config(args) {
+        error!("{}", errors);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_var_and_report() {
+        assert!(check_var("RPC_WS_URL", "wss://example.com").is_ok());
+        assert!(check_var("RPC_WS_URL", "https://example.com").is_err());
+        assert!(check_var("PUMP_PROGRAM_ID", "not-base58!").is_err());
+        assert!(check_var("PUMP_PROGRAM_VERSION", "auto").is_ok());
+        assert!(check_var("PUMP_SEED_GLOBAL", &"x".repeat(33)).is_err());
+        assert!(check_var("TOPUP_MAX_SOL", "-1").is_err());
+        assert!(check_var("DRY_RUN", "yes").is_err());
+        assert!(check_var("NOTIFY_WEBHOOK_URL", "hooks.example.com").is_err());
+        assert!(check_var("METADATA_TWITTER", "https://x.com/pve").is_ok());
+        assert!(check_var("METADATA_WEBSITE", "").is_ok());
+        assert!(check_var("METADATA_TELEGRAM", "t.me/pve").is_err());
+        assert!(check_var("METADATA_WEBSITE", "javascript:alert(1)").is_err());
+
+        let errors = ConfigErrors(vec![
+            ConfigError::Missing { key: "PRIVATE_KEY".to_string(), hint: "set it".to_string() },
+            ConfigError::Invalid { key: "DRY_RUN".to_string(), source: ConfigSource::EnvFile, reason: "bad".to_string() },
+        ]);
+        assert_eq!(errors.to_string(), "2 configuration problem(s):\n  - PRIVATE_KEY is not set (set it)\n  - DRY_RUN (.env file): bad");
+    }
+}
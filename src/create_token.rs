@@ -2,71 +2,588 @@ use dotenv::dotenv;
 use anyhow::Result;
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
+    message::Message,
     pubkey::Pubkey,
     signature::{Keypair, Signature},
     signer::Signer,
-    transaction::Transaction,
+    transaction::{Transaction, TransactionError},
 };
 use solana_commitment_config::CommitmentConfig;
+use solana_client::client_error::Result as ClientResult;
 use solana_client::nonblocking::rpc_client::RpcClient;
-use std::{env, str::FromStr, sync::Arc};
-use log::{info, error};
+use std::{collections::HashMap, env, str::FromStr, sync::Arc, time::SystemTime};
+use log::{info, warn, error};
 
-use crate::vanity_address::{VanityConfig, get_global_vanity_pool};
-use crate::secure_credentials::{SecurePrivateKey, SecureApiKey};
+use crate::vanity_address::{GeneratedVanityAddress, VanityAvailability, VanityConfig, get_global_vanity_pool};
+use crate::secure_credentials::SecureApiKey;
+use crate::pump_accounts::{GlobalAccount, BondingCurveAccount, FeeRecipientCache, TOKEN_UNIT};
+use crate::tx_size::{TransactionComponent, validate_transaction_size};
+use crate::tx_dump::dump_transaction;
+use crate::audit_log::{AuditLog, AuditEvent};
+use crate::squads::{SquadsConfig, SquadsProposal, compile_vault_message, fetch_multisig};
+use crate::price_feed::{sol_usd_price, format_sol};
+use crate::launch_cost::LaunchCost;
+use crate::funding::{system_transfer_instruction, FundingWallet};
+use crate::media::{parse_upload_filename, prepare_media, PreparedMedia};
+use crate::metadata_hosts::{pumpfun_attempts_from_env, FallbackHost};
+use crate::wallet_guard::WalletGuard;
+use crate::cluster::selected_cluster;
+use crate::progress::progress;
+use serde_json::json;
+use crate::tx_journal::{TxJournal, PriorSubmission, wait_for_signature, send_config, confirm_sent};
+use crate::jito::{wait_for_landing, JitoConfig};
+use crate::metadata_update::{parse_metadata_account, update_metadata_instruction, MetadataAccount};
+use crate::authority_check::{check_authorities, parse_mint_authorities, AuthorityReport};
+use crate::read_only::ensure_writable;
+use crate::rpc_pool::RpcPool;
+use crate::simulation::explain_failure;
+use crate::launch_dir::{mint_keypair_cipher_from_env, update_manifest, LaunchDir, LaunchManifest};
+use crate::limits::{validate_token_fields, check_advertised_limits, MAX_URI_LEN, RECOMMENDED_IMAGE_BYTES};
+use crate::config_check::{ConfigError, RPC_URL_HINT};
+use crate::wallets::{load_wallet, WalletSpec};
+use crate::time_lock::enforce_not_before;
+use crate::launch_rate::LaunchCooldown;
+use crate::extra_instructions::{check_signer_set, check_signers, has_compute_budget, memo_instruction, priority_fee_lamports, priority_fee_warn_lamports_from_env, ExtraInstructions};
+use crate::priority_fee::ComputeBudget;
+use crate::metadata_commitment::{canonical_metadata_hash, commitment_memo, fetch_metadata};
+use crate::warnings::{confirm_launch, LaunchWarning, LaunchWarnings, WarningSeverity};
+use crate::name_collision::warn_name_collisions;
+use crate::pump_swap::{self, fetch_pool_state};
+use crate::trade::{
+    CurveState, BuyOrder, SellAmount, SellOrder, SimulatedFill, Venue, buy_instruction, sell_instruction,
+    create_associated_token_account_idempotent, check_simulated_fill, price_tolerance_from_env,
+};
+use solana_client::rpc_config::{RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig};
+use solana_account_decoder_client_types::UiAccountEncoding;
 pub const IMAGE_FILENAME: &str = "image.png";
+/// Image used when no `--image` is given: `TOKEN_IMAGE`, or `data/image.png`
 pub fn get_default_image_path() -> String {
-    format!("data/{}", IMAGE_FILENAME)
+    env::var("TOKEN_IMAGE").unwrap_or_else(|_| format!("data/{}", IMAGE_FILENAME))
 }
 
 pub const DEFAULT_NAME_TEMPLATE: &str = "{}";
 pub const DEFAULT_DESCRIPTION_TEMPLATE: &str = "{}";
 pub const PUMP_FUN_API_URL: &str = "https://pump.fun/api/ipfs";
 
-// Constants from the IDL
-const PROGRAM_ID: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
-const MPL_TOKEN_METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
-const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
+// Constants from the IDL (defaults, see ProgramConfig for overrides)
+pub const PROGRAM_ID: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
+pub const MPL_TOKEN_METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+pub const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
 const CREATE_INSTRUCTION_DISCRIMINATOR: &[u8] = &[24, 30, 200, 40, 5, 28, 7, 119];
 const GLOBAL_ACCOUNT_SEED: &[u8] = b"global";
 const MINT_AUTHORITY_SEED: &[u8] = b"mint-authority";
 const BONDING_CURVE_SEED: &[u8] = b"bonding-curve";
 const METADATA_SEED: &[u8] = b"metadata";
 const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+const CREATOR_VAULT_SEED: &[u8] = b"creator-vault";
+
+// Global account data length once `creator_fee_basis_points` was added
+// (discriminator + fields up to and including creator_fee_basis_points)
+const GLOBAL_CREATOR_FEE_LAYOUT_LEN: usize = 162;
 
 // Transaction constants
-pub const MIN_REQUIRED_LAMPORTS: u64 = 15_500_000; // 0.0155 SOL
 pub const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
 
+/// Arguments of the pump.fun `create` instruction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateInstructionArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    /// Only taken by [`ProgramVersion::CreatorVault`]
+    pub creator: Option<Pubkey>,
+}
+
+/// `create` instruction data as in the IDL: discriminator, name, symbol and uri as Borsh strings
+/// (u32 LE length, then UTF-8), then the creator when the program takes one
+pub fn encode_create_args(args: &CreateInstructionArgs) -> Vec<u8> {
+    let mut data = CREATE_INSTRUCTION_DISCRIMINATOR.to_vec();
+    for field in [&args.name, &args.symbol, &args.uri] {
+        data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+        data.extend_from_slice(field.as_bytes());
+    }
+    if let Some(creator) = args.creator {
+        data.extend_from_slice(creator.as_ref());
+    }
+    data
+}
+
+/// Inverse of [`encode_create_args`]; rejects other instructions and trailing bytes
+pub fn decode_create_args(data: &[u8]) -> Result<CreateInstructionArgs> {
+    let mut rest = data.strip_prefix(CREATE_INSTRUCTION_DISCRIMINATOR)
+        .ok_or_else(|| anyhow::anyhow!("Not a pump.fun create instruction"))?;
+    let mut read_string = |field: &str| -> Result<String> {
+        let len = rest.get(..4).map(|b| u32::from_le_bytes(b.try_into().unwrap()) as usize)
+            .ok_or_else(|| anyhow::anyhow!("Create instruction data ends before the {} length", field))?;
+        let bytes = rest.get(4..4 + len)
+            .ok_or_else(|| anyhow::anyhow!("Create instruction {} is truncated", field))?;
+        let value = String::from_utf8(bytes.to_vec()).map_err(|_| anyhow::anyhow!("Create instruction {} is not UTF-8", field))?;
+        rest = &rest[4 + len..];
+        Ok(value)
+    };
+    let (name, symbol, uri) = (read_string("name")?, read_string("symbol")?, read_string("uri")?);
+    let creator = match rest.len() {
+        0 => None,
+        32 => Some(Pubkey::new_from_array(rest.try_into().unwrap())),
+        n => return Err(anyhow::anyhow!("Create instruction has {} unexpected trailing bytes", n)),
+    };
+    Ok(CreateInstructionArgs { name, symbol, uri, creator })
+}
+
+/// pump.fun program generations with different instruction layouts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgramVersion {
+    /// Original layout: `create` takes (name, symbol, uri) and trades pass `rent`
+    Legacy,
+    /// Creator-fee layout: `create` takes a `creator` argument and trades pay the creator vault PDA
+    CreatorVault,
+}
+
+impl FromStr for ProgramVersion {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "legacy" | "v1" => Ok(ProgramVersion::Legacy),
+            "creator-vault" | "creator_vault" | "v2" => Ok(ProgramVersion::CreatorVault),
+            other => Err(anyhow::anyhow!("Unknown program version '{}' (expected legacy, creator-vault or auto)", other)),
+        }
+    }
+}
+
+impl ProgramVersion {
+    /// Infer the program version from the raw global account data
+    pub fn detect_from_global(data: &[u8]) -> Self {
+        if data.len() >= GLOBAL_CREATOR_FEE_LAYOUT_LEN {
+            ProgramVersion::CreatorVault
+        } else {
+            ProgramVersion::Legacy
+        }
+    }
+}
+
+/// Program addresses and PDA seeds used to build pump.fun instructions.
+///
+/// Defaults match the mainnet deployment; every field can be overridden from the
+/// environment to target devnet clones or forked deployments without recompiling.
+#[derive(Debug, Clone)]
+pub struct ProgramConfig {
+    pub program_id: Pubkey,
+    pub metadata_program_id: Pubkey,
+    pub global_seed: Vec<u8>,
+    pub mint_authority_seed: Vec<u8>,
+    pub bonding_curve_seed: Vec<u8>,
+    pub metadata_seed: Vec<u8>,
+    pub event_authority_seed: Vec<u8>,
+    pub creator_vault_seed: Vec<u8>,
+    /// Fixed program version, or `None` to auto-detect from the global account
+    pub version: Option<ProgramVersion>,
+}
+
+impl Default for ProgramConfig {
+    fn default() -> Self {
+        Self {
+            program_id: Pubkey::from_str(PROGRAM_ID).unwrap(),
+            metadata_program_id: Pubkey::from_str(MPL_TOKEN_METADATA_PROGRAM_ID).unwrap(),
+            global_seed: GLOBAL_ACCOUNT_SEED.to_vec(),
+            mint_authority_seed: MINT_AUTHORITY_SEED.to_vec(),
+            bonding_curve_seed: BONDING_CURVE_SEED.to_vec(),
+            metadata_seed: METADATA_SEED.to_vec(),
+            event_authority_seed: EVENT_AUTHORITY_SEED.to_vec(),
+            creator_vault_seed: CREATOR_VAULT_SEED.to_vec(),
+            version: None,
+        }
+    }
+}
+
+impl ProgramConfig {
+    /// Load program configuration, applying any overrides found in the environment:
+    /// `PUMP_PROGRAM_ID`, `MPL_TOKEN_METADATA_PROGRAM_ID`, `PUMP_PROGRAM_VERSION`
+    /// and the `PUMP_SEED_*` variables.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let mut config = Self::default();
+
+        if let Ok(value) = env::var("PUMP_PROGRAM_ID") {
+            config.program_id = Pubkey::from_str(value.trim())
+                .map_err(|e| ConfigError::invalid("PUMP_PROGRAM_ID", format!("'{}' is not a pubkey: {}", value, e)))?;
+            info!("Using program id override: {}", config.program_id);
+        }
+        if let Ok(value) = env::var("MPL_TOKEN_METADATA_PROGRAM_ID") {
+            config.metadata_program_id = Pubkey::from_str(value.trim())
+                .map_err(|e| ConfigError::invalid("MPL_TOKEN_METADATA_PROGRAM_ID", format!("'{}' is not a pubkey: {}", value, e)))?;
+            info!("Using metadata program id override: {}", config.metadata_program_id);
+        }
+
+        if let Ok(value) = env::var("PUMP_PROGRAM_VERSION") {
+            if !value.trim().eq_ignore_ascii_case("auto") {
+                config.version = Some(value.parse().map_err(|e| ConfigError::invalid("PUMP_PROGRAM_VERSION", e))?);
+                info!("Using program version override: {:?}", config.version);
+            }
+        }
+
+        let seed_overrides: [(&str, &mut Vec<u8>); 6] = [
+            ("PUMP_SEED_GLOBAL", &mut config.global_seed),
+            ("PUMP_SEED_MINT_AUTHORITY", &mut config.mint_authority_seed),
+            ("PUMP_SEED_BONDING_CURVE", &mut config.bonding_curve_seed),
+            ("PUMP_SEED_METADATA", &mut config.metadata_seed),
+            ("PUMP_SEED_EVENT_AUTHORITY", &mut config.event_authority_seed),
+            ("PUMP_SEED_CREATOR_VAULT", &mut config.creator_vault_seed),
+        ];
+        for (var_name, seed) in seed_overrides {
+            if let Ok(value) = env::var(var_name) {
+                // PDA seeds are limited to 32 bytes each
+                if value.is_empty() || value.len() > 32 {
+                    return Err(ConfigError::invalid(var_name, "must be between 1 and 32 bytes"));
+                }
+                info!("Using seed override {}={}", var_name, value);
+                *seed = value.into_bytes();
+            }
+        }
+
+        Ok(config)
+    }
+
+    pub fn global_pda(&self) -> Pubkey {
+        Pubkey::find_program_address(&[&self.global_seed], &self.program_id).0
+    }
+
+    pub fn bonding_curve_pda(&self, mint: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[&self.bonding_curve_seed, mint.as_ref()], &self.program_id).0
+    }
+
+    /// The bonding curve's associated token account holding the curve's token reserves
+    pub fn associated_bonding_curve(&self, mint: &Pubkey) -> Pubkey {
+        get_associated_token_address(&self.bonding_curve_pda(mint), mint)
+    }
+
+    pub fn metadata_pda(&self, mint: &Pubkey) -> Pubkey {
+        let mpl_program = self.metadata_program_id;
+        Pubkey::find_program_address(
+            &[&self.metadata_seed, mpl_program.as_ref(), mint.as_ref()],
+            &mpl_program,
+        ).0
+    }
+
+    pub fn mint_authority_pda(&self) -> Pubkey {
+        Pubkey::find_program_address(&[&self.mint_authority_seed], &self.program_id).0
+    }
+
+    pub fn event_authority_pda(&self) -> Pubkey {
+        Pubkey::find_program_address(&[&self.event_authority_seed], &self.program_id).0
+    }
+
+    pub fn creator_vault_pda(&self, creator: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[&self.creator_vault_seed, creator.as_ref()], &self.program_id).0
+    }
+}
+
+/// Associated token account for `owner` and `mint` under the SPL token program.
+///
+/// Calculated manually to avoid type mismatches between the SDK and SPL crate versions.
+pub fn get_associated_token_address(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            owner.as_ref(),
+            &Pubkey::new_from_array(spl_token::ID.to_bytes()).to_bytes(),
+            mint.as_ref(),
+        ],
+        &Pubkey::new_from_array(spl_associated_token_account::ID.to_bytes()),
+    ).0
+}
+
+/// Raw amount held by an SPL token account, 0 if `data` is not one
+fn token_account_amount(data: &[u8]) -> u64 {
+    data.get(64..72).map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap())).unwrap_or(0)
+}
+
+/// Mint recorded with an earlier launch transaction; a landed launch is never reported without one
+fn journaled_mint(signature: &Signature, mint: Option<Pubkey>) -> Result<Pubkey> {
+    mint.ok_or_else(|| anyhow::anyhow!(
+        "Launch transaction {} already landed, but the transaction journal has no mint for it; look the mint up in that transaction",
+        signature
+    ))
+}
+
+/// Per-launch options that are not part of the token metadata
+#[derive(Debug, Clone, Default)]
+pub struct LaunchOptions {
+    /// Write the signed transaction to this file before sending
+    pub dump_tx_path: Option<String>,
+    /// Extra attempts, each with a fresh mint, after a launch transaction fails irrecoverably
+    pub auto_retry: u32,
+    /// On-chain creator when different from the payer
+    pub creator: Option<Pubkey>,
+    /// Coin page toggles and links sent with the metadata upload
+    pub metadata: MetadataForm,
+    /// Commitment the launch must reach before it is reported as successful (RPC client default when unset)
+    pub confirmation: Option<CommitmentConfig>,
+    /// Announced launch time; the transaction is never sent before it
+    pub not_before: Option<SystemTime>,
+    /// Skip the per-wallet launch cooldown for an intentional burst
+    pub ignore_cooldown: bool,
+    /// Memo, transfers and custom instructions appended to the launch transaction
+    pub extra_instructions: ExtraInstructions,
+    /// Append a memo with the SHA-256 of the uploaded metadata JSON
+    pub commit_metadata_hash: bool,
+    /// Show the collected warnings and ask before sending
+    pub confirm: bool,
+    /// Lamports the payer spends buying the new token in the create transaction
+    pub dev_buy_lamports: Option<u64>,
+    /// Slippage allowed on the dev buy
+    pub dev_buy_slippage_bps: u64,
+    /// Submit as a Jito bundle with a tip instead of only through RPC
+    pub jito: Option<JitoConfig>,
+    /// Compute unit limit and priority fee; `None` sends no compute budget instructions
+    pub compute_budget: Option<ComputeBudget>,
+    /// Send without simulating the signed transaction first
+    pub skip_simulation: bool,
+    /// Keep the launch's artifacts in a new `<root>/<unix time>-<symbol>/` directory
+    pub launch_dir_root: Option<std::path::PathBuf>,
+    /// Who triggered the launch, recorded in the audit log and launch directory
+    pub operator: Option<String>,
+    /// Fingerprint of the API key a launch was requested with
+    pub api_key_id: Option<String>,
+    /// Collects warnings across the launch; they are returned in the [`LaunchResult`]
+    pub warnings: LaunchWarnings,
+}
+
+impl LaunchOptions {
+    /// Most the dev buy can spend, including slippage
+    pub fn dev_buy_max_cost(&self) -> u64 {
+        self.dev_buy_lamports
+            .map(|lamports| (lamports as u128 * (10_000 + self.dev_buy_slippage_bps as u128) / 10_000) as u64)
+            .unwrap_or(0)
+    }
+
+    pub fn jito_tip_lamports(&self) -> u64 {
+        self.jito.as_ref().map(|jito| jito.tip_lamports).unwrap_or(0)
+    }
+
+    /// Most the priority fee can cost; compute budget instructions from `--instructions` replace ours
+    pub fn priority_fee_lamports(&self) -> u64 {
+        priority_fee_lamports(&self.extra_instructions.instructions)
+            .or_else(|| self.compute_budget.as_ref().map(ComputeBudget::max_fee_lamports))
+            .unwrap_or(0)
+    }
+}
+
+/// Outcome of [`TokenCreator::create_token`]
+#[derive(Debug, Clone)]
+pub struct LaunchResult {
+    pub signature: Signature,
+    pub mint: Pubkey,
+    /// Everything the pipeline warned about, most severe first
+    pub warnings: Vec<LaunchWarning>,
+    /// Artifacts of this launch, when [`LaunchOptions::launch_dir_root`] is set
+    pub launch_dir: Option<LaunchDir>,
+}
+
+/// Metadata uploaded for a launch: the output of [`TokenCreator::prepare_metadata`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub description: String,
+    pub uri: String,
+}
+
+enum MintSigner {
+    Keypair(Keypair),
+    Vanity(GeneratedVanityAddress),
+}
+
+/// Mint address of a launch and the key that signs for it: the output of [`TokenCreator::assign_mint`]
+pub struct AssignedMint {
+    pub address: Pubkey,
+    signer: MintSigner,
+}
+
+impl AssignedMint {
+    /// A mint from a caller-supplied keypair, e.g. one ground elsewhere
+    pub fn from_keypair(keypair: Keypair) -> Self {
+        Self { address: keypair.pubkey(), signer: MintSigner::Keypair(keypair) }
+    }
+
+    pub fn is_vanity(&self) -> bool {
+        matches!(self.signer, MintSigner::Vanity(_))
+    }
+
+    fn keypair(&self) -> &Keypair {
+        match &self.signer {
+            MintSigner::Keypair(keypair) => keypair,
+            MintSigner::Vanity(generated) => generated.keypair.keypair(),
+        }
+    }
+
+    fn signer(&self) -> &dyn Signer {
+        self.keypair()
+    }
+}
+
+/// Unsigned launch: the output of [`TokenCreator::build_transaction`]. Integrators may add
+/// instructions to `components` before signing; they run after the create instruction.
+pub struct LaunchTransaction {
+    pub mint: AssignedMint,
+    pub creator: Pubkey,
+    pub program_version: ProgramVersion,
+    pub components: Vec<TransactionComponent>,
+}
+
+impl LaunchTransaction {
+    pub fn push_instruction(&mut self, label: impl Into<String>, instruction: Instruction) {
+        self.components.push(TransactionComponent::new(label, instruction));
+    }
+}
+
+/// Launch signed by the payer and mint: the output of [`TokenCreator::sign`]
+pub struct SignedLaunch {
+    pub mint: Pubkey,
+    pub vanity: bool,
+    pub transaction: Transaction,
+    pub last_valid_block_height: u64,
+    pub size_bytes: usize,
+}
+
+impl SignedLaunch {
+    pub fn signature(&self) -> Signature {
+        self.transaction.signatures[0]
+    }
+}
+
+/// Optional fields of pump.fun's create form that control what the coin page shows
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetadataForm {
+    pub show_name: bool,
+    pub created_on: String,
+    pub twitter: String,
+    pub telegram: String,
+    pub website: String,
+    /// Filename sent with the media instead of the source file's name
+    pub upload_filename: Option<String>,
+}
+
+impl Default for MetadataForm {
+    fn default() -> Self {
+        Self {
+            show_name: true,
+            created_on: "https://pump.fun".to_string(),
+            twitter: String::new(),
+            telegram: String::new(),
+            website: String::new(),
+            upload_filename: None,
+        }
+    }
+}
+
+impl MetadataForm {
+    /// Defaults overridden by `METADATA_SHOW_NAME`, `METADATA_CREATED_ON`, `METADATA_TWITTER`,
+    /// `METADATA_TELEGRAM`, `METADATA_WEBSITE` and `METADATA_UPLOAD_FILENAME`
+    pub fn from_env() -> Result<Self> {
+        let mut form = Self::default();
+        if let Ok(value) = env::var("METADATA_SHOW_NAME") {
+            form.show_name = value.trim().parse()
+                .map_err(|_| anyhow::anyhow!("METADATA_SHOW_NAME must be true or false, got '{}'", value))?;
+        }
+        for (var_name, field) in [
+            ("METADATA_CREATED_ON", &mut form.created_on),
+            ("METADATA_TWITTER", &mut form.twitter),
+            ("METADATA_TELEGRAM", &mut form.telegram),
+            ("METADATA_WEBSITE", &mut form.website),
+        ] {
+            if let Ok(value) = env::var(var_name) {
+                *field = parse_link_url(&value).map_err(|e| anyhow::anyhow!("{}: {}", var_name, e))?;
+            }
+        }
+        if let Ok(value) = env::var("METADATA_UPLOAD_FILENAME") {
+            form.upload_filename = Some(parse_upload_filename(&value)?);
+        }
+        Ok(form)
+    }
+}
+
+/// A coin page link: an http(s) URL with a host, or empty for no link
+pub fn parse_link_url(value: &str) -> Result<String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(String::new());
+    }
+    let url = url::Url::parse(value).map_err(|e| anyhow::anyhow!("'{}' is not a URL: {}", value, e))?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none_or(str::is_empty) {
+        return Err(anyhow::anyhow!("'{}' must be an http(s) link", value));
+    }
+    Ok(value.to_string())
+}
+
+/// RPC endpoint URL configured through `HELIUS_API_KEY`, or the public endpoint of the cluster
+/// selected with `--cluster` / `SOLANA_CLUSTER`
+pub fn rpc_url_from_env() -> Result<String> {
+    if let Some(cluster) = selected_cluster()? {
+        return Ok(cluster.rpc_url().to_string());
+    }
+    let secure_api_key = SecureApiKey::load("HELIUS_API_KEY")?;
+    Ok(secure_api_key.expose_secret().to_string())
+}
+
+/// Any signer able to pay for and sign launch transactions (keypair, hardware wallet, remote signer, ...)
+pub type PayerSigner = Arc<dyn Signer + Send + Sync>;
+
+/// Seconds fee recipients stay cached unless `FEE_RECIPIENT_CACHE_SECS` is set
+pub const DEFAULT_FEE_RECIPIENT_CACHE_SECS: u64 = 300;
+
 pub struct TokenCreator {
+    /// Primary endpoint of `rpc_pool`
     rpc: Arc<RpcClient>,
-    program_id: Pubkey,
-    payer: Keypair,
+    rpc_pool: Arc<RpcPool>,
+    program: ProgramConfig,
+    payer: PayerSigner,
+    fee_recipients: FeeRecipientCache,
 }
 
 impl TokenCreator {
-    pub fn new() -> Self {
+    /// Build a creator from `PRIVATE_KEY` and the configured RPC endpoint
+    pub fn new() -> Result<Self, ConfigError> {
+        Self::with_wallet(None)
+    }
+
+    /// Build a creator paying with `wallet` (see `--wallet`), or `PRIVATE_KEY` when `None`
+    pub fn with_wallet(wallet: Option<&WalletSpec>) -> Result<Self, ConfigError> {
         dotenv().ok(); // Load .env file
 
         // Load credentials securely
-        let secure_private_key = SecurePrivateKey::from_env("PRIVATE_KEY")
-            .expect("PRIVATE_KEY must be set in .env");
-        let secure_api_key = SecureApiKey::from_env("HELIUS_API_KEY")
-            .expect("HELIUS_API_KEY must be set in .env");
-
-        let private_key_bytes = secure_private_key.to_bytes()
-            .expect("Invalid private key format");
-        let payer = Keypair::try_from(&private_key_bytes[..])
-            .expect("Failed to create keypair from private key");
-        
-        let rpc_url = secure_api_key.expose_secret().to_string();
+        let payer = load_wallet(wallet)?;
+        if let Some(wallet) = wallet {
+            info!("Using wallet {:?} ({}) as payer", wallet, payer.pubkey());
+        }
+
+        // Fallbacks only back up the configured endpoint; they may be on another cluster than --cluster
+        let rpc_urls = match selected_cluster() {
+            Ok(Some(cluster)) => vec![cluster.rpc_url().to_string()],
+            Ok(None) => std::iter::once(SecureApiKey::load("HELIUS_API_KEY")
+                    .map_err(|_| ConfigError::missing("HELIUS_API_KEY", RPC_URL_HINT))?
+                    .expose_secret()
+                    .to_string())
+                .chain(RpcPool::fallback_urls_from_env())
+                .collect(),
+            Err(e) => return Err(ConfigError::invalid("SOLANA_CLUSTER", e)),
+        };
 
-        let rpc = Arc::new(RpcClient::new_with_commitment(
-            rpc_url,
-            CommitmentConfig::confirmed(),
-        ));
+        Self::with_endpoints(rpc_urls, Arc::new(payer))
+    }
+
+    /// Build a creator around an arbitrary payer signer instead of the `PRIVATE_KEY` keypair
+    pub fn with_signer(rpc_url: String, payer: PayerSigner) -> Result<Self, ConfigError> {
+        Self::with_endpoints(vec![rpc_url], payer)
+    }
 
-        let program_id = Pubkey::from_str(PROGRAM_ID).unwrap();
+    /// Build a creator that fails over from the first of `rpc_urls` to the others
+    pub fn with_endpoints(rpc_urls: Vec<String>, payer: PayerSigner) -> Result<Self, ConfigError> {
+        let rpc_pool = Arc::new(RpcPool::new(rpc_urls, CommitmentConfig::confirmed()));
+        let rpc = rpc_pool.primary();
+        if rpc_pool.len() > 1 {
+            info!("Using {} RPC endpoints with failover", rpc_pool.len());
+        }
+
+        let program = ProgramConfig::from_env()?;
 
         if VanityConfig::from_env().enabled {
             info!("Vanity address generation enabled (using global pool)");
@@ -74,42 +591,450 @@ impl TokenCreator {
             info!("Vanity address generation disabled");
         }
 
-        TokenCreator {
+        let fee_recipient_ttl = env::var("FEE_RECIPIENT_CACHE_SECS").ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_FEE_RECIPIENT_CACHE_SECS);
+
+        Ok(TokenCreator {
             rpc,
-            program_id,
+            rpc_pool,
+            program,
             payer,
-        }
+            fee_recipients: FeeRecipientCache::new(std::time::Duration::from_secs(fee_recipient_ttl)),
+        })
+    }
+
+    /// The same payer against another RPC endpoint
+    pub fn with_rpc_url(&self, rpc_url: String) -> Result<Self, ConfigError> {
+        Self::with_signer(rpc_url, self.payer.clone())
+    }
+
+    pub fn payer(&self) -> &dyn Signer {
+        self.payer.as_ref()
+    }
+
+    pub fn rpc(&self) -> &RpcClient {
+        &self.rpc
+    }
+
+    pub fn rpc_pool(&self) -> &RpcPool {
+        &self.rpc_pool
+    }
+
+    pub async fn payer_balance(&self) -> ClientResult<u64> {
+        let payer = self.payer.pubkey();
+        self.rpc_pool.with_failover(|rpc| async move { rpc.get_balance(&payer).await }).await
+    }
+
+    pub fn program_config(&self) -> &ProgramConfig {
+        &self.program
     }
 
     pub fn get_global_pda(&self) -> Pubkey {
-        Pubkey::find_program_address(&[GLOBAL_ACCOUNT_SEED], &self.program_id).0
+        self.program.global_pda()
     }
 
     pub fn get_bonding_curve_pda(&self, mint: &Pubkey) -> Pubkey {
-        Pubkey::find_program_address(&[BONDING_CURVE_SEED, mint.as_ref()], &self.program_id).0
+        self.program.bonding_curve_pda(mint)
+    }
+
+    pub fn get_associated_bonding_curve(&self, mint: &Pubkey) -> Pubkey {
+        self.program.associated_bonding_curve(mint)
     }
 
     pub fn get_metadata_pda(&self, mint: &Pubkey) -> Pubkey {
-        let mpl_program = Pubkey::from_str(MPL_TOKEN_METADATA_PROGRAM_ID).unwrap();
-        Pubkey::find_program_address(
-            &[METADATA_SEED, mpl_program.as_ref(), mint.as_ref()],
-            &mpl_program,
-        ).0
+        self.program.metadata_pda(mint)
     }
 
     pub fn get_mint_authority_pda(&self) -> Pubkey {
-        Pubkey::find_program_address(&[MINT_AUTHORITY_SEED], &self.program_id).0
+        self.program.mint_authority_pda()
     }
 
     pub fn get_event_authority_pda(&self) -> Pubkey {
-        Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &self.program_id).0
+        self.program.event_authority_pda()
+    }
+
+    pub fn get_creator_vault_pda(&self, creator: &Pubkey) -> Pubkey {
+        self.program.creator_vault_pda(creator)
+    }
+
+    /// Fetch and decode the pump.fun global account
+    pub async fn fetch_global_account(&self) -> Result<GlobalAccount, anyhow::Error> {
+        let data = self.rpc.get_account_data(&self.get_global_pda()).await?;
+        GlobalAccount::from_account_data(&data)
+    }
+
+    /// Fee recipient for a buy instruction, read from the global account and cached
+    pub async fn fee_recipient(&self) -> Result<Pubkey, anyhow::Error> {
+        if let Some(recipient) = self.fee_recipients.get() {
+            return Ok(recipient);
+        }
+        let candidates = self.fetch_global_account().await?.fee_recipient_candidates();
+        info!("Loaded {} fee recipient(s) from the global account", candidates.len());
+        let recipient = candidates[0];
+        self.fee_recipients.set(candidates);
+        Ok(recipient)
+    }
+
+    /// Drop cached fee recipients after a failed buy; pump.fun may have rotated its fee accounts
+    pub fn invalidate_fee_recipient(&self) {
+        self.fee_recipients.invalidate();
+    }
+
+    /// Fetch and decode the bonding curve account for `mint`
+    pub async fn fetch_bonding_curve(&self, mint: &Pubkey) -> Result<BondingCurveAccount, anyhow::Error> {
+        let data = self.rpc.get_account_data(&self.get_bonding_curve_pda(mint)).await?;
+        BondingCurveAccount::from_account_data(&data)
+    }
+
+    /// Read everything a trade against `mint` needs: its bonding curve (with the on-chain
+    /// creator), the global account, the program version and a fee recipient. Works for any
+    /// pump.fun mint, not only ones launched from this wallet.
+    pub async fn fetch_curve_state(&self, mint: &Pubkey) -> Result<CurveState, anyhow::Error> {
+        let curve = self.fetch_bonding_curve(mint).await
+            .map_err(|e| anyhow::anyhow!("{} has no readable pump.fun bonding curve: {}", mint, e))?;
+        Ok(CurveState {
+            mint: *mint,
+            curve,
+            global: self.fetch_global_account().await?,
+            version: self.resolve_program_version().await?,
+            fee_recipient: self.fee_recipient().await?,
+        })
+    }
+
+    /// Instructions buying `mint` for `sol_lamports` (creating the payer's token account if needed).
+    /// Migrated tokens are bought from their PumpSwap pool instead of the completed curve.
+    pub async fn build_buy_instructions(&self, mint: &Pubkey, sol_lamports: u64, slippage_bps: u64) -> Result<(Vec<Instruction>, BuyOrder), anyhow::Error> {
+        let user = self.payer.pubkey();
+        if self.is_migrated(mint).await? {
+            let pool = fetch_pool_state(&self.rpc, &self.program, mint).await?;
+            let order = pool.buy_order(sol_lamports, slippage_bps)?;
+            return Ok((pump_swap::buy_instructions(&pool, &user, &order)?, order));
+        }
+        let state = self.fetch_curve_state(mint).await?;
+        let order = BuyOrder::quote(&state, sol_lamports, slippage_bps)?;
+        let instructions = vec![
+            create_associated_token_account_idempotent(&user, &user, mint),
+            buy_instruction(&self.program, &state, &user, &order)?,
+        ];
+        Ok((instructions, order))
+    }
+
+    /// Instructions selling `token_amount` raw tokens of `mint` from the payer's token account,
+    /// to the PumpSwap pool once the token has migrated
+    pub async fn build_sell_instructions(&self, mint: &Pubkey, token_amount: u64, slippage_bps: u64) -> Result<(Vec<Instruction>, SellOrder), anyhow::Error> {
+        let user = self.payer.pubkey();
+        if self.is_migrated(mint).await? {
+            let pool = fetch_pool_state(&self.rpc, &self.program, mint).await?;
+            let order = pool.sell_order(token_amount, slippage_bps)?;
+            return Ok((pump_swap::sell_instructions(&pool, &user, &order)?, order));
+        }
+        let state = self.fetch_curve_state(mint).await?;
+        let order = SellOrder::quote(&state, token_amount, slippage_bps)?;
+        Ok((vec![sell_instruction(&self.program, &state, &user, &order)?], order))
+    }
+
+    /// Build a buy of `mint` and simulate it against the latest chain state, refusing it when the
+    /// simulated price is more than `BUY_PRICE_TOLERANCE_BPS` above the quote
+    pub async fn build_checked_buy_instructions(&self, mint: &Pubkey, sol_lamports: u64, slippage_bps: u64) -> Result<(Vec<Instruction>, BuyOrder), anyhow::Error> {
+        let tolerance_bps = price_tolerance_from_env()?;
+        let (instructions, order) = self.build_buy_instructions(mint, sol_lamports, slippage_bps).await?;
+        let fill = self.simulate_buy(mint, &instructions).await?;
+        info!("Simulated buy of {}: {} tokens for {} lamports (quoted {} for {})",
+              mint, fill.token_amount, fill.sol_lamports, order.token_amount, order.sol_lamports);
+        check_simulated_fill(&order, &fill, tolerance_bps)?;
+        Ok((instructions, order))
+    }
+
+    /// Buy `mint` with `sol_lamports` from the payer and wait for confirmation. pump.fun's buy fixes
+    /// the token amount quoted from the curve reserves and caps the SOL paid at the quote plus
+    /// `slippage_bps`; the payer's token account is created if needed, and the buy is simulated
+    /// first (see [`TokenCreator::build_checked_buy_instructions`]).
+    pub async fn buy_token(&self, mint: &Pubkey, sol_lamports: u64, slippage_bps: u64, operator: Option<&str>) -> Result<(Signature, BuyOrder), anyhow::Error> {
+        ensure_writable("buy")?;
+        if sol_lamports == 0 {
+            return Err(anyhow::anyhow!("Buy amount must be positive"));
+        }
+        let (instructions, order) = self.build_checked_buy_instructions(mint, sol_lamports, slippage_bps).await?;
+        info!("Buying {} tokens of {} for at most {} SOL on {:?}", order.token_amount as f64 / TOKEN_UNIT, mint,
+              order.max_sol_cost as f64 / LAMPORTS_PER_SOL, order.venue);
+        let result = self.send_audited(&instructions, &[], AuditEvent {
+            purpose: "buy".to_string(),
+            mint: Some(mint.to_string()),
+            amount_lamports: Some(order.max_sol_cost),
+            token_amount: Some(order.token_amount),
+            operator: operator.map(String::from),
+            ..Default::default()
+        }).await;
+        if result.is_err() && order.venue == Venue::BondingCurve {
+            self.invalidate_fee_recipient();
+        }
+        Ok((result?, order))
+    }
+
+    /// Raw tokens of `mint` in the payer's associated token account (0 when it does not exist)
+    pub async fn token_balance(&self, mint: &Pubkey) -> Result<u64, anyhow::Error> {
+        let account = get_associated_token_address(&self.payer.pubkey(), mint);
+        let account = self.rpc_pool
+            .with_failover(|rpc| async move { rpc.get_account_with_commitment(&account, rpc.commitment()).await })
+            .await?
+            .value;
+        Ok(account.map(|account| token_account_amount(&account.data)).unwrap_or(0))
+    }
+
+    /// Sell `amount` of the payer's `mint` tokens and wait for confirmation. The sell instruction
+    /// carries `min_sol_output` (the quote less `slippage_bps`), so the program rejects a fill below it.
+    pub async fn sell_token(&self, mint: &Pubkey, amount: SellAmount, slippage_bps: u64, operator: Option<&str>) -> Result<(Signature, SellOrder), anyhow::Error> {
+        ensure_writable("sell")?;
+        let token_amount = amount.resolve(self.token_balance(mint).await?)?;
+        let (instructions, order) = self.build_sell_instructions(mint, token_amount, slippage_bps).await?;
+        info!("Selling {} tokens of {} for at least {} SOL on {:?}", order.token_amount as f64 / TOKEN_UNIT, mint,
+              order.min_sol_output as f64 / LAMPORTS_PER_SOL, order.venue);
+        let result = self.send_audited(&instructions, &[], AuditEvent {
+            purpose: "sell".to_string(),
+            mint: Some(mint.to_string()),
+            token_amount: Some(order.token_amount),
+            min_sol_output: Some(order.min_sol_output),
+            operator: operator.map(String::from),
+            ..Default::default()
+        }).await;
+        if result.is_err() && order.venue == Venue::BondingCurve {
+            self.invalidate_fee_recipient();
+        }
+        Ok((result?, order))
+    }
+
+    /// Metaplex metadata of `mint`
+    pub async fn fetch_metadata_account(&self, mint: &Pubkey) -> Result<MetadataAccount, anyhow::Error> {
+        let data = self.rpc.get_account_data(&self.get_metadata_pda(mint)).await
+            .map_err(|e| anyhow::anyhow!("{} has no readable Metaplex metadata: {}", mint, e))?;
+        parse_metadata_account(&data)
+    }
+
+    /// Check that the mint, freeze and metadata update authorities of `mint` are where pump.fun leaves them
+    pub async fn verify_authorities(&self, mint: &Pubkey) -> Result<AuthorityReport, anyhow::Error> {
+        let mint_data = self.rpc.get_account_data(mint).await
+            .map_err(|e| anyhow::anyhow!("Cannot read mint {}: {}", mint, e))?;
+        let (mint_authority, freeze_authority) = parse_mint_authorities(&mint_data)?;
+        let metadata = self.fetch_metadata_account(mint).await?;
+        Ok(check_authorities(mint, mint_authority, freeze_authority, &metadata, &self.get_mint_authority_pda()))
+    }
+
+    /// Rewrite the name and/or URI of `mint`'s metadata. Only works when the payer is the update
+    /// authority, which pump.fun keeps for itself on tokens created through its program.
+    pub async fn update_metadata(&self, mint: &Pubkey, name: Option<&str>, uri: Option<&str>, operator: Option<&str>) -> Result<Signature, anyhow::Error> {
+        ensure_writable("update token metadata")?;
+        let account = self.fetch_metadata_account(mint).await?;
+        let payer = self.payer.pubkey();
+        if account.update_authority != payer {
+            return Err(anyhow::anyhow!("The update authority of {} is {}, not this wallet ({})", mint, account.update_authority, payer));
+        }
+        let instruction = update_metadata_instruction(&self.program.metadata_program_id, &self.get_metadata_pda(mint), &account, name, uri)?;
+        self.send_audited(&[instruction], &[], AuditEvent {
+            purpose: "update_metadata".to_string(),
+            mint: Some(mint.to_string()),
+            operator: operator.map(String::from),
+            ..Default::default()
+        }).await
+    }
+
+    /// Sign `instructions` with the payer and `extra_signers`, send them and wait for confirmation, then
+    /// append `event` to the audit log with the signature, payer and outcome filled in
+    pub(crate) async fn send_audited(&self, instructions: &[Instruction], extra_signers: &[&dyn Signer], event: AuditEvent) -> Result<Signature, anyhow::Error> {
+        let payer = self.payer.pubkey();
+        let (blockhash, _) = self.rpc_pool
+            .with_failover(|rpc| async move { rpc.get_latest_blockhash_with_commitment(rpc.commitment()).await })
+            .await?;
+        let signers: Vec<&dyn Signer> = std::iter::once(self.payer()).chain(extra_signers.iter().copied()).collect();
+        let mut transaction = Transaction::new_with_payer(instructions, Some(&payer));
+        transaction.try_sign(&signers, blockhash)?;
+        let (transaction, confirmation) = (&transaction, self.rpc.commitment());
+        let result = match self.rpc_pool.send_transaction(transaction, send_config(confirmation)).await {
+            Ok(_) => self.rpc_pool.with_failover(|rpc| async move { confirm_sent(&rpc, transaction, confirmation).await }).await,
+            Err(e) => Err(e),
+        };
+
+        if let Some(audit_log) = AuditLog::from_env() {
+            let event = AuditEvent {
+                signature: transaction.signatures[0].to_string(),
+                payer: payer.to_string(),
+                status: if result.is_ok() { "confirmed" } else { "failed" }.to_string(),
+                ..event
+            };
+            if let Err(e) = audit_log.append(event) {
+                error!("Failed to write audit log entry to {}: {}", audit_log.path(), e);
+            }
+        }
+        Ok(result?)
+    }
+
+    /// Simulate buy `instructions` and measure the tokens received and SOL spent by the payer
+    pub async fn simulate_buy(&self, mint: &Pubkey, instructions: &[Instruction]) -> Result<SimulatedFill, anyhow::Error> {
+        let user = self.payer.pubkey();
+        let token_account = get_associated_token_address(&user, mint);
+        let before = self.rpc_pool
+            .with_failover(|rpc| async move { rpc.get_multiple_accounts(&[user, token_account]).await })
+            .await?;
+        let (payer_before, account_before) = (
+            before[0].as_ref().map(|a| a.lamports).unwrap_or(0),
+            before[1].as_ref().map(|a| (a.lamports, token_account_amount(&a.data))).unwrap_or((0, 0)),
+        );
+
+        // Signatures are not verified and the node fills in the blockhash, so a quote never asks a signer
+        let transaction = Transaction::new_unsigned(Message::new(instructions, Some(&user)));
+        let config = RpcSimulateTransactionConfig {
+            sig_verify: false,
+            replace_recent_blockhash: true,
+            commitment: Some(CommitmentConfig::processed()),
+            accounts: Some(RpcSimulateTransactionAccountsConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                addresses: vec![user.to_string(), token_account.to_string()],
+            }),
+            ..Default::default()
+        };
+        let transaction = &transaction;
+        let result = self.rpc_pool
+            .with_failover(|rpc| {
+                let config = config.clone();
+                async move { rpc.simulate_transaction_with_config(transaction, config).await }
+            })
+            .await?.value;
+        if let Some(err) = result.err {
+            return Err(anyhow::anyhow!("Buy simulation failed: {:?}\nLogs: {:?}", err, result.logs.unwrap_or_default()));
+        }
+        let after: Vec<Option<(u64, Vec<u8>)>> = result.accounts.unwrap_or_default().into_iter()
+            .map(|account| account.and_then(|a| Some((a.lamports, a.data.decode()?))))
+            .collect();
+        let payer_after = after.first().cloned().flatten().map(|(lamports, _)| lamports)
+            .ok_or_else(|| anyhow::anyhow!("Buy simulation did not return the payer account"))?;
+        let (account_lamports, account_amount) = after.get(1).cloned().flatten()
+            .map(|(lamports, data)| (lamports, token_account_amount(&data)))
+            .ok_or_else(|| anyhow::anyhow!("Buy simulation did not return the token account"))?;
+
+        // Rent for a newly created token account is not part of the price; the signature fee is negligible
+        let rent = account_lamports.saturating_sub(account_before.0);
+        Ok(SimulatedFill {
+            token_amount: account_amount.saturating_sub(account_before.1),
+            sol_lamports: payer_before.saturating_sub(payer_after).saturating_sub(rent),
+        })
+    }
+
+    /// Whether `mint`'s bonding curve completed and the token now trades on PumpSwap
+    pub async fn is_migrated(&self, mint: &Pubkey) -> Result<bool, anyhow::Error> {
+        Ok(self.fetch_bonding_curve(mint).await
+            .map_err(|e| anyhow::anyhow!("{} has no readable pump.fun bonding curve: {}", mint, e))?
+            .complete)
+    }
+
+    /// Verify the program accounts a launch depends on exist on the target cluster
+    /// and are owned by the expected programs.
+    pub async fn preflight_check_accounts(&self) -> Result<(), anyhow::Error> {
+        let global = self.get_global_pda();
+        let mint_authority = self.get_mint_authority_pda();
+        let program_id = self.program.program_id;
+        let metadata_program_id = self.program.metadata_program_id;
+        let system_program = Pubkey::from_str(SYSTEM_PROGRAM_ID).unwrap();
+
+        let accounts = self.rpc
+            .get_multiple_accounts(&[program_id, metadata_program_id, global, mint_authority])
+            .await?;
+        let hint = "wrong cluster or program not deployed? Check the RPC endpoint and PUMP_PROGRAM_ID";
+
+        match &accounts[0] {
+            Some(account) if account.executable => {}
+            Some(_) => return Err(anyhow::anyhow!("pump.fun program {} is not executable ({})", program_id, hint)),
+            None => return Err(anyhow::anyhow!("pump.fun program {} not found ({})", program_id, hint)),
+        }
+
+        match &accounts[1] {
+            Some(account) if account.executable => {}
+            Some(_) => return Err(anyhow::anyhow!("Metadata program {} is not executable ({})", metadata_program_id, hint)),
+            None => return Err(anyhow::anyhow!("Metadata program {} not found ({})", metadata_program_id, hint)),
+        }
+
+        match &accounts[2] {
+            Some(account) if account.owner == program_id => {}
+            Some(account) => return Err(anyhow::anyhow!(
+                "Global account {} is owned by {} instead of {} ({})", global, account.owner, program_id, hint
+            )),
+            None => return Err(anyhow::anyhow!("Global account {} not found ({})", global, hint)),
+        }
+
+        // The mint authority PDA only signs via the program and may hold no data at all
+        match &accounts[3] {
+            Some(account) if account.owner != program_id && account.owner != system_program => {
+                return Err(anyhow::anyhow!(
+                    "Mint authority {} is owned by unexpected program {} ({})", mint_authority, account.owner, hint
+                ));
+            }
+            Some(_) => {}
+            None => info!("Mint authority PDA {} has no account data (expected for an unfunded PDA)", mint_authority),
+        }
+
+        info!("Pre-flight account checks passed");
+        Ok(())
+    }
+
+    /// Resolve the program version, either from configuration or by inspecting the global account
+    pub async fn resolve_program_version(&self) -> Result<ProgramVersion, anyhow::Error> {
+        if let Some(version) = self.program.version {
+            return Ok(version);
+        }
+
+        match self.rpc.get_account_data(&self.get_global_pda()).await {
+            Ok(data) => {
+                let version = ProgramVersion::detect_from_global(&data);
+                info!("Detected program version from global account: {:?}", version);
+                Ok(version)
+            }
+            Err(e) => {
+                warn!("Could not read global account to detect program version ({}), assuming {:?}",
+                      e, ProgramVersion::CreatorVault);
+                Ok(ProgramVersion::CreatorVault)
+            }
+        }
     }
     
     pub async fn get_wallet_balance(&self) -> Result<f64, anyhow::Error> {
-        let balance = self.rpc.get_balance(&self.payer.pubkey()).await?;
+        let balance = self.payer_balance().await?;
         Ok(balance as f64 / LAMPORTS_PER_SOL)
     }
     
+    /// What a launch with `options` needs from the payer (payer and mint sign)
+    pub async fn launch_cost(&self, options: &LaunchOptions) -> Result<LaunchCost, anyhow::Error> {
+        let global = match options.dev_buy_lamports {
+            Some(_) => Some(self.fetch_global_account().await?),
+            None => None,
+        };
+        LaunchCost::estimate(2, options.priority_fee_lamports(), options.dev_buy_max_cost(), options.jito_tip_lamports(), global.as_ref())
+    }
+
+    /// Check the payer covers `cost`, topping up the shortfall from the funding wallet when
+    /// `AUTO_TOPUP` is enabled. Returns the balance available for the launch.
+    pub async fn ensure_launch_balance(&self, cost: &LaunchCost, sol_usd: Option<f64>, dry_run: bool, operator: Option<&str>) -> Result<u64, anyhow::Error> {
+        let payer = self.payer.pubkey();
+        let balance = self.payer_balance().await?;
+        info!("Wallet balance: {}", format_sol(balance, sol_usd));
+        if balance >= cost.total() {
+            return Ok(balance);
+        }
+
+        let Some(funding) = FundingWallet::from_env()? else {
+            cost.ensure_covered(balance, sol_usd)?;
+            return Ok(balance);
+        };
+        let shortfall = cost.total() - balance;
+        if dry_run {
+            info!("DRY RUN MODE - Would top up {} from funding wallet {}", format_sol(shortfall, sol_usd), funding.address());
+            return Ok(balance);
+        }
+
+        funding.top_up(&self.rpc, &payer, shortfall, sol_usd, operator).await?;
+        let balance = self.payer_balance().await?;
+        cost.ensure_covered(balance, sol_usd)?;
+        Ok(balance)
+    }
+    
     pub fn get_wallet_address(&self) -> Pubkey {
         self.payer.pubkey()
     }
@@ -134,9 +1059,222 @@ impl TokenCreator {
 
     /// Check if vanity addresses are enabled (from global pool)
     pub fn is_vanity_enabled(&self) -> bool {
-        get_global_vanity_pool().map_or(false, |pool| pool.is_vanity_enabled())
+        get_global_vanity_pool().is_some_and(|pool| pool.is_vanity_enabled())
+    }
+
+
+    /// Build the pump.fun `create` instruction for `mint`, paid for by `user`
+    #[allow(clippy::too_many_arguments)]
+    pub fn build_create_instruction(
+        &self,
+        mint: &Pubkey,
+        user: &Pubkey,
+        creator: &Pubkey,
+        name: &str,
+        symbol: &str,
+        metadata_uri: &str,
+        program_version: ProgramVersion,
+    ) -> Instruction {
+        let instruction_data = encode_create_args(&CreateInstructionArgs {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            uri: metadata_uri.to_string(),
+            creator: (program_version == ProgramVersion::CreatorVault).then_some(*creator),
+        });
+
+        Instruction {
+            program_id: self.program.program_id,
+            accounts: vec![
+                AccountMeta::new(*mint, true), // mint (always a signer)
+                AccountMeta::new_readonly(self.get_mint_authority_pda(), false),  // mint_authority
+                AccountMeta::new(self.get_bonding_curve_pda(mint), false),            // bonding_curve
+                AccountMeta::new(self.get_associated_bonding_curve(mint), false), // associated_bonding_curve
+                AccountMeta::new_readonly(self.get_global_pda(), false),          // global
+                AccountMeta::new_readonly(self.program.metadata_program_id, false), // mpl_token_metadata
+                AccountMeta::new(self.get_metadata_pda(mint), false),                 // metadata
+                AccountMeta::new(*user, true),       // user (payer)
+                AccountMeta::new_readonly(Pubkey::from_str(SYSTEM_PROGRAM_ID).unwrap(), false), // system_program
+                AccountMeta::new_readonly(Pubkey::new_from_array(spl_token::ID.to_bytes()), false),   // token_program
+                AccountMeta::new_readonly(Pubkey::new_from_array(spl_associated_token_account::ID.to_bytes()), false), // associated_token_program
+                AccountMeta::new_readonly(solana_sdk::sysvar::rent::ID, false), // rent
+                AccountMeta::new_readonly(self.get_event_authority_pda(), false), // event_authority
+                AccountMeta::new_readonly(self.program.program_id, false), // program
+            ],
+            data: instruction_data,
+        }
+    }
+
+    /// Launch phase 1: upload the image and metadata, returning the metadata URI to launch with
+    pub async fn prepare_metadata(
+        &self,
+        name: String,
+        symbol: String,
+        description: String,
+        image_path: Option<&str>,
+        form: &MetadataForm,
+    ) -> Result<PreparedMetadata, anyhow::Error> {
+        let uri = upload_metadata_to_pumpfun(&name, &symbol, &description, image_path, form).await?;
+        info!("Metadata uploaded to: {}", uri);
+        Ok(PreparedMetadata { name, symbol, description, uri })
+    }
+
+    /// Launch phase 2: pick the mint, a generated vanity address when `use_vanity` and one is ready
+    pub fn assign_mint(&self, use_vanity: bool) -> AssignedMint {
+        match get_global_vanity_pool() {
+            Some(pool) if use_vanity => match pool.get_generated_vanity_address() {
+                VanityAvailability::Available(generated_addr) => {
+                    info!("Using generated vanity address for token creation: {}", generated_addr.address);
+                    return AssignedMint { address: generated_addr.address, signer: MintSigner::Vanity(generated_addr) };
+                }
+                VanityAvailability::GeneratingEta(eta) => {
+                    info!("No generated vanity addresses available (next expected in ~{}s), using regular token creation", eta.as_secs());
+                }
+                VanityAvailability::Disabled => {
+                    info!("Vanity generation not running, using regular token creation");
+                }
+            },
+            _ if use_vanity => info!("Using regular token creation (vanity disabled)"),
+            _ => {}
+        }
+        AssignedMint::from_keypair(Keypair::new())
+    }
+
+    /// Launch phase 3: the create instruction for `mint`, with `creator` (default: the payer)
+    /// receiving creator fees
+    pub async fn build_transaction(&self, metadata: &PreparedMetadata, mint: AssignedMint, creator: Option<Pubkey>) -> Result<LaunchTransaction, anyhow::Error> {
+        let program_version = self.resolve_program_version().await?;
+        let fee_creator = creator.unwrap_or_else(|| self.payer.pubkey());
+        let create_instruction = self.build_create_instruction(
+            &mint.address,
+            &self.payer.pubkey(),
+            &fee_creator,
+            &metadata.name,
+            &metadata.symbol,
+            &metadata.uri,
+            program_version,
+        );
+        if program_version == ProgramVersion::CreatorVault {
+            info!("   Creator: {}", fee_creator);
+            info!("   Creator vault: {}", self.get_creator_vault_pda(&fee_creator));
+        } else if creator.is_some() {
+            warn!("Program version {:?} has no creator field; --creator is ignored", program_version);
+        }
+        let components = vec![TransactionComponent::new(
+            format!("create (name {}B, symbol {}B, uri {}B)", metadata.name.len(), metadata.symbol.len(), metadata.uri.len()),
+            create_instruction,
+        )];
+        Ok(LaunchTransaction { mint, creator: fee_creator, program_version, components })
+    }
+
+    /// Append a buy of `sol_lamports` by the payer, priced on the fresh curve the create instruction
+    /// initializes, so the payer acquires tokens atomically at launch
+    pub async fn add_dev_buy(&self, launch: &mut LaunchTransaction, sol_lamports: u64, slippage_bps: u64) -> Result<BuyOrder, anyhow::Error> {
+        let user = self.payer.pubkey();
+        let mint = launch.mint.address;
+        let global = self.fetch_global_account().await?;
+        let creator = (launch.program_version == ProgramVersion::CreatorVault).then_some(launch.creator);
+        let state = CurveState {
+            mint,
+            curve: global.initial_curve(creator),
+            global,
+            version: launch.program_version,
+            fee_recipient: self.fee_recipient().await?,
+        };
+        let order = BuyOrder::quote(&state, sol_lamports, slippage_bps)?;
+        launch.push_instruction("create payer token account", create_associated_token_account_idempotent(&user, &user, &mint));
+        launch.push_instruction(
+            format!("dev buy ({} SOL, max {} SOL)", sol_lamports as f64 / LAMPORTS_PER_SOL, order.max_sol_cost as f64 / LAMPORTS_PER_SOL),
+            buy_instruction(&self.program, &state, &user, &order)?,
+        );
+        info!("Dev buy: {} SOL for ~{:.0} tokens", sol_lamports as f64 / LAMPORTS_PER_SOL, order.token_amount as f64 / TOKEN_UNIT);
+        Ok(order)
+    }
+
+    /// Launch phase 4: check the size and sign with the payer and mint against a fresh blockhash
+    pub async fn sign(&self, launch: LaunchTransaction) -> Result<SignedLaunch, anyhow::Error> {
+        check_signers(&launch.components, &self.payer.pubkey(), &launch.mint.address)?;
+        // Validate the size before signing so oversized transactions fail with a clear breakdown
+        let size_bytes = validate_transaction_size(&launch.components, &self.payer.pubkey())?;
+        info!("Transaction size: {} bytes", size_bytes);
+        let instructions: Vec<Instruction> = launch.components.into_iter().map(|c| c.instruction).collect();
+
+        let (recent_blockhash, last_valid_block_height) = self.rpc_pool
+            .with_failover(|rpc| async move { rpc.get_latest_blockhash_with_commitment(rpc.commitment()).await })
+            .await?;
+        let mut transaction = Transaction::new_with_payer(&instructions, Some(&self.payer.pubkey()));
+        let message = &transaction.message;
+        check_signer_set(&message.account_keys[..message.header.num_required_signatures as usize],
+            &[("payer", self.payer.pubkey()), ("mint", launch.mint.address)])?;
+        info!("Signing transaction with {} mint keypair", if launch.mint.is_vanity() { "generated vanity" } else { "regular" });
+        let signers: Vec<&dyn Signer> = vec![self.payer.as_ref(), launch.mint.signer()];
+        transaction.try_sign(&signers, recent_blockhash)?;
+        Ok(SignedLaunch {
+            mint: launch.mint.address,
+            vanity: launch.mint.is_vanity(),
+            transaction,
+            last_valid_block_height,
+            size_bytes,
+        })
+    }
+
+    /// Check a signed launch against the latest chain state before sending it, so a transaction that
+    /// would fail does not pay a fee; returns the compute units it used
+    pub async fn simulate(&self, launch: &SignedLaunch) -> Result<Option<u64>, anyhow::Error> {
+        let transaction = &launch.transaction;
+        let config = RpcSimulateTransactionConfig {
+            sig_verify: false,
+            replace_recent_blockhash: true,
+            commitment: Some(CommitmentConfig::processed()),
+            ..Default::default()
+        };
+        let result = self.rpc_pool
+            .with_failover(|rpc| {
+                let config = config.clone();
+                async move { rpc.simulate_transaction_with_config(transaction, config).await }
+            })
+            .await?.value;
+        match result.err {
+            Some(err) => {
+                let logs = result.logs.unwrap_or_default();
+                Err(anyhow::anyhow!("Launch simulation failed: {}\nLogs:\n  {}",
+                    explain_failure(transaction, &TransactionError::from(err.clone()), &logs, &self.program.program_id), logs.join("\n  ")))
+            }
+            None => Ok(result.units_consumed),
+        }
+    }
+
+    /// Launch phase 5: send and wait for `confirmation`. Journaling, audit logging and retries are
+    /// left to the caller; [`TokenCreator::create_token`] runs all phases with them.
+    /// The transaction goes out through every endpoint; confirmation fails over between them.
+    pub async fn submit(&self, launch: &SignedLaunch, confirmation: CommitmentConfig) -> ClientResult<Signature> {
+        let transaction = &launch.transaction;
+        self.rpc_pool.send_transaction(transaction, send_config(confirmation)).await?;
+        self.rpc_pool.with_failover(|rpc| async move { confirm_sent(&rpc, transaction, confirmation).await }).await
     }
 
+    /// Launch phase 5 with `--jito`: send as a bundle, and through RPC if it has not landed
+    /// within `jito.max_slots`. Both paths send the same signed transaction, so it lands at most once.
+    pub async fn submit_bundle(&self, launch: &SignedLaunch, jito: &JitoConfig, confirmation: CommitmentConfig) -> ClientResult<Signature> {
+        let deadline_slot = self.rpc.get_slot_with_commitment(CommitmentConfig::processed()).await? + jito.max_slots;
+        match jito.send_bundle(&[&launch.transaction]).await {
+            Ok(bundle_id) => {
+                info!("Sent bundle {} to {}", bundle_id, jito.block_engine.url);
+                match wait_for_landing(&self.rpc, &launch.signature(), deadline_slot).await? {
+                    Some(Ok(())) => return confirm_sent(&self.rpc, &launch.transaction, confirmation).await,
+                    Some(Err(e)) => return Err(e.into()),
+                    None => warn!("Bundle {} did not land within {} slots; sending through RPC", bundle_id, jito.max_slots),
+                }
+            }
+            Err(e) => warn!("Bundle submission failed ({}); sending through RPC", e),
+        }
+        match self.submit(launch, confirmation).await {
+            // The bundle landed between the last status check and the RPC send
+            Err(e) if e.get_transaction_error() == Some(TransactionError::AlreadyProcessed) => {
+                confirm_sent(&self.rpc, &launch.transaction, confirmation).await
+            }
+            result => result,
+        }
+    }
 
     pub async fn create_token(
         &self,
@@ -144,208 +1282,575 @@ impl TokenCreator {
         symbol: String,
         description: String,
         image_path: Option<String>,
-    ) -> Result<(Signature, Pubkey), anyhow::Error> {
+        options: &LaunchOptions,
+    ) -> Result<LaunchResult, anyhow::Error> {
         // Check if we're in dry-run mode first
         let dry_run = env::var("DRY_RUN").unwrap_or_else(|_| "false".to_string()).to_lowercase() == "true";
         
-        // Try generated vanity first, then fallback to regular
-        let (mint_pubkey, mint_keypair, generated_vanity) = if let Some(pool) = get_global_vanity_pool() {
-            // Try to get a generated vanity address
-            if let Some(generated_addr) = pool.get_generated_vanity_address() {
-                info!("Using generated vanity address for token creation: {}", generated_addr.address);
-                (generated_addr.address, None, Some(generated_addr))
-            } else {
-                info!("No generated vanity addresses available, using regular token creation");
-                let mint = Keypair::new();
-                (mint.pubkey(), Some(mint), None)
-            }
-        } else {
-            info!("Using regular token creation (vanity disabled)");
-            let mint = Keypair::new();
-            (mint.pubkey(), Some(mint), None)
-        };
+        let confirmation = options.confirmation.unwrap_or(self.rpc.commitment());
         
-        info!("Creating token...");
-        info!("   Name: {}", name);
-        info!("   Symbol: {}", symbol);
-        info!("   Mint address: {}", mint_pubkey);
-        
-        // Check wallet balance before proceeding
-        let balance = self.rpc.get_balance(&self.payer.pubkey()).await?;
-        info!("Wallet balance: {} SOL", balance as f64 / LAMPORTS_PER_SOL);
+        // A re-run of a launch whose transaction may still land must not send a competing one
+        let journal = TxJournal::from_env();
+        let launch_key = TxJournal::launch_key(&self.payer.pubkey(), &name, &symbol, &description);
+        match journal.prior_submission(&self.rpc, &launch_key).await? {
+            PriorSubmission::Landed { signature, mint } => {
+                info!("This launch already landed in transaction {}; not sending again", signature);
+                // Landed means confirmed; a stricter level still has to be reached before reporting success
+                if !wait_for_signature(&self.rpc, &signature, 0, confirmation).await? {
+                    return Err(anyhow::anyhow!("Earlier transaction {} did not reach {:?}", signature, confirmation.commitment));
+                }
+                return Ok(LaunchResult { signature, mint: journaled_mint(&signature, mint)?, warnings: options.warnings.list(), launch_dir: None });
+            }
+            PriorSubmission::InFlight { signature, mint, last_valid_block_height } => {
+                info!("Transaction {} for this launch may still land; waiting for it instead of resending", signature);
+                if wait_for_signature(&self.rpc, &signature, last_valid_block_height, confirmation).await? {
+                    journal.record(&launch_key, &self.payer.pubkey(), &signature, mint.as_ref(), last_valid_block_height, "confirmed")?;
+                    info!("Earlier transaction confirmed: {}", signature);
+                    return Ok(LaunchResult { signature, mint: journaled_mint(&signature, mint)?, warnings: options.warnings.list(), launch_dir: None });
+                }
+                journal.record(&launch_key, &self.payer.pubkey(), &signature, mint.as_ref(), last_valid_block_height, "failed")?;
+                info!("Earlier transaction did not land; building a new one");
+            }
+            PriorSubmission::None => {}
+        }
+
+        // Launching from one wallet in quick succession is a pattern that gets creator wallets flagged
+        match (LaunchCooldown::from_env()?, options.ignore_cooldown) {
+            (Some(_), true) => options.warnings.push(WarningSeverity::Warning, "cooldown_ignored",
+                format!("Ignoring the launch cooldown for {}", self.payer.pubkey())),
+            (Some(cooldown), false) => {
+                if let Err(e) = cooldown.enforce(&journal, &self.payer.pubkey()) {
+                    if !dry_run {
+                        return Err(e);
+                    }
+                    options.warnings.push(WarningSeverity::Critical, "cooldown_active", e.to_string());
+                }
+            }
+            (None, _) => {}
+        }
+
+        // Fail before uploading anything when the announced launch time has not come yet
+        if let (Some(not_before), false) = (options.not_before, dry_run) {
+            enforce_not_before(not_before).await?;
+        }
         
-        // Check if we have enough SOL for the transaction
-        if balance < MIN_REQUIRED_LAMPORTS {
-            return Err(anyhow::anyhow!(
-                "Insufficient wallet balance. Current: {} SOL, Required: {} SOL. Please add more SOL to your wallet.",
-                balance as f64 / LAMPORTS_PER_SOL,
-                MIN_REQUIRED_LAMPORTS as f64 / LAMPORTS_PER_SOL
-            ));
+        // A concurrent transfer from a shared hot wallet could drain the balance mid-launch
+        if !dry_run {
+            WalletGuard::from_env()?.check(&self.rpc, &self.payer.pubkey()).await?;
         }
         
-        // Upload metadata to pump.fun IPFS
-        let metadata_uri = self.upload_metadata_to_pumpfun(&name, &symbol, &description, image_path.as_deref()).await?;
-        info!("Metadata uploaded to: {}", metadata_uri);
+        // Check we have enough SOL for the transaction (payer and mint sign), topping up if configured
+        let sol_usd = sol_usd_price().await;
+        let cost = self.launch_cost(options).await?;
+        info!("Estimated launch cost: {}", format_sol(cost.total(), sol_usd));
+        let balance = self.ensure_launch_balance(&cost, sol_usd, dry_run, options.operator.as_deref()).await?;
+        progress("balance_checked", json!({ "balance_lamports": balance, "required_lamports": cost.total() }));
         
-        let bonding_curve = self.get_bonding_curve_pda(&mint_pubkey);
-        let metadata = self.get_metadata_pda(&mint_pubkey);
-        let mint_authority = self.get_mint_authority_pda();
-        let global = self.get_global_pda();
-        let event_authority = self.get_event_authority_pda();
+        // Make sure we are pointed at a cluster where the program is deployed before uploading anything
+        let skip_preflight = env::var("SKIP_PREFLIGHT_CHECKS").unwrap_or_else(|_| "false".to_string()).to_lowercase() == "true";
+        if skip_preflight {
+            options.warnings.push(WarningSeverity::Warning, "preflight_skipped", "SKIP_PREFLIGHT_CHECKS set - not verifying program accounts");
+        } else {
+            self.preflight_check_accounts().await?;
+        }
         
-        // Calculate associated token address manually to avoid type mismatch
-        let associated_bonding_curve = Pubkey::find_program_address(
-            &[
-                bonding_curve.as_ref(),
-                &Pubkey::new_from_array(spl_token::ID.to_bytes()).to_bytes(),
-                mint_pubkey.as_ref(),
-            ],
-            &Pubkey::new_from_array(spl_associated_token_account::ID.to_bytes()),
-        ).0;
+        warn_name_collisions(&name, &symbol, &options.warnings).await;
+        if let Some(path) = &image_path {
+            match std::fs::metadata(path) {
+                Ok(file) if file.len() > RECOMMENDED_IMAGE_BYTES as u64 => options.warnings.push(WarningSeverity::Warning, "image_large",
+                    format!("Image {} is {:.1} MB, larger than the recommended {} MB", path, file.len() as f64 / 1_048_576.0, RECOMMENDED_IMAGE_BYTES / 1_048_576)),
+                _ => {}
+            }
+        }
+        let (fee, threshold) = (options.priority_fee_lamports(), priority_fee_warn_lamports_from_env());
+        if fee > threshold {
+            let severity = if fee > threshold * 10 { WarningSeverity::Critical } else { WarningSeverity::Warning };
+            options.warnings.push(severity, "priority_fee_high",
+                format!("Priority fee of up to {} SOL is above PRIORITY_FEE_WARN_SOL ({} SOL)", fee as f64 / LAMPORTS_PER_SOL, threshold as f64 / LAMPORTS_PER_SOL));
+        }
 
-        // CREATE instruction discriminator from IDL
-        let mut instruction_data = CREATE_INSTRUCTION_DISCRIMINATOR.to_vec();
-        
-        // Serialize arguments: name, symbol, uri, creator
-        let name_bytes = name.as_bytes();
-        let symbol_bytes = symbol.as_bytes();
-        let uri_bytes = metadata_uri.as_bytes();
-        let creator_bytes = self.payer.pubkey().to_bytes();
-        
-        // Add string length prefixes and data
-        instruction_data.extend_from_slice(&(name_bytes.len() as u32).to_le_bytes());
-        instruction_data.extend_from_slice(name_bytes);
-        instruction_data.extend_from_slice(&(symbol_bytes.len() as u32).to_le_bytes());
-        instruction_data.extend_from_slice(symbol_bytes);
-        instruction_data.extend_from_slice(&(uri_bytes.len() as u32).to_le_bytes());
-        instruction_data.extend_from_slice(uri_bytes);
-        instruction_data.extend_from_slice(&creator_bytes);
-
-        let create_instruction = Instruction {
-            program_id: self.program_id,
-            accounts: vec![
-                AccountMeta::new(mint_pubkey, true), // mint (always a signer)
-                AccountMeta::new_readonly(mint_authority, false),  // mint_authority
-                AccountMeta::new(bonding_curve, false),            // bonding_curve
-                AccountMeta::new(Pubkey::new_from_array(associated_bonding_curve.to_bytes()), false), // associated_bonding_curve
-                AccountMeta::new_readonly(global, false),          // global
-                AccountMeta::new_readonly(Pubkey::from_str(MPL_TOKEN_METADATA_PROGRAM_ID).unwrap(), false), // mpl_token_metadata
-                AccountMeta::new(metadata, false),                 // metadata
-                AccountMeta::new(self.payer.pubkey(), true),       // user (payer)
-                AccountMeta::new_readonly(Pubkey::from_str(SYSTEM_PROGRAM_ID).unwrap(), false), // system_program
-                AccountMeta::new_readonly(Pubkey::new_from_array(spl_token::ID.to_bytes()), false),   // token_program
-                AccountMeta::new_readonly(Pubkey::new_from_array(spl_associated_token_account::ID.to_bytes()), false), // associated_token_program
-                AccountMeta::new_readonly(solana_sdk::sysvar::rent::ID, false), // rent
-                AccountMeta::new_readonly(event_authority, false), // event_authority
-                AccountMeta::new_readonly(self.program_id, false), // program
-            ],
-            data: instruction_data,
+        // Upload metadata to pump.fun IPFS; retries reuse the same URI
+        progress("metadata_uploading", json!({}));
+        let metadata = self.prepare_metadata(name, symbol, description, image_path.as_deref(), &options.metadata).await?;
+        progress("metadata_uploaded", json!({ "uri": metadata.uri }));
+
+        let launch_dir = match &options.launch_dir_root {
+            Some(root) => {
+                let dir = LaunchDir::create(root, &metadata.symbol, SystemTime::now())?;
+                dir.write_manifest(&LaunchManifest {
+                    name: metadata.name.clone(),
+                    symbol: metadata.symbol.clone(),
+                    description: metadata.description.clone(),
+                    metadata_uri: metadata.uri.clone(),
+                    payer: self.payer.pubkey().to_string(),
+                    started_at: SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+                    status: "uploaded".to_string(),
+                    operator: options.operator.clone(),
+                    ..Default::default()
+                })?;
+                if let Some(path) = &image_path {
+                    dir.copy_image(path)?;
+                }
+                info!("Launch artifacts: {}", dir.path().display());
+                Some(dir)
+            }
+            None => None,
         };
+        // Only a commitment needs the document; a copy for the launch directory is best effort
+        let document = match (options.commit_metadata_hash, &launch_dir) {
+            (true, _) => Some(fetch_metadata(&metadata.uri).await?),
+            (false, Some(dir)) => fetch_metadata(&metadata.uri).await
+                .map_err(|e| warn!("Not keeping the metadata document in {}: {}", dir.path().display(), e))
+                .ok(),
+            (false, None) => None,
+        };
+        if let (Some(dir), Some(document)) = (&launch_dir, &document) {
+            std::fs::write(dir.metadata_path(), serde_json::to_string_pretty(document)?)?;
+        }
 
-        let recent_blockhash = self.rpc.get_latest_blockhash().await?;
-        let mut transaction = Transaction::new_with_payer(&[create_instruction], Some(&self.payer.pubkey()));
+        // Hashed once: every attempt commits to the same upload
+        let commitment = match (options.commit_metadata_hash, &document) {
+            (true, Some(document)) => {
+                let hash = canonical_metadata_hash(document);
+                info!("Committing to metadata hash {}", hash);
+                Some(commitment_memo(&hash))
+            }
+            _ => None,
+        };
         
-        // Sign the transaction based on address type
-        if let Some(generated_vanity) = &generated_vanity {
-            // For generated vanity addresses: sign with vanity private key and payer
-            info!("Signing transaction with generated vanity address private key");
-            transaction.sign(&[generated_vanity.keypair.keypair(), &self.payer], recent_blockhash);
-        } else {
-            // For regular addresses: sign with payer and mint keypair
-            if let Some(mint) = mint_keypair {
-                info!("Signing transaction with regular mint keypair");
-                transaction.sign(&[&self.payer, &mint], recent_blockhash);
-            } else {
-                return Err(anyhow::anyhow!("Missing mint keypair for regular address"));
+        let mint_cipher = match &launch_dir {
+            Some(_) => mint_keypair_cipher_from_env()?,
+            None => None,
+        };
+        let max_attempts = options.auto_retry + 1;
+        for attempt in 1..=max_attempts {
+            // Try generated vanity first, then fallback to regular; retries always use a fresh keypair
+            let mint = self.assign_mint(attempt == 1);
+            let mint_pubkey = mint.address;
+            // Kept before anything is signed, so the key of a transaction that may land is never lost
+            if let Some(dir) = &launch_dir {
+                dir.save_mint_keypair(mint.keypair(), mint_cipher.as_ref())?;
+            }
+            
+            info!("Creating token{}...", if max_attempts > 1 { format!(" (attempt {}/{})", attempt, max_attempts) } else { String::new() });
+            info!("   Name: {}", metadata.name);
+            info!("   Symbol: {}", metadata.symbol);
+            info!("   Mint address: {}", mint_pubkey);
+            progress("mint_selected", json!({ "mint": mint_pubkey.to_string(), "attempt": attempt, "vanity": mint.is_vanity() }));
+            
+            // Re-resolved each attempt: a program upgrade is the usual cause of a hard failure
+            let mut launch = self.build_transaction(&metadata, mint, options.creator).await?;
+            if let Some(lamports) = options.dev_buy_lamports {
+                self.add_dev_buy(&mut launch, lamports, options.dev_buy_slippage_bps).await?;
+            }
+            launch.components.extend(options.extra_instructions.components(&self.payer.pubkey()));
+            if let Some(budget) = options.compute_budget.as_ref().filter(|_| !has_compute_budget(&options.extra_instructions.instructions)) {
+                // Estimated per attempt: a retry usually follows congestion
+                let price = budget.unit_price(&self.rpc, &[self.payer.pubkey(), self.fee_recipient().await?]).await;
+                launch.components.splice(0..0, budget.components(price));
+            }
+            if let Some(memo) = &commitment {
+                launch.push_instruction("metadata commitment memo", memo_instruction(&self.payer.pubkey(), memo));
+            }
+            if let Some(jito) = &options.jito {
+                let tip_account = jito.tip_accounts.next();
+                launch.push_instruction(format!("jito tip to {}", tip_account),
+                    system_transfer_instruction(&self.payer.pubkey(), &tip_account, jito.tip_lamports));
+            }
+            let signed = self.sign(launch).await?;
+            let transaction = &signed.transaction;
+            let last_valid_block_height = signed.last_valid_block_height;
+            progress("transaction_signed", json!({ "signature": signed.signature().to_string(), "size_bytes": signed.size_bytes }));
+
+            let dump_path = options.dump_tx_path.clone()
+                .or_else(|| launch_dir.as_ref().map(|dir| dir.transaction_path().display().to_string()));
+            if let Some(path) = &dump_path {
+                let labels: HashMap<Pubkey, String> = [
+                    (mint_pubkey, "mint"),
+                    (self.get_mint_authority_pda(), "mint_authority"),
+                    (self.get_bonding_curve_pda(&mint_pubkey), "bonding_curve"),
+                    (self.get_associated_bonding_curve(&mint_pubkey), "associated_bonding_curve"),
+                    (self.get_global_pda(), "global"),
+                    (self.program.metadata_program_id, "mpl_token_metadata"),
+                    (self.get_metadata_pda(&mint_pubkey), "metadata"),
+                    (self.payer.pubkey(), "payer"),
+                    (self.get_event_authority_pda(), "event_authority"),
+                    (self.program.program_id, "pump_program"),
+                ].into_iter().map(|(key, label)| (key, label.to_string())).collect();
+                dump_transaction(path, transaction, &labels)?;
+                info!("Signed transaction written to {}", path);
+            }
+
+            if !options.skip_simulation {
+                match self.simulate(&signed).await {
+                    Ok(units) => info!("Simulation succeeded ({} compute units)", units.map(|u| u.to_string()).unwrap_or_else(|| "?".to_string())),
+                    Err(e) if dry_run => options.warnings.push(WarningSeverity::Critical, "simulation_failed", e.to_string()),
+                    Err(e) => {
+                        error!("{}", e);
+                        progress("failed", json!({ "error": e.to_string(), "attempt": attempt, "retrying": attempt < max_attempts }));
+                        if attempt == max_attempts {
+                            return Err(e);
+                        }
+                        warn!("Attempt {} failed in simulation; retrying with a new mint", attempt);
+                        continue;
+                    }
+                }
+            }
+
+            if options.confirm && !dry_run && attempt == 1 {
+                confirm_launch(&options.warnings, &format!("the launch of ${} at {}", metadata.symbol, mint_pubkey))?;
+            }
+
+            if dry_run {
+                if !options.warnings.is_empty() {
+                    info!("Warnings:\n{}", options.warnings.summary());
+                }
+                info!("DRY RUN MODE - Not sending transaction");
+                info!("   Would create token at address: {}", mint_pubkey);
+                info!("   Transaction would be signed and sent to network");
+                // Return a fake signature for dry run
+                let fake_signature = Signature::default();
+                progress("dry_run_complete", json!({ "mint": mint_pubkey.to_string() }));
+                update_manifest(launch_dir.as_ref(), |manifest| {
+                    manifest.status = "dry_run".to_string();
+                    manifest.mint = Some(mint_pubkey.to_string());
+                });
+                return Ok(LaunchResult { signature: fake_signature, mint: mint_pubkey, warnings: options.warnings.list(), launch_dir });
+            }
+            
+            // Checked again right before sending: vanity waits and uploads happen in between
+            if let Some(not_before) = options.not_before {
+                enforce_not_before(not_before).await?;
+            }
+            info!("Sending transaction...");
+            progress("transaction_sending", json!({ "signature": transaction.signatures[0].to_string(), "attempt": attempt }));
+            let audit_log = AuditLog::from_env();
+            let audit_event = AuditEvent {
+                purpose: "create_token".to_string(),
+                signature: transaction.signatures[0].to_string(),
+                payer: self.payer.pubkey().to_string(),
+                mint: Some(mint_pubkey.to_string()),
+                operator: options.operator.clone(),
+                api_key_id: options.api_key_id.clone(),
+                ..Default::default()
+            };
+            let signature = transaction.signatures[0];
+            journal.record_attempt(&launch_key, &self.payer.pubkey(), &signature, Some(&mint_pubkey), last_valid_block_height, "sent", attempt)?;
+            update_manifest(launch_dir.as_ref(), |manifest| {
+                manifest.status = "sent".to_string();
+                manifest.attempts = attempt;
+                manifest.mint = Some(mint_pubkey.to_string());
+                manifest.signature = Some(signature.to_string());
+            });
+            info!("Waiting for {:?} confirmation...", confirmation.commitment);
+            let result = match &options.jito {
+                Some(jito) => self.submit_bundle(&signed, jito, confirmation).await,
+                None => self.submit(&signed, confirmation).await,
+            };
+            // A transaction error means the transaction was rejected or failed on-chain, so it
+            // can never land. Any other error leaves the entry "sent": it may still land, which
+            // the next run checks.
+            let hard_failure = result.as_ref().err().and_then(|e| e.get_transaction_error());
+            let status = match (&result, &hard_failure) {
+                (Ok(_), _) => Some("confirmed"),
+                (Err(_), Some(_)) => Some("failed"),
+                (Err(_), None) => None,
+            };
+            if let Some(status) = status {
+                update_manifest(launch_dir.as_ref(), |manifest| manifest.status = status.to_string());
+                if let Err(e) = journal.record_attempt(&launch_key, &self.payer.pubkey(), &signature, Some(&mint_pubkey), last_valid_block_height, status, attempt) {
+                    error!("Failed to update transaction journal: {}", e);
+                }
+            }
+
+            if let Some(audit_log) = &audit_log {
+                let event = match &result {
+                    Ok(_) => {
+                        let balance_after = self.rpc.get_balance(&self.payer.pubkey()).await.ok();
+                        if let Some(after) = balance_after {
+                            info!("Launch cost: {}", format_sol(balance.saturating_sub(after), sol_usd));
+                        }
+                        AuditEvent {
+                            amount_lamports: balance_after.map(|after| balance.saturating_sub(after)),
+                            status: "confirmed".to_string(),
+                            ..audit_event
+                        }
+                    }
+                    Err(_) => AuditEvent { status: "failed".to_string(), ..audit_event },
+                };
+                if let Err(e) = audit_log.append(event) {
+                    error!("Failed to write audit log entry to {}: {}", audit_log.path(), e);
+                }
+            }
+
+            match result {
+                Ok(signature) => {
+                    if signed.vanity {
+                        info!("Generated vanity address used successfully");
+                    }
+                    
+                    info!("Token created successfully!");
+                    info!("    Transaction signature: {}", signature);
+                    info!("    Token address: {}", mint_pubkey);
+                    progress("confirmed", json!({ "signature": signature.to_string(), "mint": mint_pubkey.to_string(), "commitment": format!("{:?}", confirmation.commitment).to_lowercase() }));
+                    return Ok(LaunchResult { signature, mint: mint_pubkey, warnings: options.warnings.list(), launch_dir });
+                }
+                Err(e) => {
+                    error!("Token creation failed: {}", e);
+                    progress("failed", json!({ "error": e.to_string(), "attempt": attempt, "retrying": hard_failure.is_some() && attempt < max_attempts }));
+                    match hard_failure {
+                        Some(tx_error) if attempt < max_attempts => {
+                            warn!("Attempt {} failed irrecoverably ({}); retrying with a new mint", attempt, tx_error);
+                        }
+                        _ => return Err(e.into()),
+                    }
+                }
             }
         }
+        Err(anyhow::anyhow!("Token creation failed after {} attempts", max_attempts))
+    }
 
-        if dry_run {
-            info!("DRY RUN MODE - Not sending transaction");
-            info!("   Would create token at address: {}", mint_pubkey);
-            info!("   Transaction would be signed and sent to network");
-            // Return a fake signature for dry run
-            let fake_signature = Signature::default();
-            return Ok((fake_signature, mint_pubkey));
+    /// Propose the launch to a Squads multisig instead of sending it directly.
+    ///
+    /// The multisig vault is payer and creator, and the mint is the vault transaction's
+    /// ephemeral signer PDA. Our wallet only pays rent for the proposal and casts the
+    /// first approval when it is a member.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_token_via_squads(
+        &self,
+        name: String,
+        symbol: String,
+        description: String,
+        image_path: Option<String>,
+        metadata: &MetadataForm,
+        squads: &SquadsConfig,
+        operator: Option<&str>,
+    ) -> Result<SquadsProposal, anyhow::Error> {
+        let dry_run = env::var("DRY_RUN").unwrap_or_else(|_| "false".to_string()).to_lowercase() == "true";
+        let member = self.payer.pubkey();
+
+        let multisig = fetch_multisig(&self.rpc, &squads.multisig).await?;
+        let transaction_index = multisig.transaction_index + 1;
+        let transaction = squads.transaction_pda(transaction_index);
+        let proposal = squads.proposal_pda(transaction_index);
+        let vault = squads.vault_pda();
+        let mint = squads.ephemeral_signer_pda(&transaction, 0);
+
+        info!("Proposing token launch to Squads multisig {}", squads.multisig);
+        info!("   Vault (payer/creator): {}", vault);
+        info!("   Transaction index: {}", transaction_index);
+        info!("   Mint (ephemeral signer): {}", mint);
+        info!("   Threshold: {} of {} members", multisig.threshold, multisig.members.len());
+
+        // The executing member pays the signature fee; the vault only funds the launch itself
+        let vault_balance = self.rpc.get_balance(&vault).await?;
+        let vault_cost = LaunchCost::estimate(0, 0, 0, 0, None)?;
+        if let Err(e) = vault_cost.ensure_covered(vault_balance, sol_usd_price().await) {
+            warn!("Vault cannot execute the launch yet: {}", e);
         }
-        
-        info!("Sending transaction...");
-        match self.rpc.send_and_confirm_transaction(&transaction).await {
-            Ok(signature) => {
-                if generated_vanity.is_some() {
-                    info!("Generated vanity address used successfully");
+
+        self.preflight_check_accounts().await?;
+        let metadata_uri = upload_metadata_to_pumpfun(&name, &symbol, &description, image_path.as_deref(), metadata).await?;
+        info!("Metadata uploaded to: {}", metadata_uri);
+
+        let program_version = self.resolve_program_version().await?;
+        let create_instruction = self.build_create_instruction(
+            &mint, &vault, &vault, &name, &symbol, &metadata_uri, program_version,
+        );
+        let message = compile_vault_message(&[create_instruction], &vault)?;
+
+        let mut instructions = vec![
+            squads.vault_transaction_create_instruction(
+                transaction_index, &member, 1, message, Some(&format!("pump.fun launch {}", symbol)),
+            ),
+            squads.proposal_create_instruction(transaction_index, &member),
+        ];
+        if multisig.members.contains(&member) {
+            instructions.push(squads.proposal_approve_instruction(transaction_index, &member));
+        } else {
+            warn!("Wallet {} is not a member of the multisig; proposal will not be auto-approved", member);
+        }
+
+        let components: Vec<TransactionComponent> = instructions
+            .into_iter()
+            .enumerate()
+            .map(|(i, ix)| TransactionComponent::new(format!("squads instruction #{}", i), ix))
+            .collect();
+        validate_transaction_size(&components, &member)?;
+        let instructions: Vec<Instruction> = components.into_iter().map(|c| c.instruction).collect();
+
+        let signature = if dry_run {
+            info!("DRY RUN MODE - Not sending Squads proposal");
+            Signature::default()
+        } else {
+            info!("Sending Squads proposal transaction...");
+            self.send_audited(&instructions, &[], AuditEvent {
+                purpose: "squads_proposal".to_string(),
+                mint: Some(mint.to_string()),
+                operator: operator.map(String::from),
+                ..Default::default()
+            }).await?
+        };
+
+        Ok(SquadsProposal {
+            signature,
+            transaction_index,
+            transaction,
+            proposal,
+            vault,
+            mint,
+            metadata_uri,
+        })
+    }
+}
+
+/// Upload token metadata and image to pump.fun IPFS, returning the metadata URI. When pump.fun keeps
+/// failing, the configured fallback hosts (Pinata, a self-hosted IPFS node) are tried in order.
+pub async fn upload_metadata_to_pumpfun(
+    name: &str,
+    symbol: &str,
+    description: &str,
+    image_path: Option<&str>,
+    metadata: &MetadataForm,
+) -> Result<String, anyhow::Error> {
+    validate_token_fields(symbol, name, description)?;
+    check_advertised_limits().await;
+    let fallback_hosts = FallbackHost::chain_from_env()?;
+    println!("Uploading metadata to pump.fun IPFS...");
+    
+    // Use provided image path or fall back to image.png
+    let actual_image_path = image_path
+        .map(String::from)
+        .unwrap_or(get_default_image_path());
+    println!("Using image file: {}", actual_image_path);
+    
+    // Read the image, converting it when pump.fun would reject it as-is
+    let media = prepare_media(&actual_image_path)?;
+    if media.kind.is_video() {
+        info!("Uploading as a video token ({})", media.kind.mime());
+    }
+    info!("Uploading media as {}", media.upload_name(metadata.upload_filename.as_deref()));
+
+    let attempts = pumpfun_attempts_from_env();
+    let mut last_error = None;
+    for attempt in 1..=attempts {
+        match upload_to_pumpfun_ipfs(&media, name, symbol, description, metadata).await {
+            Ok(uri) => return check_metadata_uri(uri, "pump.fun"),
+            Err(e) => {
+                warn!("pump.fun metadata upload attempt {}/{} failed: {}", attempt, attempts, e);
+                last_error = Some(e);
+                if attempt < attempts {
+                    tokio::time::sleep(std::time::Duration::from_secs(2u64.pow(attempt))).await;
                 }
-                
-                info!("Token created successfully!");
-                info!("    Transaction signature: {}", signature);
-                info!("    Token address: {}", mint_pubkey);
-                return Ok((signature, mint_pubkey));
             }
+        }
+    }
+
+    for host in &fallback_hosts {
+        info!("Falling back to {} for the metadata upload", host.name());
+        match host.upload(&media, name, symbol, description, metadata).await {
+            Ok(uri) => return check_metadata_uri(uri, host.name()),
             Err(e) => {
-                error!("Token creation failed: {}", e);
-                return Err(e.into());
+                warn!("{} metadata upload failed: {}", host.name(), e);
+                last_error = Some(e);
             }
         }
     }
+    Err(last_error.unwrap_or_else(|| anyhow::anyhow!("Metadata upload failed")))
+}
 
-    async fn upload_metadata_to_pumpfun(
-        &self,
-        name: &str,
-        symbol: &str,
-        description: &str,
-        image_path: Option<&str>,
-    ) -> Result<String, anyhow::Error> {
-        println!("Uploading metadata to pump.fun IPFS...");
-        
-        let client = reqwest::Client::new();
-        
-        // Use provided image path or fall back to image.png
-        let actual_image_path = image_path
-            .map(String::from)
-            .unwrap_or(get_default_image_path());
-        println!("Using image file: {}", actual_image_path);
-        
-        // Read image file
-        let image_data = std::fs::read(actual_image_path)?;
-        
-        let form = reqwest::multipart::Form::new()
-            .part(
-                "file",
-                reqwest::multipart::Part::bytes(image_data)
-                    .file_name(IMAGE_FILENAME)
-                    .mime_str("image/png")?,
-            )
-            .text("name", name.to_string())
-            .text("symbol", symbol.to_string())
-            .text("description", description.to_string())
-            .text("showName", "true")
-            .text("createdOn", "https://pump.fun")
-            .text("twitter", "")
-            .text("telegram", "")
-            .text("website", "");
-
-        let response = client
-            .post(PUMP_FUN_API_URL)
-            .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:126.0) Gecko/20100101 Firefox/126.0")
-            .header("Accept", "*/*")
-            .header("Accept-Language", "en-US,en;q=0.5")
-            .header("Referer", "https://www.pump.fun/create")
-            .header("Origin", "https://www.pump.fun")
-            .multipart(form)
-            .send()
-            .await?;
+fn check_metadata_uri(uri: String, host: &str) -> Result<String, anyhow::Error> {
+    if uri.len() > MAX_URI_LEN {
+        return Err(anyhow::anyhow!("Metadata URI from {} is {} bytes, above the {} byte on-chain limit", host, uri.len(), MAX_URI_LEN));
+    }
+    info!("Metadata uploaded via {}: {}", host, uri);
+    Ok(uri)
+}
 
-        if !response.status().is_success() {
-            return Err(anyhow::anyhow!("Failed to upload metadata: {}", response.status()));
-        }
+async fn upload_to_pumpfun_ipfs(
+    media: &PreparedMedia,
+    name: &str,
+    symbol: &str,
+    description: &str,
+    metadata: &MetadataForm,
+) -> Result<String, anyhow::Error> {
+    let client = reqwest::Client::new();
+    let form = reqwest::multipart::Form::new()
+        .part(
+            "file",
+            reqwest::multipart::Part::bytes(media.data.clone())
+                .file_name(media.upload_name(metadata.upload_filename.as_deref()))
+                .mime_str(media.kind.mime())?,
+        )
+        .text("name", name.to_string())
+        .text("symbol", symbol.to_string())
+        .text("description", description.to_string())
+        .text("showName", metadata.show_name.to_string())
+        .text("createdOn", metadata.created_on.clone())
+        .text("twitter", metadata.twitter.clone())
+        .text("telegram", metadata.telegram.clone())
+        .text("website", metadata.website.clone());
 
-        let result: serde_json::Value = response.json().await?;
-        let metadata_uri = result["metadataUri"]
-            .as_str()
-            .ok_or_else(|| anyhow::anyhow!("No metadataUri in response"))?;
+    let response = client
+        .post(PUMP_FUN_API_URL)
+        .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:126.0) Gecko/20100101 Firefox/126.0")
+        .header("Accept", "*/*")
+        .header("Accept-Language", "en-US,en;q=0.5")
+        .header("Referer", "https://www.pump.fun/create")
+        .header("Origin", "https://www.pump.fun")
+        .multipart(form)
+        .send()
+        .await?;
 
-        Ok(metadata_uri.to_string())
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("Failed to upload metadata: {}", response.status()));
     }
+
+    let result: serde_json::Value = response.json().await?;
+    let metadata_uri = result["metadataUri"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("No metadataUri in response"))?;
+    Ok(metadata_uri.to_string())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_args_encoding() {
+        let creator = Pubkey::new_unique();
+        let args = CreateInstructionArgs { name: "PVE".to_string(), symbol: "P".to_string(), uri: "ipfs://x".to_string(), creator: None };
+        let mut expected = CREATE_INSTRUCTION_DISCRIMINATOR.to_vec();
+        expected.extend_from_slice(&[3, 0, 0, 0, b'P', b'V', b'E', 1, 0, 0, 0, b'P', 8, 0, 0, 0]);
+        expected.extend_from_slice(b"ipfs://x");
+        assert_eq!(encode_create_args(&args), expected);
+
+        let with_creator = CreateInstructionArgs { creator: Some(creator), ..args.clone() };
+        let encoded = encode_create_args(&with_creator);
+        assert_eq!(&encoded[expected.len()..], creator.as_ref());
 
+        for case in [args, with_creator, CreateInstructionArgs { name: "Ünï 🚀".to_string(), symbol: String::new(), uri: "u".repeat(200), creator: None }] {
+            assert_eq!(decode_create_args(&encode_create_args(&case)).unwrap(), case);
+        }
+        assert!(decode_create_args(&encoded[..encoded.len() - 1]).is_err());
+        assert!(decode_create_args(&encoded[..12]).is_err());
+        assert!(decode_create_args(&[0; 16]).is_err());
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_create_args_round_trip(
+            symbol in "[ -~]{1,10}|\\PC{1,2}", name in "[ -~]{0,32}|\\PC{0,8}", uri in "[ -~]{0,200}",
+            creator in proptest::option::of(proptest::prelude::any::<[u8; 32]>()),
+        ) {
+            // Within the byte limits even when every character takes four bytes
+            proptest::prop_assert!(validate_token_fields(&symbol, &name, "").is_ok());
+            let args = CreateInstructionArgs { name, symbol, uri, creator: creator.map(Pubkey::new_from_array) };
+            let encoded = encode_create_args(&args);
+            let expected_len = 8 + 12 + args.name.len() + args.symbol.len() + args.uri.len() + if args.creator.is_some() { 32 } else { 0 };
+            proptest::prop_assert_eq!(encoded.len(), expected_len);
+            proptest::prop_assert_eq!(decode_create_args(&encoded).unwrap(), args);
+        }
+    }
+}
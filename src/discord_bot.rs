@@ -0,0 +1,230 @@
+use anyhow::Result;
+use log::{info, warn};
+use solana_sdk::signature::Signature;
+use std::env;
+use std::fmt::Write;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use crate::create_token::{get_associated_token_address, TokenCreator};
+use crate::pump_accounts::TOKEN_UNIT;
+use crate::read_only::ensure_writable;
+use crate::triggers::{FiredTrigger, LaunchDraft, MAX_REQUEST_AGE_SECS};
+use crate::tx_journal::TxJournal;
+
+const DISCORD_API_URL: &str = "https://discord.com/api/v10";
+pub const SIGNATURE_HEADER: &str = "x-signature-ed25519";
+pub const TIMESTAMP_HEADER: &str = "x-signature-timestamp";
+// Interaction and response types from the Discord interactions API
+const PING: u64 = 1;
+const APPLICATION_COMMAND: u64 = 2;
+const PONG: u64 = 1;
+const CHANNEL_MESSAGE: u64 = 4;
+const DEFERRED_CHANNEL_MESSAGE: u64 = 5;
+/// Only the invoking user sees the reply
+const EPHEMERAL: u64 = 1 << 6;
+
+/// Discord application whose slash commands the daemon answers
+#[derive(Debug, Clone)]
+pub struct DiscordConfig {
+    pub application_id: String,
+    /// Ed25519 key Discord signs interactions with
+    pub public_key: [u8; 32],
+    /// Only members holding this role may run commands
+    pub allowed_role: String,
+}
+
+impl DiscordConfig {
+    /// From `DISCORD_APPLICATION_ID`, `DISCORD_PUBLIC_KEY` (hex) and `DISCORD_ALLOWED_ROLE_ID`;
+    /// `None` when the bot is not configured
+    pub fn from_env() -> Result<Option<Self>> {
+        let Ok(application_id) = env::var("DISCORD_APPLICATION_ID") else {
+            return Ok(None);
+        };
+        let public_key = env::var("DISCORD_PUBLIC_KEY")
+            .map_err(|_| anyhow::anyhow!("DISCORD_PUBLIC_KEY must be set with DISCORD_APPLICATION_ID"))?;
+        let public_key: [u8; 32] = decode_hex(public_key.trim())
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| anyhow::anyhow!("DISCORD_PUBLIC_KEY must be 32 bytes of hex"))?;
+        let allowed_role = env::var("DISCORD_ALLOWED_ROLE_ID")
+            .map_err(|_| anyhow::anyhow!("DISCORD_ALLOWED_ROLE_ID must be set; commands are restricted to one role"))?;
+        Ok(Some(Self { application_id, public_key, allowed_role }))
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    (0..hex.len()).step_by(2)
+        .map(|i| hex.get(i..i + 2).and_then(|byte| u8::from_str_radix(byte, 16).ok()))
+        .collect()
+}
+
+/// Check Discord's ed25519 signature over `<timestamp><body>` at time `now`, rejecting replays
+/// outside the same window as webhook triggers
+pub fn verify_interaction(public_key: &[u8; 32], timestamp: Option<&str>, signature: Option<&str>, body: &[u8], now: u64) -> Result<()> {
+    let timestamp = timestamp.ok_or_else(|| anyhow::anyhow!("Missing {} header", TIMESTAMP_HEADER))?;
+    let seconds: u64 = timestamp.trim().parse()
+        .map_err(|_| anyhow::anyhow!("Invalid {} header", TIMESTAMP_HEADER))?;
+    if now.abs_diff(seconds) > MAX_REQUEST_AGE_SECS {
+        return Err(anyhow::anyhow!("Interaction timestamp is outside the {}s window", MAX_REQUEST_AGE_SECS));
+    }
+    let signature: [u8; 64] = signature.and_then(decode_hex)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| anyhow::anyhow!("Missing or invalid {} header", SIGNATURE_HEADER))?;
+    let mut message = timestamp.as_bytes().to_vec();
+    message.extend_from_slice(body);
+    if !Signature::from(signature).verify(public_key, &message) {
+        return Err(anyhow::anyhow!("Invalid interaction signature"));
+    }
+    Ok(())
+}
+
+fn message(content: impl Into<String>) -> serde_json::Value {
+    serde_json::json!({ "type": CHANNEL_MESSAGE, "data": { "content": content.into(), "flags": EPHEMERAL } })
+}
+
+/// Response to a verified interaction; launches go through the daemon's queue like webhook triggers
+pub async fn handle_interaction(config: &DiscordConfig, interaction: &serde_json::Value, queue: &mpsc::Sender<FiredTrigger>) -> serde_json::Value {
+    match interaction["type"].as_u64() {
+        Some(PING) => return serde_json::json!({ "type": PONG }),
+        Some(APPLICATION_COMMAND) => {}
+        _ => return message("Unsupported interaction"),
+    }
+    let member = &interaction["member"];
+    let user = member["user"]["username"].as_str().unwrap_or("unknown");
+    let allowed = member["roles"].as_array()
+        .is_some_and(|roles| roles.iter().any(|role| role.as_str() == Some(config.allowed_role.as_str())));
+    if !allowed {
+        warn!("Discord user {} without the launcher role tried /{}", user, interaction["data"]["name"]);
+        return message("You do not have permission to use the launcher");
+    }
+
+    match interaction["data"]["name"].as_str().unwrap_or_default() {
+        "launch" => {
+            if let Err(e) = ensure_writable("launch") {
+                return message(e.to_string());
+            }
+            let draft_name = interaction["data"]["options"].as_array()
+                .and_then(|options| options.iter().find(|o| o["name"] == "draft"))
+                .and_then(|option| option["value"].as_str())
+                .unwrap_or_default();
+            let draft = match LaunchDraft::load(draft_name) {
+                Ok(draft) => draft,
+                Err(e) => return message(format!("Cannot launch: {}", e)),
+            };
+            let trigger = format!("discord:{}", user);
+            if queue.send(FiredTrigger { trigger, draft }).await.is_err() {
+                return message("Launch queue is closed");
+            }
+            info!("Discord user {} queued draft {}", user, draft_name);
+            message(format!("Launch of draft `{}` queued", draft_name))
+        }
+        "status" => message(status_report(queue)),
+        "portfolio" => {
+            // Balances need RPC calls that can outlast Discord's 3s deadline, so answer later
+            let (application_id, token) = (config.application_id.clone(), interaction["token"].as_str().unwrap_or_default().to_string());
+            tokio::spawn(async move {
+                let content = portfolio_report().await.unwrap_or_else(|e| format!("Failed to read portfolio: {}", e));
+                if let Err(e) = edit_original_response(&application_id, &token, &content).await {
+                    warn!("Failed to send Discord portfolio reply: {}", e);
+                }
+            });
+            serde_json::json!({ "type": DEFERRED_CHANNEL_MESSAGE, "data": { "flags": EPHEMERAL } })
+        }
+        other => message(format!("Unknown command /{}", other)),
+    }
+}
+
+fn status_report(queue: &mpsc::Sender<FiredTrigger>) -> String {
+    let mut report = format!("{} launch(es) queued\n", queue.max_capacity() - queue.capacity());
+    match TxJournal::from_env().launched_mints() {
+        Ok(mints) => {
+            let _ = writeln!(report, "{} confirmed launch(es)", mints.len());
+            for mint in mints.iter().rev().take(5) {
+                let _ = writeln!(report, "- `{}`", mint);
+            }
+        }
+        Err(e) => {
+            let _ = writeln!(report, "Cannot read the transaction journal: {}", e);
+        }
+    }
+    report
+}
+
+async fn portfolio_report() -> Result<String> {
+    let creator = TokenCreator::new()?;
+    let wallet = creator.get_wallet_address();
+    let mut report = format!("Wallet `{}`: {:.4} SOL\n", wallet, creator.get_wallet_balance().await?);
+    for mint in TxJournal::from_env().launched_mints()? {
+        let account = get_associated_token_address(&wallet, &mint);
+        let tokens = match creator.rpc().get_token_account_balance(&account).await {
+            Ok(balance) => balance.amount.parse::<u64>().unwrap_or(0) as f64 / TOKEN_UNIT,
+            Err(_) => 0.0,
+        };
+        let _ = writeln!(report, "- `{}`: {:.0} tokens", mint, tokens);
+    }
+    Ok(report)
+}
+
+async fn edit_original_response(application_id: &str, token: &str, content: &str) -> Result<()> {
+    reqwest::Client::builder().timeout(Duration::from_secs(10)).build()?
+        .patch(format!("{}/webhooks/{}/{}/messages/@original", DISCORD_API_URL, application_id, token))
+        .json(&serde_json::json!({ "content": content }))
+        .send().await?
+        .error_for_status()?;
+    Ok(())
+}
+
+/// Register the `/launch`, `/status` and `/portfolio` commands with Discord (needs `DISCORD_BOT_TOKEN`)
+pub async fn register_commands(config: &DiscordConfig) -> Result<()> {
+    let token = env::var("DISCORD_BOT_TOKEN").map_err(|_| anyhow::anyhow!("DISCORD_BOT_TOKEN must be set to register commands"))?;
+    let commands = serde_json::json!([
+        {
+            "name": "launch",
+            "description": "Launch a stored draft",
+            "options": [{ "type": 3, "name": "draft", "description": "Draft name", "required": true }]
+        },
+        { "name": "status", "description": "Queued and recent launches" },
+        { "name": "portfolio", "description": "Launcher wallet balance and token holdings" }
+    ]);
+    reqwest::Client::builder().timeout(Duration::from_secs(10)).build()?
+        .put(format!("{}/applications/{}/commands", DISCORD_API_URL, config.application_id))
+        .header(reqwest::header::AUTHORIZATION, format!("Bot {}", token))
+        .json(&commands)
+        .send().await?
+        .error_for_status()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{signature::Keypair, signer::Signer};
+
+    #[tokio::test]
+    async fn test_interactions() {
+        let key = Keypair::new();
+        let body = br#"{"type":1}"#;
+        let signature = key.sign_message(&[b"1700000000".as_slice(), body].concat());
+        let hex: String = signature.as_ref().iter().map(|b| format!("{:02x}", b)).collect();
+        let public_key = key.pubkey().to_bytes();
+        assert!(verify_interaction(&public_key, Some("1700000000"), Some(&hex), body, 1_700_000_100).is_ok());
+        assert!(verify_interaction(&public_key, Some("1700000001"), Some(&hex), body, 1_700_000_100).is_err());
+        // A captured interaction cannot be replayed later
+        assert!(verify_interaction(&public_key, Some("1700000000"), Some(&hex), body, 1_700_001_000).is_err());
+
+        let config = DiscordConfig { application_id: "app".to_string(), public_key, allowed_role: "42".to_string() };
+        let (queue, _fired) = mpsc::channel(1);
+        let ping = handle_interaction(&config, &serde_json::json!({ "type": 1 }), &queue).await;
+        assert_eq!(ping["type"], PONG);
+
+        let command = |roles: serde_json::Value| serde_json::json!({
+            "type": 2,
+            "member": { "roles": roles, "user": { "username": "ops" } },
+            "data": { "name": "launch", "options": [{ "name": "draft", "value": "../secret" }] }
+        });
+        let denied = handle_interaction(&config, &command(serde_json::json!(["7"])), &queue).await;
+        assert!(denied["data"]["content"].as_str().unwrap().contains("permission"));
+        let invalid = handle_interaction(&config, &command(serde_json::json!(["42"])), &queue).await;
+        assert!(invalid["data"]["content"].as_str().unwrap().contains("Invalid draft name"));
+    }
+}
@@ -0,0 +1,169 @@
+use anyhow::Result;
+use serde::Serialize;
+use solana_client::rpc_config::RpcSimulateTransactionConfig;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair};
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+use crate::cluster::SolanaCluster;
+use crate::create_token::{AssignedMint, PreparedMetadata, TokenCreator};
+use crate::secure_credentials::SecureApiKey;
+
+/// An RPC endpoint a launch is simulated against
+#[derive(Debug, Clone)]
+pub struct SimulationTarget {
+    pub label: String,
+    rpc_url: String,
+}
+
+impl FromStr for SimulationTarget {
+    type Err = anyhow::Error;
+
+    /// `mainnet`, `devnet`, `localnet`, `helius` (the `HELIUS_API_KEY` endpoint), or `[LABEL=]URL`
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("helius") {
+            let url = SecureApiKey::load("HELIUS_API_KEY")?;
+            return Ok(Self { label: "helius".to_string(), rpc_url: url.expose_secret().to_string() });
+        }
+        if let Ok(cluster) = s.parse::<SolanaCluster>() {
+            return Ok(Self { label: s.to_lowercase(), rpc_url: cluster.rpc_url().to_string() });
+        }
+        // Text before `=` is a label only when it is not part of the URL (e.g. `?api-key=...`)
+        let (label, url) = match s.split_once('=') {
+            Some((label, url)) if !label.contains(['/', '?', ':']) => (Some(label.trim().to_string()), url.trim()),
+            _ => (None, s),
+        };
+        let parsed = url::Url::parse(url).map_err(|e| anyhow::anyhow!("Invalid simulation target '{}': {}", s, e))?;
+        // Default to the host: provider URLs often carry an API key
+        let label = label.or_else(|| parsed.host_str().map(str::to_string))
+            .ok_or_else(|| anyhow::anyhow!("Simulation target '{}' has no host", s))?;
+        Ok(Self { label, rpc_url: url.to_string() })
+    }
+}
+
+/// Result of simulating the launch on one target
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationOutcome {
+    pub target: String,
+    pub program_version: Option<String>,
+    pub success: bool,
+    pub error: Option<String>,
+    pub units_consumed: Option<u64>,
+    pub size_bytes: Option<usize>,
+    pub accounts: Vec<String>,
+    pub logs: Vec<String>,
+}
+
+impl SimulationOutcome {
+    fn failed(target: &str, error: String) -> Self {
+        Self {
+            target: target.to_string(),
+            program_version: None,
+            success: false,
+            error: Some(error),
+            units_consumed: None,
+            size_bytes: None,
+            accounts: Vec::new(),
+            logs: Vec::new(),
+        }
+    }
+}
+
+/// Build, sign and simulate the launch of `metadata` with `mint` through `creator`'s RPC
+pub async fn simulate_launch(creator: &TokenCreator, target: &str, metadata: &PreparedMetadata, mint: &Keypair, fee_creator: Option<Pubkey>) -> SimulationOutcome {
+    let result = async {
+        let launch = creator.build_transaction(metadata, AssignedMint::from_keypair(mint.insecure_clone()), fee_creator).await?;
+        let program_version = format!("{:?}", launch.program_version);
+        let signed = creator.sign(launch).await?;
+        let config = RpcSimulateTransactionConfig {
+            sig_verify: false,
+            replace_recent_blockhash: true,
+            commitment: Some(CommitmentConfig::processed()),
+            ..Default::default()
+        };
+        let result = creator.rpc().simulate_transaction_with_config(&signed.transaction, config).await?.value;
+        Ok::<_, anyhow::Error>(SimulationOutcome {
+            target: target.to_string(),
+            program_version: Some(program_version),
+            success: result.err.is_none(),
+            error: result.err.map(|e| format!("{:?}", e)),
+            units_consumed: result.units_consumed,
+            size_bytes: Some(signed.size_bytes),
+            accounts: signed.transaction.message.account_keys.iter().map(|k| k.to_string()).collect(),
+            logs: result.logs.unwrap_or_default(),
+        })
+    }.await;
+    result.unwrap_or_else(|e| SimulationOutcome::failed(target, e.to_string()))
+}
+
+/// Simulate the same launch (same mint and metadata) on every target concurrently
+pub async fn compare_dry_runs(base: &TokenCreator, targets: &[SimulationTarget], metadata: &PreparedMetadata, fee_creator: Option<Pubkey>) -> Result<Vec<SimulationOutcome>> {
+    let mint = Keypair::new();
+    let creators = targets.iter()
+        .map(|target| base.with_rpc_url(target.rpc_url.clone()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let simulations = creators.iter().zip(targets)
+        .map(|(creator, target)| simulate_launch(creator, &target.label, metadata, &mint, fee_creator));
+    Ok(futures::future::join_all(simulations).await)
+}
+
+/// Human-readable differences between the outcomes
+pub fn differences(outcomes: &[SimulationOutcome]) -> Vec<String> {
+    let mut differences = Vec::new();
+    let Some((first, rest)) = outcomes.split_first() else {
+        return differences;
+    };
+    for other in rest {
+        let pair = format!("{} vs {}", first.target, other.target);
+        if first.program_version != other.program_version {
+            differences.push(format!("{}: program version {} vs {}", pair,
+                first.program_version.as_deref().unwrap_or("unresolved"), other.program_version.as_deref().unwrap_or("unresolved")));
+        }
+        if first.success != other.success {
+            differences.push(format!("{}: simulation {} vs {}", pair,
+                if first.success { "succeeded" } else { "failed" }, if other.success { "succeeded" } else { "failed" }));
+        }
+        if first.size_bytes != other.size_bytes {
+            differences.push(format!("{}: transaction size {:?} vs {:?} bytes", pair, first.size_bytes, other.size_bytes));
+        }
+        let (ours, theirs): (BTreeSet<_>, BTreeSet<_>) = (first.accounts.iter().collect(), other.accounts.iter().collect());
+        for account in ours.difference(&theirs) {
+            differences.push(format!("{}: account {} only used on {}", pair, account, first.target));
+        }
+        for account in theirs.difference(&ours) {
+            differences.push(format!("{}: account {} only used on {}", pair, account, other.target));
+        }
+    }
+    differences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_outcomes() {
+        let devnet: SimulationTarget = "devnet".parse().unwrap();
+        assert_eq!(devnet.rpc_url, SolanaCluster::Devnet.rpc_url());
+        let provider: SimulationTarget = "https://rpc.example.com/?api-key=secret".parse().unwrap();
+        assert_eq!(provider.label, "rpc.example.com");
+        assert_eq!("backup=https://rpc.example.com".parse::<SimulationTarget>().unwrap().label, "backup");
+        let labelled: SimulationTarget = "paid=https://rpc.example.com/?api-key=secret".parse().unwrap();
+        assert_eq!((labelled.label.as_str(), labelled.rpc_url.as_str()), ("paid", "https://rpc.example.com/?api-key=secret"));
+        assert!("nope".parse::<SimulationTarget>().is_err());
+
+        let mut mainnet = SimulationOutcome::failed("mainnet", "boom".to_string());
+        let mut devnet = mainnet.clone();
+        devnet.target = "devnet".to_string();
+        assert!(differences(&[mainnet.clone(), devnet.clone()]).is_empty());
+
+        mainnet.program_version = Some("CreatorVault".to_string());
+        mainnet.accounts = vec!["fee".to_string()];
+        let found = differences(&[mainnet, devnet]);
+        assert_eq!(found.len(), 2);
+        assert!(found[0].contains("program version CreatorVault vs unresolved"));
+        assert!(found[1].contains("account fee only used on mainnet"));
+    }
+}
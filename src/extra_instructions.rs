@@ -0,0 +1,219 @@
+use anyhow::Result;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::Deserialize;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+use crate::create_token::LAMPORTS_PER_SOL;
+use crate::funding::system_transfer_instruction;
+use crate::tx_size::TransactionComponent;
+
+pub const MEMO_PROGRAM_ID: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
+pub const COMPUTE_BUDGET_PROGRAM_ID: &str = "ComputeBudget111111111111111111111111111111";
+// ComputeBudgetInstruction discriminators
+const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+const SET_COMPUTE_UNIT_PRICE: u8 = 3;
+/// Compute unit limit the runtime applies when none is requested
+const DEFAULT_COMPUTE_UNIT_LIMIT: u64 = 200_000;
+/// Priority fees above this many SOL are flagged unless `PRIORITY_FEE_WARN_SOL` is set
+pub const DEFAULT_PRIORITY_FEE_WARN_SOL: f64 = 0.01;
+
+/// Instructions appended to the launch transaction after the create instruction
+#[derive(Debug, Clone, Default)]
+pub struct ExtraInstructions {
+    pub memo: Option<String>,
+    /// SOL transfers (e.g. tips) from the payer: recipient and lamports
+    pub transfers: Vec<(Pubkey, u64)>,
+    /// Arbitrary instructions, e.g. loaded with [`load_instructions_file`]
+    pub instructions: Vec<TransactionComponent>,
+}
+
+impl ExtraInstructions {
+    pub fn is_empty(&self) -> bool {
+        self.memo.is_none() && self.transfers.is_empty() && self.instructions.is_empty()
+    }
+
+    /// Components for a launch paid by `payer`
+    pub fn components(&self, payer: &Pubkey) -> Vec<TransactionComponent> {
+        let mut components = Vec::new();
+        if let Some(memo) = &self.memo {
+            components.push(TransactionComponent::new(format!("memo ({}B)", memo.len()), memo_instruction(payer, memo)));
+        }
+        for (recipient, lamports) in &self.transfers {
+            components.push(TransactionComponent::new(
+                format!("transfer {} SOL to {}", *lamports as f64 / LAMPORTS_PER_SOL, recipient),
+                system_transfer_instruction(payer, recipient, *lamports),
+            ));
+        }
+        components.extend(self.instructions.iter().cloned());
+        components
+    }
+}
+
+/// SPL memo signed by `payer`
+pub fn memo_instruction(payer: &Pubkey, memo: &str) -> Instruction {
+    Instruction {
+        program_id: Pubkey::from_str(MEMO_PROGRAM_ID).unwrap(),
+        accounts: vec![AccountMeta::new_readonly(*payer, true)],
+        data: memo.as_bytes().to_vec(),
+    }
+}
+
+pub fn compute_unit_limit_instruction(units: u32) -> Instruction {
+    let mut data = vec![SET_COMPUTE_UNIT_LIMIT];
+    data.extend_from_slice(&units.to_le_bytes());
+    Instruction { program_id: Pubkey::from_str(COMPUTE_BUDGET_PROGRAM_ID).unwrap(), accounts: vec![], data }
+}
+
+pub fn compute_unit_price_instruction(micro_lamports: u64) -> Instruction {
+    let mut data = vec![SET_COMPUTE_UNIT_PRICE];
+    data.extend_from_slice(&micro_lamports.to_le_bytes());
+    Instruction { program_id: Pubkey::from_str(COMPUTE_BUDGET_PROGRAM_ID).unwrap(), accounts: vec![], data }
+}
+
+/// Whether any of `components` is a compute budget instruction; a transaction may carry each kind only once
+pub fn has_compute_budget(components: &[TransactionComponent]) -> bool {
+    let budget = Pubkey::from_str(COMPUTE_BUDGET_PROGRAM_ID).unwrap();
+    components.iter().any(|c| c.instruction.program_id == budget)
+}
+
+/// Parse `--transfer <PUBKEY>=<SOL>`
+pub fn parse_transfer(value: &str) -> Result<(Pubkey, u64), String> {
+    let (recipient, sol) = value.split_once('=').ok_or_else(|| format!("expected <PUBKEY>=<SOL>, got '{}'", value))?;
+    let recipient = Pubkey::from_str(recipient.trim()).map_err(|e| format!("invalid pubkey '{}': {}", recipient, e))?;
+    match sol.trim().parse::<f64>() {
+        Ok(sol) if sol.is_finite() && sol > 0.0 => Ok((recipient, (sol * LAMPORTS_PER_SOL) as u64)),
+        _ => Err(format!("expected a positive SOL amount, got '{}'", sol)),
+    }
+}
+
+#[derive(Deserialize)]
+struct AccountSpec {
+    pubkey: String,
+    #[serde(default)]
+    is_signer: bool,
+    #[serde(default)]
+    is_writable: bool,
+}
+
+#[derive(Deserialize)]
+struct InstructionSpec {
+    #[serde(default)]
+    label: Option<String>,
+    program_id: String,
+    #[serde(default)]
+    accounts: Vec<AccountSpec>,
+    /// Base64 instruction data
+    #[serde(default)]
+    data: String,
+}
+
+/// Instructions from a JSON array of `{ "program_id", "accounts": [{ "pubkey", "is_signer", "is_writable" }], "data": "<base64>" }`
+pub fn load_instructions_file(path: &str) -> Result<Vec<TransactionComponent>> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read extra instructions {}: {}", path, e))?;
+    let specs: Vec<InstructionSpec> = serde_json::from_str(&contents)
+        .map_err(|e| anyhow::anyhow!("Invalid extra instructions file {}: {}", path, e))?;
+    specs.into_iter().enumerate().map(|(index, spec)| {
+        let program_id = Pubkey::from_str(&spec.program_id)
+            .map_err(|e| anyhow::anyhow!("Instruction #{} in {}: invalid program id: {}", index, path, e))?;
+        let accounts = spec.accounts.iter()
+            .map(|account| Ok(AccountMeta {
+                pubkey: Pubkey::from_str(&account.pubkey)
+                    .map_err(|e| anyhow::anyhow!("Instruction #{} in {}: invalid account: {}", index, path, e))?,
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            }))
+            .collect::<Result<Vec<_>>>()?;
+        let data = BASE64.decode(spec.data.trim())
+            .map_err(|e| anyhow::anyhow!("Instruction #{} in {}: data is not base64: {}", index, path, e))?;
+        let label = spec.label.unwrap_or_else(|| format!("extra instruction #{} ({})", index, program_id));
+        Ok(TransactionComponent::new(label, Instruction { program_id, accounts, data }))
+    }).collect()
+}
+
+/// Most the compute budget instructions among `components` can cost in priority fees, if they set a price
+pub fn priority_fee_lamports(components: &[TransactionComponent]) -> Option<u64> {
+    let budget = Pubkey::from_str(COMPUTE_BUDGET_PROGRAM_ID).unwrap();
+    let (mut price, mut limit) = (None, None);
+    for component in components.iter().filter(|c| c.instruction.program_id == budget) {
+        let data = &component.instruction.data;
+        match data.first() {
+            Some(&SET_COMPUTE_UNIT_PRICE) if data.len() >= 9 => price = Some(u64::from_le_bytes(data[1..9].try_into().unwrap())),
+            Some(&SET_COMPUTE_UNIT_LIMIT) if data.len() >= 5 => limit = Some(u32::from_le_bytes(data[1..5].try_into().unwrap()) as u64),
+            _ => {}
+        }
+    }
+    // The price is in micro-lamports per compute unit
+    price.map(|price| (price as u128 * limit.unwrap_or(DEFAULT_COMPUTE_UNIT_LIMIT) as u128 / 1_000_000) as u64)
+}
+
+pub fn priority_fee_warn_lamports_from_env() -> u64 {
+    let sol = std::env::var("PRIORITY_FEE_WARN_SOL").ok()
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|sol| sol.is_finite() && *sol >= 0.0)
+        .unwrap_or(DEFAULT_PRIORITY_FEE_WARN_SOL);
+    (sol * LAMPORTS_PER_SOL) as u64
+}
+
+/// Fail when an instruction needs a signature other than the payer's or the mint's
+pub fn check_signers(components: &[TransactionComponent], payer: &Pubkey, mint: &Pubkey) -> Result<()> {
+    for component in components {
+        if let Some(account) = component.instruction.accounts.iter().find(|a| a.is_signer && a.pubkey != *payer && a.pubkey != *mint) {
+            return Err(anyhow::anyhow!("{} needs a signature from {}, which the launcher cannot provide", component.label, account.pubkey));
+        }
+    }
+    Ok(())
+}
+
+/// Match the signers a message requires against the labelled keys about to sign it, so a mismatch
+/// fails with a specific error instead of the RPC's "missing signature"
+pub fn check_signer_set(required: &[Pubkey], provided: &[(&str, Pubkey)]) -> Result<()> {
+    for (index, (label, key)) in provided.iter().enumerate() {
+        if let Some((other, _)) = provided[..index].iter().find(|(_, earlier)| earlier == key) {
+            return Err(anyhow::anyhow!("The {} and the {} are the same key {}; each role needs its own keypair", other, label, key));
+        }
+    }
+    if let Some(missing) = required.iter().find(|key| !provided.iter().any(|(_, signer)| signer == *key)) {
+        return Err(anyhow::anyhow!("The transaction needs a signature from {}, but no keypair for it is loaded", missing));
+    }
+    if let Some((label, unused)) = provided.iter().find(|(_, key)| !required.contains(key)) {
+        return Err(anyhow::anyhow!("The {} {} is not a signer of any instruction in the transaction", label, unused));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extra_instructions() {
+        let payer = Pubkey::new_unique();
+        let tip = Pubkey::new_unique();
+        assert_eq!(parse_transfer(&format!("{}=0.01", tip)), Ok((tip, 10_000_000)));
+        assert!(parse_transfer(&format!("{}=-1", tip)).is_err());
+        assert!(parse_transfer("nope").is_err());
+
+        let extras = ExtraInstructions { memo: Some("gm".to_string()), transfers: vec![(tip, 10_000_000)], ..Default::default() };
+        let components = extras.components(&payer);
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].instruction.data, b"gm");
+        assert!(check_signers(&components, &payer, &Pubkey::new_unique()).is_ok());
+        assert!(check_signers(&components, &Pubkey::new_unique(), &Pubkey::new_unique()).is_err());
+
+        let mint = Pubkey::new_unique();
+        assert!(check_signer_set(&[payer, mint], &[("payer", payer), ("mint", mint)]).is_ok());
+        assert!(check_signer_set(&[payer], &[("payer", payer), ("mint", payer)]).unwrap_err().to_string().contains("payer and the mint are the same key"));
+        assert!(check_signer_set(&[payer, mint, tip], &[("payer", payer), ("mint", mint)]).unwrap_err().to_string().contains(&tip.to_string()));
+        assert!(check_signer_set(&[payer], &[("payer", payer), ("mint", mint)]).unwrap_err().to_string().contains("mint"));
+
+        let price = TransactionComponent::new("price", compute_unit_price_instruction(5_000_000));
+        let limit = TransactionComponent::new("limit", compute_unit_limit_instruction(400_000));
+        assert_eq!(priority_fee_lamports(&components), None);
+        assert!(!has_compute_budget(&components));
+        assert_eq!(priority_fee_lamports(std::slice::from_ref(&price)), Some(1_000_000));
+        assert_eq!(priority_fee_lamports(&[limit, price]), Some(2_000_000));
+    }
+}
@@ -0,0 +1,128 @@
+use anyhow::Result;
+use log::{info, error};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    transaction::Transaction,
+};
+use std::env;
+use std::str::FromStr;
+
+use crate::audit_log::{AuditLog, AuditEvent};
+use crate::create_token::{LAMPORTS_PER_SOL, SYSTEM_PROGRAM_ID};
+use crate::launch_cost::LAMPORTS_PER_SIGNATURE;
+use crate::price_feed::format_sol;
+use crate::read_only::ensure_writable;
+use crate::secure_credentials::SecurePrivateKey;
+
+// SystemInstruction::Transfer variant index
+const SYSTEM_TRANSFER_INDEX: u32 = 2;
+/// Largest single top-up unless `TOPUP_MAX_SOL` is set
+pub const DEFAULT_TOPUP_MAX_LAMPORTS: u64 = 100_000_000; // 0.1 SOL
+
+/// System program transfer of `lamports` from `from` to `to`
+pub fn system_transfer_instruction(from: &Pubkey, to: &Pubkey, lamports: u64) -> Instruction {
+    let mut data = SYSTEM_TRANSFER_INDEX.to_le_bytes().to_vec();
+    data.extend_from_slice(&lamports.to_le_bytes());
+    Instruction {
+        program_id: Pubkey::from_str(SYSTEM_PROGRAM_ID).unwrap(),
+        accounts: vec![
+            AccountMeta::new(*from, true),
+            AccountMeta::new(*to, false),
+        ],
+        data,
+    }
+}
+
+/// Wallet that tops up the launch wallet when it is short of the required balance
+pub struct FundingWallet {
+    funder: Keypair,
+    max_topup_lamports: u64,
+}
+
+impl FundingWallet {
+    /// Enabled by `AUTO_TOPUP=true`; reads `FUNDING_PRIVATE_KEY` and the `TOPUP_MAX_SOL` cap
+    pub fn from_env() -> Result<Option<Self>> {
+        let enabled = env::var("AUTO_TOPUP").unwrap_or_else(|_| "false".to_string()).to_lowercase() == "true";
+        if !enabled {
+            return Ok(None);
+        }
+
+        let key_bytes = SecurePrivateKey::load("FUNDING_PRIVATE_KEY")?.to_bytes()?;
+        let funder = Keypair::try_from(&key_bytes[..])
+            .map_err(|e| anyhow::anyhow!("Invalid FUNDING_PRIVATE_KEY: {}", e))?;
+
+        let max_topup_lamports = match env::var("TOPUP_MAX_SOL") {
+            Ok(value) => {
+                let sol: f64 = value.trim().parse()
+                    .map_err(|_| anyhow::anyhow!("Invalid TOPUP_MAX_SOL '{}'", value))?;
+                (sol * LAMPORTS_PER_SOL) as u64
+            }
+            Err(_) => DEFAULT_TOPUP_MAX_LAMPORTS,
+        };
+
+        Ok(Some(Self { funder, max_topup_lamports }))
+    }
+
+    /// Funding wallet paying from `funder`, capped at `max_topup_lamports` per transfer
+    pub fn new(funder: Keypair, max_topup_lamports: u64) -> Self {
+        Self { funder, max_topup_lamports }
+    }
+
+    pub fn address(&self) -> Pubkey {
+        self.funder.pubkey()
+    }
+
+    /// Transfer `shortfall` lamports to `recipient`, refusing amounts above the cap; `operator` is recorded in the audit log
+    pub async fn top_up(&self, rpc: &RpcClient, recipient: &Pubkey, shortfall: u64, sol_usd: Option<f64>, operator: Option<&str>) -> Result<Signature> {
+        ensure_writable("transfer SOL")?;
+        if shortfall > self.max_topup_lamports {
+            return Err(anyhow::anyhow!(
+                "Top-up of {} exceeds the {} cap (TOPUP_MAX_SOL)",
+                format_sol(shortfall, sol_usd),
+                format_sol(self.max_topup_lamports, sol_usd)
+            ));
+        }
+
+        let funder = self.funder.pubkey();
+        let funder_balance = rpc.get_balance(&funder).await?;
+        if shortfall.checked_add(LAMPORTS_PER_SIGNATURE).is_none_or(|needed| funder_balance < needed) {
+            return Err(anyhow::anyhow!(
+                "Funding wallet {} has {}, cannot top up {}",
+                funder, format_sol(funder_balance, sol_usd), format_sol(shortfall, sol_usd)
+            ));
+        }
+
+        info!("Topping up {} with {} from funding wallet {}", recipient, format_sol(shortfall, sol_usd), funder);
+        let recent_blockhash = rpc.get_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(
+            &[system_transfer_instruction(&funder, recipient, shortfall)],
+            Some(&funder),
+            &[&self.funder],
+            recent_blockhash,
+        );
+        let result = rpc.send_and_confirm_transaction(&transaction).await;
+
+        if let Some(audit_log) = AuditLog::from_env() {
+            let event = AuditEvent {
+                purpose: "top_up".to_string(),
+                signature: transaction.signatures[0].to_string(),
+                payer: funder.to_string(),
+                amount_lamports: Some(shortfall),
+                status: if result.is_ok() { "confirmed" } else { "failed" }.to_string(),
+                operator: operator.map(String::from),
+                ..Default::default()
+            };
+            if let Err(e) = audit_log.append(event) {
+                error!("Failed to write audit log entry to {}: {}", audit_log.path(), e);
+            }
+        }
+
+        let signature = result?;
+        info!("Top-up confirmed: {}", signature);
+        Ok(signature)
+    }
+}
@@ -0,0 +1,168 @@
+use anyhow::Result;
+use log::{info, warn};
+use serde::Serialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write as _;
+use std::str::FromStr;
+
+use crate::buyers::{fetch_address_history, fetch_transaction_json, transaction_account_keys};
+
+/// Oldest transactions of each wallet inspected for funding and transfer links
+pub const FUNDING_SCAN_TXS: usize = 10;
+/// Wallets with longer histories than this are treated as established and not traced
+pub const MAX_WALLET_HISTORY: usize = 2_000;
+
+/// A relationship between two wallets
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+    /// "funded" (first SOL came from `from`) or "transfer" (wallets appear in the same transaction)
+    pub kind: String,
+}
+
+/// Buyer wallets connected through shared funders or direct transfers
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Cluster {
+    pub wallets: Vec<String>,
+    pub funders: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FundingGraph {
+    pub clusters: Vec<Cluster>,
+    pub edges: Vec<Edge>,
+}
+
+/// Account that paid for the first transaction raising `wallet`'s SOL balance, if not the wallet itself
+pub fn funding_source(tx: &serde_json::Value, wallet: &str) -> Option<String> {
+    let keys = transaction_account_keys(tx);
+    let index = keys.iter().position(|k| k == wallet)?;
+    let pre = tx["meta"]["preBalances"][index].as_u64()?;
+    let post = tx["meta"]["postBalances"][index].as_u64()?;
+    let fee_payer = keys.first()?;
+    (post > pre && fee_payer != wallet).then(|| fee_payer.clone())
+}
+
+/// Trace funding and transfer links between `buyers`
+pub async fn analyze_buyers(rpc: &RpcClient, buyers: &[String]) -> Result<FundingGraph> {
+    let mut edges = BTreeSet::new();
+    for buyer in buyers {
+        let history = match fetch_address_history(rpc, &Pubkey::from_str(buyer)?, MAX_WALLET_HISTORY).await {
+            Ok(history) => history,
+            Err(e) => {
+                warn!("Skipping {}: {}", buyer, e);
+                continue;
+            }
+        };
+        info!("Tracing {} ({} transactions)", buyer, history.len());
+
+        let mut funded = false;
+        for entry in history.iter().filter(|s| s.err.is_none()).take(FUNDING_SCAN_TXS) {
+            let tx = fetch_transaction_json(rpc, &entry.signature).await?;
+            if !funded {
+                if let Some(funder) = funding_source(&tx, buyer) {
+                    edges.insert(Edge { from: funder, to: buyer.clone(), kind: "funded".to_string() });
+                    funded = true;
+                }
+            }
+            for other in transaction_account_keys(&tx).into_iter().filter(|k| k != buyer && buyers.contains(k)) {
+                let (from, to) = if other < *buyer { (other, buyer.clone()) } else { (buyer.clone(), other) };
+                edges.insert(Edge { from, to, kind: "transfer".to_string() });
+            }
+        }
+    }
+
+    let edges: Vec<Edge> = edges.into_iter().collect();
+    Ok(FundingGraph { clusters: build_clusters(buyers, &edges), edges })
+}
+
+/// Group buyers sharing a funder or linked by a transfer; only groups of two or more buyers are returned
+pub fn build_clusters(buyers: &[String], edges: &[Edge]) -> Vec<Cluster> {
+    fn find(parent: &mut HashMap<String, String>, node: &str) -> String {
+        let next = parent.get(node).cloned().unwrap_or_else(|| node.to_string());
+        if next == node {
+            return next;
+        }
+        let root = find(parent, &next);
+        parent.insert(node.to_string(), root.clone());
+        root
+    }
+
+    let mut parent: HashMap<String, String> = HashMap::new();
+    for edge in edges {
+        let (a, b) = (find(&mut parent, &edge.from), find(&mut parent, &edge.to));
+        if a != b {
+            parent.insert(a, b);
+        }
+    }
+
+    let mut groups: BTreeMap<String, Cluster> = BTreeMap::new();
+    let nodes: BTreeSet<&String> = edges.iter().flat_map(|e| [&e.from, &e.to]).collect();
+    for node in nodes {
+        let root = find(&mut parent, node);
+        let cluster = groups.entry(root).or_insert_with(|| Cluster { wallets: Vec::new(), funders: Vec::new() });
+        if buyers.contains(node) {
+            cluster.wallets.push(node.clone());
+        } else {
+            cluster.funders.push(node.clone());
+        }
+    }
+    groups.into_values().filter(|c| c.wallets.len() > 1).collect()
+}
+
+/// Graphviz rendering of the funding graph, buyers in clusters highlighted
+pub fn to_dot(graph: &FundingGraph) -> String {
+    let mut out = String::from("digraph funding {\n  rankdir=LR;\n");
+    for cluster in &graph.clusters {
+        for wallet in &cluster.wallets {
+            let _ = writeln!(out, "  \"{}\" [style=filled, fillcolor=salmon];", wallet);
+        }
+    }
+    for edge in &graph.edges {
+        let style = if edge.kind == "transfer" { "dashed" } else { "solid" };
+        let _ = writeln!(out, "  \"{}\" -> \"{}\" [label=\"{}\", style={}];", edge.from, edge.to, edge.kind, style);
+    }
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(from: &str, to: &str, kind: &str) -> Edge {
+        Edge { from: from.to_string(), to: to.to_string(), kind: kind.to_string() }
+    }
+
+    #[test]
+    fn test_clusters_share_funders_and_transfers() {
+        let buyers: Vec<String> = ["a", "b", "c", "d", "e"].iter().map(|s| s.to_string()).collect();
+        let edges = vec![
+            edge("funder1", "a", "funded"),
+            edge("funder1", "b", "funded"),
+            edge("b", "c", "transfer"),
+            edge("funder2", "d", "funded"),
+        ];
+        let clusters = build_clusters(&buyers, &edges);
+        assert_eq!(clusters, vec![Cluster {
+            wallets: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            funders: vec!["funder1".to_string()],
+        }]);
+
+        let dot = to_dot(&FundingGraph { clusters, edges });
+        assert!(dot.contains("\"b\" -> \"c\" [label=\"transfer\", style=dashed];"));
+    }
+
+    #[test]
+    fn test_funding_source() {
+        let tx = serde_json::json!({
+            "transaction": { "message": { "accountKeys": ["funder", "wallet"] } },
+            "meta": { "preBalances": [5, 0], "postBalances": [2, 3] }
+        });
+        assert_eq!(funding_source(&tx, "wallet"), Some("funder".to_string()));
+        assert_eq!(funding_source(&tx, "funder"), None);
+    }
+}
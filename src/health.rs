@@ -0,0 +1,114 @@
+use anyhow::Result;
+use serde::Serialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+use std::env;
+use std::time::Duration;
+
+use crate::create_token::{rpc_url_from_env, LAMPORTS_PER_SOL};
+use crate::vanity_address::{get_global_vanity_pool, VanityConfig};
+use crate::wallets::load_wallet;
+
+/// Payer balance below which the daemon reports not ready, unless `HEALTH_MIN_BALANCE_SOL` is set
+pub const DEFAULT_HEALTH_MIN_BALANCE_SOL: f64 = 0.05;
+/// RPC calls slower than this fail the probe; Kubernetes probes time out after a few seconds
+const CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Result of checking one dependency
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DependencyCheck {
+    pub name: &'static str,
+    pub ok: bool,
+    pub detail: String,
+}
+
+impl DependencyCheck {
+    fn new(name: &'static str, ok: bool, detail: impl Into<String>) -> Self {
+        Self { name, ok, detail: detail.into() }
+    }
+}
+
+/// Ready only when every dependency check passed
+pub fn is_ready(checks: &[DependencyCheck]) -> bool {
+    checks.iter().all(|check| check.ok)
+}
+
+pub fn balance_check(result: Result<u64>, min_lamports: u64) -> DependencyCheck {
+    match result {
+        Ok(lamports) => DependencyCheck::new("balance", lamports >= min_lamports,
+            format!("{:.4} SOL (minimum {:.4} SOL)", lamports as f64 / LAMPORTS_PER_SOL, min_lamports as f64 / LAMPORTS_PER_SOL)),
+        Err(e) => DependencyCheck::new("balance", false, format!("cannot read balance: {}", e)),
+    }
+}
+
+/// `status` is `(has_generated, generated_count, is_generating)` of the global pool, `None` when it was never started
+pub fn vanity_check(enabled: bool, status: Option<(bool, usize, bool)>) -> DependencyCheck {
+    match (enabled, status) {
+        (false, _) => DependencyCheck::new("vanity_pool", true, "disabled"),
+        (true, None) => DependencyCheck::new("vanity_pool", false, "pool not initialized"),
+        // An empty pool that is still grinding only delays the next launch
+        (true, Some((_, count, generating))) => DependencyCheck::new("vanity_pool", count > 0 || generating,
+            format!("{} address(es) ready, generation {}", count, if generating { "running" } else { "stopped" })),
+    }
+}
+
+/// Dependency checks behind the daemon's `/readyz`
+pub struct HealthChecker {
+    rpc: RpcClient,
+    payer: Pubkey,
+    min_balance_lamports: u64,
+    vanity_enabled: bool,
+}
+
+impl HealthChecker {
+    /// Check the `PRIVATE_KEY` payer on the configured RPC endpoint
+    pub fn from_env() -> Result<Self> {
+        let min_balance = env::var("HEALTH_MIN_BALANCE_SOL").ok()
+            .and_then(|v| v.trim().parse::<f64>().ok())
+            .filter(|sol| sol.is_finite() && *sol >= 0.0)
+            .unwrap_or(DEFAULT_HEALTH_MIN_BALANCE_SOL);
+        Ok(Self {
+            rpc: RpcClient::new_with_timeout(rpc_url_from_env()?, CHECK_TIMEOUT),
+            payer: load_wallet(None)?.pubkey(),
+            min_balance_lamports: (min_balance * LAMPORTS_PER_SOL) as u64,
+            vanity_enabled: VanityConfig::from_env().enabled,
+        })
+    }
+
+    /// RPC reachability, payer balance and vanity pool state
+    pub async fn check(&self) -> Vec<DependencyCheck> {
+        let (slot, balance) = tokio::join!(self.rpc.get_slot(), self.rpc.get_balance(&self.payer));
+        let rpc = match slot {
+            Ok(slot) => DependencyCheck::new("rpc", true, format!("slot {}", slot)),
+            Err(e) => DependencyCheck::new("rpc", false, e.to_string()),
+        };
+        let pool = get_global_vanity_pool()
+            .map(|pool| (pool.has_generated_addresses(), pool.generated_addresses_count(), pool.is_generation_running()));
+        vec![
+            rpc,
+            balance_check(balance.map_err(Into::into), self.min_balance_lamports),
+            vanity_check(self.vanity_enabled, pool),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dependency_checks() {
+        assert!(balance_check(Ok(100_000_000), 50_000_000).ok);
+        assert!(!balance_check(Ok(10_000_000), 50_000_000).ok);
+        assert!(!balance_check(Err(anyhow::anyhow!("timeout")), 0).ok);
+
+        assert!(vanity_check(false, None).ok);
+        assert!(!vanity_check(true, None).ok);
+        assert!(vanity_check(true, Some((false, 0, true))).ok);
+        assert!(!vanity_check(true, Some((false, 0, false))).ok);
+
+        let checks = vec![balance_check(Ok(1), 0), vanity_check(true, None)];
+        assert!(!is_ready(&checks));
+        assert!(is_ready(&checks[..1]));
+    }
+}
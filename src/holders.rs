@@ -0,0 +1,175 @@
+use anyhow::Result;
+use log::{info, warn};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::pubkey::Pubkey;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::str::FromStr;
+
+use crate::pump_accounts::TOKEN_UNIT;
+
+const TOKEN_ACCOUNT_LEN: u64 = 165;
+const DAS_PAGE_LIMIT: usize = 1000;
+
+/// One owner's aggregated balance of a mint
+#[derive(Debug, Clone, PartialEq)]
+pub struct Holder {
+    pub owner: Pubkey,
+    /// Raw token amount summed over all of the owner's token accounts
+    pub amount: u64,
+    pub percent: f64,
+    /// Known role of the owner, e.g. "bonding_curve"
+    pub label: Option<String>,
+}
+
+/// (owner, amount) for every token account of `mint`.
+///
+/// Pages through Helius' `getTokenAccounts` when the RPC supports it, otherwise reads all
+/// token accounts with a single `getProgramAccounts` call.
+pub async fn fetch_token_balances(rpc: &RpcClient, rpc_url: &str, mint: &Pubkey) -> Result<Vec<(Pubkey, u64)>> {
+    match fetch_token_balances_das(rpc_url, mint).await {
+        Ok(balances) => Ok(balances),
+        Err(e) => {
+            warn!("Paged getTokenAccounts unavailable ({}), falling back to getProgramAccounts", e);
+            fetch_token_balances_program_accounts(rpc, mint).await
+        }
+    }
+}
+
+async fn fetch_token_balances_das(rpc_url: &str, mint: &Pubkey) -> Result<Vec<(Pubkey, u64)>> {
+    let client = reqwest::Client::new();
+    let mut balances = Vec::new();
+    for page in 1.. {
+        let response: serde_json::Value = client.post(rpc_url)
+            .json(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": "holders",
+                "method": "getTokenAccounts",
+                "params": { "mint": mint.to_string(), "page": page, "limit": DAS_PAGE_LIMIT },
+            }))
+            .send().await?
+            .error_for_status()?
+            .json().await?;
+        if let Some(error) = response.get("error") {
+            return Err(anyhow::anyhow!("{}", error));
+        }
+        let accounts = response["result"]["token_accounts"].as_array()
+            .ok_or_else(|| anyhow::anyhow!("Unexpected getTokenAccounts response"))?;
+        for account in accounts {
+            let owner = account["owner"].as_str().and_then(|o| Pubkey::from_str(o).ok());
+            if let (Some(owner), Some(amount)) = (owner, account["amount"].as_u64()) {
+                balances.push((owner, amount));
+            }
+        }
+        info!("Fetched page {} ({} token accounts)", page, accounts.len());
+        if accounts.len() < DAS_PAGE_LIMIT {
+            break;
+        }
+    }
+    Ok(balances)
+}
+
+async fn fetch_token_balances_program_accounts(rpc: &RpcClient, mint: &Pubkey) -> Result<Vec<(Pubkey, u64)>> {
+    let token_program = Pubkey::new_from_array(spl_token::ID.to_bytes());
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::DataSize(TOKEN_ACCOUNT_LEN),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, mint.as_ref())),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(solana_account_decoder_client_types::UiAccountEncoding::Base64),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let accounts = rpc.get_program_ui_accounts_with_config(&token_program, config).await?;
+    Ok(accounts.into_iter()
+        .filter_map(|(_, account)| {
+            let data = account.data.decode()?;
+            let owner = Pubkey::try_from(data.get(32..64)?).ok()?;
+            let amount = u64::from_le_bytes(data.get(64..72)?.try_into().ok()?);
+            Some((owner, amount))
+        })
+        .collect())
+}
+
+/// (mint, amount) of every non-empty token account `owner` holds
+pub async fn fetch_wallet_tokens(rpc: &RpcClient, owner: &Pubkey) -> Result<Vec<(Pubkey, u64)>> {
+    let token_program = Pubkey::new_from_array(spl_token::ID.to_bytes());
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::DataSize(TOKEN_ACCOUNT_LEN),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(32, owner.as_ref())),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(solana_account_decoder_client_types::UiAccountEncoding::Base64),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let accounts = rpc.get_program_ui_accounts_with_config(&token_program, config).await?;
+    let mut tokens: Vec<(Pubkey, u64)> = accounts.into_iter()
+        .filter_map(|(_, account)| {
+            let data = account.data.decode()?;
+            let mint = Pubkey::try_from(data.get(0..32)?).ok()?;
+            let amount = u64::from_le_bytes(data.get(64..72)?.try_into().ok()?);
+            Some((mint, amount))
+        })
+        .filter(|(_, amount)| *amount > 0)
+        .collect();
+    tokens.sort_by_key(|(_, amount)| Reverse(*amount));
+    Ok(tokens)
+}
+
+/// Aggregate balances per owner, drop owners below `min_amount`, and sort largest first
+pub fn build_snapshot(balances: &[(Pubkey, u64)], supply: u64, min_amount: u64, labels: &HashMap<Pubkey, String>) -> Vec<Holder> {
+    let mut per_owner: HashMap<Pubkey, u64> = HashMap::new();
+    for (owner, amount) in balances {
+        *per_owner.entry(*owner).or_default() += amount;
+    }
+    let mut holders: Vec<Holder> = per_owner.into_iter()
+        .filter(|(_, amount)| *amount > 0 && *amount >= min_amount)
+        .map(|(owner, amount)| Holder {
+            owner,
+            amount,
+            percent: if supply > 0 { amount as f64 * 100.0 / supply as f64 } else { 0.0 },
+            label: labels.get(&owner).cloned(),
+        })
+        .collect();
+    holders.sort_by(|a, b| b.amount.cmp(&a.amount).then(a.owner.cmp(&b.owner)));
+    holders
+}
+
+/// CSV with one row per holder: rank, owner, balance (UI units), percent of supply, label
+pub fn snapshot_to_csv(holders: &[Holder]) -> String {
+    let mut out = String::from("rank,owner,balance,percent,label\n");
+    for (index, holder) in holders.iter().enumerate() {
+        let _ = writeln!(out, "{},{},{:.6},{:.4},{}",
+                         index + 1, holder.owner, holder.amount as f64 / TOKEN_UNIT, holder.percent,
+                         holder.label.as_deref().unwrap_or(""));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_aggregates_and_filters() {
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let balances = vec![(a, 300), (b, 500), (a, 300), (c, 50), (c, 0)];
+        let labels = HashMap::from([(b, "bonding_curve".to_string())]);
+
+        let holders = build_snapshot(&balances, 1000, 100, &labels);
+        assert_eq!(holders.len(), 2);
+        assert_eq!((holders[0].owner, holders[0].amount, holders[0].percent), (a, 600, 60.0));
+        assert_eq!(holders[1].label.as_deref(), Some("bonding_curve"));
+
+        let csv = snapshot_to_csv(&holders);
+        assert!(csv.lines().nth(2).unwrap().ends_with(",bonding_curve"));
+    }
+}
@@ -0,0 +1,174 @@
+use anyhow::Result;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use solana_client::client_error::Result as ClientResult;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::{Transaction, TransactionError}};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::create_token::LAMPORTS_PER_SOL;
+
+/// Block engine regions, selectable by name with `--jito-block-engine`
+pub const BLOCK_ENGINES: &[(&str, &str)] = &[
+    ("mainnet", "https://mainnet.block-engine.jito.wtf"),
+    ("amsterdam", "https://amsterdam.mainnet.block-engine.jito.wtf"),
+    ("frankfurt", "https://frankfurt.mainnet.block-engine.jito.wtf"),
+    ("london", "https://london.mainnet.block-engine.jito.wtf"),
+    ("ny", "https://ny.mainnet.block-engine.jito.wtf"),
+    ("slc", "https://slc.mainnet.block-engine.jito.wtf"),
+    ("singapore", "https://singapore.mainnet.block-engine.jito.wtf"),
+    ("tokyo", "https://tokyo.mainnet.block-engine.jito.wtf"),
+];
+
+/// Jito's tip payment accounts; any of them credits the tip to the bundle
+pub const TIP_ACCOUNTS: &[&str] = &[
+    "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5",
+    "HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe",
+    "Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY",
+    "ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49",
+    "DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh",
+    "ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt",
+    "DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL",
+    "3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT",
+];
+
+/// Tip unless `--jito-tip` or `JITO_TIP_SOL` is set
+pub const DEFAULT_JITO_TIP_SOL: f64 = 0.001;
+/// Slots to wait for the bundle before sending through RPC, unless `--jito-max-slots` is set
+pub const DEFAULT_JITO_MAX_SLOTS: u64 = 20;
+
+/// A block engine endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockEngine {
+    pub url: String,
+}
+
+impl FromStr for BlockEngine {
+    type Err = anyhow::Error;
+
+    /// A region from [`BLOCK_ENGINES`] or a block engine URL
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if let Some((_, url)) = BLOCK_ENGINES.iter().find(|(region, _)| region.eq_ignore_ascii_case(s)) {
+            return Ok(Self { url: url.to_string() });
+        }
+        match url::Url::parse(s) {
+            Ok(url) if url.scheme() == "https" || url.scheme() == "http" => Ok(Self { url: s.trim_end_matches('/').to_string() }),
+            _ => Err(anyhow::anyhow!("Unknown block engine '{}' (expected one of {} or a URL)", s,
+                BLOCK_ENGINES.iter().map(|(region, _)| *region).collect::<Vec<_>>().join(", "))),
+        }
+    }
+}
+
+/// Tip accounts handed out round-robin, starting at a random one
+#[derive(Debug, Clone)]
+pub struct TipAccounts {
+    accounts: Vec<Pubkey>,
+    next: Arc<AtomicUsize>,
+}
+
+impl Default for TipAccounts {
+    fn default() -> Self {
+        let start = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.subsec_nanos() as usize).unwrap_or(0);
+        Self {
+            accounts: TIP_ACCOUNTS.iter().map(|a| Pubkey::from_str(a).unwrap()).collect(),
+            next: Arc::new(AtomicUsize::new(start)),
+        }
+    }
+}
+
+impl TipAccounts {
+    /// Spreading tips keeps concurrent bundles from contending for one write-locked account
+    pub fn next(&self) -> Pubkey {
+        self.accounts[self.next.fetch_add(1, Ordering::Relaxed) % self.accounts.len()]
+    }
+}
+
+/// Bundle submission settings for `--jito`
+#[derive(Debug, Clone)]
+pub struct JitoConfig {
+    pub block_engine: BlockEngine,
+    pub tip_lamports: u64,
+    /// Slots after submission before falling back to RPC
+    pub max_slots: u64,
+    pub tip_accounts: TipAccounts,
+}
+
+impl JitoConfig {
+    /// Tip from `JITO_TIP_SOL` and block engine from `JITO_BLOCK_ENGINE` where the flags leave them unset
+    pub fn resolve(block_engine: Option<&str>, tip_sol: Option<f64>, max_slots: u64) -> Result<Self> {
+        let block_engine = match block_engine {
+            Some(engine) => engine.parse()?,
+            None => std::env::var("JITO_BLOCK_ENGINE").unwrap_or_else(|_| "mainnet".to_string()).parse()?,
+        };
+        let tip_sol = match tip_sol {
+            Some(sol) => sol,
+            None => match std::env::var("JITO_TIP_SOL") {
+                Ok(value) => value.trim().parse::<f64>().map_err(|_| anyhow::anyhow!("Invalid JITO_TIP_SOL '{}'", value))?,
+                Err(_) => DEFAULT_JITO_TIP_SOL,
+            },
+        };
+        if !tip_sol.is_finite() || tip_sol <= 0.0 {
+            return Err(anyhow::anyhow!("Jito tip must be a positive SOL amount, got {}", tip_sol));
+        }
+        Ok(Self { block_engine, tip_lamports: (tip_sol * LAMPORTS_PER_SOL) as u64, max_slots, tip_accounts: TipAccounts::default() })
+    }
+
+    /// Send `transactions` as one bundle and return its id
+    pub async fn send_bundle(&self, transactions: &[&Transaction]) -> Result<String> {
+        let encoded = transactions.iter()
+            .map(|tx| Ok(BASE64.encode(bincode::serialize(tx)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sendBundle",
+            "params": [encoded, { "encoding": "base64" }],
+        });
+        let response: serde_json::Value = reqwest::Client::builder().timeout(Duration::from_secs(10)).build()?
+            .post(format!("{}/api/v1/bundles", self.block_engine.url))
+            .json(&request)
+            .send().await?
+            .json().await?;
+        if let Some(error) = response.get("error") {
+            return Err(anyhow::anyhow!("Block engine rejected the bundle: {}", error));
+        }
+        response["result"].as_str().map(str::to_string)
+            .ok_or_else(|| anyhow::anyhow!("Unexpected block engine response: {}", response))
+    }
+}
+
+/// Wait until `signature` is processed or the cluster passes `deadline_slot`; `None` means it did not land in time
+pub async fn wait_for_landing(rpc: &RpcClient, signature: &Signature, deadline_slot: u64) -> ClientResult<Option<Result<(), TransactionError>>> {
+    loop {
+        if let Some(status) = rpc.get_signature_status_with_commitment(signature, CommitmentConfig::processed()).await? {
+            return Ok(Some(status));
+        }
+        if rpc.get_slot_with_commitment(CommitmentConfig::processed()).await? > deadline_slot {
+            return Ok(None);
+        }
+        tokio::time::sleep(Duration::from_millis(400)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_engines_and_tip_rotation() {
+        assert_eq!("Tokyo".parse::<BlockEngine>().unwrap().url, "https://tokyo.mainnet.block-engine.jito.wtf");
+        assert_eq!("https://be.example.com/".parse::<BlockEngine>().unwrap().url, "https://be.example.com");
+        assert!("mars".parse::<BlockEngine>().is_err());
+
+        let tips = TipAccounts::default();
+        let shared = tips.clone();
+        let first = tips.next();
+        let rotated: Vec<Pubkey> = (1..TIP_ACCOUNTS.len()).map(|_| shared.next()).collect();
+        assert!(!rotated.contains(&first));
+        assert_eq!(tips.next(), first);
+    }
+}
@@ -0,0 +1,144 @@
+use anyhow::Result;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signer::Signer,
+    transaction::VersionedTransaction,
+};
+use std::env;
+use std::time::Duration;
+
+use crate::pump_swap::WSOL_MINT;
+use crate::trade::{BuyOrder, SellOrder, Venue};
+
+/// Jupiter swap API unless `JUPITER_API_URL` is set
+pub const DEFAULT_JUPITER_API_URL: &str = "https://lite-api.jup.ag/swap/v1";
+
+/// A Jupiter route quote; `raw` is sent back unchanged to build the swap transaction
+#[derive(Debug, Clone)]
+pub struct JupiterQuote {
+    pub in_amount: u64,
+    pub out_amount: u64,
+    /// Minimum output after slippage
+    pub other_amount_threshold: u64,
+    pub price_impact_pct: f64,
+    pub raw: serde_json::Value,
+}
+
+impl JupiterQuote {
+    pub fn from_response(raw: serde_json::Value) -> Result<Self> {
+        let amount = |field: &str| -> Result<u64> {
+            raw[field].as_str()
+                .and_then(|value| value.parse().ok())
+                .ok_or_else(|| anyhow::anyhow!("Missing {} in Jupiter quote", field))
+        };
+        Ok(Self {
+            in_amount: amount("inAmount")?,
+            out_amount: amount("outAmount")?,
+            other_amount_threshold: amount("otherAmountThreshold")?,
+            price_impact_pct: raw["priceImpactPct"].as_str().and_then(|p| p.parse().ok()).unwrap_or(0.0),
+            raw,
+        })
+    }
+}
+
+/// Jupiter aggregator, an alternative venue for migrated or illiquid tokens
+pub struct Jupiter {
+    api_url: String,
+    client: reqwest::Client,
+}
+
+impl Jupiter {
+    /// Enabled as a fallback venue by `JUPITER_FALLBACK=true`; `JUPITER_API_URL` overrides the endpoint
+    pub fn fallback_from_env() -> Result<Option<Self>> {
+        let enabled = env::var("JUPITER_FALLBACK").unwrap_or_else(|_| "false".to_string()).to_lowercase() == "true";
+        if enabled { Self::from_env().map(Some) } else { Ok(None) }
+    }
+
+    pub fn from_env() -> Result<Self> {
+        Ok(Self {
+            api_url: env::var("JUPITER_API_URL").unwrap_or_else(|_| DEFAULT_JUPITER_API_URL.to_string()),
+            client: reqwest::Client::builder().timeout(Duration::from_secs(10)).build()?,
+        })
+    }
+
+    /// Best route swapping `amount` raw units of `input_mint` into `output_mint`
+    pub async fn quote(&self, input_mint: &str, output_mint: &str, amount: u64, slippage_bps: u64) -> Result<JupiterQuote> {
+        let response: serde_json::Value = self.client
+            .get(format!("{}/quote", self.api_url.trim_end_matches('/')))
+            .query(&[
+                ("inputMint", input_mint.to_string()),
+                ("outputMint", output_mint.to_string()),
+                ("amount", amount.to_string()),
+                ("slippageBps", slippage_bps.to_string()),
+            ])
+            .send().await?
+            .error_for_status()?
+            .json().await?;
+        if let Some(error) = response["error"].as_str() {
+            return Err(anyhow::anyhow!("Jupiter has no route: {}", error));
+        }
+        JupiterQuote::from_response(response)
+    }
+
+    /// Quote buying `mint` with `sol_lamports`
+    pub async fn buy_order(&self, mint: &Pubkey, sol_lamports: u64, slippage_bps: u64) -> Result<(BuyOrder, JupiterQuote)> {
+        let quote = self.quote(WSOL_MINT, &mint.to_string(), sol_lamports, slippage_bps).await?;
+        // Jupiter fixes the input; the slippage bound is on the tokens received instead
+        let order = BuyOrder { venue: Venue::Jupiter, sol_lamports, token_amount: quote.out_amount, max_sol_cost: sol_lamports };
+        Ok((order, quote))
+    }
+
+    /// Quote selling `token_amount` raw tokens of `mint` for SOL
+    pub async fn sell_order(&self, mint: &Pubkey, token_amount: u64, slippage_bps: u64) -> Result<(SellOrder, JupiterQuote)> {
+        let quote = self.quote(&mint.to_string(), WSOL_MINT, token_amount, slippage_bps).await?;
+        let order = SellOrder {
+            venue: Venue::Jupiter,
+            token_amount,
+            expected_sol: quote.out_amount,
+            min_sol_output: quote.other_amount_threshold,
+        };
+        Ok((order, quote))
+    }
+
+    /// Swap transaction for `quote`, signed by `user` (SOL is wrapped and unwrapped automatically)
+    pub async fn swap_transaction(&self, quote: &JupiterQuote, user: &dyn Signer) -> Result<VersionedTransaction> {
+        let response: serde_json::Value = self.client
+            .post(format!("{}/swap", self.api_url.trim_end_matches('/')))
+            .json(&serde_json::json!({
+                "quoteResponse": quote.raw,
+                "userPublicKey": user.pubkey().to_string(),
+                "wrapAndUnwrapSol": true,
+                "dynamicComputeUnitLimit": true,
+            }))
+            .send().await?
+            .error_for_status()?
+            .json().await?;
+        let encoded = response["swapTransaction"].as_str()
+            .ok_or_else(|| anyhow::anyhow!("No swapTransaction in Jupiter response"))?;
+        let unsigned: VersionedTransaction = bincode::deserialize(&BASE64.decode(encoded)?)?;
+        if unsigned.message.static_account_keys().first() != Some(&user.pubkey()) {
+            return Err(anyhow::anyhow!("Jupiter swap transaction is not paid by {}", user.pubkey()));
+        }
+        Ok(VersionedTransaction::try_new(unsigned.message, &[user])?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_from_response() {
+        let quote = JupiterQuote::from_response(serde_json::json!({
+            "inAmount": "1000000000",
+            "outAmount": "3500000000000",
+            "otherAmountThreshold": "3465000000000",
+            "priceImpactPct": "0.012",
+            "routePlan": [],
+        })).unwrap();
+        assert_eq!((quote.in_amount, quote.out_amount, quote.other_amount_threshold), (1_000_000_000, 3_500_000_000_000, 3_465_000_000_000));
+        assert!((quote.price_impact_pct - 0.012).abs() < 1e-12);
+        assert!(JupiterQuote::from_response(serde_json::json!({ "inAmount": 5 })).is_err());
+    }
+}
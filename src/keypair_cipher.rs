@@ -0,0 +1,104 @@
+use anyhow::Result;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use solana_sdk::signature::Keypair;
+use zeroize::Zeroize;
+
+/// ChaCha20-Poly1305 key length; keys are random bytes, never derived from a passphrase
+pub const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+/// A keypair encrypted by [`KeypairCipher::seal`], as base64
+#[derive(Debug, Clone, PartialEq)]
+pub struct SealedKeypair {
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+/// Encrypts keypairs with ChaCha20-Poly1305 for the vanity store, launch directories and the grinding fleet
+#[derive(Clone)]
+pub struct KeypairCipher {
+    cipher: ChaCha20Poly1305,
+    /// Setting the key came from, named in errors
+    source: String,
+}
+
+impl KeypairCipher {
+    /// Cipher keyed by `key`: 32 random bytes as 64 hex characters or base64 (`openssl rand -hex 32`).
+    /// `source` names the setting the key came from.
+    pub fn from_key(source: &str, key: &str) -> Result<Self> {
+        let key = key.trim();
+        let mut bytes = decode_hex(key)
+            .or_else(|| BASE64.decode(key).ok())
+            .filter(|bytes| bytes.len() == KEY_LEN)
+            .ok_or_else(|| anyhow::anyhow!(
+                "{} must be a random {}-byte key as hex or base64 (generate one with `openssl rand -hex 32`)", source, KEY_LEN
+            ))?;
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&bytes));
+        bytes.zeroize();
+        Ok(Self { cipher, source: source.to_string() })
+    }
+
+    /// Encrypt `keypair`, authenticating `associated_data` along with it
+    pub fn seal(&self, keypair: &Keypair, associated_data: &[u8]) -> Result<SealedKeypair> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let mut secret = keypair.to_bytes();
+        let ciphertext = self.cipher.encrypt(&nonce, Payload { msg: &secret, aad: associated_data });
+        secret.zeroize();
+        let ciphertext = ciphertext.map_err(|_| anyhow::anyhow!("Failed to encrypt keypair"))?;
+        Ok(SealedKeypair { nonce: BASE64.encode(nonce), ciphertext: BASE64.encode(ciphertext) })
+    }
+
+    /// Decrypt a keypair sealed with the same key and `associated_data`
+    pub fn open(&self, sealed: &SealedKeypair, associated_data: &[u8]) -> Result<Keypair> {
+        let nonce = BASE64.decode(&sealed.nonce)?;
+        if nonce.len() != NONCE_LEN {
+            return Err(anyhow::anyhow!("Invalid nonce length"));
+        }
+        let mut plaintext = self.cipher.decrypt(Nonce::from_slice(&nonce), Payload {
+            msg: &BASE64.decode(&sealed.ciphertext)?,
+            aad: associated_data,
+        }).map_err(|_| anyhow::anyhow!("failed authentication (wrong {}?)", self.source))?;
+        let keypair = Keypair::try_from(&plaintext[..]);
+        plaintext.zeroize();
+        keypair.map_err(|e| anyhow::anyhow!("Decrypted keypair is invalid: {}", e))
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len()).step_by(2)
+        .map(|i| hex.get(i..i + 2).and_then(|byte| u8::from_str_radix(byte, 16).ok()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signer::Signer;
+
+    #[test]
+    fn test_keys_and_round_trip() {
+        let hex_key = "42".repeat(KEY_LEN);
+        let cipher = KeypairCipher::from_key("TEST_KEY", &hex_key).unwrap();
+        let keypair = Keypair::new();
+        let sealed = cipher.seal(&keypair, b"context").unwrap();
+        assert_eq!(cipher.open(&sealed, b"context").unwrap().pubkey(), keypair.pubkey());
+        assert!(cipher.open(&sealed, b"other context").is_err());
+
+        // The same key in base64 opens it too; any other key does not
+        let base64_key = BASE64.encode([0x42; KEY_LEN]);
+        assert_eq!(KeypairCipher::from_key("TEST_KEY", &base64_key).unwrap().open(&sealed, b"context").unwrap().pubkey(), keypair.pubkey());
+        let wrong = KeypairCipher::from_key("TEST_KEY", &"43".repeat(KEY_LEN)).unwrap();
+        assert!(wrong.open(&sealed, b"context").unwrap_err().to_string().contains("wrong TEST_KEY"));
+
+        // Passphrases and short keys are refused
+        assert!(KeypairCipher::from_key("TEST_KEY", "correct horse battery staple").is_err());
+        assert!(KeypairCipher::from_key("TEST_KEY", &"42".repeat(16)).is_err());
+        let truncated = SealedKeypair { nonce: BASE64.encode([0u8; 8]), ..sealed };
+        assert!(cipher.open(&truncated, b"context").is_err());
+    }
+}
@@ -0,0 +1,177 @@
+use anyhow::Result;
+use solana_sdk::rent::Rent;
+use std::env;
+use std::fmt::Write as _;
+
+use crate::create_token::LAMPORTS_PER_SOL;
+use crate::price_feed::format_sol;
+use crate::pump_accounts::GlobalAccount;
+
+/// Account sizes created by a launch, used for rent-exemption estimates
+const MINT_ACCOUNT_LEN: usize = 82;
+const BONDING_CURVE_ACCOUNT_LEN: usize = 150;
+const TOKEN_ACCOUNT_LEN: usize = 165;
+const METADATA_ACCOUNT_LEN: usize = 679;
+/// Base fee charged per transaction signature
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+/// Safety margin added on top of the estimate unless `BALANCE_SAFETY_MARGIN_SOL` is set
+pub const DEFAULT_SAFETY_MARGIN_LAMPORTS: u64 = 5_000_000; // 0.005 SOL
+
+/// Lamports a single launch needs from the payer, by component
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LaunchCost {
+    /// Rent for the mint, bonding curve, its token account and the metadata account
+    pub rent_lamports: u64,
+    /// Signature fees plus priority fees
+    pub fee_lamports: u64,
+    /// SOL spent on the creator's initial buy, net of trade fees
+    pub buy_lamports: u64,
+    /// Protocol and creator fees the initial buy pays, from the global config
+    pub trade_fee_lamports: u64,
+    /// Tip paid to a block engine / relay
+    pub tip_lamports: u64,
+    pub margin_lamports: u64,
+}
+
+impl LaunchCost {
+    /// Estimate the cost of a launch signed by `signatures` keys with the given buy, priority fee and tip.
+    /// With `global`, trade fees are split out of the buy (see [`LaunchCost::with_global`]).
+    pub fn estimate(signatures: u64, priority_fee_lamports: u64, buy_lamports: u64, tip_lamports: u64, global: Option<&GlobalAccount>) -> Result<Self> {
+        let rent = Rent::default();
+        let rent_lamports = [MINT_ACCOUNT_LEN, BONDING_CURVE_ACCOUNT_LEN, TOKEN_ACCOUNT_LEN, METADATA_ACCOUNT_LEN]
+            .iter()
+            .map(|len| rent.minimum_balance(*len))
+            .sum();
+        let (buy_lamports, trade_fee_lamports) = match global {
+            Some(global) => price_buy(buy_lamports, global),
+            None => (buy_lamports, 0),
+        };
+
+        Ok(Self {
+            rent_lamports,
+            fee_lamports: signatures * LAMPORTS_PER_SIGNATURE + priority_fee_lamports,
+            buy_lamports,
+            trade_fee_lamports,
+            tip_lamports,
+            margin_lamports: safety_margin_from_env()?,
+        })
+    }
+
+    /// Price the initial buy with the pump.fun global config: fees come out of the buy, and the buy
+    /// never costs more than taking every token of the fresh curve
+    pub fn with_global(mut self, global: &GlobalAccount) -> Self {
+        (self.buy_lamports, self.trade_fee_lamports) = price_buy(self.buy_lamports + self.trade_fee_lamports, global);
+        self
+    }
+
+    pub fn total(&self) -> u64 {
+        self.rent_lamports + self.fee_lamports + self.buy_lamports + self.trade_fee_lamports + self.tip_lamports + self.margin_lamports
+    }
+
+    /// Fail with a per-component breakdown when `balance` does not cover the launch
+    pub fn ensure_covered(&self, balance: u64, sol_usd: Option<f64>) -> Result<()> {
+        if balance >= self.total() {
+            return Ok(());
+        }
+        Err(anyhow::anyhow!(
+            "Insufficient wallet balance. Current: {}, Required: {} (short by {}). Please add more SOL to your wallet.\n{}",
+            format_sol(balance, sol_usd),
+            format_sol(self.total(), sol_usd),
+            format_sol(self.total() - balance, sol_usd),
+            self.breakdown(sol_usd)
+        ))
+    }
+
+    /// One line per non-zero component
+    pub fn breakdown(&self, sol_usd: Option<f64>) -> String {
+        let mut out = String::new();
+        for (label, lamports) in [
+            ("rent", self.rent_lamports),
+            ("fees", self.fee_lamports),
+            ("dev buy", self.buy_lamports),
+            ("trade fees", self.trade_fee_lamports),
+            ("tip", self.tip_lamports),
+            ("safety margin", self.margin_lamports),
+        ] {
+            if lamports > 0 {
+                let _ = writeln!(out, "  {:<14} {}", label, format_sol(lamports, sol_usd));
+            }
+        }
+        out
+    }
+}
+
+/// (net buy, trade fees) of spending `lamports` on the initial buy of a fresh curve
+fn price_buy(lamports: u64, global: &GlobalAccount) -> (u64, u64) {
+    let fee_basis_points = global.total_fee_basis_points().min(10_000);
+    let mut buy = lamports;
+    if let Some(buy_out) = global.initial_curve(None).buy_out_cost(fee_basis_points) {
+        buy = buy.min(buy_out);
+    }
+    let fee = (buy as u128 * fee_basis_points as u128 / 10_000) as u64;
+    (buy - fee, fee)
+}
+
+/// `BALANCE_SAFETY_MARGIN_SOL`, or the default margin
+fn safety_margin_from_env() -> Result<u64> {
+    match env::var("BALANCE_SAFETY_MARGIN_SOL") {
+        Ok(value) => {
+            let sol: f64 = value.trim().parse()
+                .map_err(|_| anyhow::anyhow!("Invalid BALANCE_SAFETY_MARGIN_SOL '{}'", value))?;
+            if sol < 0.0 {
+                return Err(anyhow::anyhow!("BALANCE_SAFETY_MARGIN_SOL must not be negative"));
+            }
+            Ok((sol * LAMPORTS_PER_SOL) as u64)
+        }
+        Err(_) => Ok(DEFAULT_SAFETY_MARGIN_LAMPORTS),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shortfall_error_lists_components() {
+        let cost = LaunchCost {
+            rent_lamports: 5_000_000,
+            fee_lamports: 10_000,
+            buy_lamports: 100_000_000,
+            margin_lamports: 5_000_000,
+            ..Default::default()
+        };
+        assert_eq!(cost.total(), 110_010_000);
+        assert!(cost.ensure_covered(110_010_000, None).is_ok());
+
+        let message = cost.ensure_covered(10_000_000, None).unwrap_err().to_string();
+        assert!(message.contains("short by 0.10001 SOL"));
+        assert!(message.contains("dev buy"));
+        assert!(!message.contains("tip"));
+
+        let global = GlobalAccount {
+            initialized: true,
+            authority: Default::default(),
+            fee_recipient: Default::default(),
+            initial_virtual_token_reserves: 1_073_000_000_000_000,
+            initial_virtual_sol_reserves: 30_000_000_000,
+            initial_real_token_reserves: 793_100_000_000_000,
+            token_total_supply: 1_000_000_000_000_000,
+            fee_basis_points: 95,
+            withdraw_authority: Default::default(),
+            enable_migrate: true,
+            pool_migration_fee: None,
+            creator_fee_basis_points: Some(5),
+            fee_recipients: Vec::new(),
+        };
+        let priced = LaunchCost { buy_lamports: 1_050_000_000, ..cost.clone() }.with_global(&global);
+        assert_eq!((priced.buy_lamports, priced.trade_fee_lamports), (1_039_500_000, 10_500_000));
+        assert_eq!(priced.total(), cost.total() - 100_000_000 + 1_050_000_000);
+        // More than the whole curve costs only what buying it out costs
+        let capped = LaunchCost { buy_lamports: 200_000_000_000, ..cost }.with_global(&global);
+        assert_eq!(capped.buy_lamports + capped.trade_fee_lamports, 85_863_999_048);
+        assert_eq!(global.initial_curve(None).quote_buy(85_863_999_048, 100), 793_100_000_000_000);
+        let estimated = LaunchCost::estimate(2, 0, 1_050_000_000, 0, Some(&global)).unwrap();
+        assert_eq!((estimated.buy_lamports, estimated.trade_fee_lamports), (1_039_500_000, 10_500_000));
+        assert_eq!(LaunchCost::estimate(2, 0, 1_050_000_000, 0, None).unwrap().trade_fee_lamports, 0);
+    }
+}
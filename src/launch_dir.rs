@@ -0,0 +1,165 @@
+use anyhow::Result;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use solana_sdk::signature::Keypair;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::keypair_cipher::KeypairCipher;
+use crate::vanity_store::VanityStore;
+
+/// Root of the per-launch directories unless `--launch-dir-root` or `LAUNCH_DIR_ROOT` is set
+pub const DEFAULT_LAUNCH_DIR_ROOT: &str = "launches";
+/// Encrypted store for the mint keypairs of a launch, one entry per attempt
+const MINT_KEYPAIR_FILE: &str = "mint-keypair.json";
+
+/// What a launch was asked to do and how far it got, kept in `manifest.json`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LaunchManifest {
+    pub name: String,
+    pub symbol: String,
+    pub description: String,
+    pub metadata_uri: String,
+    pub payer: String,
+    #[serde(default)]
+    pub operator: Option<String>,
+    pub started_at: u64,
+    /// `uploaded`, `sent`, `confirmed`, `failed` or `dry_run`
+    pub status: String,
+    pub attempts: u32,
+    pub mint: Option<String>,
+    pub signature: Option<String>,
+}
+
+/// Artifacts of one launch under `<root>/<unix time>-<symbol>/`: the manifest, report, dumped
+/// transaction, metadata and encrypted mint keypairs
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchDir {
+    path: PathBuf,
+}
+
+impl LaunchDir {
+    /// Create a fresh directory; launches started in the same second get a numbered suffix
+    pub fn create(root: &Path, symbol: &str, started: SystemTime) -> Result<Self> {
+        let secs = started.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        let symbol: String = symbol.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+        std::fs::create_dir_all(root)
+            .map_err(|e| anyhow::anyhow!("Cannot create launch directory root {}: {}", root.display(), e))?;
+        let mut path = root.join(format!("{}-{}", secs, symbol));
+        let mut suffix = 1;
+        loop {
+            match std::fs::create_dir(&path) {
+                Ok(()) => return Ok(Self { path }),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    suffix += 1;
+                    path = root.join(format!("{}-{}-{}", secs, symbol, suffix));
+                }
+                Err(e) => return Err(anyhow::anyhow!("Cannot create launch directory {}: {}", path.display(), e)),
+            }
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn manifest_path(&self) -> PathBuf {
+        self.path.join("manifest.json")
+    }
+
+    pub fn transaction_path(&self) -> PathBuf {
+        self.path.join("transaction.json")
+    }
+
+    pub fn metadata_path(&self) -> PathBuf {
+        self.path.join("metadata.json")
+    }
+
+    pub fn report_path(&self) -> PathBuf {
+        self.path.join("report.json")
+    }
+
+    pub fn write_manifest(&self, manifest: &LaunchManifest) -> Result<()> {
+        std::fs::write(self.manifest_path(), serde_json::to_string_pretty(manifest)?)?;
+        Ok(())
+    }
+
+    pub fn read_manifest(&self) -> Result<LaunchManifest> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(self.manifest_path())?)?)
+    }
+
+    /// Copy the token image next to the metadata
+    pub fn copy_image(&self, image_path: &str) -> Result<PathBuf> {
+        let extension = Path::new(image_path).extension().and_then(|e| e.to_str()).unwrap_or("bin");
+        let target = self.path.join(format!("image.{}", extension.to_lowercase()));
+        std::fs::copy(image_path, &target)?;
+        Ok(target)
+    }
+
+    /// Keep `mint` encrypted with `cipher` (see [`mint_keypair_cipher_from_env`]); skipped without one
+    pub fn save_mint_keypair(&self, mint: &Keypair, cipher: Option<&KeypairCipher>) -> Result<bool> {
+        let Some(cipher) = cipher else {
+            warn!("Not keeping the mint keypair in {}: set LAUNCH_KEYPAIR_SECRET to encrypt it", self.path.display());
+            return Ok(false);
+        };
+        VanityStore::new(self.path.join(MINT_KEYPAIR_FILE), cipher.clone()).add(mint)?;
+        Ok(true)
+    }
+}
+
+/// Cipher for mint keypairs from `LAUNCH_KEYPAIR_SECRET` (or `VANITY_STORE_SECRET`); `None` when neither is set
+pub fn mint_keypair_cipher_from_env() -> Result<Option<KeypairCipher>> {
+    ["LAUNCH_KEYPAIR_SECRET", "VANITY_STORE_SECRET"].into_iter()
+        .find_map(|name| std::env::var(name).ok().map(|secret| (name, secret)))
+        .map(|(source, secret)| KeypairCipher::from_key(source, &secret))
+        .transpose()
+}
+
+/// Launch directory root from `LAUNCH_DIR_ROOT`, or the default
+pub fn launch_dir_root_from_env() -> PathBuf {
+    PathBuf::from(std::env::var("LAUNCH_DIR_ROOT").unwrap_or_else(|_| DEFAULT_LAUNCH_DIR_ROOT.to_string()))
+}
+
+/// Record `update` in the manifest; a launch never fails because its bookkeeping did
+pub fn update_manifest(dir: Option<&LaunchDir>, update: impl FnOnce(&mut LaunchManifest)) {
+    let Some(dir) = dir else {
+        return;
+    };
+    let result = dir.read_manifest().and_then(|mut manifest| {
+        update(&mut manifest);
+        dir.write_manifest(&manifest)
+    });
+    if let Err(e) = result {
+        warn!("Failed to update {}: {}", dir.manifest_path().display(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signer::Signer;
+
+    #[test]
+    fn test_launch_dirs_are_unique_and_self_contained() {
+        let root = std::env::temp_dir().join(format!("launch_dir_test_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let started = UNIX_EPOCH + std::time::Duration::from_secs(1_760_000_000);
+        let first = LaunchDir::create(&root, "M/CAT", started).unwrap();
+        let second = LaunchDir::create(&root, "M/CAT", started).unwrap();
+        assert_eq!(first.path(), root.join("1760000000-M_CAT"));
+        assert_eq!(second.path(), root.join("1760000000-M_CAT-2"));
+
+        first.write_manifest(&LaunchManifest { symbol: "MCAT".to_string(), status: "uploaded".to_string(), ..Default::default() }).unwrap();
+        update_manifest(Some(&first), |manifest| manifest.status = "confirmed".to_string());
+        assert_eq!(first.read_manifest().unwrap().status, "confirmed");
+
+        let cipher = KeypairCipher::from_key("LAUNCH_KEYPAIR_SECRET", &"d4".repeat(32)).unwrap();
+        let mint = Keypair::new();
+        assert!(!second.save_mint_keypair(&mint, None).unwrap());
+        assert!(!second.path().join(MINT_KEYPAIR_FILE).exists());
+        assert!(first.save_mint_keypair(&mint, Some(&cipher)).unwrap());
+        let stored = VanityStore::new(first.path().join(MINT_KEYPAIR_FILE), cipher).load().unwrap();
+        assert_eq!(stored[0].address, mint.pubkey());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
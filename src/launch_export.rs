@@ -0,0 +1,208 @@
+use anyhow::Result;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fmt::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SHEETS_SCOPE: &str = "https://www.googleapis.com/auth/spreadsheets";
+const SHEETS_API_URL: &str = "https://sheets.googleapis.com/v4/spreadsheets";
+/// Sheet range rows are appended to unless `GOOGLE_SHEETS_RANGE` is set
+pub const DEFAULT_SHEETS_RANGE: &str = "Launches!A1";
+pub const CSV_HEADER: &str = "timestamp,symbol,name,mint,signature,wallet,cluster,operator";
+
+/// One completed launch, as a spreadsheet row
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchRow {
+    /// Unix seconds
+    pub timestamp: u64,
+    pub symbol: String,
+    pub name: String,
+    pub mint: String,
+    pub signature: String,
+    pub wallet: String,
+    pub cluster: String,
+    /// Empty when no operator was named
+    pub operator: String,
+}
+
+impl LaunchRow {
+    pub fn new(symbol: &str, name: &str, mint: &str, signature: &str, wallet: &str, cluster: &str, operator: Option<&str>) -> Self {
+        Self {
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+            symbol: symbol.to_string(),
+            name: name.to_string(),
+            mint: mint.to_string(),
+            signature: signature.to_string(),
+            wallet: wallet.to_string(),
+            cluster: cluster.to_string(),
+            operator: operator.unwrap_or_default().to_string(),
+        }
+    }
+
+    pub fn values(&self) -> Vec<String> {
+        vec![
+            self.timestamp.to_string(), self.symbol.clone(), self.name.clone(), self.mint.clone(),
+            self.signature.clone(), self.wallet.clone(), self.cluster.clone(), self.operator.clone(),
+        ]
+    }
+
+    /// CSV line (with trailing newline), quoting fields that need it
+    pub fn to_csv(&self) -> String {
+        let mut line = self.values().iter().map(|value| csv_field(value)).collect::<Vec<_>>().join(",");
+        let _ = writeln!(line);
+        line
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Where completed launches are exported
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportSink {
+    /// POST the header and row as `text/csv`
+    CsvWebhook(String),
+    /// Append the row to a spreadsheet as a Google service account
+    GoogleSheets { spreadsheet_id: String, range: String, credentials_path: String },
+}
+
+/// Pushes every completed launch to the configured spreadsheet sinks
+#[derive(Debug, Clone, Default)]
+pub struct LaunchExporter {
+    sinks: Vec<ExportSink>,
+}
+
+impl LaunchExporter {
+    /// Sinks from `EXPORT_CSV_WEBHOOK_URL` and `GOOGLE_SHEETS_SPREADSHEET_ID` +
+    /// `GOOGLE_SERVICE_ACCOUNT_FILE` (optionally `GOOGLE_SHEETS_RANGE`)
+    pub fn from_env() -> Self {
+        let mut sinks = Vec::new();
+        if let Ok(url) = env::var("EXPORT_CSV_WEBHOOK_URL") {
+            sinks.push(ExportSink::CsvWebhook(url));
+        }
+        if let (Ok(spreadsheet_id), Ok(credentials_path)) = (env::var("GOOGLE_SHEETS_SPREADSHEET_ID"), env::var("GOOGLE_SERVICE_ACCOUNT_FILE")) {
+            let range = env::var("GOOGLE_SHEETS_RANGE").unwrap_or_else(|_| DEFAULT_SHEETS_RANGE.to_string());
+            sinks.push(ExportSink::GoogleSheets { spreadsheet_id, range, credentials_path });
+        }
+        Self { sinks }
+    }
+
+    /// Export `row` to all sinks. Failures are logged, never returned: the launch already happened.
+    pub async fn export(&self, row: &LaunchRow) {
+        if self.sinks.is_empty() {
+            return;
+        }
+        let client = match reqwest::Client::builder().timeout(Duration::from_secs(15)).build() {
+            Ok(client) => client,
+            Err(e) => {
+                warn!("Cannot build HTTP client for launch export: {}", e);
+                return;
+            }
+        };
+        for sink in &self.sinks {
+            let result = match sink {
+                ExportSink::CsvWebhook(url) => post_csv(&client, url, row).await,
+                ExportSink::GoogleSheets { spreadsheet_id, range, credentials_path } => {
+                    append_to_sheet(&client, spreadsheet_id, range, credentials_path, row).await
+                }
+            };
+            match result {
+                Ok(()) => info!("Exported launch of {} to {}", row.symbol, sink_name(sink)),
+                Err(e) => warn!("Failed to export launch of {} to {}: {}", row.symbol, sink_name(sink), e),
+            }
+        }
+    }
+}
+
+fn sink_name(sink: &ExportSink) -> &'static str {
+    match sink {
+        ExportSink::CsvWebhook(_) => "CSV webhook",
+        ExportSink::GoogleSheets { .. } => "Google Sheets",
+    }
+}
+
+async fn post_csv(client: &reqwest::Client, url: &str, row: &LaunchRow) -> Result<()> {
+    client.post(url)
+        .header(reqwest::header::CONTENT_TYPE, "text/csv")
+        .body(format!("{}\n{}", CSV_HEADER, row.to_csv()))
+        .send().await?
+        .error_for_status()?;
+    Ok(())
+}
+
+/// Fields of a Google service account key file that the token exchange needs
+#[derive(Deserialize)]
+struct ServiceAccountKey {
+    client_email: String,
+    private_key: String,
+    token_uri: String,
+}
+
+#[derive(Serialize)]
+struct JwtClaims<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: &'a str,
+    iat: u64,
+    exp: u64,
+}
+
+/// OAuth access token with `scope` for the service account in `credentials_path` (JWT bearer grant)
+pub(crate) async fn google_access_token(client: &reqwest::Client, credentials_path: &str, scope: &str) -> Result<String> {
+    let key: ServiceAccountKey = serde_json::from_str(&std::fs::read_to_string(credentials_path)?)
+        .map_err(|e| anyhow::anyhow!("{} is not a service account key file: {}", credentials_path, e))?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let claims = JwtClaims { iss: &key.client_email, scope, aud: &key.token_uri, iat: now, exp: now + 3600 };
+    let assertion = jsonwebtoken::encode(
+        &jsonwebtoken::Header::new(jsonwebtoken::Algorithm::RS256),
+        &claims,
+        &jsonwebtoken::EncodingKey::from_rsa_pem(key.private_key.as_bytes())?,
+    )?;
+
+    let response: serde_json::Value = client.post(&key.token_uri)
+        .form(&[("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"), ("assertion", assertion.as_str())])
+        .send().await?
+        .error_for_status()?
+        .json().await?;
+    response["access_token"].as_str()
+        .map(str::to_string)
+        .ok_or_else(|| anyhow::anyhow!("Token response has no access_token"))
+}
+
+async fn append_to_sheet(client: &reqwest::Client, spreadsheet_id: &str, range: &str, credentials_path: &str, row: &LaunchRow) -> Result<()> {
+    let token = google_access_token(client, credentials_path, SHEETS_SCOPE).await?;
+    let mut url = url::Url::parse(SHEETS_API_URL)?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("Invalid Sheets API URL"))?
+        .extend([spreadsheet_id, "values", format!("{}:append", range).as_str()]);
+    url.query_pairs_mut().append_pair("valueInputOption", "RAW");
+
+    client.post(url)
+        .bearer_auth(token)
+        .json(&serde_json::json!({ "values": [row.values()] }))
+        .send().await?
+        .error_for_status()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launch_row_csv() {
+        let mut row = LaunchRow::new("PVE", "PVE Token", "Mint111", "Sig111", "Wallet111", "mainnet", Some("alice"));
+        row.timestamp = 1_700_000_000;
+        assert_eq!(row.to_csv(), "1700000000,PVE,PVE Token,Mint111,Sig111,Wallet111,mainnet,alice\n");
+        assert_eq!(row.values().len(), CSV_HEADER.split(',').count());
+
+        row.name = "Say \"gm\", frens".to_string();
+        assert_eq!(row.to_csv(), "1700000000,PVE,\"Say \"\"gm\"\", frens\",Mint111,Sig111,Wallet111,mainnet,alice\n");
+    }
+}
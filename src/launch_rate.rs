@@ -0,0 +1,82 @@
+use anyhow::Result;
+use solana_sdk::pubkey::Pubkey;
+use std::env;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::tx_journal::TxJournal;
+
+/// Launches allowed per window unless `LAUNCH_COOLDOWN_MAX_LAUNCHES` is set
+pub const DEFAULT_MAX_LAUNCHES: usize = 1;
+
+/// At most `max_launches` confirmed launches per wallet within `window`, counted from the transaction journal
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchCooldown {
+    pub max_launches: usize,
+    pub window: Duration,
+}
+
+impl LaunchCooldown {
+    /// From `LAUNCH_COOLDOWN_MINUTES` and `LAUNCH_COOLDOWN_MAX_LAUNCHES`; `None` when no cooldown is configured
+    pub fn from_env() -> Result<Option<Self>> {
+        let Ok(minutes) = env::var("LAUNCH_COOLDOWN_MINUTES") else {
+            return Ok(None);
+        };
+        let minutes: u64 = minutes.trim().parse()
+            .map_err(|_| anyhow::anyhow!("LAUNCH_COOLDOWN_MINUTES must be a whole number of minutes, got '{}'", minutes))?;
+        let max_launches = match env::var("LAUNCH_COOLDOWN_MAX_LAUNCHES") {
+            Ok(value) => match value.trim().parse::<usize>() {
+                Ok(max) if max > 0 => max,
+                _ => return Err(anyhow::anyhow!("LAUNCH_COOLDOWN_MAX_LAUNCHES must be a positive whole number, got '{}'", value)),
+            },
+            Err(_) => DEFAULT_MAX_LAUNCHES,
+        };
+        if minutes == 0 {
+            return Ok(None);
+        }
+        Ok(Some(Self { max_launches, window: Duration::from_secs(minutes * 60) }))
+    }
+
+    /// Refuse when `launch_times` (Unix seconds) already hold `max_launches` inside the window ending at `now`
+    pub fn check(&self, wallet: &Pubkey, launch_times: &[u64], now: u64) -> Result<()> {
+        let window_start = now.saturating_sub(self.window.as_secs());
+        let mut recent: Vec<u64> = launch_times.iter().copied().filter(|&t| t > window_start).collect();
+        if recent.len() < self.max_launches {
+            return Ok(());
+        }
+        // The window frees up when the oldest launch that still counts ages out
+        recent.sort_unstable();
+        let next_allowed = recent[recent.len() - self.max_launches] + self.window.as_secs();
+        Err(anyhow::anyhow!(
+            "{} already launched {} token(s) in the last {} minute(s) (limit {}); next launch allowed in {} minute(s). \
+             Pass --ignore-cooldown for an intentional burst",
+            wallet, recent.len(), self.window.as_secs() / 60, self.max_launches,
+            next_allowed.saturating_sub(now).div_ceil(60).max(1)
+        ))
+    }
+
+    /// Check `wallet` against the launches recorded in `journal`
+    pub fn enforce(&self, journal: &TxJournal, wallet: &Pubkey) -> Result<()> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        self.check(wallet, &journal.launch_times(wallet)?, now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cooldown() {
+        let wallet = Pubkey::new_unique();
+        let cooldown = LaunchCooldown { max_launches: 2, window: Duration::from_secs(30 * 60) };
+        let now = 10_000;
+        assert!(cooldown.check(&wallet, &[], now).is_ok());
+        assert!(cooldown.check(&wallet, &[now - 60], now).is_ok());
+        // Launches older than the window do not count
+        assert!(cooldown.check(&wallet, &[now - 31 * 60, now - 60], now).is_ok());
+
+        let error = cooldown.check(&wallet, &[now - 20 * 60, now - 60], now).unwrap_err().to_string();
+        assert!(error.contains("next launch allowed in 10 minute(s)"));
+        assert!(error.contains("--ignore-cooldown"));
+    }
+}
@@ -0,0 +1,431 @@
+use anyhow::Result;
+use futures::StreamExt;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::{pubkey::Pubkey, signature::Signature, signer::Signer};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::authority_check::AuthorityReport;
+use crate::chart::{chart_attachment, ChartPoint};
+use crate::notifications::{Notification, Notifier};
+use crate::create_token::LAMPORTS_PER_SOL;
+use crate::buyers::{fetch_transaction_json, parse_buys, transaction_account_keys};
+use crate::pump_accounts::BondingCurveAccount;
+
+/// Report directory unless `LAUNCH_REPORT_DIR` is set
+pub const DEFAULT_LAUNCH_REPORT_DIR: &str = "data/reports";
+/// Prefixed to signed manifests so a receipt signature can never double as a transaction signature
+const RECEIPT_DOMAIN: &str = "pump-fun-launcher launch receipt v1\n";
+
+/// Everything known about a launch, written to `<LAUNCH_REPORT_DIR>/<mint>.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchReport {
+    pub mint: String,
+    pub name: String,
+    pub symbol: String,
+    pub signature: String,
+    pub launched_at: u64,
+    /// Who triggered the launch; not covered by the receipt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operator: Option<String>,
+    /// Present when the launch was followed by a watch session
+    pub watch: Option<WatchSummary>,
+    /// Latest `verify-authorities` result; not covered by the receipt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authorities: Option<AuthorityReport>,
+    /// Creator wallet's signature over the launch manifest, when requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receipt: Option<LaunchReceipt>,
+}
+
+/// ed25519 signature by `signer` over the canonical manifest of a report
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LaunchReceipt {
+    pub signer: String,
+    pub signature: String,
+}
+
+/// Fields a receipt vouches for, serialized in this order as compact JSON
+#[derive(Serialize)]
+struct LaunchManifest<'a> {
+    mint: &'a str,
+    name: &'a str,
+    symbol: &'a str,
+    signature: &'a str,
+    launched_at: u64,
+    creator: &'a str,
+}
+
+/// Trading activity observed on the bonding curve during a watch session
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct WatchSummary {
+    pub duration_secs: u64,
+    pub trades: u64,
+    pub buys: u64,
+    pub sells: u64,
+    pub unique_buyers: usize,
+    /// SOL moved in and out of the curve, in lamports
+    pub volume_lamports: u64,
+    pub peak_market_cap_lamports: u64,
+    pub final_market_cap_lamports: u64,
+    pub final_progress_percent: f64,
+    pub complete: bool,
+}
+
+/// A single trade against the bonding curve
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trade {
+    pub is_buy: bool,
+    /// Change of the curve's SOL balance, in lamports
+    pub sol_lamports: u64,
+    pub buyers: Vec<String>,
+}
+
+impl LaunchReport {
+    pub fn new(mint: &Pubkey, name: &str, symbol: &str, signature: &Signature) -> Result<Self> {
+        Ok(Self {
+            mint: mint.to_string(),
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            signature: signature.to_string(),
+            launched_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            operator: None,
+            watch: None,
+            authorities: None,
+            receipt: None,
+        })
+    }
+
+    /// Bytes a receipt by `creator` signs: the domain prefix and the manifest as compact JSON
+    pub fn manifest_bytes(&self, creator: &str) -> Result<Vec<u8>> {
+        let manifest = LaunchManifest {
+            mint: &self.mint,
+            name: &self.name,
+            symbol: &self.symbol,
+            signature: &self.signature,
+            launched_at: self.launched_at,
+            creator,
+        };
+        let mut bytes = RECEIPT_DOMAIN.as_bytes().to_vec();
+        bytes.extend_from_slice(&serde_json::to_vec(&manifest)?);
+        Ok(bytes)
+    }
+
+    /// Sign the manifest with the wallet that performed the launch
+    pub fn sign(&mut self, creator: &dyn Signer) -> Result<()> {
+        let signer = creator.pubkey().to_string();
+        let signature = creator.try_sign_message(&self.manifest_bytes(&signer)?)?;
+        self.receipt = Some(LaunchReceipt { signer, signature: signature.to_string() });
+        Ok(())
+    }
+
+    /// Wallet that signed the report, once its receipt checks out against the current fields
+    pub fn verify_receipt(&self) -> Result<Pubkey> {
+        let receipt = self.receipt.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Report for {} has no signed receipt", self.mint))?;
+        let signer = Pubkey::from_str(&receipt.signer)?;
+        let signature = Signature::from_str(&receipt.signature)?;
+        if !signature.verify(signer.as_ref(), &self.manifest_bytes(&receipt.signer)?) {
+            return Err(anyhow::anyhow!("Receipt signature does not match the report; it was altered or signed by another wallet"));
+        }
+        Ok(signer)
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// `LAUNCH_REPORT_DIR`, or the default directory
+    pub fn dir() -> PathBuf {
+        PathBuf::from(std::env::var("LAUNCH_REPORT_DIR").unwrap_or_else(|_| DEFAULT_LAUNCH_REPORT_DIR.to_string()))
+    }
+
+    /// Report file for `mint` in `LAUNCH_REPORT_DIR`, or the default directory
+    pub fn path_for(mint: &str) -> PathBuf {
+        Self::dir().join(format!("{}.json", mint))
+    }
+
+    /// Every report in `LAUNCH_REPORT_DIR`, newest launch first; unreadable files are skipped
+    pub fn list() -> Result<Vec<Self>> {
+        let dir = Self::dir();
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(anyhow::anyhow!("Cannot read {}: {}", dir.display(), e)),
+        };
+        let mut reports = Vec::new();
+        for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+            if path.extension().is_some_and(|ext| ext == "json") {
+                match Self::read(&path) {
+                    Ok(report) => reports.push(report),
+                    Err(e) => warn!("Skipping {}: {}", path.display(), e),
+                }
+            }
+        }
+        reports.sort_by_key(|report| Reverse(report.launched_at));
+        Ok(reports)
+    }
+
+    pub fn write(&self) -> Result<PathBuf> {
+        let path = Self::path_for(&self.mint);
+        self.write_to(&path)?;
+        Ok(path)
+    }
+
+    /// Write a copy elsewhere, such as the launch's artifacts directory
+    pub fn write_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Trade made by a json-encoded transaction, judged by the curve's SOL balance change
+pub fn parse_trade(tx: &serde_json::Value, mint: &str, bonding_curve: &str) -> Option<Trade> {
+    let meta = &tx["meta"];
+    if !meta["err"].is_null() {
+        return None;
+    }
+    let index = transaction_account_keys(tx).iter().position(|k| k == bonding_curve)?;
+    let pre = meta["preBalances"][index].as_u64()?;
+    let post = meta["postBalances"][index].as_u64()?;
+    if pre == post {
+        return None;
+    }
+    let buyers = parse_buys(tx, mint).into_iter()
+        .map(|(buyer, _, _)| buyer)
+        .filter(|buyer| buyer != bonding_curve)
+        .collect();
+    Some(Trade { is_buy: post > pre, sol_lamports: pre.abs_diff(post), buyers })
+}
+
+/// Curve progress levels announced while a launch is watched
+const PROGRESS_MILESTONES: &[u32] = &[25, 50, 75, 100];
+
+/// Accumulates trades and curve snapshots into a `WatchSummary`
+#[derive(Debug, Default)]
+pub struct WatchTracker {
+    summary: WatchSummary,
+    buyers: HashSet<String>,
+    started: Option<Instant>,
+    points: Vec<ChartPoint>,
+    milestone: Option<u32>,
+}
+
+impl WatchTracker {
+    pub fn record_trade(&mut self, trade: &Trade) {
+        self.summary.trades += 1;
+        if trade.is_buy {
+            self.summary.buys += 1;
+        } else {
+            self.summary.sells += 1;
+        }
+        self.summary.volume_lamports += trade.sol_lamports;
+        self.buyers.extend(trade.buyers.iter().cloned());
+        self.summary.unique_buyers = self.buyers.len();
+    }
+
+    /// Record a curve snapshot; returns the progress milestone (percent) it newly reached, if any.
+    /// The first snapshot only sets the starting point.
+    pub fn record_curve(&mut self, curve: &BondingCurveAccount, initial_real_token_reserves: u64) -> Option<u32> {
+        let market_cap = curve.market_cap_lamports();
+        self.summary.peak_market_cap_lamports = self.summary.peak_market_cap_lamports.max(market_cap);
+        self.summary.final_market_cap_lamports = market_cap;
+        self.summary.final_progress_percent = curve.progress_percent(initial_real_token_reserves);
+        self.summary.complete = curve.complete;
+
+        let secs = self.started.get_or_insert_with(Instant::now).elapsed().as_secs_f64();
+        self.points.push(ChartPoint { secs, market_cap_lamports: market_cap });
+        let reached = PROGRESS_MILESTONES.iter().rev().find(|m| self.summary.final_progress_percent >= **m as f64).copied();
+        if reached <= self.milestone {
+            return None;
+        }
+        self.milestone = reached;
+        if self.points.len() > 1 { reached } else { None }
+    }
+
+    /// Market cap over the session so far, for chart snapshots
+    pub fn chart_points(&self) -> &[ChartPoint] {
+        &self.points
+    }
+
+    pub fn finish(mut self, duration: Duration) -> WatchSummary {
+        self.summary.duration_secs = duration.as_secs();
+        self.summary
+    }
+}
+
+async fn fetch_curve(rpc: &RpcClient, bonding_curve: &Pubkey) -> Result<BondingCurveAccount> {
+    BondingCurveAccount::from_account_data(&rpc.get_account_data(bonding_curve).await?)
+}
+
+/// Stream trades on `bonding_curve` for `duration`, logging each trade and the curve's progress
+pub async fn watch_launch(
+    rpc: &RpcClient,
+    ws_url: &str,
+    mint: &Pubkey,
+    bonding_curve: &Pubkey,
+    initial_real_token_reserves: u64,
+    duration: Duration,
+) -> Result<WatchSummary> {
+    let mut tracker = WatchTracker::default();
+    let notifier = Notifier::from_env();
+    match fetch_curve(rpc, bonding_curve).await {
+        Ok(curve) => {
+            tracker.record_curve(&curve, initial_real_token_reserves);
+        }
+        Err(e) => warn!("Failed to read bonding curve {}: {}", bonding_curve, e),
+    }
+
+    let client = PubsubClient::new(ws_url).await?;
+    let (mut stream, unsubscribe) = client.logs_subscribe(
+        RpcTransactionLogsFilter::Mentions(vec![bonding_curve.to_string()]),
+        RpcTransactionLogsConfig { commitment: Some(CommitmentConfig::confirmed()) },
+    ).await?;
+    info!("Watching trades of {} for {}s", mint, duration.as_secs());
+
+    let (mint_str, curve_str) = (mint.to_string(), bonding_curve.to_string());
+    let started = std::time::Instant::now();
+    let deadline = tokio::time::sleep(duration);
+    tokio::pin!(deadline);
+    loop {
+        tokio::select! {
+            _ = &mut deadline => break,
+            update = stream.next() => {
+                let Some(update) = update else {
+                    warn!("Log subscription for {} closed", bonding_curve);
+                    break;
+                };
+                if update.value.err.is_some() {
+                    continue;
+                }
+                let tx = match fetch_transaction_json(rpc, &update.value.signature).await {
+                    Ok(tx) if !tx.is_null() => tx,
+                    Ok(_) => continue,
+                    Err(e) => {
+                        warn!("Failed to fetch {}: {}", update.value.signature, e);
+                        continue;
+                    }
+                };
+                let Some(trade) = parse_trade(&tx, &mint_str, &curve_str) else { continue };
+                tracker.record_trade(&trade);
+                match fetch_curve(rpc, bonding_curve).await {
+                    Ok(curve) => {
+                        if let Some(milestone) = tracker.record_curve(&curve, initial_real_token_reserves) {
+                            let message = format!("{} reached {}% of its bonding curve (mcap {:.2} SOL)",
+                                                  mint, milestone, curve.market_cap_lamports() as f64 / LAMPORTS_PER_SOL);
+                            notifier.notify(Notification::new("curve_milestone", Some(mint_str.clone()), message)
+                                .with_data(serde_json::json!({ "progress_percent": milestone, "market_cap_lamports": curve.market_cap_lamports() }))
+                                .with_attachment(chart_attachment(mint, tracker.chart_points()))).await;
+                        }
+                        info!("{} {:.4} SOL | mcap {:.2} SOL | curve {:.1}%",
+                              if trade.is_buy { "Buy " } else { "Sell" },
+                              trade.sol_lamports as f64 / LAMPORTS_PER_SOL,
+                              curve.market_cap_lamports() as f64 / LAMPORTS_PER_SOL,
+                              curve.progress_percent(initial_real_token_reserves));
+                        if curve.complete {
+                            info!("Bonding curve of {} completed", mint);
+                            break;
+                        }
+                    }
+                    Err(e) => warn!("Failed to read bonding curve {}: {}", bonding_curve, e),
+                }
+            }
+        }
+    }
+
+    unsubscribe().await;
+    let chart = chart_attachment(mint, tracker.chart_points());
+    let summary = tracker.finish(started.elapsed());
+    let message = format!("Watch of {} ended after {}s: {} trades ({} buys, {} sells), peak mcap {:.2} SOL, curve {:.1}%",
+                          mint, summary.duration_secs, summary.trades, summary.buys, summary.sells,
+                          summary.peak_market_cap_lamports as f64 / LAMPORTS_PER_SOL, summary.final_progress_percent);
+    notifier.notify(Notification::new("watch_finished", Some(mint_str), message)
+        .with_data(serde_json::to_value(&summary)?)
+        .with_attachment(chart)).await;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trades_accumulate_into_summary() {
+        let tx = |pre: u64, post: u64| serde_json::json!({
+            "transaction": { "message": { "accountKeys": ["buyer1", "curve"] } },
+            "meta": {
+                "err": null,
+                "preBalances": [5_000_000_000u64, pre],
+                "postBalances": [4_000_000_000u64, post],
+                "preTokenBalances": [],
+                "postTokenBalances": [
+                    { "mint": "MINT", "owner": "buyer1", "uiTokenAmount": { "amount": "600" } }
+                ]
+            }
+        });
+        let buy = parse_trade(&tx(100, 1_100), "MINT", "curve").unwrap();
+        assert_eq!(buy, Trade { is_buy: true, sol_lamports: 1_000, buyers: vec!["buyer1".to_string()] });
+        let sell = parse_trade(&tx(1_100, 600), "MINT", "curve").unwrap();
+        assert!(!sell.is_buy);
+        assert!(parse_trade(&tx(100, 100), "MINT", "curve").is_none());
+
+        let curve = |virtual_sol_reserves, real_token_reserves| BondingCurveAccount {
+            virtual_token_reserves: 1_000,
+            virtual_sol_reserves,
+            real_token_reserves,
+            real_sol_reserves: 0,
+            token_total_supply: 2_000,
+            complete: false,
+            creator: None,
+        };
+        let mut tracker = WatchTracker::default();
+        tracker.record_trade(&buy);
+        tracker.record_curve(&curve(50, 600), 800);
+        tracker.record_trade(&buy);
+        tracker.record_trade(&sell);
+        tracker.record_curve(&curve(40, 700), 800);
+        assert_eq!(tracker.chart_points().iter().map(|p| p.market_cap_lamports).collect::<Vec<_>>(), vec![100, 80]);
+        let summary = tracker.finish(Duration::from_secs(60));
+
+        assert_eq!((summary.trades, summary.buys, summary.sells), (3, 2, 1));
+        assert_eq!((summary.unique_buyers, summary.volume_lamports), (1, 2_500));
+        assert_eq!((summary.peak_market_cap_lamports, summary.final_market_cap_lamports), (100, 80));
+        assert_eq!(summary.final_progress_percent, 12.5);
+
+        // The starting snapshot is not announced; later ones announce the highest milestone newly passed
+        let mut tracker = WatchTracker::default();
+        assert_eq!(tracker.record_curve(&curve(50, 500), 800), None);
+        assert_eq!(tracker.record_curve(&curve(50, 350), 800), Some(50));
+        assert_eq!(tracker.record_curve(&curve(50, 340), 800), None);
+        assert_eq!(tracker.record_curve(&BondingCurveAccount { complete: true, ..curve(50, 0) }, 800), Some(100));
+    }
+
+    #[test]
+    fn test_signed_receipt() {
+        let creator = solana_sdk::signature::Keypair::new();
+        let mut report = LaunchReport::new(&Pubkey::new_unique(), "PVE Token", "PVE", &Signature::default()).unwrap();
+        assert!(report.verify_receipt().is_err());
+
+        report.sign(&creator).unwrap();
+        report.watch = Some(WatchSummary::default());
+        report.operator = Some("alice".to_string());
+        assert_eq!(report.verify_receipt().unwrap(), creator.pubkey());
+
+        let mut tampered = report.clone();
+        tampered.symbol = "EVP".to_string();
+        assert!(tampered.verify_receipt().is_err());
+    }
+}
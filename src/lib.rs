@@ -1,4 +1,67 @@
 pub mod parser;
 pub mod create_token;
 pub mod vanity_address;
-pub mod secure_credentials;
\ No newline at end of file
+pub mod secure_credentials;
+pub mod pump_accounts;
+pub mod tx_size;
+pub mod tx_dump;
+pub mod audit_log;
+pub mod secret_backends;
+pub mod squads;
+pub mod price_feed;
+pub mod launch_cost;
+pub mod funding;
+pub mod media;
+pub mod limits;
+pub mod tx_journal;
+pub mod notifications;
+pub mod mint_monitor;
+pub mod watcher;
+pub mod holders;
+pub mod buyers;
+pub mod funding_graph;
+pub mod launch_report;
+pub mod wallet_guard;
+pub mod cluster;
+pub mod localnet;
+pub mod progress;
+pub mod vanity_worker;
+pub mod vanity_fleet;
+pub mod config_check;
+pub mod wallets;
+pub mod trade;
+pub mod pump_swap;
+pub mod jupiter;
+pub mod metadata_lint;
+pub mod name_collision;
+pub mod launch_export;
+pub mod time_lock;
+pub mod triggers;
+pub mod discord_bot;
+pub mod launch_rate;
+pub mod wallet_report;
+pub mod nonce;
+pub mod metadata_hosts;
+pub mod pin_check;
+pub mod extra_instructions;
+pub mod metadata_commitment;
+pub mod dry_run_compare;
+pub mod warnings;
+pub mod metrics_push;
+pub mod health;
+pub mod jito;
+pub mod vanity_store;
+pub mod vanity_stats;
+pub mod priority_fee;
+pub mod rpc_pool;
+pub mod simulation;
+pub mod api_server;
+pub mod launch_dir;
+pub mod artifact_upload;
+pub mod config;
+pub mod read_only;
+pub mod metadata_update;
+pub mod authority_check;
+pub mod market_data;
+pub mod chart;
+pub mod keypair_cipher;
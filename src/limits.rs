@@ -0,0 +1,99 @@
+use anyhow::Result;
+use log::{info, warn};
+use std::time::Duration;
+
+/// Metaplex token name limit (bytes)
+pub const MAX_NAME_LEN: usize = 32;
+/// Metaplex token symbol limit (bytes)
+pub const MAX_SYMBOL_LEN: usize = 10;
+/// Metaplex metadata URI limit (bytes)
+pub const MAX_URI_LEN: usize = 200;
+/// Longest description pump.fun displays
+pub const MAX_DESCRIPTION_LEN: usize = 500;
+/// Largest image pump.fun accepts for upload
+pub const MAX_IMAGE_BYTES: usize = 15 * 1024 * 1024;
+/// Larger images upload and load slowly on the coin page
+pub const RECOMMENDED_IMAGE_BYTES: usize = 4 * 1024 * 1024;
+/// Largest video pump.fun accepts for upload
+pub const MAX_VIDEO_BYTES: usize = 30 * 1024 * 1024;
+/// Longest playback accepted for animated GIFs and videos
+pub const MAX_ANIMATION_SECONDS: f64 = 60.0;
+
+/// Check name, symbol and description against the pump.fun / Metaplex limits
+pub fn validate_token_fields(symbol: &str, name: &str, description: &str) -> Result<()> {
+    if symbol.is_empty() {
+        return Err(anyhow::anyhow!("Symbol must not be empty"));
+    }
+    if symbol.len() > MAX_SYMBOL_LEN {
+        return Err(anyhow::anyhow!("Symbol '{}' is too long. Maximum {} characters allowed.", symbol, MAX_SYMBOL_LEN));
+    }
+    if name.len() > MAX_NAME_LEN {
+        return Err(anyhow::anyhow!("Token name '{}' is too long. Maximum {} characters allowed.", name, MAX_NAME_LEN));
+    }
+    if description.chars().count() > MAX_DESCRIPTION_LEN {
+        return Err(anyhow::anyhow!("Description is too long. Maximum {} characters allowed.", MAX_DESCRIPTION_LEN));
+    }
+    Ok(())
+}
+
+/// Compare the built-in limits with the JSON document at `PUMP_LIMITS_URL`, if configured.
+///
+/// The document may contain any of `name`, `symbol`, `uri`, `description`, `image_bytes`,
+/// `video_bytes` and `animation_seconds`. Mismatches are logged as warnings and never fail a launch.
+pub async fn check_advertised_limits() {
+    let Ok(url) = std::env::var("PUMP_LIMITS_URL") else {
+        return;
+    };
+    match fetch_advertised_limits(&url).await {
+        Ok(advertised) => {
+            let mismatches = limit_mismatches(&advertised);
+            if mismatches.is_empty() {
+                info!("Built-in pump.fun limits match {}", url);
+            }
+            for mismatch in mismatches {
+                warn!("pump.fun limit changed: {} (update src/limits.rs)", mismatch);
+            }
+        }
+        Err(e) => warn!("Could not check pump.fun limits at {}: {}", url, e),
+    }
+}
+
+async fn fetch_advertised_limits(url: &str) -> Result<serde_json::Value> {
+    let client = reqwest::Client::builder().timeout(Duration::from_secs(5)).build()?;
+    Ok(client.get(url).send().await?.error_for_status()?.json().await?)
+}
+
+/// Human-readable differences between `advertised` and the built-in limits
+fn limit_mismatches(advertised: &serde_json::Value) -> Vec<String> {
+    let built_in = [
+        ("name", MAX_NAME_LEN as f64),
+        ("symbol", MAX_SYMBOL_LEN as f64),
+        ("uri", MAX_URI_LEN as f64),
+        ("description", MAX_DESCRIPTION_LEN as f64),
+        ("image_bytes", MAX_IMAGE_BYTES as f64),
+        ("video_bytes", MAX_VIDEO_BYTES as f64),
+        ("animation_seconds", MAX_ANIMATION_SECONDS),
+    ];
+    built_in.iter()
+        .filter_map(|(key, ours)| {
+            let theirs = advertised.get(*key)?.as_f64()?;
+            (theirs != *ours).then(|| format!("{} is {} (built-in {})", key, theirs, ours))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limits() {
+        assert!(validate_token_fields("PVE", "PVE Token", "desc").is_ok());
+        assert!(validate_token_fields("TOOLONGSYMBOL", "PVE", "").is_err());
+        assert!(validate_token_fields("PVE", &"x".repeat(33), "").is_err());
+        assert!(validate_token_fields("PVE", "PVE", &"x".repeat(501)).is_err());
+
+        let advertised = serde_json::json!({ "name": 32, "symbol": 12, "unrelated": 1 });
+        assert_eq!(limit_mismatches(&advertised), vec!["symbol is 12 (built-in 10)".to_string()]);
+    }
+}
@@ -0,0 +1,90 @@
+use anyhow::Result;
+use log::{info, warn};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use std::env;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::cluster::{SolanaCluster, LOCALNET_RPC_URL};
+use crate::create_token::ProgramConfig;
+
+/// Cluster the pump.fun accounts are cloned from unless `LOCALNET_CLONE_URL` is set
+pub const DEFAULT_CLONE_URL: &str = "https://api.mainnet-beta.solana.com";
+/// Seconds to wait for the validator to answer RPC requests
+const STARTUP_TIMEOUT_SECS: u64 = 120;
+
+/// `solana-test-validator` arguments for a fresh ledger with the pump.fun program,
+/// the metadata program and the global account cloned from `clone_url`
+pub fn validator_args(ledger: &str, clone_url: &str, program: &ProgramConfig) -> Vec<String> {
+    vec![
+        "--reset".to_string(),
+        "--quiet".to_string(),
+        "--ledger".to_string(), ledger.to_string(),
+        "--url".to_string(), clone_url.to_string(),
+        "--clone-upgradeable-program".to_string(), program.program_id.to_string(),
+        "--clone-upgradeable-program".to_string(), program.metadata_program_id.to_string(),
+        "--clone".to_string(), program.global_pda().to_string(),
+    ]
+}
+
+/// A `solana-test-validator` child process, killed on drop
+pub struct LocalValidator {
+    child: Child,
+}
+
+impl LocalValidator {
+    /// Start a validator on the default localnet ports and wait until it serves RPC requests
+    pub async fn start(ledger: &str, program: &ProgramConfig) -> Result<Self> {
+        let clone_url = env::var("LOCALNET_CLONE_URL").unwrap_or_else(|_| DEFAULT_CLONE_URL.to_string());
+        let binary = env::var("SOLANA_TEST_VALIDATOR").unwrap_or_else(|_| "solana-test-validator".to_string());
+        info!("Starting {} (ledger {}, cloning from {})", binary, ledger, clone_url);
+
+        let child = Command::new(&binary)
+            .args(validator_args(ledger, &clone_url, program))
+            .stdout(Stdio::null())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| anyhow::anyhow!("Failed to start {}: {} (is the Solana CLI installed?)", binary, e))?;
+        let mut validator = Self { child };
+
+        let rpc = RpcClient::new(SolanaCluster::Localnet.rpc_url().to_string());
+        let started = Instant::now();
+        while rpc.get_health().await.is_err() {
+            if let Some(status) = validator.child.try_wait()? {
+                return Err(anyhow::anyhow!("{} exited during startup ({})", binary, status));
+            }
+            if started.elapsed() > Duration::from_secs(STARTUP_TIMEOUT_SECS) {
+                return Err(anyhow::anyhow!("Validator did not become healthy within {}s", STARTUP_TIMEOUT_SECS));
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+        info!("Local validator ready at {}", LOCALNET_RPC_URL);
+        Ok(validator)
+    }
+}
+
+impl Drop for LocalValidator {
+    fn drop(&mut self) {
+        if let Err(e) = self.child.kill() {
+            warn!("Failed to stop local validator: {}", e);
+        }
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validator_args_clone_launch_accounts() {
+        let program = ProgramConfig::default();
+        let args = validator_args("data/test-ledger", DEFAULT_CLONE_URL, &program);
+        let global = program.global_pda().to_string();
+        let clones: Vec<&str> = args.windows(2)
+            .filter(|pair| pair[0].starts_with("--clone"))
+            .map(|pair| pair[1].as_str())
+            .collect();
+        assert_eq!(clones, vec![program.program_id.to_string().as_str(), program.metadata_program_id.to_string().as_str(), global.as_str()]);
+    }
+}
@@ -2,8 +2,15 @@ use log::{info, error};
 use anyhow::Result;
 use clap::Parser;
 
-use pumpfun_launcher::parser::{Args, handle_token_creation};
-use pumpfun_launcher::vanity_address::{init_global_vanity_pool, get_global_vanity_status};
+use pumpfun_launcher::parser::{Args, handle_command};
+use pumpfun_launcher::secret_backends::load_secrets_from_backend;
+use pumpfun_launcher::cluster::set_cluster;
+use pumpfun_launcher::config::load_config_file;
+use pumpfun_launcher::config_check::validate_config_or_exit;
+use pumpfun_launcher::progress::enable_progress_json;
+use pumpfun_launcher::read_only::init_read_only;
+use pumpfun_launcher::secure_credentials::{install_secret_from_stdin, install_secret_from_fd, install_secret_from_file};
+use pumpfun_launcher::vanity_address::{init_global_vanity_pool, get_global_vanity_status, VanityPattern};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -14,24 +21,75 @@ async fn main() -> Result<()> {
     
     dotenv::dotenv().ok();
     
-    // Initialize global vanity address pool first
-    info!("Initializing global vanity address generation...");
-    if let Err(e) = init_global_vanity_pool() {
-        error!("Failed to initialize global vanity pool: {}", e);
+    // Parse command line arguments
+    let args = Args::parse();
+    
+    // launcher.toml only fills in what the environment and .env leave unset
+    if let Err(e) = load_config_file(args.config.as_deref(), args.profile.as_deref()) {
+        error!("Failed to load config file: {}", e);
+        std::process::exit(1);
+    }
+    
+    // Pull secrets from AWS Secrets Manager / Vault when configured
+    if let Err(e) = load_secrets_from_backend().await {
+        error!("Failed to load secrets from backend: {}", e);
+        std::process::exit(1);
+    }
+    
+    if args.progress_json {
+        enable_progress_json();
+    }
+    if let Some(cluster) = args.cluster {
+        set_cluster(cluster);
+    }
+    
+    // A piped private key never appears in the process environment or `docker inspect`
+    let piped_key = if let Some(path) = &args.keypair {
+        Some(install_secret_from_file("PRIVATE_KEY", path))
+    } else if args.private_key_stdin {
+        Some(install_secret_from_stdin("PRIVATE_KEY"))
+    } else if let Ok(fd) = std::env::var("PRIVATE_KEY_FD") {
+        Some(fd.trim().parse::<i32>()
+            .map_err(|_| anyhow::anyhow!("PRIVATE_KEY_FD must be a file descriptor number"))
+            .and_then(|fd| install_secret_from_fd("PRIVATE_KEY", fd)))
+    } else {
+        None
+    };
+    if let Some(Err(e)) = piped_key {
+        error!("Failed to read private key: {}", e);
+        std::process::exit(1);
     }
     
-    // Check vanity address status
-    let (has_generated, generated_count, is_generating) = get_global_vanity_status();
+    // Report every configuration problem at once instead of failing on the first one mid-launch
+    validate_config_or_exit(&args);
     
-    info!("Global vanity address status - Generated: {} (count: {}), Generating: {}", 
-          has_generated, generated_count, is_generating);
+    // Only the token creation flow needs vanity addresses
+    if args.is_launch() {
+        // Initialize global vanity address pool first
+        info!("Initializing global vanity address generation...");
+        let pattern = args.create_args().map(|create| create.vanity_pattern()).transpose()?.unwrap_or_else(VanityPattern::from_env);
+        if let Err(e) = init_global_vanity_pool(pattern) {
+            error!("Failed to initialize global vanity pool: {}", e);
+        }
+        
+        // Check vanity address status
+        let (has_generated, generated_count, is_generating) = get_global_vanity_status();
+        
+        info!("Global vanity address status - Generated: {} (count: {}), Generating: {}", 
+              has_generated, generated_count, is_generating);
+    }
     
-    // Parse command line arguments
-    let args = Args::parse();
+    // LAUNCHER_READ_ONLY refuses signing commands everywhere; daemon and serve can also toggle it at runtime
+    init_read_only(false);
     
-    // Handle token creation
-    if let Err(e) = handle_token_creation(args).await {
-        error!("Failed to create token: {}", e);
+    // Handle the requested command
+    let is_create = args.is_launch();
+    if let Err(e) = handle_command(args).await {
+        if is_create {
+            error!("Failed to create token: {}", e);
+        } else {
+            error!("Command failed: {}", e);
+        }
         std::process::exit(1);
     }
     
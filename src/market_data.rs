@@ -0,0 +1,180 @@
+use anyhow::Result;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::env;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+pub const DEXSCREENER_URL: &str = "https://api.dexscreener.com";
+pub const BIRDEYE_URL: &str = "https://public-api.birdeye.so";
+/// How long a lookup is reused unless `MARKET_DATA_TTL_SECS` is set; both APIs rate limit by IP or key
+const DEFAULT_MARKET_DATA_TTL: Duration = Duration::from_secs(300);
+
+/// When a lookup was made and what it found
+type CachedLookup = (Instant, Option<MarketData>);
+
+// Lookups per mint, including misses, so a view listing many tokens stays within the rate limits
+static MARKET_CACHE: LazyLock<Mutex<HashMap<Pubkey, CachedLookup>>> = LazyLock::new(Default::default);
+
+/// Trading data of a migrated token, in USD
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MarketData {
+    pub price_usd: Option<f64>,
+    pub liquidity_usd: Option<f64>,
+    pub volume_24h_usd: Option<f64>,
+    /// `dexscreener` or `birdeye`
+    pub source: String,
+}
+
+/// Where market data comes from, selected by `MARKET_DATA_SOURCE`
+#[derive(Debug, Clone, PartialEq)]
+pub enum MarketDataSource {
+    /// Dexscreener's public API (default); the most liquid pair wins
+    Dexscreener { url: String },
+    /// Birdeye's token overview, which needs `BIRDEYE_API_KEY`
+    Birdeye { url: String, api_key: String },
+}
+
+impl MarketDataSource {
+    /// Read `MARKET_DATA_SOURCE` (dexscreener/birdeye), `MARKET_DATA_URL` and `BIRDEYE_API_KEY`
+    pub fn from_env() -> Result<Self> {
+        let source = env::var("MARKET_DATA_SOURCE").unwrap_or_else(|_| "dexscreener".to_string());
+        match source.trim().to_lowercase().as_str() {
+            "" | "dexscreener" => Ok(Self::Dexscreener {
+                url: env::var("MARKET_DATA_URL").unwrap_or_else(|_| DEXSCREENER_URL.to_string()),
+            }),
+            "birdeye" => Ok(Self::Birdeye {
+                url: env::var("MARKET_DATA_URL").unwrap_or_else(|_| BIRDEYE_URL.to_string()),
+                api_key: env::var("BIRDEYE_API_KEY")
+                    .map_err(|_| anyhow::anyhow!("Set BIRDEYE_API_KEY to use MARKET_DATA_SOURCE=birdeye"))?,
+            }),
+            other => Err(anyhow::anyhow!("Unknown MARKET_DATA_SOURCE '{}' (expected dexscreener or birdeye)", other)),
+        }
+    }
+
+    /// Fetch `mint`'s market data; `None` when the source knows no market for it
+    pub async fn fetch(&self, mint: &Pubkey) -> Result<Option<MarketData>> {
+        let client = reqwest::Client::builder().timeout(Duration::from_secs(10)).build()?;
+        match self {
+            Self::Dexscreener { url } => {
+                let response: serde_json::Value = client
+                    .get(format!("{}/latest/dex/tokens/{}", url.trim_end_matches('/'), mint))
+                    .send().await?
+                    .error_for_status()?
+                    .json().await?;
+                Ok(parse_dexscreener(&response))
+            }
+            Self::Birdeye { url, api_key } => {
+                let response: serde_json::Value = client
+                    .get(format!("{}/defi/token_overview", url.trim_end_matches('/')))
+                    .query(&[("address", mint.to_string())])
+                    .header("X-API-KEY", api_key)
+                    .header("x-chain", "solana")
+                    .send().await?
+                    .error_for_status()?
+                    .json().await?;
+                Ok(parse_birdeye(&response))
+            }
+        }
+    }
+}
+
+/// A number that may be sent as a JSON string
+fn number(value: &serde_json::Value) -> Option<f64> {
+    value.as_f64().or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+}
+
+/// Most liquid pair of a Dexscreener `/latest/dex/tokens/<mint>` response
+fn parse_dexscreener(response: &serde_json::Value) -> Option<MarketData> {
+    let pairs = response["pairs"].as_array()?;
+    let pair = pairs.iter()
+        .filter(|pair| pair["chainId"].as_str().is_none_or(|chain| chain == "solana"))
+        .max_by(|a, b| {
+            let liquidity = |pair: &serde_json::Value| number(&pair["liquidity"]["usd"]).unwrap_or(0.0);
+            liquidity(a).total_cmp(&liquidity(b))
+        })?;
+    Some(MarketData {
+        price_usd: number(&pair["priceUsd"]),
+        liquidity_usd: number(&pair["liquidity"]["usd"]),
+        volume_24h_usd: number(&pair["volume"]["h24"]),
+        source: "dexscreener".to_string(),
+    })
+}
+
+/// Data of a Birdeye `/defi/token_overview` response
+fn parse_birdeye(response: &serde_json::Value) -> Option<MarketData> {
+    let data = response["data"].as_object()?;
+    Some(MarketData {
+        price_usd: data.get("price").and_then(number),
+        liquidity_usd: data.get("liquidity").and_then(number),
+        volume_24h_usd: data.get("v24hUSD").and_then(number),
+        source: "birdeye".to_string(),
+    })
+}
+
+fn market_data_ttl() -> Duration {
+    env::var("MARKET_DATA_TTL_SECS").ok()
+        .and_then(|secs| secs.trim().parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_MARKET_DATA_TTL)
+}
+
+/// Market data of `mint`, cached for `MARKET_DATA_TTL_SECS`. `None` when unknown or the source is unavailable.
+pub async fn market_data(mint: &Pubkey) -> Option<MarketData> {
+    if let Some((fetched_at, data)) = MARKET_CACHE.lock().unwrap().get(mint) {
+        if fetched_at.elapsed() < market_data_ttl() {
+            return data.clone();
+        }
+    }
+
+    let source = match MarketDataSource::from_env() {
+        Ok(source) => source,
+        Err(e) => {
+            warn!("{}; market data disabled", e);
+            return None;
+        }
+    };
+    match source.fetch(mint).await {
+        Ok(data) => {
+            MARKET_CACHE.lock().unwrap().insert(*mint, (Instant::now(), data.clone()));
+            data
+        }
+        Err(e) => {
+            warn!("Failed to fetch market data of {}: {}", mint, e);
+            None
+        }
+    }
+}
+
+/// "price $0.00012000, liquidity $45000, 24h volume $12300 (dexscreener)"
+pub fn format_market_data(data: &MarketData) -> String {
+    let usd = |value: Option<f64>| match value {
+        Some(value) if value >= 1.0 => format!("${:.0}", value),
+        Some(value) => format!("${:.8}", value),
+        None => "unknown".to_string(),
+    };
+    format!("price {}, liquidity {}, 24h volume {} ({})",
+            usd(data.price_usd), usd(data.liquidity_usd), usd(data.volume_24h_usd), data.source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_market_responses() {
+        let dexscreener = serde_json::json!({ "pairs": [
+            { "chainId": "solana", "priceUsd": "0.0001", "liquidity": { "usd": 1200.5 }, "volume": { "h24": 300 } },
+            { "chainId": "solana", "priceUsd": "0.00012", "liquidity": { "usd": 45000 }, "volume": { "h24": 12300.25 } },
+        ]});
+        let data = parse_dexscreener(&dexscreener).unwrap();
+        assert_eq!((data.price_usd, data.liquidity_usd, data.volume_24h_usd), (Some(0.00012), Some(45000.0), Some(12300.25)));
+        assert_eq!(format_market_data(&data), "price $0.00012000, liquidity $45000, 24h volume $12300 (dexscreener)");
+        assert!(parse_dexscreener(&serde_json::json!({ "pairs": null })).is_none());
+
+        let birdeye = serde_json::json!({ "success": true, "data": { "price": 0.5, "liquidity": 9000.0, "v24hUSD": 100.0 } });
+        assert_eq!(parse_birdeye(&birdeye).unwrap().price_usd, Some(0.5));
+    }
+}
@@ -0,0 +1,629 @@
+use anyhow::Result;
+use log::info;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::limits::{MAX_IMAGE_BYTES, MAX_VIDEO_BYTES, MAX_ANIMATION_SECONDS};
+
+/// Where downloaded images are cached unless `IMAGE_CACHE_DIR` is set
+pub const DEFAULT_IMAGE_CACHE_DIR: &str = "data/cache";
+const IMAGE_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+/// Longest side of images produced by conversion
+pub const CONVERTED_MAX_DIMENSION: u32 = 1000;
+/// Shortest side below which artwork looks blurry on pump.fun
+pub const MIN_IMAGE_DIMENSION: u32 = 200;
+
+/// Media formats recognised from file contents
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaKind {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+    Bmp,
+    Tiff,
+    Mp4,
+    Unknown,
+}
+
+impl MediaKind {
+    /// Detect the format from magic bytes, ignoring the file extension
+    pub fn detect(data: &[u8]) -> Self {
+        match data {
+            [0x89, b'P', b'N', b'G', ..] => MediaKind::Png,
+            [0xFF, 0xD8, 0xFF, ..] => MediaKind::Jpeg,
+            [b'G', b'I', b'F', b'8', ..] => MediaKind::Gif,
+            [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => MediaKind::Webp,
+            [b'B', b'M', ..] => MediaKind::Bmp,
+            [b'I', b'I', 0x2A, 0x00, ..] | [b'M', b'M', 0x00, 0x2A, ..] => MediaKind::Tiff,
+            [_, _, _, _, b'f', b't', b'y', b'p', ..] => MediaKind::Mp4,
+            _ => MediaKind::Unknown,
+        }
+    }
+
+    /// Formats pump.fun accepts as token media
+    pub fn is_supported(&self) -> bool {
+        matches!(self, MediaKind::Png | MediaKind::Jpeg | MediaKind::Gif | MediaKind::Webp | MediaKind::Mp4)
+    }
+
+    pub fn is_video(&self) -> bool {
+        *self == MediaKind::Mp4
+    }
+
+    /// Upload size limit for this kind of media
+    pub fn max_bytes(&self) -> usize {
+        if self.is_video() { MAX_VIDEO_BYTES } else { MAX_IMAGE_BYTES }
+    }
+
+    pub fn mime(&self) -> &'static str {
+        match self {
+            MediaKind::Png => "image/png",
+            MediaKind::Jpeg => "image/jpeg",
+            MediaKind::Gif => "image/gif",
+            MediaKind::Webp => "image/webp",
+            MediaKind::Bmp => "image/bmp",
+            MediaKind::Tiff => "image/tiff",
+            MediaKind::Mp4 => "video/mp4",
+            MediaKind::Unknown => "application/octet-stream",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            MediaKind::Png => "png",
+            MediaKind::Jpeg => "jpg",
+            MediaKind::Gif => "gif",
+            MediaKind::Webp => "webp",
+            MediaKind::Bmp => "bmp",
+            MediaKind::Tiff => "tiff",
+            MediaKind::Mp4 => "mp4",
+            MediaKind::Unknown => "bin",
+        }
+    }
+}
+
+/// Token media ready for the multipart upload
+#[derive(Debug, Clone)]
+pub struct PreparedMedia {
+    pub data: Vec<u8>,
+    pub kind: MediaKind,
+    /// Upload-safe stem of the source file
+    pub stem: Option<String>,
+}
+
+impl PreparedMedia {
+    /// Source file stem with the extension of the content, which conversion may have changed
+    pub fn file_name(&self) -> String {
+        self.upload_name(None)
+    }
+
+    /// `name` when given, with the content's extension added if it has none; otherwise [`Self::file_name`].
+    /// pump.fun infers the media type from this extension.
+    pub fn upload_name(&self, name: Option<&str>) -> String {
+        match name {
+            Some(name) if Path::new(name).extension().is_some() => name.to_string(),
+            Some(stem) => format!("{}.{}", stem, self.kind.extension()),
+            None => {
+                let fallback = if self.kind.is_video() { "video" } else { "image" };
+                format!("{}.{}", self.stem.as_deref().unwrap_or(fallback), self.kind.extension())
+            }
+        }
+    }
+}
+
+fn is_filename_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')
+}
+
+/// Stem of `path` with anything that could break a content-disposition header replaced by `_`
+fn upload_stem(path: &str) -> Option<String> {
+    let stem = Path::new(path).file_stem()?.to_string_lossy();
+    let stem: String = stem.chars().map(|c| if is_filename_char(c) { c } else { '_' }).collect();
+    (!stem.trim_matches(['_', '.']).is_empty()).then_some(stem)
+}
+
+/// Validate an upload filename override from `--upload-filename` or `METADATA_UPLOAD_FILENAME`
+pub fn parse_upload_filename(value: &str) -> Result<String> {
+    let value = value.trim();
+    if value.is_empty() || value.len() > 100 || value.starts_with('.') || !value.chars().all(is_filename_char) {
+        return Err(anyhow::anyhow!("Invalid upload filename '{}': use up to 100 letters, digits, '-', '_' and '.'", value));
+    }
+    Ok(value.to_string())
+}
+
+/// Read the media at `path`, converting unsupported or oversized images when the
+/// `image-conversion` feature is enabled
+pub fn prepare_media(path: &str) -> Result<PreparedMedia> {
+    let data = std::fs::read(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read image {}: {}", path, e))?;
+    let kind = MediaKind::detect(&data);
+    let stem = upload_stem(path);
+
+    // Animated media cannot be converted without losing the animation, so it is only validated
+    if let Some(animation) = Animation::probe(kind, &data)? {
+        if data.len() > kind.max_bytes() {
+            return Err(anyhow::anyhow!("{} is {} bytes, above the {} byte limit for {:?}", path, data.len(), kind.max_bytes(), kind));
+        }
+        if animation.seconds > MAX_ANIMATION_SECONDS {
+            return Err(anyhow::anyhow!("{} plays for {:.1}s, above the {}s limit", path, animation.seconds, MAX_ANIMATION_SECONDS));
+        }
+        info!("Using animated {:?} media: {:.1}s{}", kind, animation.seconds,
+              animation.frames.map(|f| format!(", {} frames", f)).unwrap_or_default());
+        return Ok(PreparedMedia { data, kind, stem });
+    }
+
+    if kind.is_supported() && data.len() <= kind.max_bytes() {
+        return Ok(PreparedMedia { data, kind, stem });
+    }
+    convert_image(path, data, kind).map(|media| PreparedMedia { stem, ..media })
+}
+
+/// Download the image at `url` into the local cache and return its path.
+///
+/// Downloads are keyed by URL, so repeated launches with the same artwork reuse the cached file.
+pub async fn fetch_image_url(url: &str) -> Result<String> {
+    if !url.starts_with("https://") && !url.starts_with("http://") {
+        return Err(anyhow::anyhow!("Image URL must be http(s): {}", url));
+    }
+    let cache_dir = PathBuf::from(std::env::var("IMAGE_CACHE_DIR").unwrap_or_else(|_| DEFAULT_IMAGE_CACHE_DIR.to_string()));
+    let key: String = Sha256::digest(url.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect();
+
+    if let Ok(entries) = std::fs::read_dir(&cache_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.file_stem().and_then(|s| s.to_str()) == Some(key.as_str()) {
+                info!("Using cached image for {}: {}", url, path.display());
+                return Ok(path.to_string_lossy().to_string());
+            }
+        }
+    }
+
+    info!("Downloading image from {}", url);
+    let client = reqwest::Client::builder().timeout(IMAGE_DOWNLOAD_TIMEOUT).build()?;
+    let mut response = client.get(url).send().await?.error_for_status()?;
+
+    let content_type = response.headers().get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    if !content_type.is_empty() && !content_type.starts_with("image/") && !content_type.starts_with("video/") {
+        return Err(anyhow::anyhow!("{} returned {}, not an image or video", url, content_type));
+    }
+    if response.content_length().is_some_and(|len| len as usize > MAX_VIDEO_BYTES) {
+        return Err(anyhow::anyhow!("{} is larger than the {} byte media limit", url, MAX_VIDEO_BYTES));
+    }
+
+    let mut data = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        data.extend_from_slice(&chunk);
+        if data.len() > MAX_VIDEO_BYTES {
+            return Err(anyhow::anyhow!("{} is larger than the {} byte media limit", url, MAX_VIDEO_BYTES));
+        }
+    }
+
+    let kind = MediaKind::detect(&data);
+    if kind == MediaKind::Unknown {
+        return Err(anyhow::anyhow!("{} did not return a recognised image or video", url));
+    }
+
+    std::fs::create_dir_all(&cache_dir)?;
+    let path = cache_dir.join(format!("{}.{}", key, kind.extension()));
+    std::fs::write(&path, &data)?;
+    info!("Cached {:?} image ({} bytes) at {}", kind, data.len(), path.display());
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Width and height from an image header, without decoding the pixels
+pub fn image_dimensions(kind: MediaKind, data: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |pos: usize| data.get(pos..pos + 2).map(|b| u16::from_be_bytes([b[0], b[1]]) as u32);
+    let le16 = |pos: usize| data.get(pos..pos + 2).map(|b| u16::from_le_bytes([b[0], b[1]]) as u32);
+    let be32 = |pos: usize| data.get(pos..pos + 4).map(|b| u32::from_be_bytes(b.try_into().unwrap()));
+    let le24 = |pos: usize| data.get(pos..pos + 3).map(|b| u32::from_le_bytes([b[0], b[1], b[2], 0]));
+    match kind {
+        MediaKind::Png => Some((be32(16)?, be32(20)?)),
+        MediaKind::Gif => Some((le16(6)?, le16(8)?)),
+        MediaKind::Bmp => {
+            let read = |pos: usize| data.get(pos..pos + 4).map(|b| i32::from_le_bytes(b.try_into().unwrap()).unsigned_abs());
+            Some((read(18)?, read(22)?))
+        }
+        MediaKind::Webp => match data.get(12..16)? {
+            b"VP8 " => Some((le16(26)? & 0x3FFF, le16(28)? & 0x3FFF)),
+            b"VP8L" => {
+                let bits = u32::from_le_bytes(data.get(21..25)?.try_into().unwrap());
+                Some(((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1))
+            }
+            b"VP8X" => Some((le24(24)? + 1, le24(27)? + 1)),
+            _ => None,
+        },
+        MediaKind::Jpeg => {
+            // Walk marker segments up to the start-of-frame header
+            let mut pos = 2;
+            while *data.get(pos)? == 0xFF {
+                let marker = *data.get(pos + 1)?;
+                if (0xC0..=0xCF).contains(&marker) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
+                    return Some((be16(pos + 7)?, be16(pos + 5)?));
+                }
+                pos += 2 + be16(pos + 2)? as usize;
+            }
+            None
+        }
+        MediaKind::Tiff | MediaKind::Mp4 | MediaKind::Unknown => None,
+    }
+}
+
+/// Validation result for one artwork file of `prepare-images`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ImageReport {
+    pub file: String,
+    pub format: String,
+    pub bytes: usize,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// What must change before the file can be launched as-is; empty when it is ready
+    pub issues: Vec<String>,
+    /// Normalized copy written for a file with issues
+    pub fixed: Option<String>,
+}
+
+/// Check artwork against pump.fun's format and size limits and the square layout it is displayed in
+pub fn inspect_image(path: &Path, data: &[u8]) -> ImageReport {
+    let kind = MediaKind::detect(data);
+    let dimensions = image_dimensions(kind, data);
+    let mut issues = Vec::new();
+    if !kind.is_supported() {
+        issues.push(format!("unsupported format ({:?}); use PNG, JPEG, GIF, WebP or MP4", kind));
+    }
+    if data.len() > kind.max_bytes() {
+        issues.push(format!("{} bytes, above the {} byte limit", data.len(), kind.max_bytes()));
+    }
+    match Animation::probe(kind, data) {
+        Ok(Some(animation)) if animation.seconds > MAX_ANIMATION_SECONDS => {
+            issues.push(format!("plays for {:.1}s, above the {}s limit", animation.seconds, MAX_ANIMATION_SECONDS));
+        }
+        Ok(_) => {}
+        Err(e) => issues.push(format!("unreadable: {}", e)),
+    }
+    match dimensions {
+        Some((width, height)) => {
+            if width != height {
+                issues.push(format!("not square ({}x{})", width, height));
+            }
+            if width.min(height) < MIN_IMAGE_DIMENSION {
+                issues.push(format!("smaller than {}px ({}x{})", MIN_IMAGE_DIMENSION, width, height));
+            }
+        }
+        None if !kind.is_video() => issues.push("cannot read image dimensions".to_string()),
+        None => {}
+    }
+    ImageReport {
+        file: path.to_string_lossy().to_string(),
+        format: kind.mime().to_string(),
+        bytes: data.len(),
+        width: dimensions.map(|d| d.0),
+        height: dimensions.map(|d| d.1),
+        issues,
+        fixed: None,
+    }
+}
+
+/// Inspect every file in `dir`, writing normalized copies of fixable images to `out_dir`
+/// (with the `image-conversion` feature)
+pub fn prepare_images(dir: &Path, out_dir: &Path) -> Result<Vec<ImageReport>> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", dir.display(), e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect();
+    paths.sort();
+
+    let mut reports = Vec::with_capacity(paths.len());
+    for path in paths {
+        let data = std::fs::read(&path)?;
+        let mut report = inspect_image(&path, &data);
+        let kind = MediaKind::detect(&data);
+        // Animations would lose their frames and video cannot be re-encoded here
+        if !report.issues.is_empty() && !matches!(Animation::probe(kind, &data), Ok(Some(_))) {
+            match normalize_image(&data) {
+                Ok(media) => {
+                    std::fs::create_dir_all(out_dir)?;
+                    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+                    let fixed = out_dir.join(format!("{}.{}", stem, media.kind.extension()));
+                    std::fs::write(&fixed, &media.data)?;
+                    report.fixed = Some(fixed.to_string_lossy().to_string());
+                }
+                Err(e) => info!("Not normalizing {}: {}", path.display(), e),
+            }
+        }
+        reports.push(report);
+    }
+    Ok(reports)
+}
+
+/// Playback details of an animated GIF or video
+#[derive(Debug, Clone, PartialEq)]
+pub struct Animation {
+    pub seconds: f64,
+    /// Frame count, when cheaply known (GIF)
+    pub frames: Option<usize>,
+}
+
+impl Animation {
+    /// `None` for still images (including single-frame GIFs)
+    pub fn probe(kind: MediaKind, data: &[u8]) -> Result<Option<Self>> {
+        match kind {
+            MediaKind::Gif => {
+                let animation = gif_animation(data)?;
+                Ok(animation.filter(|a| a.frames.unwrap_or(0) > 1))
+            }
+            MediaKind::Mp4 => Ok(Some(Animation { seconds: mp4_duration_seconds(data)?, frames: None })),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Walk GIF blocks summing Graphic Control Extension delays
+fn gif_animation(data: &[u8]) -> Result<Option<Animation>> {
+    let truncated = || anyhow::anyhow!("Truncated GIF");
+    let byte = |pos: usize| data.get(pos).copied().ok_or_else(truncated);
+    let skip_sub_blocks = |mut pos: usize| -> Result<usize> {
+        loop {
+            let len = byte(pos)? as usize;
+            pos += 1 + len;
+            if len == 0 {
+                return Ok(pos);
+            }
+        }
+    };
+    let color_table_len = |packed: u8| if packed & 0x80 != 0 { 3 * (1usize << ((packed & 0x07) + 1)) } else { 0 };
+
+    let mut pos = 13 + color_table_len(byte(10)?);
+    let mut centiseconds = 0u64;
+    let mut frames = 0usize;
+    loop {
+        match byte(pos)? {
+            0x21 => {
+                if byte(pos + 1)? == 0xF9 {
+                    centiseconds += u16::from_le_bytes([byte(pos + 4)?, byte(pos + 5)?]) as u64;
+                }
+                pos = skip_sub_blocks(pos + 2)?;
+            }
+            0x2C => {
+                frames += 1;
+                pos += 10 + color_table_len(byte(pos + 9)?);
+                pos = skip_sub_blocks(pos + 1)?; // LZW minimum code size, then image data
+            }
+            0x3B => break,
+            other => return Err(anyhow::anyhow!("Unexpected GIF block 0x{:02x} at offset {}", other, pos)),
+        }
+    }
+    Ok(Some(Animation { seconds: centiseconds as f64 / 100.0, frames: Some(frames) }))
+}
+
+/// Duration from the `moov/mvhd` box of an MP4
+fn mp4_duration_seconds(data: &[u8]) -> Result<f64> {
+    let moov = find_box(data, b"moov").ok_or_else(|| anyhow::anyhow!("MP4 has no moov box"))?;
+    let mvhd = find_box(moov, b"mvhd").ok_or_else(|| anyhow::anyhow!("MP4 has no mvhd box"))?;
+    let read_u32 = |pos: usize| mvhd.get(pos..pos + 4).map(|b| u32::from_be_bytes(b.try_into().unwrap()) as u64);
+    let read_u64 = |pos: usize| mvhd.get(pos..pos + 8).map(|b| u64::from_be_bytes(b.try_into().unwrap()));
+
+    let (timescale, duration) = match mvhd.first() {
+        Some(1) => (read_u32(20), read_u64(24)),
+        Some(_) => (read_u32(12), read_u32(16)),
+        None => (None, None),
+    };
+    match (timescale, duration) {
+        (Some(timescale), Some(duration)) if timescale > 0 => Ok(duration as f64 / timescale as f64),
+        _ => Err(anyhow::anyhow!("Malformed MP4 mvhd box")),
+    }
+}
+
+/// Payload of the first box of type `kind` among the boxes in `data`
+fn find_box<'a>(data: &'a [u8], kind: &[u8; 4]) -> Option<&'a [u8]> {
+    let mut pos = 0;
+    while pos + 8 <= data.len() {
+        let size = u32::from_be_bytes(data[pos..pos + 4].try_into().unwrap()) as usize;
+        let (header, size) = match size {
+            0 => (8, data.len() - pos),
+            1 => (16, usize::try_from(u64::from_be_bytes(data.get(pos + 8..pos + 16)?.try_into().unwrap())).ok()?),
+            size => (8, size),
+        };
+        // Sizes come from untrusted files; never let one point past the end of the data
+        if size < header || size > data.len() - pos {
+            return None;
+        }
+        if &data[pos + 4..pos + 8] == kind {
+            return Some(&data[pos + header..pos + size]);
+        }
+        pos += size;
+    }
+    None
+}
+
+#[cfg(feature = "image-conversion")]
+fn convert_image(path: &str, data: Vec<u8>, kind: MediaKind) -> Result<PreparedMedia> {
+    let original_len = data.len();
+    let mut decoded = image::load_from_memory(&data)
+        .map_err(|e| anyhow::anyhow!("Cannot convert {} ({:?}): {}", path, kind, e))?;
+    if original_len > MAX_IMAGE_BYTES
+        && (decoded.width() > CONVERTED_MAX_DIMENSION || decoded.height() > CONVERTED_MAX_DIMENSION)
+    {
+        decoded = decoded.thumbnail(CONVERTED_MAX_DIMENSION, CONVERTED_MAX_DIMENSION);
+    }
+
+    let converted = encode_within_limit(&decoded)
+        .map_err(|e| anyhow::anyhow!("{} {}", path, e))?;
+
+    info!("Converted {} from {:?} ({} bytes) to {:?} {}x{} ({} bytes)",
+          path, kind, original_len, converted.kind, decoded.width(), decoded.height(), converted.data.len());
+    Ok(converted)
+}
+
+/// PNG, or JPEG when the PNG would exceed `MAX_IMAGE_BYTES`
+#[cfg(feature = "image-conversion")]
+fn encode_within_limit(decoded: &image::DynamicImage) -> Result<PreparedMedia> {
+    use image::{codecs::jpeg::JpegEncoder, ImageFormat};
+    use std::io::Cursor;
+
+    let mut png = Vec::new();
+    decoded.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)?;
+    let (data, kind) = if png.len() <= MAX_IMAGE_BYTES {
+        (png, MediaKind::Png)
+    } else {
+        let mut jpeg = Vec::new();
+        decoded.to_rgb8().write_with_encoder(JpegEncoder::new_with_quality(&mut jpeg, 85))?;
+        (jpeg, MediaKind::Jpeg)
+    };
+    if data.len() > MAX_IMAGE_BYTES {
+        return Err(anyhow::anyhow!("is still {} bytes after conversion (max {})", data.len(), MAX_IMAGE_BYTES));
+    }
+    Ok(PreparedMedia { data, kind, stem: None })
+}
+
+/// Center-crop to a square, shrink to `CONVERTED_MAX_DIMENSION` and re-encode under the size limit
+#[cfg(feature = "image-conversion")]
+fn normalize_image(data: &[u8]) -> Result<PreparedMedia> {
+    let decoded = image::load_from_memory(data)?;
+    let side = decoded.width().min(decoded.height());
+    let square = decoded.crop_imm((decoded.width() - side) / 2, (decoded.height() - side) / 2, side, side);
+    let resized = if side > CONVERTED_MAX_DIMENSION {
+        square.thumbnail(CONVERTED_MAX_DIMENSION, CONVERTED_MAX_DIMENSION)
+    } else {
+        square
+    };
+    if resized.width() < MIN_IMAGE_DIMENSION {
+        return Err(anyhow::anyhow!("only {}px after cropping, below {}px", resized.width(), MIN_IMAGE_DIMENSION));
+    }
+    encode_within_limit(&resized)
+}
+
+#[cfg(not(feature = "image-conversion"))]
+fn normalize_image(_data: &[u8]) -> Result<PreparedMedia> {
+    Err(anyhow::anyhow!("normalizing requires building with --features image-conversion"))
+}
+
+#[cfg(not(feature = "image-conversion"))]
+fn convert_image(path: &str, data: Vec<u8>, kind: MediaKind) -> Result<PreparedMedia> {
+    let problem = if kind.is_supported() {
+        format!("is {} bytes (max {})", data.len(), kind.max_bytes())
+    } else {
+        format!("is in an unsupported format ({:?})", kind)
+    };
+    info!("Automatic conversion is available when built with --features image-conversion");
+    Err(anyhow::anyhow!("Image {} {}; use PNG, JPEG, GIF, WebP or MP4", path, problem))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_media_kind() {
+        assert_eq!(MediaKind::detect(b"\x89PNG\r\n\x1a\n"), MediaKind::Png);
+        assert_eq!(MediaKind::detect(b"GIF89a"), MediaKind::Gif);
+        assert_eq!(MediaKind::detect(b"RIFF\0\0\0\0WEBPVP8 "), MediaKind::Webp);
+        assert_eq!(MediaKind::detect(b"BM\0\0"), MediaKind::Bmp);
+        assert_eq!(MediaKind::detect(b"hello"), MediaKind::Unknown);
+        assert!(!MediaKind::Tiff.is_supported());
+    }
+
+    fn test_gif(delays: &[u16]) -> Vec<u8> {
+        let mut gif = b"GIF89a\x01\x00\x01\x00\x00\x00\x00".to_vec();
+        for delay in delays {
+            gif.extend_from_slice(&[0x21, 0xF9, 0x04, 0x00]);
+            gif.extend_from_slice(&delay.to_le_bytes());
+            gif.extend_from_slice(&[0x00, 0x00]);
+            gif.extend_from_slice(&[0x2C, 0, 0, 0, 0, 1, 0, 1, 0, 0x00, 0x02, 0x02, 0x4C, 0x01, 0x00]);
+        }
+        gif.push(0x3B);
+        gif
+    }
+
+    fn test_mp4(timescale: u32, duration: u32) -> Vec<u8> {
+        let mut mvhd = vec![0u8; 12];
+        mvhd.extend_from_slice(&timescale.to_be_bytes());
+        mvhd.extend_from_slice(&duration.to_be_bytes());
+        let mut mvhd_box = ((mvhd.len() + 8) as u32).to_be_bytes().to_vec();
+        mvhd_box.extend_from_slice(b"mvhd");
+        mvhd_box.extend_from_slice(&mvhd);
+
+        let mut mp4 = vec![0, 0, 0, 16];
+        mp4.extend_from_slice(b"ftypisom\0\0\0\0");
+        mp4.extend_from_slice(&((mvhd_box.len() + 8) as u32).to_be_bytes());
+        mp4.extend_from_slice(b"moov");
+        mp4.extend_from_slice(&mvhd_box);
+        mp4
+    }
+
+    #[test]
+    fn test_animated_media_validation() {
+        let gif = test_gif(&[150, 250]);
+        let animation = Animation::probe(MediaKind::Gif, &gif).unwrap().unwrap();
+        assert_eq!(animation, Animation { seconds: 4.0, frames: Some(2) });
+        assert_eq!(Animation::probe(MediaKind::Gif, &test_gif(&[0])).unwrap(), None);
+
+        let mp4 = test_mp4(1000, 12_500);
+        assert_eq!(MediaKind::detect(&mp4), MediaKind::Mp4);
+        assert_eq!(Animation::probe(MediaKind::Mp4, &mp4).unwrap().unwrap().seconds, 12.5);
+
+        let dir = std::env::temp_dir();
+        let ok_path = dir.join(format!("media_ok_{}.mp4", std::process::id()));
+        let long_path = dir.join(format!("media_long_{}.mp4", std::process::id()));
+        std::fs::write(&ok_path, &mp4).unwrap();
+        std::fs::write(&long_path, test_mp4(1000, 90_000)).unwrap();
+
+        let media = prepare_media(ok_path.to_str().unwrap()).unwrap();
+        assert_eq!(media.kind.mime(), "video/mp4");
+        assert_eq!(media.file_name(), format!("media_ok_{}.mp4", std::process::id()));
+        assert_eq!(media.upload_name(Some("launch")), "launch.mp4");
+        assert_eq!(media.upload_name(Some("clip.MP4")), "clip.MP4");
+        assert_eq!(upload_stem("/tmp/my \"art\".gif").as_deref(), Some("my__art_"));
+        assert_eq!(PreparedMedia { stem: None, ..media }.file_name(), "video.mp4");
+        assert!(parse_upload_filename("../x.png").is_err());
+        assert!(prepare_media(long_path.to_str().unwrap()).unwrap_err().to_string().contains("60s limit"));
+
+        let _ = std::fs::remove_file(ok_path);
+        let _ = std::fs::remove_file(long_path);
+    }
+
+    #[test]
+    fn test_malformed_mp4_boxes() {
+        // 64-bit size 1 box whose largesize overflows `pos + size`
+        let mut huge = vec![0, 0, 0, 1];
+        huge.extend_from_slice(b"moov");
+        huge.extend_from_slice(&u64::MAX.to_be_bytes());
+        assert_eq!(find_box(&huge, b"moov"), None);
+        assert!(mp4_duration_seconds(&huge).is_err());
+
+        let mut truncated = test_mp4(1000, 12_500);
+        truncated.truncate(truncated.len() - 1);
+        assert!(mp4_duration_seconds(&truncated).is_err());
+        let mut short = vec![0, 0, 0, 4];
+        short.extend_from_slice(b"moov");
+        assert_eq!(find_box(&short, b"moov"), None);
+    }
+
+    fn png_header(width: u32, height: u32) -> Vec<u8> {
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        png.extend_from_slice(&width.to_be_bytes());
+        png.extend_from_slice(&height.to_be_bytes());
+        png
+    }
+
+    #[test]
+    fn test_inspect_image() {
+        assert_eq!(image_dimensions(MediaKind::Png, &png_header(512, 256)), Some((512, 256)));
+        assert_eq!(image_dimensions(MediaKind::Gif, &test_gif(&[0])), Some((1, 1)));
+        let jpeg = [0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x01, 0x90, 0x02, 0x58];
+        assert_eq!(image_dimensions(MediaKind::Jpeg, &jpeg), Some((600, 400)));
+
+        let square = inspect_image(Path::new("a.png"), &png_header(512, 512));
+        assert!(square.issues.is_empty(), "{:?}", square.issues);
+        let wide = inspect_image(Path::new("b.png"), &png_header(800, 100));
+        assert_eq!(wide.issues, vec!["not square (800x100)".to_string(), "smaller than 200px (800x100)".to_string()]);
+        let tiff = inspect_image(Path::new("c.tiff"), b"II\x2a\0");
+        assert!(tiff.issues[0].starts_with("unsupported format"));
+    }
+}
@@ -0,0 +1,122 @@
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use std::time::Duration;
+
+use crate::buyers::{fetch_transaction_json, transaction_account_keys};
+use crate::create_token::ProgramConfig;
+use crate::extra_instructions::MEMO_PROGRAM_ID;
+use crate::pin_check::onchain_metadata_uri;
+
+/// Memo prefix of a metadata commitment; the hex SHA-256 follows
+pub const COMMITMENT_PREFIX: &str = "pump-metadata:sha256:";
+const FETCH_ATTEMPTS: u32 = 3;
+
+/// Serialize with object keys sorted at every level and no whitespace
+pub fn canonical_json(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            let fields: Vec<String> = entries.iter()
+                .map(|(key, value)| format!("{}:{}", serde_json::Value::String(key.to_string()), canonical_json(value)))
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        serde_json::Value::Array(items) => format!("[{}]", items.iter().map(canonical_json).collect::<Vec<_>>().join(",")),
+        other => other.to_string(),
+    }
+}
+
+/// Hex SHA-256 of the canonical metadata JSON
+pub fn canonical_metadata_hash(metadata: &serde_json::Value) -> String {
+    Sha256::digest(canonical_json(metadata).as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn commitment_memo(hash: &str) -> String {
+    format!("{}{}", COMMITMENT_PREFIX, hash)
+}
+
+/// Fetch the metadata JSON at `uri`, retrying while a fresh upload propagates to the gateway
+pub async fn fetch_metadata(uri: &str) -> Result<serde_json::Value> {
+    let client = reqwest::Client::builder().timeout(Duration::from_secs(20)).build()?;
+    let mut attempt = 1;
+    loop {
+        let result = async {
+            Ok::<_, anyhow::Error>(client.get(uri).send().await?.error_for_status()?.json::<serde_json::Value>().await?)
+        }.await;
+        match result {
+            Ok(metadata) => return Ok(metadata),
+            Err(e) if attempt >= FETCH_ATTEMPTS => return Err(anyhow::anyhow!("Cannot fetch metadata at {}: {}", uri, e)),
+            Err(_) => {
+                tokio::time::sleep(Duration::from_secs(2 * attempt as u64)).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Committed hash from the memo instructions of a json-encoded transaction
+pub fn committed_hash(tx: &serde_json::Value) -> Option<String> {
+    let keys = transaction_account_keys(tx);
+    tx["transaction"]["message"]["instructions"].as_array()?.iter()
+        .filter(|ix| ix["programIdIndex"].as_u64().and_then(|i| keys.get(i as usize)).map(String::as_str) == Some(MEMO_PROGRAM_ID))
+        .filter_map(|ix| bs58::decode(ix["data"].as_str()?).into_vec().ok())
+        .filter_map(|data| String::from_utf8(data).ok())
+        .find_map(|memo| memo.strip_prefix(COMMITMENT_PREFIX).map(str::to_string))
+}
+
+/// Outcome of comparing a launch's memo commitment with the metadata served today
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CommitmentCheck {
+    pub uri: String,
+    /// Hash in the launch transaction's memo, if it carried one
+    pub committed: Option<String>,
+    pub current: String,
+}
+
+impl CommitmentCheck {
+    pub fn matches(&self) -> bool {
+        self.committed.as_deref() == Some(self.current.as_str())
+    }
+}
+
+/// Compare the commitment in launch transaction `signature` with the metadata `mint` points at now
+pub async fn verify_commitment(rpc: &RpcClient, program: &ProgramConfig, mint: &Pubkey, signature: &str) -> Result<CommitmentCheck> {
+    let tx = fetch_transaction_json(rpc, signature).await?;
+    if tx.is_null() {
+        return Err(anyhow::anyhow!("Transaction {} not found", signature));
+    }
+    if !transaction_account_keys(&tx).contains(&mint.to_string()) {
+        return Err(anyhow::anyhow!("Transaction {} does not involve {}", signature, mint));
+    }
+    let uri = onchain_metadata_uri(rpc, program, mint).await?;
+    let current = canonical_metadata_hash(&fetch_metadata(&uri).await?);
+    Ok(CommitmentCheck { uri, committed: committed_hash(&tx), current })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_commitment() {
+        let a: serde_json::Value = serde_json::from_str(r#"{"name": "PVE", "image": "ipfs://x", "symbol": "PVE"}"#).unwrap();
+        let b: serde_json::Value = serde_json::from_str(r#"{"symbol":"PVE","name":"PVE","image":"ipfs://x"}"#).unwrap();
+        assert_eq!(canonical_json(&a), r#"{"image":"ipfs://x","name":"PVE","symbol":"PVE"}"#);
+        let c: serde_json::Value = serde_json::from_str(r#"{"symbol":"PVE","name":"PVE","image":"ipfs://y"}"#).unwrap();
+        assert_eq!(canonical_metadata_hash(&a), canonical_metadata_hash(&b));
+        assert_ne!(canonical_metadata_hash(&a), canonical_metadata_hash(&c));
+
+        let memo = commitment_memo(&canonical_metadata_hash(&a));
+        let tx = serde_json::json!({
+            "transaction": { "message": {
+                "accountKeys": ["payer", MEMO_PROGRAM_ID],
+                "instructions": [{ "programIdIndex": 1, "accounts": [0], "data": bs58::encode(memo.as_bytes()).into_string() }]
+            } },
+            "meta": {}
+        });
+        assert_eq!(committed_hash(&tx), Some(canonical_metadata_hash(&a)));
+    }
+}
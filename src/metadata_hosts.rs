@@ -0,0 +1,140 @@
+use anyhow::Result;
+use std::env;
+use std::time::Duration;
+
+use crate::create_token::MetadataForm;
+use crate::media::PreparedMedia;
+use crate::secure_credentials::SecureApiKey;
+
+const PINATA_API_URL: &str = "https://api.pinata.cloud";
+/// Gateway for Pinata-pinned content unless `PINATA_GATEWAY_URL` is set
+pub const DEFAULT_PINATA_GATEWAY_URL: &str = "https://gateway.pinata.cloud";
+/// Gateway for content added to a self-hosted node unless `IPFS_GATEWAY_URL` is set
+pub const DEFAULT_IPFS_GATEWAY_URL: &str = "https://ipfs.io";
+/// pump.fun upload attempts before falling back unless `PUMPFUN_UPLOAD_ATTEMPTS` is set
+pub const DEFAULT_PUMPFUN_UPLOAD_ATTEMPTS: u32 = 3;
+
+/// An uploader used when pump.fun's IPFS endpoint keeps failing
+#[derive(Debug, Clone, PartialEq)]
+pub enum FallbackHost {
+    /// Pinata pinning API, authenticated with the `PINATA_JWT` secret
+    Pinata { gateway: String },
+    /// A Kubo (go-ipfs) node's HTTP API
+    IpfsNode { api_url: String, gateway: String },
+}
+
+impl FallbackHost {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Pinata { .. } => "Pinata",
+            Self::IpfsNode { .. } => "self-hosted IPFS node",
+        }
+    }
+
+    /// Configured hosts in `METADATA_FALLBACK_HOSTS` order (default: pinata, ipfs);
+    /// Pinata needs `PINATA_JWT`, the node needs `IPFS_API_URL`
+    pub fn chain_from_env() -> Result<Vec<Self>> {
+        let configured = env::var("METADATA_FALLBACK_HOSTS").ok();
+        let explicit = configured.is_some();
+        let order = configured.unwrap_or_else(|| "pinata,ipfs".to_string());
+        let mut hosts = Vec::new();
+        for name in order.split(',').map(|n| n.trim().to_lowercase()).filter(|n| !n.is_empty()) {
+            let host = match name.as_str() {
+                "pinata" if SecureApiKey::load("PINATA_JWT").is_ok() => Self::Pinata {
+                    gateway: env::var("PINATA_GATEWAY_URL").unwrap_or_else(|_| DEFAULT_PINATA_GATEWAY_URL.to_string()),
+                },
+                "ipfs" => match env::var("IPFS_API_URL") {
+                    Ok(api_url) => Self::IpfsNode {
+                        api_url,
+                        gateway: env::var("IPFS_GATEWAY_URL").unwrap_or_else(|_| DEFAULT_IPFS_GATEWAY_URL.to_string()),
+                    },
+                    Err(_) if explicit => return Err(anyhow::anyhow!("METADATA_FALLBACK_HOSTS lists ipfs but IPFS_API_URL is not set")),
+                    Err(_) => continue,
+                },
+                "pinata" if explicit => return Err(anyhow::anyhow!("METADATA_FALLBACK_HOSTS lists pinata but PINATA_JWT is not set")),
+                "pinata" => continue,
+                other => return Err(anyhow::anyhow!("Unknown metadata host '{}' in METADATA_FALLBACK_HOSTS (expected pinata or ipfs)", other)),
+            };
+            hosts.push(host);
+        }
+        Ok(hosts)
+    }
+
+    /// Upload the media, then pump.fun-style metadata JSON pointing at it; returns the metadata URI
+    pub async fn upload(&self, media: &PreparedMedia, name: &str, symbol: &str, description: &str, form: &MetadataForm) -> Result<String> {
+        let client = reqwest::Client::builder().timeout(Duration::from_secs(60)).build()?;
+        let gateway = match self {
+            Self::Pinata { gateway } | Self::IpfsNode { gateway, .. } => gateway,
+        };
+        let media_cid = self.add(&client, media.data.clone(), &media.upload_name(form.upload_filename.as_deref()), media.kind.mime()).await?;
+        let metadata = metadata_json(name, symbol, description, &gateway_uri(gateway, &media_cid), form);
+        let metadata_cid = self.add(&client, serde_json::to_vec(&metadata)?, "metadata.json", "application/json").await?;
+        Ok(gateway_uri(gateway, &metadata_cid))
+    }
+
+    /// Pin one file and return its CID
+    async fn add(&self, client: &reqwest::Client, data: Vec<u8>, file_name: &str, mime: &str) -> Result<String> {
+        let form = reqwest::multipart::Form::new()
+            .part("file", reqwest::multipart::Part::bytes(data).file_name(file_name.to_string()).mime_str(mime)?);
+        let (request, cid_field) = match self {
+            Self::Pinata { .. } => {
+                let jwt = SecureApiKey::load("PINATA_JWT")?;
+                (client.post(format!("{}/pinning/pinFileToIPFS", PINATA_API_URL)).bearer_auth(jwt.expose_secret()), "IpfsHash")
+            }
+            Self::IpfsNode { api_url, .. } => {
+                (client.post(format!("{}/api/v0/add?pin=true&cid-version=1", api_url.trim_end_matches('/'))), "Hash")
+            }
+        };
+        let response: serde_json::Value = request.multipart(form).send().await?.error_for_status()?.json().await?;
+        response[cid_field].as_str()
+            .map(str::to_string)
+            .ok_or_else(|| anyhow::anyhow!("{} response has no {}", self.name(), cid_field))
+    }
+}
+
+/// `<gateway>/ipfs/<cid>`
+pub fn gateway_uri(gateway: &str, cid: &str) -> String {
+    format!("{}/ipfs/{}", gateway.trim_end_matches('/'), cid)
+}
+
+/// Metadata JSON in the shape pump.fun's own uploader produces
+pub fn metadata_json(name: &str, symbol: &str, description: &str, image_uri: &str, form: &MetadataForm) -> serde_json::Value {
+    let mut metadata = serde_json::json!({
+        "name": name,
+        "symbol": symbol,
+        "description": description,
+        "image": image_uri,
+        "showName": form.show_name,
+        "createdOn": form.created_on,
+    });
+    for (key, value) in [("twitter", &form.twitter), ("telegram", &form.telegram), ("website", &form.website)] {
+        if !value.is_empty() {
+            metadata[key] = serde_json::Value::String(value.clone());
+        }
+    }
+    metadata
+}
+
+pub fn pumpfun_attempts_from_env() -> u32 {
+    env::var("PUMPFUN_UPLOAD_ATTEMPTS").ok()
+        .and_then(|v| v.trim().parse().ok())
+        .filter(|&attempts| attempts > 0)
+        .unwrap_or(DEFAULT_PUMPFUN_UPLOAD_ATTEMPTS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fallback_metadata() {
+        assert_eq!(gateway_uri("https://ipfs.io/", "bafy123"), "https://ipfs.io/ipfs/bafy123");
+
+        let form = MetadataForm { twitter: "https://x.com/pve".to_string(), ..Default::default() };
+        let metadata = metadata_json("PVE Token", "PVE", "desc", "https://ipfs.io/ipfs/img", &form);
+        assert_eq!(metadata["image"], "https://ipfs.io/ipfs/img");
+        assert_eq!(metadata["twitter"], "https://x.com/pve");
+        assert_eq!(metadata["showName"], true);
+        assert!(metadata.get("website").is_none());
+    }
+}
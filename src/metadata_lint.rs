@@ -0,0 +1,197 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::limits::{MAX_DESCRIPTION_LEN, MAX_NAME_LEN, MAX_SYMBOL_LEN};
+
+/// Zero-width characters that hide content or make look-alike names
+const ZERO_WIDTH_CHARS: &[char] = &['\u{200B}', '\u{200C}', '\u{200D}', '\u{2060}', '\u{FEFF}'];
+/// Bidirectional overrides and isolates that reorder displayed text
+const BIDI_CONTROL_CHARS: &[char] = &[
+    '\u{202A}', '\u{202B}', '\u{202C}', '\u{202D}', '\u{202E}', '\u{2066}', '\u{2067}', '\u{2068}', '\u{2069}',
+];
+
+/// Token metadata as written to the pump.fun metadata JSON; every field is optional so partial files lint
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TokenMetadata {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub twitter: Option<String>,
+    pub telegram: Option<String>,
+    pub website: Option<String>,
+}
+
+impl TokenMetadata {
+    pub fn from_file(path: &str) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path, e))?;
+        serde_json::from_str(&contents).map_err(|e| anyhow::anyhow!("{} is not valid metadata JSON: {}", path, e))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// The launch would be rejected or the token displayed incorrectly
+    Error,
+    /// Allowed, but looks suspicious or unfinished to buyers
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LintFinding {
+    pub severity: Severity,
+    pub field: &'static str,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LintReport {
+    pub findings: Vec<LintFinding>,
+    /// Completeness from 0 to 100, weighted towards the fields buyers see first
+    pub completeness: u8,
+}
+
+impl LintReport {
+    pub fn has_errors(&self) -> bool {
+        self.findings.iter().any(|f| f.severity == Severity::Error)
+    }
+}
+
+/// Check `metadata` against the pump.fun/Metaplex limits and for deceptive text, offline
+pub fn lint_metadata(metadata: &TokenMetadata) -> LintReport {
+    let mut findings = Vec::new();
+    let mut push = |severity, field, message: String| findings.push(LintFinding { severity, field, message });
+
+    let text_fields = [
+        ("name", &metadata.name, MAX_NAME_LEN),
+        ("symbol", &metadata.symbol, MAX_SYMBOL_LEN),
+    ];
+    for (field, value, max_len) in text_fields {
+        let Some(value) = value.as_deref() else { continue };
+        if value.len() > max_len {
+            push(Severity::Error, field, format!("{} bytes, above the {} byte limit", value.len(), max_len));
+        }
+        if looks_like_url(value) {
+            push(Severity::Warning, field, "contains a URL or domain".to_string());
+        }
+    }
+    match metadata.symbol.as_deref() {
+        None | Some("") => push(Severity::Error, "symbol", "missing".to_string()),
+        Some(symbol) if symbol.chars().any(char::is_whitespace) => {
+            push(Severity::Warning, "symbol", "contains whitespace".to_string());
+        }
+        Some(_) => {}
+    }
+    if let Some(description) = metadata.description.as_deref() {
+        let chars = description.chars().count();
+        if chars > MAX_DESCRIPTION_LEN {
+            push(Severity::Error, "description", format!("{} characters, above the {} character limit", chars, MAX_DESCRIPTION_LEN));
+        }
+    }
+
+    let all_text = [
+        ("name", &metadata.name),
+        ("symbol", &metadata.symbol),
+        ("description", &metadata.description),
+    ];
+    for (field, value) in all_text {
+        let Some(value) = value.as_deref() else { continue };
+        if value.contains(BIDI_CONTROL_CHARS) {
+            push(Severity::Error, field, "contains right-to-left/bidirectional override characters".to_string());
+        }
+        if value.contains(ZERO_WIDTH_CHARS) {
+            push(Severity::Error, field, "contains zero-width characters".to_string());
+        }
+        if value.chars().any(|c| c.is_control() && c != '\n' && c != '\t') {
+            push(Severity::Warning, field, "contains control characters".to_string());
+        }
+        if value.trim() != value {
+            push(Severity::Warning, field, "has leading or trailing whitespace".to_string());
+        }
+    }
+
+    let links = [
+        ("twitter", &metadata.twitter, &["twitter.com", "x.com"][..]),
+        ("telegram", &metadata.telegram, &["t.me", "telegram.me"][..]),
+        ("website", &metadata.website, &[][..]),
+    ];
+    for (field, value, hosts) in links {
+        let Some(value) = value.as_deref().filter(|v| !v.is_empty()) else { continue };
+        match url::Url::parse(value) {
+            Ok(url) if url.scheme() != "https" => push(Severity::Warning, field, format!("not an https link: {}", value)),
+            Ok(url) => {
+                let host = url.host_str().unwrap_or_default().trim_start_matches("www.");
+                if !hosts.is_empty() && !hosts.contains(&host) {
+                    push(Severity::Warning, field, format!("expected a {} link, got {}", hosts.join(" or "), host));
+                }
+            }
+            Err(e) => push(Severity::Error, field, format!("malformed URL: {}", e)),
+        }
+    }
+
+    LintReport { findings, completeness: completeness(metadata) }
+}
+
+fn looks_like_url(value: &str) -> bool {
+    let lower = value.to_lowercase();
+    lower.contains("://") || lower.contains("www.")
+        || [".com", ".io", ".xyz", ".fun", ".net", ".org"].iter().any(|tld| lower.contains(tld))
+}
+
+fn completeness(metadata: &TokenMetadata) -> u8 {
+    let filled = |value: &Option<String>| value.as_deref().is_some_and(|v| !v.trim().is_empty());
+    [
+        (&metadata.name, 20),
+        (&metadata.symbol, 20),
+        (&metadata.description, 20),
+        (&metadata.image, 20),
+        (&metadata.website, 10),
+        (&metadata.twitter, 5),
+        (&metadata.telegram, 5),
+    ]
+    .iter()
+    .filter(|(value, _)| filled(value))
+    .map(|(_, weight)| weight)
+    .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint_metadata() {
+        let clean = TokenMetadata {
+            name: Some("PVE Token".to_string()),
+            symbol: Some("PVE".to_string()),
+            description: Some("A great token".to_string()),
+            image: Some("https://ipfs.io/ipfs/Qm".to_string()),
+            twitter: Some("https://x.com/pve".to_string()),
+            ..Default::default()
+        };
+        let report = lint_metadata(&clean);
+        assert!(report.findings.is_empty(), "{:?}", report.findings);
+        assert_eq!(report.completeness, 85);
+
+        let shady = TokenMetadata {
+            name: Some("Claim at pve.xyz".to_string()),
+            symbol: Some("PV\u{200B}E".to_string()),
+            description: Some("safe \u{202E}gnp.exe".to_string()),
+            telegram: Some("http://t.me/pve".to_string()),
+            ..Default::default()
+        };
+        let report = lint_metadata(&shady);
+        let found: Vec<(&str, Severity)> = report.findings.iter().map(|f| (f.field, f.severity)).collect();
+        assert!(found.contains(&("name", Severity::Warning)));
+        assert!(found.contains(&("symbol", Severity::Error)));
+        assert!(found.contains(&("description", Severity::Error)));
+        assert!(found.contains(&("telegram", Severity::Warning)));
+        assert!(report.has_errors());
+
+        let parsed: TokenMetadata = serde_json::from_str(r#"{"name":"PVE","showName":true}"#).unwrap();
+        assert!(lint_metadata(&parsed).findings.iter().any(|f| f.field == "symbol" && f.message == "missing"));
+    }
+}
@@ -0,0 +1,180 @@
+use anyhow::Result;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+// Token Metadata `UpdateMetadataAccountV2` instruction index
+const UPDATE_METADATA_ACCOUNT_V2: u8 = 15;
+/// Longest name, symbol and URI Token Metadata stores
+pub const MAX_NAME_LEN: usize = 32;
+pub const MAX_SYMBOL_LEN: usize = 10;
+pub const MAX_URI_LEN: usize = 200;
+
+/// The parts of a Metaplex metadata account an update has to carry over. Creators, collection and
+/// uses are kept as their Borsh bytes, since an update only rewrites them unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataAccount {
+    pub update_authority: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub is_mutable: bool,
+    creators: Vec<u8>,
+    collection: Vec<u8>,
+    uses: Vec<u8>,
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize, field: &str) -> Result<&'a [u8]> {
+        let bytes = self.data.get(self.offset..self.offset + len)
+            .ok_or_else(|| anyhow::anyhow!("Metadata account ends before {}", field))?;
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn pubkey(&mut self, field: &str) -> Result<Pubkey> {
+        Ok(Pubkey::new_from_array(self.take(32, field)?.try_into().unwrap()))
+    }
+
+    /// Borsh string; stored values are padded with NUL bytes
+    fn string(&mut self, field: &str) -> Result<String> {
+        let len = u32::from_le_bytes(self.take(4, field)?.try_into().unwrap()) as usize;
+        Ok(String::from_utf8_lossy(self.take(len, field)?).trim_end_matches('\0').to_string())
+    }
+
+    /// Raw bytes of a Borsh `Option` whose `Some` payload is `len(reader)` bytes long. Accounts
+    /// written before a field existed end early or are zero-padded, both of which read as `None`.
+    fn option(&mut self, field: &str, len: impl FnOnce(&mut Self) -> Result<usize>) -> Result<Vec<u8>> {
+        let start = self.offset;
+        match self.data.get(start) {
+            None | Some(0) => {
+                self.offset = (start + 1).min(self.data.len());
+                Ok(vec![0])
+            }
+            Some(_) => {
+                self.offset += 1;
+                let len = len(self)?;
+                self.take(len, field)?;
+                Ok(self.data[start..self.offset].to_vec())
+            }
+        }
+    }
+}
+
+/// Decode a Token Metadata `Metadata` account
+pub fn parse_metadata_account(data: &[u8]) -> Result<MetadataAccount> {
+    let mut reader = Reader { data, offset: 1 };
+    let update_authority = reader.pubkey("the update authority")?;
+    let mint = reader.pubkey("the mint")?;
+    let (name, symbol, uri) = (reader.string("the name")?, reader.string("the symbol")?, reader.string("the uri")?);
+    let seller_fee_basis_points = u16::from_le_bytes(reader.take(2, "the seller fee")?.try_into().unwrap());
+    // Vec<Creator>: u32 count, then 34 bytes (address, verified, share) each
+    let creators = reader.option("the creators", |r| {
+        Ok(4 + u32::from_le_bytes(r.data.get(r.offset..r.offset + 4).and_then(|b| b.try_into().ok())
+            .ok_or_else(|| anyhow::anyhow!("Metadata account ends before the creators"))?) as usize * 34)
+    })?;
+    let _primary_sale_happened = reader.take(1, "primary_sale_happened")?;
+    let is_mutable = reader.take(1, "is_mutable")?[0] != 0;
+    reader.option("the edition nonce", |_| Ok(1))?;
+    reader.option("the token standard", |_| Ok(1))?;
+    let collection = reader.option("the collection", |_| Ok(33))?;
+    let uses = reader.option("the uses", |_| Ok(17))?;
+    Ok(MetadataAccount { update_authority, mint, name, symbol, uri, seller_fee_basis_points, is_mutable, creators, collection, uses })
+}
+
+/// `UpdateMetadataAccountV2` replacing `name` and/or `uri` of `account` and keeping everything else,
+/// signed by its update authority
+pub fn update_metadata_instruction(metadata_program: &Pubkey, metadata: &Pubkey, account: &MetadataAccount, name: Option<&str>, uri: Option<&str>) -> Result<Instruction> {
+    if !account.is_mutable {
+        return Err(anyhow::anyhow!("The metadata of {} is immutable", account.mint));
+    }
+    let (name, uri) = (name.unwrap_or(&account.name), uri.unwrap_or(&account.uri));
+    if name.len() > MAX_NAME_LEN {
+        return Err(anyhow::anyhow!("Name is {} bytes; Token Metadata stores at most {}", name.len(), MAX_NAME_LEN));
+    }
+    if uri.len() > MAX_URI_LEN {
+        return Err(anyhow::anyhow!("URI is {} bytes; Token Metadata stores at most {}", uri.len(), MAX_URI_LEN));
+    }
+
+    let mut data = vec![UPDATE_METADATA_ACCOUNT_V2, 1];
+    for field in [name, account.symbol.as_str(), uri] {
+        data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+        data.extend_from_slice(field.as_bytes());
+    }
+    data.extend_from_slice(&account.seller_fee_basis_points.to_le_bytes());
+    data.extend_from_slice(&account.creators);
+    data.extend_from_slice(&account.collection);
+    data.extend_from_slice(&account.uses);
+    // Keep the update authority, primary sale flag and mutability
+    data.extend_from_slice(&[0, 0, 0]);
+    Ok(Instruction {
+        program_id: *metadata_program,
+        accounts: vec![
+            AccountMeta::new(*metadata, false),
+            AccountMeta::new_readonly(account.update_authority, true),
+        ],
+        data,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn padded_string(value: &str, len: usize) -> Vec<u8> {
+        let mut bytes = (len as u32).to_le_bytes().to_vec();
+        bytes.extend_from_slice(value.as_bytes());
+        bytes.resize(4 + len, 0);
+        bytes
+    }
+
+    #[test]
+    fn test_update_keeps_other_fields() {
+        let (authority, mint, creator) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut data = vec![4];
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(mint.as_ref());
+        data.extend(padded_string("Moon Cat", MAX_NAME_LEN));
+        data.extend(padded_string("MCAT", MAX_SYMBOL_LEN));
+        data.extend(padded_string("https://ipfs.io/ipfs/old", MAX_URI_LEN));
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(&[1, 1, 0, 0, 0]);
+        data.extend_from_slice(creator.as_ref());
+        data.extend_from_slice(&[1, 100]);
+        data.extend_from_slice(&[0, 1, 1, 254, 1, 0]);
+        data.resize(data.len() + 60, 0);
+
+        let account = parse_metadata_account(&data).unwrap();
+        assert_eq!((account.update_authority, account.mint), (authority, mint));
+        assert_eq!((account.name.as_str(), account.symbol.as_str(), account.uri.as_str()), ("Moon Cat", "MCAT", "https://ipfs.io/ipfs/old"));
+        assert!(account.is_mutable);
+        assert_eq!(account.creators.len(), 1 + 4 + 34);
+
+        let program = Pubkey::new_unique();
+        let ix = update_metadata_instruction(&program, &Pubkey::new_unique(), &account, None, Some("https://ipfs.io/ipfs/new")).unwrap();
+        let mut expected = vec![UPDATE_METADATA_ACCOUNT_V2, 1];
+        expected.extend_from_slice(&8u32.to_le_bytes());
+        expected.extend_from_slice(b"Moon Cat");
+        expected.extend_from_slice(&4u32.to_le_bytes());
+        expected.extend_from_slice(b"MCAT");
+        expected.extend_from_slice(&24u32.to_le_bytes());
+        expected.extend_from_slice(b"https://ipfs.io/ipfs/new");
+        expected.extend_from_slice(&[0, 0]);
+        expected.extend_from_slice(&account.creators);
+        expected.extend_from_slice(&[0, 0, 0, 0, 0]);
+        assert_eq!(ix.data, expected);
+        assert!(ix.accounts[1].is_signer && ix.accounts[1].pubkey == authority);
+
+        assert!(update_metadata_instruction(&program, &Pubkey::new_unique(), &account, Some(&"x".repeat(33)), None).is_err());
+        let frozen = MetadataAccount { is_mutable: false, ..account };
+        assert!(update_metadata_instruction(&program, &Pubkey::new_unique(), &frozen, Some("Moon Cat 2"), None).is_err());
+    }
+}
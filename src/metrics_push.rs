@@ -0,0 +1,93 @@
+use anyhow::Result;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD as BASE64_URL, Engine};
+use log::{info, warn};
+use std::env;
+use std::fmt::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Pushgateway job name unless `PUSHGATEWAY_JOB` is set
+pub const DEFAULT_PUSHGATEWAY_JOB: &str = "pumpfun_launcher";
+
+/// Metrics of one launch attempt from the CLI
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchMetrics {
+    pub symbol: String,
+    pub cluster: String,
+    pub success: bool,
+    pub dry_run: bool,
+    pub duration: Duration,
+    pub warnings: usize,
+}
+
+impl LaunchMetrics {
+    /// Prometheus text exposition format
+    pub fn to_exposition(&self) -> String {
+        let labels = format!("cluster=\"{}\",dry_run=\"{}\"", escape_label(&self.cluster), self.dry_run);
+        let finished = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        let mut text = String::new();
+        let mut gauge = |name: &str, help: &str, value: String| {
+            let _ = writeln!(text, "# HELP {} {}\n# TYPE {} gauge\n{}{{{}}} {}", name, help, name, name, labels, value);
+        };
+        gauge("pumpfun_launch_success", "1 if the last launch succeeded, 0 if it failed", (self.success as u8).to_string());
+        gauge("pumpfun_launch_duration_seconds", "Wall time of the last launch", format!("{:.3}", self.duration.as_secs_f64()));
+        gauge("pumpfun_launch_warnings", "Warnings collected during the last launch", self.warnings.to_string());
+        gauge("pumpfun_launch_finished_timestamp_seconds", "Unix time the last launch finished", finished.to_string());
+        text
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Pushgateway group URL for `symbol`: `<base>/metrics/job/<job>/symbol@base64/<symbol>`
+pub fn group_url(base: &str, job: &str, symbol: &str) -> String {
+    // base64 keeps symbols with slashes or other reserved characters valid in the path
+    format!("{}/metrics/job/{}/symbol@base64/{}", base.trim_end_matches('/'), job, BASE64_URL.encode(symbol))
+}
+
+/// Replace the metrics of the launch's group on the Pushgateway at `PUSHGATEWAY_URL`
+pub async fn push(metrics: &LaunchMetrics, base: &str) -> Result<()> {
+    let job = env::var("PUSHGATEWAY_JOB").unwrap_or_else(|_| DEFAULT_PUSHGATEWAY_JOB.to_string());
+    let url = group_url(base, &job, &metrics.symbol);
+    reqwest::Client::builder().timeout(Duration::from_secs(10)).build()?
+        .put(&url)
+        .header("Content-Type", "text/plain; version=0.0.4")
+        .body(metrics.to_exposition())
+        .send().await?
+        .error_for_status()?;
+    Ok(())
+}
+
+/// Push `metrics` when `PUSHGATEWAY_URL` is set. Never fails the launch.
+pub async fn push_launch_metrics(metrics: &LaunchMetrics) {
+    let Ok(base) = env::var("PUSHGATEWAY_URL") else {
+        return;
+    };
+    match push(metrics, &base).await {
+        Ok(()) => info!("Pushed launch metrics to {}", base),
+        Err(e) => warn!("Failed to push launch metrics to {}: {}", base, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launch_metrics_exposition() {
+        let metrics = LaunchMetrics {
+            symbol: "PVE".to_string(),
+            cluster: "main\"net".to_string(),
+            success: true,
+            dry_run: false,
+            duration: Duration::from_millis(12_500),
+            warnings: 2,
+        };
+        let text = metrics.to_exposition();
+        assert!(text.contains("# TYPE pumpfun_launch_success gauge\npumpfun_launch_success{cluster=\"main\\\"net\",dry_run=\"false\"} 1\n"));
+        assert!(text.contains("pumpfun_launch_duration_seconds{cluster=\"main\\\"net\",dry_run=\"false\"} 12.500\n"));
+        assert!(text.contains("pumpfun_launch_warnings{cluster=\"main\\\"net\",dry_run=\"false\"} 2\n"));
+        assert_eq!(group_url("http://push:9091/", "launcher", "A/B"), "http://push:9091/metrics/job/launcher/symbol@base64/QS9C");
+    }
+}
@@ -0,0 +1,149 @@
+use anyhow::Result;
+use futures::StreamExt;
+use log::{info, warn};
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use std::env;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::cluster::selected_cluster;
+use crate::create_token::rpc_url_from_env;
+use crate::notifications::{Notification, Notifier};
+
+pub const HELIUS_WEBHOOKS_URL: &str = "https://api.helius.xyz/v0/webhooks";
+/// How long a websocket monitor runs after launch unless `MINT_MONITOR_SECS` is set
+pub const DEFAULT_MONITOR_SECS: u64 = 300;
+
+/// How a freshly launched mint is monitored, selected by `MINT_MONITOR`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MintMonitor {
+    Off,
+    /// Register a Helius enhanced webhook delivering the mint's transactions to `HELIUS_WEBHOOK_TARGET_URL`
+    HeliusWebhook,
+    /// Subscribe to the mint's logs over the RPC websocket and forward them as notifications
+    Websocket,
+}
+
+impl FromStr for MintMonitor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "off" | "none" => Ok(MintMonitor::Off),
+            "helius-webhook" | "webhook" => Ok(MintMonitor::HeliusWebhook),
+            "websocket" | "ws" => Ok(MintMonitor::Websocket),
+            other => Err(anyhow::anyhow!("Unknown MINT_MONITOR '{}' (expected off, helius-webhook or websocket)", other)),
+        }
+    }
+}
+
+impl MintMonitor {
+    pub fn from_env() -> Result<Self> {
+        env::var("MINT_MONITOR").unwrap_or_default().parse()
+    }
+
+    /// Start monitoring `mint`. The websocket monitor runs for `MINT_MONITOR_SECS` before returning.
+    pub async fn start(&self, mint: &Pubkey, notifier: &Notifier) -> Result<()> {
+        match self {
+            MintMonitor::Off => Ok(()),
+            MintMonitor::HeliusWebhook => {
+                let target = env::var("HELIUS_WEBHOOK_TARGET_URL")
+                    .map_err(|_| anyhow::anyhow!("HELIUS_WEBHOOK_TARGET_URL must be set for MINT_MONITOR=helius-webhook"))?;
+                let webhook_id = register_helius_webhook(&helius_api_key()?, mint, &target).await?;
+                notifier.notify(Notification::new(
+                    "monitor_registered",
+                    Some(mint.to_string()),
+                    format!("Helius webhook {} now forwards {} transactions to {}", webhook_id, mint, target),
+                )).await;
+                Ok(())
+            }
+            MintMonitor::Websocket => {
+                let seconds = env::var("MINT_MONITOR_SECS").ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(DEFAULT_MONITOR_SECS);
+                watch_mint_logs(&websocket_url_from_env()?, mint, notifier, Duration::from_secs(seconds)).await
+            }
+        }
+    }
+}
+
+/// `HELIUS_WEBHOOK_API_KEY`, or the `api-key` parameter of the Helius RPC URL
+fn helius_api_key() -> Result<String> {
+    if let Ok(key) = env::var("HELIUS_WEBHOOK_API_KEY") {
+        return Ok(key);
+    }
+    let rpc_url = url::Url::parse(&rpc_url_from_env()?)?;
+    rpc_url.query_pairs()
+        .find(|(name, _)| name == "api-key")
+        .map(|(_, value)| value.to_string())
+        .ok_or_else(|| anyhow::anyhow!("Set HELIUS_WEBHOOK_API_KEY or use a Helius RPC URL with an api-key parameter"))
+}
+
+/// `RPC_WS_URL`, or the RPC URL with its scheme switched to websocket
+pub fn websocket_url_from_env() -> Result<String> {
+    if let Ok(url) = env::var("RPC_WS_URL") {
+        return Ok(url);
+    }
+    if let Some(cluster) = selected_cluster()? {
+        return Ok(cluster.ws_url().to_string());
+    }
+    let rpc_url = rpc_url_from_env()?;
+    Ok(rpc_url.replacen("https://", "wss://", 1).replacen("http://", "ws://", 1))
+}
+
+/// Register a Helius enhanced webhook for `mint`, returning the webhook id
+pub async fn register_helius_webhook(api_key: &str, mint: &Pubkey, target_url: &str) -> Result<String> {
+    let response: serde_json::Value = reqwest::Client::new()
+        .post(HELIUS_WEBHOOKS_URL)
+        .query(&[("api-key", api_key)])
+        .json(&serde_json::json!({
+            "webhookURL": target_url,
+            "transactionTypes": ["ANY"],
+            "accountAddresses": [mint.to_string()],
+            "webhookType": "enhanced",
+        }))
+        .send().await?
+        .error_for_status()?
+        .json().await?;
+    let webhook_id = response["webhookID"].as_str()
+        .ok_or_else(|| anyhow::anyhow!("No webhookID in Helius response"))?;
+    info!("Registered Helius webhook {} for {}", webhook_id, mint);
+    Ok(webhook_id.to_string())
+}
+
+/// Forward every transaction mentioning `mint` to the notifier for `duration`
+pub async fn watch_mint_logs(ws_url: &str, mint: &Pubkey, notifier: &Notifier, duration: Duration) -> Result<()> {
+    let client = PubsubClient::new(ws_url).await?;
+    let (mut stream, unsubscribe) = client.logs_subscribe(
+        RpcTransactionLogsFilter::Mentions(vec![mint.to_string()]),
+        RpcTransactionLogsConfig { commitment: Some(CommitmentConfig::confirmed()) },
+    ).await?;
+    info!("Watching {} transactions for {}s", mint, duration.as_secs());
+
+    let deadline = tokio::time::sleep(duration);
+    tokio::pin!(deadline);
+    loop {
+        tokio::select! {
+            _ = &mut deadline => break,
+            update = stream.next() => {
+                let Some(update) = update else {
+                    warn!("Log subscription for {} closed", mint);
+                    break;
+                };
+                let logs = update.value;
+                let status = if logs.err.is_some() { "failed" } else { "ok" };
+                notifier.notify(Notification::new(
+                    "mint_activity",
+                    Some(mint.to_string()),
+                    format!("{} transaction {} ({})", mint, logs.signature, status),
+                ).with_data(serde_json::json!({ "signature": logs.signature, "logs": logs.logs }))).await;
+            }
+        }
+    }
+
+    unsubscribe().await;
+    Ok(())
+}
@@ -0,0 +1,179 @@
+use anyhow::Result;
+use log::{info, warn};
+use serde::Serialize;
+use std::env;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::warnings::{LaunchWarnings, WarningSeverity};
+use crate::watcher::DEFAULT_FRONTEND_API_URL;
+
+/// Similarity at or above which a recent token counts as a near-duplicate unless `NAME_COLLISION_THRESHOLD` is set
+pub const DEFAULT_COLLISION_THRESHOLD: f64 = 0.8;
+const PAGE_SIZE: usize = 50;
+/// Stop paging after this many coins even if the window is not covered yet
+const MAX_RECENT_TOKENS: usize = 2_000;
+
+/// A token from pump.fun's newest-first listing
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecentToken {
+    pub mint: String,
+    pub name: String,
+    pub symbol: String,
+    /// Milliseconds since the epoch
+    pub created_timestamp: u64,
+}
+
+/// A recent token whose name or ticker is close to the proposed one
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct NameCollision {
+    pub mint: String,
+    pub name: String,
+    pub symbol: String,
+    pub name_similarity: f64,
+    pub symbol_similarity: f64,
+}
+
+impl NameCollision {
+    pub fn score(&self) -> f64 {
+        self.name_similarity.max(self.symbol_similarity)
+    }
+}
+
+/// Tokens launched on pump.fun in the last `hours`, newest first
+pub async fn fetch_recent_tokens(hours: u64) -> Result<Vec<RecentToken>> {
+    let api_url = env::var("PUMP_FRONTEND_API_URL").unwrap_or_else(|_| DEFAULT_FRONTEND_API_URL.to_string());
+    let client = reqwest::Client::builder().timeout(Duration::from_secs(10)).build()?;
+    let now_ms = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64;
+    let cutoff = now_ms.saturating_sub(hours * 3_600_000);
+
+    let mut tokens = Vec::new();
+    while tokens.len() < MAX_RECENT_TOKENS {
+        let page: serde_json::Value = client
+            .get(format!("{}/coins?offset={}&limit={}&sort=created_timestamp&order=DESC&includeNsfw=true",
+                         api_url, tokens.len(), PAGE_SIZE))
+            .send().await?.error_for_status()?.json().await?;
+        let coins = page.as_array().cloned().unwrap_or_default();
+        let fetched = coins.len();
+        let mut reached_cutoff = false;
+        for coin in coins {
+            let created_timestamp = coin["created_timestamp"].as_u64().unwrap_or(0);
+            if created_timestamp < cutoff {
+                reached_cutoff = true;
+                break;
+            }
+            tokens.push(RecentToken {
+                mint: coin["mint"].as_str().unwrap_or_default().to_string(),
+                name: coin["name"].as_str().unwrap_or_default().to_string(),
+                symbol: coin["symbol"].as_str().unwrap_or_default().to_string(),
+                created_timestamp,
+            });
+        }
+        if reached_cutoff || fetched < PAGE_SIZE {
+            break;
+        }
+    }
+    Ok(tokens)
+}
+
+/// Lowercase alphanumerics only, so "$PEPE 2.0" and "pepe20" compare equal
+fn normalize(value: &str) -> Vec<char> {
+    value.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+/// Levenshtein distance between `a` and `b`
+pub fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// 1.0 for identical normalized strings down to 0.0 for nothing in common
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (normalize(a), normalize(b));
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 0.0;
+    }
+    1.0 - edit_distance(&a, &b) as f64 / longest as f64
+}
+
+/// Recent tokens whose name or ticker is at least `threshold` similar, closest first
+pub fn find_collisions(name: &str, symbol: &str, recent: &[RecentToken], threshold: f64) -> Vec<NameCollision> {
+    let mut collisions: Vec<NameCollision> = recent.iter()
+        .map(|token| NameCollision {
+            mint: token.mint.clone(),
+            name: token.name.clone(),
+            symbol: token.symbol.clone(),
+            name_similarity: similarity(name, &token.name),
+            symbol_similarity: similarity(symbol, &token.symbol),
+        })
+        .filter(|collision| collision.score() >= threshold)
+        .collect();
+    collisions.sort_by(|a, b| b.score().total_cmp(&a.score()));
+    collisions
+}
+
+pub fn threshold_from_env() -> Result<f64> {
+    match env::var("NAME_COLLISION_THRESHOLD") {
+        Ok(value) => match value.trim().parse::<f64>() {
+            Ok(threshold) if (0.0..=1.0).contains(&threshold) => Ok(threshold),
+            _ => Err(anyhow::anyhow!("NAME_COLLISION_THRESHOLD must be between 0 and 1, got '{}'", value)),
+        },
+        Err(_) => Ok(DEFAULT_COLLISION_THRESHOLD),
+    }
+}
+
+/// Before a launch, warn about near-duplicates from the last `NAME_COLLISION_HOURS` hours (off when unset).
+/// Never fails the launch.
+pub async fn warn_name_collisions(name: &str, symbol: &str, warnings: &LaunchWarnings) {
+    let Some(hours) = env::var("NAME_COLLISION_HOURS").ok().and_then(|h| h.trim().parse::<u64>().ok()) else {
+        return;
+    };
+    let result = async {
+        let threshold = threshold_from_env()?;
+        let recent = fetch_recent_tokens(hours).await?;
+        Ok::<_, anyhow::Error>((recent.len(), find_collisions(name, symbol, &recent, threshold)))
+    }.await;
+    match result {
+        Ok((checked, collisions)) if collisions.is_empty() => {
+            info!("No near-duplicate names among {} tokens launched in the last {}h", checked, hours);
+        }
+        Ok((_, collisions)) => {
+            for collision in collisions.iter().take(5) {
+                warnings.push(WarningSeverity::Warning, "name_collision", format!("'{}' (${}) is {:.0}% similar to {} (${}) launched in the last {}h: {}",
+                      name, symbol, collision.score() * 100.0, collision.name, collision.symbol, hours, collision.mint));
+            }
+        }
+        Err(e) => warn!("Could not check recent launches for name collisions: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_collisions() {
+        assert_eq!(edit_distance(&normalize("kitten"), &normalize("sitting")), 3);
+        assert_eq!(similarity("$PEPE 2.0", "pepe20"), 1.0);
+        assert_eq!(similarity("", ""), 0.0);
+
+        let token = |name: &str, symbol: &str| RecentToken {
+            mint: format!("{}-mint", symbol),
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            created_timestamp: 0,
+        };
+        let recent = vec![token("Moon Cat", "MCAT"), token("Moon Kat", "MKAT"), token("Doge Rocket", "DRKT")];
+        let collisions = find_collisions("Moon Cat", "MOONCAT", &recent, 0.8);
+        assert_eq!(collisions.iter().map(|c| c.symbol.as_str()).collect::<Vec<_>>(), vec!["MCAT", "MKAT"]);
+        assert_eq!(collisions[0].name_similarity, 1.0);
+    }
+}
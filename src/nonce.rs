@@ -0,0 +1,215 @@
+use anyhow::Result;
+use log::info;
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::audit_log::AuditEvent;
+use crate::create_token::{TokenCreator, SYSTEM_PROGRAM_ID};
+use crate::read_only::ensure_writable;
+
+const RECENT_BLOCKHASHES_SYSVAR_ID: &str = "SysvarRecentB1ockHashes11111111111111111111";
+// SystemInstruction variant indices
+const SYSTEM_CREATE_ACCOUNT_INDEX: u32 = 0;
+const SYSTEM_ADVANCE_NONCE_INDEX: u32 = 4;
+const SYSTEM_WITHDRAW_NONCE_INDEX: u32 = 5;
+const SYSTEM_INITIALIZE_NONCE_INDEX: u32 = 6;
+/// Size of a nonce account: version, state, authority, durable nonce and fee calculator
+pub const NONCE_ACCOUNT_SIZE: usize = 80;
+/// Where `nonce create` records the account unless `NONCE_ACCOUNT_FILE` is set
+pub const DEFAULT_NONCE_ACCOUNT_FILE: &str = "data/nonce_account.json";
+
+fn system_instruction(index: u32, payload: &[u8], accounts: Vec<AccountMeta>) -> Instruction {
+    let mut data = index.to_le_bytes().to_vec();
+    data.extend_from_slice(payload);
+    Instruction { program_id: Pubkey::from_str(SYSTEM_PROGRAM_ID).unwrap(), accounts, data }
+}
+
+fn recent_blockhashes_sysvar() -> Pubkey {
+    Pubkey::from_str(RECENT_BLOCKHASHES_SYSVAR_ID).unwrap()
+}
+
+/// Create `nonce` funded with `lamports` by `payer` and initialize it with `authority`
+pub fn create_nonce_instructions(payer: &Pubkey, nonce: &Pubkey, authority: &Pubkey, lamports: u64) -> Vec<Instruction> {
+    let mut create = lamports.to_le_bytes().to_vec();
+    create.extend_from_slice(&(NONCE_ACCOUNT_SIZE as u64).to_le_bytes());
+    create.extend_from_slice(Pubkey::from_str(SYSTEM_PROGRAM_ID).unwrap().as_ref());
+    vec![
+        system_instruction(SYSTEM_CREATE_ACCOUNT_INDEX, &create, vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*nonce, true),
+        ]),
+        system_instruction(SYSTEM_INITIALIZE_NONCE_INDEX, authority.as_ref(), vec![
+            AccountMeta::new(*nonce, false),
+            AccountMeta::new_readonly(recent_blockhashes_sysvar(), false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::rent::ID, false),
+        ]),
+    ]
+}
+
+/// Must be the first instruction of a transaction signed against the stored nonce
+pub fn advance_nonce_instruction(nonce: &Pubkey, authority: &Pubkey) -> Instruction {
+    system_instruction(SYSTEM_ADVANCE_NONCE_INDEX, &[], vec![
+        AccountMeta::new(*nonce, false),
+        AccountMeta::new_readonly(recent_blockhashes_sysvar(), false),
+        AccountMeta::new_readonly(*authority, true),
+    ])
+}
+
+/// Withdraw `lamports` from `nonce` to `to`; withdrawing the whole balance closes the account
+pub fn withdraw_nonce_instruction(nonce: &Pubkey, authority: &Pubkey, to: &Pubkey, lamports: u64) -> Instruction {
+    system_instruction(SYSTEM_WITHDRAW_NONCE_INDEX, &lamports.to_le_bytes(), vec![
+        AccountMeta::new(*nonce, false),
+        AccountMeta::new(*to, false),
+        AccountMeta::new_readonly(recent_blockhashes_sysvar(), false),
+        AccountMeta::new_readonly(solana_sdk::sysvar::rent::ID, false),
+        AccountMeta::new_readonly(*authority, true),
+    ])
+}
+
+/// Contents of an initialized nonce account
+#[derive(Debug, Clone, PartialEq)]
+pub struct NonceState {
+    pub authority: Pubkey,
+    /// Use as the recent blockhash of a durable-nonce transaction
+    pub blockhash: Hash,
+    pub lamports_per_signature: u64,
+}
+
+pub fn parse_nonce_account(data: &[u8]) -> Result<NonceState> {
+    if data.len() < NONCE_ACCOUNT_SIZE {
+        return Err(anyhow::anyhow!("Not a nonce account: {} bytes of data", data.len()));
+    }
+    let state = u32::from_le_bytes(data[4..8].try_into().unwrap());
+    if state != 1 {
+        return Err(anyhow::anyhow!("Nonce account is not initialized"));
+    }
+    Ok(NonceState {
+        authority: Pubkey::try_from(&data[8..40]).unwrap(),
+        blockhash: Hash::new_from_array(data[40..72].try_into().unwrap()),
+        lamports_per_signature: u64::from_le_bytes(data[72..80].try_into().unwrap()),
+    })
+}
+
+/// Nonce account recorded for the offline signing flow
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NonceAccountConfig {
+    pub address: String,
+    pub authority: String,
+}
+
+impl NonceAccountConfig {
+    fn path() -> String {
+        std::env::var("NONCE_ACCOUNT_FILE").unwrap_or_else(|_| DEFAULT_NONCE_ACCOUNT_FILE.to_string())
+    }
+
+    /// The recorded nonce account, if any
+    pub fn load() -> Result<Option<Self>> {
+        let path = Self::path();
+        if !Path::new(&path).exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&std::fs::read_to_string(&path)?)?))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path();
+        if let Some(parent) = Path::new(&path).parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn remove() -> Result<()> {
+        let path = Self::path();
+        if Path::new(&path).exists() {
+            std::fs::remove_file(&path)?;
+        }
+        Ok(())
+    }
+
+    pub fn address(&self) -> Result<Pubkey> {
+        Pubkey::from_str(&self.address).map_err(|e| anyhow::anyhow!("Invalid nonce account in {}: {}", Self::path(), e))
+    }
+}
+
+/// Nonce account from `NONCE_ACCOUNT`, or the one recorded by `nonce create`
+pub fn configured_nonce_account() -> Result<Option<Pubkey>> {
+    if let Ok(address) = std::env::var("NONCE_ACCOUNT") {
+        return Ok(Some(Pubkey::from_str(address.trim())
+            .map_err(|e| anyhow::anyhow!("NONCE_ACCOUNT is not a pubkey: {}", e))?));
+    }
+    NonceAccountConfig::load()?.map(|config| config.address()).transpose()
+}
+
+/// Current state of `nonce` on chain
+pub async fn fetch_nonce_state(rpc: &RpcClient, nonce: &Pubkey) -> Result<NonceState> {
+    parse_nonce_account(&rpc.get_account_data(nonce).await?)
+}
+
+/// Create a nonce account with `authority` as its authority, paid by the creator's wallet
+pub async fn create_nonce_account(creator: &TokenCreator, authority: &Pubkey, operator: Option<&str>) -> Result<(Pubkey, Signature)> {
+    ensure_writable("create a nonce account")?;
+    let nonce = Keypair::new();
+    let lamports = creator.rpc().get_minimum_balance_for_rent_exemption(NONCE_ACCOUNT_SIZE).await?;
+    let instructions = create_nonce_instructions(&creator.payer().pubkey(), &nonce.pubkey(), authority, lamports);
+    let signature = creator.send_audited(&instructions, &[&nonce], AuditEvent {
+        purpose: "nonce_create".to_string(),
+        amount_lamports: Some(lamports),
+        operator: operator.map(String::from),
+        ..Default::default()
+    }).await?;
+    info!("Created nonce account {}: {}", nonce.pubkey(), signature);
+    Ok((nonce.pubkey(), signature))
+}
+
+/// Withdraw the whole balance of `nonce` to the creator's wallet, which must be its authority, closing it
+pub async fn close_nonce_account(creator: &TokenCreator, nonce: &Pubkey, operator: Option<&str>) -> Result<Signature> {
+    ensure_writable("close the nonce account")?;
+    let authority = creator.payer().pubkey();
+    let state = fetch_nonce_state(creator.rpc(), nonce).await?;
+    if state.authority != authority {
+        return Err(anyhow::anyhow!("Nonce account {} is controlled by {}, not {}", nonce, state.authority, authority));
+    }
+    let lamports = creator.rpc().get_balance(nonce).await?;
+    let instruction = withdraw_nonce_instruction(nonce, &authority, &authority, lamports);
+    creator.send_audited(&[instruction], &[], AuditEvent {
+        purpose: "nonce_close".to_string(),
+        operator: operator.map(String::from),
+        ..Default::default()
+    }).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nonce_account_layout() {
+        let (payer, nonce, authority) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let instructions = create_nonce_instructions(&payer, &nonce, &authority, 1_447_680);
+        assert_eq!(instructions[0].data.len(), 4 + 8 + 8 + 32);
+        assert_eq!(&instructions[1].data[..4], &SYSTEM_INITIALIZE_NONCE_INDEX.to_le_bytes());
+        assert_eq!(&instructions[1].data[4..], authority.as_ref());
+        assert!(advance_nonce_instruction(&nonce, &authority).accounts[2].is_signer);
+
+        let blockhash = Hash::new_unique();
+        let mut data = vec![0u8; NONCE_ACCOUNT_SIZE];
+        data[0..4].copy_from_slice(&1u32.to_le_bytes());
+        assert!(parse_nonce_account(&data).is_err());
+        data[4..8].copy_from_slice(&1u32.to_le_bytes());
+        data[8..40].copy_from_slice(authority.as_ref());
+        data[40..72].copy_from_slice(blockhash.as_ref());
+        data[72..80].copy_from_slice(&5_000u64.to_le_bytes());
+        assert_eq!(parse_nonce_account(&data).unwrap(), NonceState { authority, blockhash, lamports_per_signature: 5_000 });
+    }
+}
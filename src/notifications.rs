@@ -0,0 +1,137 @@
+use anyhow::Result;
+use log::{info, warn};
+use serde::Serialize;
+use std::env;
+use std::time::Duration;
+
+/// Something worth telling the operator about, e.g. a launch or a trade on a launched mint
+#[derive(Debug, Clone, Serialize)]
+pub struct Notification {
+    /// Machine-readable event type, e.g. "launch_confirmed", "mint_activity"
+    pub kind: String,
+    pub mint: Option<String>,
+    pub message: String,
+    /// Event-specific details forwarded as-is to webhook sinks
+    #[serde(skip_serializing_if = "serde_json::Value::is_null")]
+    pub data: serde_json::Value,
+    /// Image sent along with the message by Discord and Telegram; webhooks only get the JSON
+    #[serde(skip)]
+    pub attachment: Option<Attachment>,
+}
+
+/// A file attached to a notification, such as a chart snapshot
+#[derive(Debug, Clone)]
+pub struct Attachment {
+    pub filename: String,
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
+impl Attachment {
+    fn part(&self) -> Result<reqwest::multipart::Part> {
+        Ok(reqwest::multipart::Part::bytes(self.data.clone())
+            .file_name(self.filename.clone())
+            .mime_str(&self.content_type)?)
+    }
+}
+
+impl Notification {
+    pub fn new(kind: impl Into<String>, mint: Option<String>, message: impl Into<String>) -> Self {
+        Self { kind: kind.into(), mint, message: message.into(), data: serde_json::Value::Null, attachment: None }
+    }
+
+    pub fn with_data(mut self, data: serde_json::Value) -> Self {
+        self.data = data;
+        self
+    }
+
+    pub fn with_attachment(mut self, attachment: Option<Attachment>) -> Self {
+        self.attachment = attachment;
+        self
+    }
+}
+
+/// Destinations notifications are delivered to
+#[derive(Debug, Clone, PartialEq)]
+pub enum NotificationSink {
+    /// POST the notification as JSON
+    Webhook(String),
+    Discord(String),
+    Telegram { bot_token: String, chat_id: String },
+}
+
+/// Fan-out of notifications to every configured sink; always logs them as well
+#[derive(Debug, Clone, Default)]
+pub struct Notifier {
+    sinks: Vec<NotificationSink>,
+}
+
+impl Notifier {
+    pub fn new(sinks: Vec<NotificationSink>) -> Self {
+        Self { sinks }
+    }
+
+    /// Sinks from `NOTIFY_WEBHOOK_URL`, `NOTIFY_DISCORD_WEBHOOK_URL` and
+    /// `NOTIFY_TELEGRAM_BOT_TOKEN` + `NOTIFY_TELEGRAM_CHAT_ID`
+    pub fn from_env() -> Self {
+        let mut sinks = Vec::new();
+        if let Ok(url) = env::var("NOTIFY_WEBHOOK_URL") {
+            sinks.push(NotificationSink::Webhook(url));
+        }
+        if let Ok(url) = env::var("NOTIFY_DISCORD_WEBHOOK_URL") {
+            sinks.push(NotificationSink::Discord(url));
+        }
+        if let (Ok(bot_token), Ok(chat_id)) = (env::var("NOTIFY_TELEGRAM_BOT_TOKEN"), env::var("NOTIFY_TELEGRAM_CHAT_ID")) {
+            sinks.push(NotificationSink::Telegram { bot_token, chat_id });
+        }
+        Self { sinks }
+    }
+
+    pub fn sinks(&self) -> &[NotificationSink] {
+        &self.sinks
+    }
+
+    /// Deliver to all sinks. Delivery failures are logged, never returned.
+    pub async fn notify(&self, notification: Notification) {
+        info!("[{}] {}", notification.kind, notification.message);
+        if self.sinks.is_empty() {
+            return;
+        }
+        let client = match reqwest::Client::builder().timeout(Duration::from_secs(10)).build() {
+            Ok(client) => client,
+            Err(e) => {
+                warn!("Cannot build HTTP client for notifications: {}", e);
+                return;
+            }
+        };
+        for sink in &self.sinks {
+            if let Err(e) = deliver(&client, sink, &notification).await {
+                warn!("Failed to deliver {} notification: {}", notification.kind, e);
+            }
+        }
+    }
+}
+
+async fn deliver(client: &reqwest::Client, sink: &NotificationSink, notification: &Notification) -> Result<()> {
+    let request = match (sink, &notification.attachment) {
+        (NotificationSink::Webhook(url), _) => client.post(url).json(notification),
+        (NotificationSink::Discord(url), None) => client.post(url).json(&serde_json::json!({ "content": notification.message })),
+        (NotificationSink::Discord(url), Some(attachment)) => client.post(url).multipart(
+            reqwest::multipart::Form::new()
+                .text("payload_json", serde_json::json!({ "content": notification.message }).to_string())
+                .part("files[0]", attachment.part()?),
+        ),
+        (NotificationSink::Telegram { bot_token, chat_id }, None) => client
+            .post(format!("https://api.telegram.org/bot{}/sendMessage", bot_token))
+            .json(&serde_json::json!({ "chat_id": chat_id, "text": notification.message })),
+        // Photo captions are limited to 1024 characters
+        (NotificationSink::Telegram { bot_token, chat_id }, Some(attachment)) => client
+            .post(format!("https://api.telegram.org/bot{}/sendPhoto", bot_token))
+            .multipart(reqwest::multipart::Form::new()
+                .text("chat_id", chat_id.clone())
+                .text("caption", notification.message.chars().take(1024).collect::<String>())
+                .part("photo", attachment.part()?)),
+    };
+    request.send().await?.error_for_status()?;
+    Ok(())
+}
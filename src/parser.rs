@@ -1,17 +1,583 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use anyhow::Result;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::time::sleep;
 
-use crate::create_token::{TokenCreator, DEFAULT_NAME_TEMPLATE, DEFAULT_DESCRIPTION_TEMPLATE, MIN_REQUIRED_LAMPORTS, LAMPORTS_PER_SOL};
-use crate::vanity_address::get_global_vanity_status;
+use crate::create_token::{TokenCreator, ProgramConfig, LaunchOptions, LaunchResult, MetadataForm, PreparedMetadata, upload_metadata_to_pumpfun, parse_link_url, rpc_url_from_env, DEFAULT_NAME_TEMPLATE, DEFAULT_DESCRIPTION_TEMPLATE, LAMPORTS_PER_SOL};
+use crate::audit_log::AuditLog;
+use crate::squads::{SquadsConfig, fetch_multisig, fetch_proposal};
+use crate::price_feed::{sol_usd_price, format_sol};
+use crate::media::{fetch_image_url, parse_upload_filename, prepare_images};
+use crate::limits::validate_token_fields;
+use crate::notifications::{Notification, Notifier};
+use crate::mint_monitor::{MintMonitor, websocket_url_from_env};
+use crate::watcher::Watcher;
+use crate::tx_journal::TxJournal;
+use crate::holders::{fetch_token_balances, fetch_wallet_tokens, build_snapshot, snapshot_to_csv};
+use crate::health::HealthChecker;
+use crate::metadata_update::update_metadata_instruction;
+use crate::read_only::{ensure_writable, init_read_only};
+use crate::buyers::first_buyers;
+use crate::funding_graph::{analyze_buyers, to_dot};
+use crate::wallet_report::build_report;
+use crate::extra_instructions::{load_instructions_file, parse_transfer, ExtraInstructions};
+use crate::pin_check::{onchain_metadata_uri, verify_launch_pins, verify_pins};
+use crate::metadata_commitment::verify_commitment;
+use crate::warnings::LaunchWarnings;
+use crate::metrics_push::{push_launch_metrics, LaunchMetrics};
+use crate::dry_run_compare::{compare_dry_runs, differences, SimulationTarget};
+use crate::launch_report::{LaunchReport, watch_launch};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use crate::pump_accounts::{BondingCurveAccount, TOKEN_UNIT};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+use crate::vanity_address::{expected_search_time, get_global_vanity_pool, get_global_vanity_status, init_global_vanity_pool, VanityAddressPool, VanityConfig, VanityPattern};
+use crate::cluster::{SolanaCluster, selected_cluster, set_cluster};
+use crate::localnet::LocalValidator;
+use crate::progress::progress;
+use crate::vanity_fleet::{run_remote_grinder, FleetKey};
+use crate::vanity_stats::StatsFile;
+use crate::vanity_store::VanityStore;
+use crate::vanity_worker::{run_worker, worker_socket_path, WorkerClient, WorkerRequest};
+use crate::wallets::{create_wallet_file, load_wallet, write_keypair_file, WalletSpec, DEFAULT_WALLETS_DIR};
+use crate::funding::FundingWallet;
+use crate::nonce::{close_nonce_account, configured_nonce_account, create_nonce_account, fetch_nonce_state, NonceAccountConfig};
+use crate::trade::{SellAmount, DEFAULT_SLIPPAGE_BPS};
+use crate::jito::{JitoConfig, DEFAULT_JITO_MAX_SLOTS};
+use crate::priority_fee::{ComputeBudget, PriorityFee};
+use crate::jupiter::Jupiter;
+use crate::name_collision::{fetch_recent_tokens, find_collisions, threshold_from_env, warn_name_collisions};
+use crate::time_lock::parse_timestamp;
+use crate::triggers::{load_triggers, serve_triggers, FiredTrigger};
+use crate::api_server::{serve_api, ApiLaunches, LaunchState, QueuedLaunch, API_KEY_VAR};
+use crate::secure_credentials::SecureApiKey;
+use crate::artifact_upload::ArtifactStore;
+use crate::launch_dir::launch_dir_root_from_env;
+use crate::discord_bot::{register_commands, DiscordConfig};
+use crate::launch_export::{LaunchExporter, LaunchRow};
+use crate::metadata_lint::{lint_metadata, Severity, TokenMetadata};
+use crate::market_data::{format_market_data, market_data, MarketData};
+use solana_commitment_config::CommitmentConfig;
 
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, subcommand_negates_reqs = true)]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Read the payer private key from stdin instead of PRIVATE_KEY (see also PRIVATE_KEY_FD)
+    #[arg(long, global = true)]
+    pub private_key_stdin: bool,
+
+    /// Read the payer keypair from a solana-keygen JSON file (or a file holding a base58 key or mnemonic) instead of PRIVATE_KEY
+    #[arg(long, global = true, value_name = "PATH", conflicts_with = "private_key_stdin")]
+    pub keypair: Option<PathBuf>,
+
+    /// Emit line-delimited JSON progress events on stderr alongside the human-readable logs
+    #[arg(long, global = true)]
+    pub progress_json: bool,
+
+    /// Use the public RPC of this cluster instead of HELIUS_API_KEY (localnet: solana-test-validator)
+    #[arg(long, global = true, value_enum)]
+    pub cluster: Option<SolanaCluster>,
+
+    /// Config file with defaults for unset environment variables (default: LAUNCHER_CONFIG or launcher.toml)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Apply this `[profiles.<NAME>]` table of the config file (default: LAUNCHER_PROFILE)
+    #[arg(long, global = true, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    #[command(flatten)]
+    pub create: CreateArgs,
+}
+
+// Commands other than the default token creation flow
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Create a token; the same as passing the creation flags without a subcommand
+    Create(CreateArgs),
+    /// Grind vanity addresses ahead of launches
+    Vanity {
+        #[command(subcommand)]
+        action: VanityCommand,
+    },
+    /// Show the payer wallet, RPC endpoint and vanity pool state
+    Status {
+        /// Print JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the SOL balance of the payer or another wallet
+    Balance {
+        /// A registered wallet name or keypair file (default: PRIVATE_KEY)
+        #[arg(long, value_name = "NAME|PATH")]
+        wallet: Option<WalletSpec>,
+    },
+    /// Fetch and display the pump.fun global config (fees, initial reserves)
+    GlobalConfig,
+    /// Upload metadata to pump.fun IPFS only and print the metadata URI
+    Upload(UploadArgs),
+    /// Print all pump.fun PDAs for a mint
+    Derive(DeriveArgs),
+    /// Simulate the same launch on several clusters or RPC providers at once and compare the results
+    CompareDryRun {
+        /// Token symbol (ticker)
+        #[arg(short, long)]
+        symbol: String,
+
+        /// Token name
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Metadata URI to put in the simulated create instruction (nothing is uploaded)
+        #[arg(long, default_value = "https://ipfs.io/ipfs/QmSimulatedLaunchMetadata")]
+        uri: String,
+
+        /// mainnet, devnet, localnet, helius (HELIUS_API_KEY) or [LABEL=]URL; at least two
+        #[arg(long = "target", value_name = "TARGET", default_values_t = ["devnet".to_string(), "mainnet".to_string()])]
+        targets: Vec<String>,
+
+        /// On-chain creator when different from the payer
+        #[arg(long)]
+        creator: Option<Pubkey>,
+
+        /// Print the outcomes as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show approval/execution status of a Squads launch proposal
+    SquadsStatus {
+        /// Squads multisig address
+        #[arg(long)]
+        multisig: Pubkey,
+
+        /// Transaction index of the proposal
+        #[arg(long)]
+        index: u64,
+
+        /// Keep polling until the proposal is executed, rejected or cancelled
+        #[arg(long)]
+        wait: bool,
+    },
+    /// Notify when launched mints reach King of the Hill or trending on pump.fun
+    Watch {
+        /// Mints to watch (defaults to every confirmed launch in the transaction journal)
+        mints: Vec<Pubkey>,
+
+        /// Seconds between polls
+        #[arg(long, default_value_t = 30)]
+        interval: u64,
+
+        /// Stop after this many seconds (runs until interrupted when omitted)
+        #[arg(long)]
+        duration: Option<u64>,
+    },
+    /// Snapshot the holders of a mint to CSV
+    Holders {
+        /// Token mint address
+        mint: Pubkey,
+
+        /// Skip holders with less than this many tokens
+        #[arg(long, default_value_t = 0.0)]
+        min_balance: f64,
+
+        /// Output CSV file (printed to stdout when omitted)
+        #[arg(long)]
+        out: Option<String>,
+    },
+    /// Report the first buyers after creation, flagging likely snipers and bundlers
+    FirstBuyers {
+        /// Token mint address
+        mint: Pubkey,
+
+        /// Number of buys to report
+        #[arg(long, default_value_t = 20)]
+        count: usize,
+
+        /// Give up when the bonding curve has more transactions than this
+        #[arg(long, default_value_t = 20_000)]
+        max_signatures: usize,
+
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Grind vanity addresses in this process and serve them to launchers over a unix socket
+    VanityWorker {
+        /// Socket path (default: VANITY_WORKER_SOCKET or data/vanity-worker.sock)
+        #[arg(long)]
+        socket: Option<String>,
+
+        /// Also accept encrypted submissions from remote grinders on this TCP address (e.g. 0.0.0.0:7070)
+        #[arg(long)]
+        listen: Option<String>,
+    },
+    /// Grind vanity addresses on this machine and submit them to a remote vanity worker
+    VanityGrind {
+        /// Address of the central worker's fleet listener (host:port)
+        #[arg(long)]
+        submit_to: String,
+
+        /// Name this machine reports with its submissions (default: hostname)
+        #[arg(long)]
+        worker_id: Option<String>,
+    },
+    /// Show lifetime vanity grinding statistics from VANITY_STATS_FILE
+    VanityStats {
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+
+        /// Print Prometheus text format (e.g. for the node_exporter textfile collector)
+        #[arg(long, conflicts_with = "json")]
+        prometheus: bool,
+    },
+    /// Run the full launch against a fresh solana-test-validator with pump.fun cloned from mainnet
+    LocalnetTest {
+        #[command(flatten)]
+        create: CreateArgs,
+
+        /// Ledger directory for the test validator (reset on every run)
+        #[arg(long, default_value = "data/test-ledger")]
+        ledger: String,
+
+        /// SOL airdropped to the payer before launching
+        #[arg(long, default_value_t = 10.0)]
+        sol: f64,
+    },
+    /// Launch a token, then stream its trades and write a launch report with the session summary
+    LaunchAndWatch {
+        #[command(flatten)]
+        create: CreateArgs,
+
+        /// Seconds to watch trades after the launch confirms
+        #[arg(long, default_value_t = 600)]
+        duration: u64,
+
+        /// Sign the launch manifest in the report with the creator wallet
+        #[arg(long)]
+        sign_receipt: bool,
+    },
+    /// Run as a daemon that launches stored drafts when signed webhook triggers arrive
+    Daemon {
+        /// Address to accept trigger webhooks and Discord interactions on
+        #[arg(long, default_value = "127.0.0.1:8787")]
+        listen: String,
+
+        /// Register the Discord slash commands before starting (needs DISCORD_BOT_TOKEN)
+        #[arg(long)]
+        register_discord_commands: bool,
+
+        /// Start in read-only mode (see LAUNCHER_READ_ONLY and /admin/read-only)
+        #[arg(long)]
+        read_only: bool,
+    },
+    /// Serve the launcher as an HTTP API (POST /tokens, GET /tokens/<id>, /vanity/status, /wallet/balance)
+    Serve {
+        /// Address to serve the API on
+        #[arg(long, default_value = "127.0.0.1:8788")]
+        listen: String,
+
+        /// Start in read-only mode (see LAUNCHER_READ_ONLY and /admin/read-only)
+        #[arg(long)]
+        read_only: bool,
+    },
+    /// List past launches from the reports in LAUNCH_REPORT_DIR, newest first
+    History {
+        /// Show only the most recent launches
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+
+        /// Add price, liquidity and 24h volume of migrated tokens (MARKET_DATA_SOURCE)
+        #[arg(long)]
+        market_data: bool,
+
+        /// Print the launches as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// List the tokens a wallet holds with their bonding curve progress or migration state
+    Portfolio {
+        /// A registered wallet name or keypair file (default: PRIVATE_KEY)
+        #[arg(long, value_name = "NAME|PATH")]
+        wallet: Option<WalletSpec>,
+
+        /// Add price, liquidity and 24h volume of migrated tokens (MARKET_DATA_SOURCE)
+        #[arg(long)]
+        market_data: bool,
+
+        /// Print the holdings as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Check the signed receipt of a launch report
+    VerifyReceipt {
+        /// Launch report JSON
+        path: String,
+    },
+    /// Cluster early buyers of a mint by common funding sources and transfer links
+    FundingGraph {
+        /// Token mint address
+        mint: Pubkey,
+
+        /// Number of early buyers to analyze
+        #[arg(long, default_value_t = 50)]
+        buyers: usize,
+
+        /// Output format: json or dot
+        #[arg(long, default_value = "json")]
+        format: String,
+
+        /// Output file (printed to stdout when omitted)
+        #[arg(long)]
+        out: Option<String>,
+    },
+    /// Check that a launched token's metadata and image are retrievable from public IPFS gateways
+    VerifyPins {
+        /// Token mint address
+        mint: Pubkey,
+
+        /// Also pin both CIDs to the PINATA_JWT account
+        #[arg(long)]
+        repin: bool,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Check a launch's metadata hash memo against the metadata its mint points at now
+    VerifyMetadata {
+        /// Token mint address
+        mint: Pubkey,
+
+        /// Signature of the launch transaction
+        signature: String,
+
+        /// Print the result as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Summarize wallets' age, balance history, pump.fun creations and recent activity
+    WalletReport {
+        /// Addresses, registered wallet names or keypair files (default: the PRIVATE_KEY wallet)
+        wallets: Vec<String>,
+
+        /// Stop reading history after this many transactions per wallet
+        #[arg(long, default_value_t = 5_000)]
+        max_signatures: usize,
+
+        /// Print the reports as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Quote a buy or sell of any pump.fun token (bonding curve, or PumpSwap once migrated)
+    Quote {
+        /// Token mint address
+        mint: Pubkey,
+
+        /// Quote buying with this much SOL
+        #[arg(long, conflicts_with = "tokens", required_unless_present = "tokens")]
+        sol: Option<f64>,
+
+        /// Quote selling this many tokens
+        #[arg(long)]
+        tokens: Option<f64>,
+
+        /// Allowed price movement in basis points
+        #[arg(long, default_value_t = DEFAULT_SLIPPAGE_BPS)]
+        slippage_bps: u64,
+
+        /// Quote through the Jupiter aggregator instead of the bonding curve / PumpSwap pool
+        #[arg(long)]
+        jupiter: bool,
+    },
+    /// Buy a token with SOL from the wallet, on its bonding curve or its PumpSwap pool once migrated
+    Buy {
+        /// Token mint address
+        mint: Pubkey,
+
+        /// SOL to spend
+        #[arg(long)]
+        sol: f64,
+
+        /// Allowed price movement in basis points
+        #[arg(long, default_value_t = DEFAULT_SLIPPAGE_BPS)]
+        slippage_bps: u64,
+
+        /// A registered wallet name or keypair file (default: PRIVATE_KEY)
+        #[arg(long, value_name = "NAME|PATH")]
+        wallet: Option<WalletSpec>,
+
+        /// Who is trading, recorded in the audit log (default: LAUNCHER_OPERATOR)
+        #[arg(long, value_name = "NAME")]
+        operator: Option<String>,
+    },
+    /// Change the name or metadata URI of a token whose Metaplex update authority is the wallet
+    UpdateMetadata {
+        /// Token mint address
+        mint: Pubkey,
+
+        /// New metadata JSON URI
+        #[arg(long, required_unless_present = "name")]
+        uri: Option<String>,
+
+        /// New token name
+        #[arg(long)]
+        name: Option<String>,
+
+        /// A registered wallet name or keypair file (default: PRIVATE_KEY)
+        #[arg(long, value_name = "NAME|PATH")]
+        wallet: Option<WalletSpec>,
+
+        /// Who is making the change, recorded in the audit log (default: LAUNCHER_OPERATOR)
+        #[arg(long, value_name = "NAME")]
+        operator: Option<String>,
+    },
+    /// Check that the mint, freeze and metadata update authorities of a launched token are held by
+    /// the pump.fun program, recording the result in the token's launch report when there is one
+    VerifyAuthorities {
+        /// Token mint address
+        mint: Pubkey,
+
+        /// Print the result as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Sell tokens from the wallet back to the bonding curve, or to the PumpSwap pool once migrated
+    Sell {
+        /// Token mint address
+        mint: Pubkey,
+
+        /// Tokens to sell
+        #[arg(long, required_unless_present = "all", conflicts_with = "all")]
+        tokens: Option<f64>,
+
+        /// Sell the wallet's whole balance
+        #[arg(long)]
+        all: bool,
+
+        /// Allowed price movement in basis points
+        #[arg(long, default_value_t = DEFAULT_SLIPPAGE_BPS)]
+        slippage_bps: u64,
+
+        /// A registered wallet name or keypair file (default: PRIVATE_KEY)
+        #[arg(long, value_name = "NAME|PATH")]
+        wallet: Option<WalletSpec>,
+
+        /// Who is trading, recorded in the audit log (default: LAUNCHER_OPERATOR)
+        #[arg(long, value_name = "NAME")]
+        operator: Option<String>,
+    },
+    /// Check token metadata against pump.fun limits and for suspicious content, without network calls
+    Lint(LintArgs),
+    /// Compare a proposed name/ticker with tokens launched recently and list near-duplicates
+    NameCheck {
+        /// Proposed token symbol (ticker)
+        #[arg(short, long)]
+        symbol: String,
+
+        /// Proposed token name (defaults to the symbol)
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// How far back to look, in hours
+        #[arg(long, default_value_t = 24)]
+        hours: u64,
+
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Validate a folder of artwork ahead of a batch launch and report which files need fixing
+    PrepareImages {
+        /// Directory of token images
+        dir: String,
+
+        /// Where normalized copies of fixable images are written (default: <DIR>/prepared)
+        #[arg(long)]
+        out_dir: Option<String>,
+
+        /// Report file (default: <DIR>/image-report.json)
+        #[arg(long)]
+        report: Option<String>,
+    },
+    /// Request a devnet/testnet airdrop to the configured wallet
+    Airdrop {
+        /// Amount of SOL to request
+        #[arg(long, default_value_t = 1.0)]
+        sol: f64,
+    },
+    /// Generate a new keypair file under WALLETS_DIR, optionally funding it
+    NewWallet {
+        /// File name (without .json) of the new keypair
+        name: String,
+
+        /// Transfer this much SOL to the new wallet
+        #[arg(long, value_name = "SOL")]
+        fund: Option<f64>,
+
+        /// Wallet paying for --fund: a registered name or keypair file (default: PRIVATE_KEY)
+        #[arg(long, value_name = "NAME|PATH", requires = "fund")]
+        from: Option<WalletSpec>,
+    },
+    /// Manage the durable nonce account used for offline-signed launches
+    Nonce {
+        #[command(subcommand)]
+        action: NonceCommand,
+    },
+    /// Verify the hash chain of the audit log
+    AuditVerify {
+        /// Audit log file (defaults to AUDIT_LOG_PATH)
+        #[arg(long)]
+        path: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum VanityCommand {
+    /// Grind addresses into the vanity store (VANITY_STORE_SECRET) and/or keypair files
+    Generate {
+        /// Number of addresses to grind
+        #[arg(long, default_value_t = 1)]
+        count: usize,
+
+        /// Required address prefix (default: VANITY_PREFIX)
+        #[arg(long)]
+        prefix: Option<String>,
+
+        /// Required address suffix (default: VANITY_SUFFIX or "pump")
+        #[arg(long)]
+        suffix: Option<String>,
+
+        /// Match the prefix and suffix regardless of case
+        #[arg(long)]
+        ignore_case: bool,
+
+        /// Also write each keypair to <DIR>/<address>.json
+        #[arg(long, value_name = "DIR")]
+        out_dir: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum NonceCommand {
+    /// Create a nonce account with the payer as authority and record it in NONCE_ACCOUNT_FILE
+    Create,
+    /// Show the configured nonce account and its current nonce
+    Show,
+    /// Withdraw the nonce account's balance to the payer and forget it
+    Close,
+}
+
+/// Token fields and launch options of the default creation flow
+#[derive(clap::Args, Debug, Clone)]
+pub struct CreateArgs {
     /// Token symbol (ticker)
-    #[arg(short, long)]
-    pub symbol: String,
+    #[arg(short, long, required = true)]
+    pub symbol: Option<String>,
 
     /// Token name
     #[arg(short, long)]
@@ -25,17 +591,296 @@ pub struct Args {
     #[arg(short, long)]
     pub image: Option<String>,
 
+    /// Download the token image from this URL (cached locally) instead of using --image
+    #[arg(long, value_name = "URL", conflicts_with = "image")]
+    pub image_url: Option<String>,
+
     /// Don't wait for vanity address (launch immediately)
     #[arg(long)]
     pub no_vanity: bool,
+
+    /// Grind mint addresses starting with this (default: VANITY_PREFIX)
+    #[arg(long, value_name = "BASE58")]
+    pub vanity_prefix: Option<String>,
+
+    /// Grind mint addresses ending with this (default: VANITY_SUFFIX, or pump when no prefix or suffix is set)
+    #[arg(long, value_name = "BASE58")]
+    pub vanity_suffix: Option<String>,
+
+    /// Match the vanity prefix and suffix in any letter case (default: VANITY_IGNORE_CASE)
+    #[arg(long)]
+    pub vanity_ignore_case: bool,
+
+    /// Write the signed transaction (base64 and decoded listing) to this file before sending
+    #[arg(long, value_name = "PATH")]
+    pub dump_tx: Option<String>,
+
+    #[command(flatten)]
+    pub metadata: MetadataFormArgs,
+
+    /// Pay for this launch with a registered wallet (`WALLET_<NAME>`) or a keypair file instead of PRIVATE_KEY
+    #[arg(long, value_name = "NAME|PATH")]
+    pub wallet: Option<WalletSpec>,
+
+    /// On-chain creator (receives creator fees) when it should differ from the paying wallet
+    #[arg(long, value_name = "PUBKEY", value_parser = parse_creator, conflicts_with = "squads_multisig")]
+    pub creator: Option<Pubkey>,
+
+    /// Commitment the launch must reach before success is reported and post-launch actions run (default: LAUNCH_CONFIRMATION or confirmed)
+    #[arg(long, value_name = "LEVEL", value_parser = ["processed", "confirmed", "finalized"])]
+    pub confirmation: Option<String>,
+
+    /// Refuse to send the launch before this time (Unix seconds or RFC 3339; default: LAUNCH_NOT_BEFORE)
+    #[arg(long, value_name = "TIME", value_parser = parse_not_before)]
+    pub not_before: Option<SystemTime>,
+
+    /// Launch even if this wallet is still within its LAUNCH_COOLDOWN_MINUTES cooldown
+    #[arg(long)]
+    pub ignore_cooldown: bool,
+
+    /// Show the collected warnings and ask for confirmation before sending the launch
+    #[arg(long)]
+    pub confirm: bool,
+
+    /// Append an SPL memo signed by the payer to the launch transaction
+    #[arg(long, value_name = "TEXT")]
+    pub memo: Option<String>,
+
+    /// Append a SOL transfer from the payer, e.g. a tip (repeatable)
+    #[arg(long = "transfer", value_name = "PUBKEY=SOL", value_parser = parse_transfer)]
+    pub transfers: Vec<(Pubkey, u64)>,
+
+    /// Append instructions from a JSON file (program_id, accounts, base64 data)
+    #[arg(long, value_name = "PATH")]
+    pub extra_instructions: Option<String>,
+
+    /// Commit to the uploaded metadata with a memo holding its SHA-256 (default: COMMIT_METADATA_HASH)
+    #[arg(long)]
+    pub commit_metadata_hash: bool,
+
+    /// Buy the new token with this much SOL from the paying wallet in the create transaction
+    #[arg(long, value_name = "SOL", value_parser = parse_dev_buy, conflicts_with = "squads_multisig")]
+    pub dev_buy: Option<u64>,
+
+    /// Allowed price movement of the dev buy in basis points
+    #[arg(long, default_value_t = DEFAULT_SLIPPAGE_BPS, requires = "dev_buy")]
+    pub dev_buy_slippage_bps: u64,
+
+    /// Submit the launch as a Jito bundle with a tip, falling back to RPC if it does not land
+    #[arg(long, conflicts_with = "squads_multisig")]
+    pub jito: bool,
+
+    /// Jito tip in SOL (default: JITO_TIP_SOL or 0.001)
+    #[arg(long, value_name = "SOL", requires = "jito")]
+    pub jito_tip: Option<f64>,
+
+    /// Block engine region (mainnet, amsterdam, frankfurt, london, ny, slc, singapore, tokyo) or URL (default: JITO_BLOCK_ENGINE or mainnet)
+    #[arg(long, value_name = "REGION|URL", requires = "jito")]
+    pub jito_block_engine: Option<String>,
+
+    /// Slots to wait for the bundle before sending the same transaction through RPC
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_JITO_MAX_SLOTS, requires = "jito")]
+    pub jito_max_slots: u64,
+
+    /// Priority fee in micro-lamports per compute unit, or 'auto' to pay a percentile of recent fees (default: PRIORITY_FEE or auto)
+    #[arg(long, value_name = "MICROLAMPORTS|auto")]
+    pub priority_fee: Option<PriorityFee>,
+
+    /// Compute unit limit requested for the launch transaction (default: COMPUTE_UNIT_LIMIT or 300000)
+    #[arg(long, value_name = "N")]
+    pub compute_units: Option<u32>,
+
+    /// Send the launch without simulating it first
+    #[arg(long)]
+    pub skip_simulation: bool,
+
+    /// Directory under which each launch gets its own artifacts directory (default: LAUNCH_DIR_ROOT or launches)
+    #[arg(long, value_name = "PATH")]
+    pub launch_dir_root: Option<PathBuf>,
+
+    /// Do not create a per-launch artifacts directory
+    #[arg(long, conflicts_with = "launch_dir_root")]
+    pub no_launch_dir: bool,
+
+    /// Who is launching, recorded in the audit log, launch directory, export and report (default: LAUNCHER_OPERATOR)
+    #[arg(long, value_name = "NAME")]
+    pub operator: Option<String>,
+
+    /// Fingerprint of the API key a `serve` launch was requested with
+    #[arg(skip)]
+    pub api_key_id: Option<String>,
+
+    /// Retry up to N times with a fresh mint (reusing the uploaded metadata) when the launch transaction fails irrecoverably
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    pub auto_retry: u32,
+
+    /// Propose the launch to this Squads multisig (vault pays and is the creator) instead of sending it
+    #[arg(long, value_name = "MULTISIG")]
+    pub squads_multisig: Option<Pubkey>,
+
+    /// Squads vault index used as payer/creator
+    #[arg(long, default_value_t = 0)]
+    pub squads_vault_index: u8,
+}
+
+/// Metadata to lint: a metadata JSON file, individual fields, or both (flags win)
+#[derive(clap::Args, Debug)]
+pub struct LintArgs {
+    /// pump.fun metadata JSON (name, symbol, description, image, twitter, telegram, website)
+    #[arg(long, value_name = "PATH")]
+    pub metadata_file: Option<String>,
+
+    /// Token symbol (ticker)
+    #[arg(short, long)]
+    pub symbol: Option<String>,
+
+    /// Token name
+    #[arg(short, long)]
+    pub name: Option<String>,
+
+    /// Token description
+    #[arg(short, long)]
+    pub description: Option<String>,
+
+    /// Image path or URL
+    #[arg(short, long)]
+    pub image: Option<String>,
+
+    /// Twitter/X link
+    #[arg(long, value_name = "URL", value_parser = parse_link_url_arg)]
+    pub twitter: Option<String>,
+
+    /// Telegram link
+    #[arg(long, value_name = "URL", value_parser = parse_link_url_arg)]
+    pub telegram: Option<String>,
+
+    /// Website link
+    #[arg(long, value_name = "URL", value_parser = parse_link_url_arg)]
+    pub website: Option<String>,
+
+    /// Print JSON instead of a list
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct DeriveArgs {
+    /// Token mint address
+    pub mint: Pubkey,
+
+    /// Creator used for the creator vault PDA (read from the bonding curve when omitted)
+    #[arg(long)]
+    pub creator: Option<Pubkey>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct UploadArgs {
+    /// Token symbol (ticker)
+    #[arg(short, long)]
+    pub symbol: String,
+
+    /// Token name
+    #[arg(short, long)]
+    pub name: Option<String>,
+
+    /// Token description
+    #[arg(short, long)]
+    pub description: Option<String>,
+
+    /// Path to token image
+    #[arg(short, long)]
+    pub image: Option<String>,
+
+    /// Download the token image from this URL (cached locally) instead of using --image
+    #[arg(long, value_name = "URL", conflicts_with = "image")]
+    pub image_url: Option<String>,
+
+    #[command(flatten)]
+    pub metadata: MetadataFormArgs,
+}
+
+/// Coin page toggles and links; unset flags fall back to the `METADATA_*` environment variables
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct MetadataFormArgs {
+    /// Show the token name on the coin page
+    #[arg(long, value_name = "BOOL")]
+    pub show_name: Option<bool>,
+
+    /// Site recorded as where the coin was created
+    #[arg(long, value_name = "URL", value_parser = parse_link_url_arg)]
+    pub created_on: Option<String>,
+
+    /// Twitter/X link shown on the coin page
+    #[arg(long, value_name = "URL", value_parser = parse_link_url_arg)]
+    pub twitter: Option<String>,
+
+    /// Telegram link shown on the coin page
+    #[arg(long, value_name = "URL", value_parser = parse_link_url_arg)]
+    pub telegram: Option<String>,
+
+    /// Website link shown on the coin page
+    #[arg(long, value_name = "URL", value_parser = parse_link_url_arg)]
+    pub website: Option<String>,
+
+    /// Filename sent with the image or video (default: the source file's name, with the extension of its content)
+    #[arg(long, value_name = "NAME", value_parser = parse_upload_filename_arg)]
+    pub upload_filename: Option<String>,
+}
+
+impl MetadataFormArgs {
+    pub fn to_form(&self) -> Result<MetadataForm> {
+        let mut form = MetadataForm::from_env()?;
+        if let Some(show_name) = self.show_name {
+            form.show_name = show_name;
+        }
+        for (flag, field) in [
+            (&self.created_on, &mut form.created_on),
+            (&self.twitter, &mut form.twitter),
+            (&self.telegram, &mut form.telegram),
+            (&self.website, &mut form.website),
+        ] {
+            if let Some(value) = flag {
+                *field = value.clone();
+            }
+        }
+        if let Some(name) = &self.upload_filename {
+            form.upload_filename = Some(name.clone());
+        }
+        Ok(form)
+    }
 }
 
 impl Args {
+    /// Whether this invocation launches a token (default flow, `create`, `launch-and-watch` or `localnet-test`)
+    pub fn is_launch(&self) -> bool {
+        matches!(self.command, None | Some(Command::Create(_)) | Some(Command::LaunchAndWatch { .. }) | Some(Command::LocalnetTest { .. }))
+    }
+
+    /// Token fields of the launch this invocation runs, if any
+    pub fn create_args(&self) -> Option<&CreateArgs> {
+        match &self.command {
+            None => Some(&self.create),
+            Some(Command::Create(create)) | Some(Command::LaunchAndWatch { create, .. }) | Some(Command::LocalnetTest { create, .. }) => Some(create),
+            _ => None,
+        }
+    }
+}
+
+impl CreateArgs {
+    /// Vanity pattern from the flags, falling back to the environment
+    pub fn vanity_pattern(&self) -> Result<VanityPattern> {
+        VanityPattern::resolve(self.vanity_prefix.clone(), self.vanity_suffix.clone(), self.vanity_ignore_case)
+    }
+
+    pub fn get_symbol(&self) -> String {
+        self.symbol.clone().unwrap_or_default()
+    }
+
     pub fn get_token_name(&self) -> String {
         if let Some(name) = &self.name {
             name.clone()
         } else {
-            DEFAULT_NAME_TEMPLATE.replace("{}", &self.symbol.to_uppercase())
+            DEFAULT_NAME_TEMPLATE.replace("{}", &self.get_symbol().to_uppercase())
         }
     }
 
@@ -43,49 +888,1233 @@ impl Args {
         if let Some(description) = &self.description {
             description.clone()
         } else {
-            DEFAULT_DESCRIPTION_TEMPLATE.replace("{}", &self.symbol.to_uppercase())
+            DEFAULT_DESCRIPTION_TEMPLATE.replace("{}", &self.get_symbol().to_uppercase())
         }
     }
 
     pub fn get_image_path(&self) -> Option<String> {
         self.image.clone()
     }
+
+    pub fn get_launch_options(&self) -> Result<LaunchOptions> {
+        Ok(LaunchOptions {
+            dump_tx_path: self.dump_tx.clone(),
+            auto_retry: self.auto_retry,
+            creator: self.creator,
+            metadata: self.metadata.to_form()?,
+            confirmation: self.confirmation.clone()
+                .or_else(|| std::env::var("LAUNCH_CONFIRMATION").ok())
+                .map(|level| level.trim().to_lowercase().parse::<CommitmentConfig>()
+                    .map_err(|_| anyhow::anyhow!("Invalid confirmation level '{}' (expected processed, confirmed or finalized)", level)))
+                .transpose()?,
+            not_before: match self.not_before {
+                Some(time) => Some(time),
+                None => std::env::var("LAUNCH_NOT_BEFORE").ok().map(|v| parse_timestamp(&v)).transpose()?,
+            },
+            ignore_cooldown: self.ignore_cooldown,
+            extra_instructions: ExtraInstructions {
+                memo: self.memo.clone(),
+                transfers: self.transfers.clone(),
+                instructions: self.extra_instructions.as_deref().map(load_instructions_file).transpose()?.unwrap_or_default(),
+            },
+            commit_metadata_hash: self.commit_metadata_hash
+                || std::env::var("COMMIT_METADATA_HASH").map(|v| v.trim().eq_ignore_ascii_case("true")).unwrap_or(false),
+            confirm: self.confirm,
+            warnings: LaunchWarnings::default(),
+            dev_buy_lamports: self.dev_buy,
+            dev_buy_slippage_bps: self.dev_buy_slippage_bps,
+            jito: self.jito
+                .then(|| JitoConfig::resolve(self.jito_block_engine.as_deref(), self.jito_tip, self.jito_max_slots))
+                .transpose()?,
+            compute_budget: Some(ComputeBudget::resolve(self.priority_fee, self.compute_units)?),
+            skip_simulation: self.skip_simulation,
+            launch_dir_root: (!self.no_launch_dir).then(|| self.launch_dir_root.clone().unwrap_or_else(launch_dir_root_from_env)),
+            operator: self.get_operator(),
+            api_key_id: self.api_key_id.clone(),
+        })
+    }
+
+    /// `--operator`, or `LAUNCHER_OPERATOR`
+    pub fn get_operator(&self) -> Option<String> {
+        resolve_operator(self.operator.clone())
+    }
 }
 
-pub async fn handle_token_creation(args: Args) -> Result<()> {
-    log::info!("Processing token creation...");
-    
-    // Validate symbol length (Metaplex symbol limit is typically 10 characters)
-    if args.symbol.len() > 10 {
-        return Err(anyhow::anyhow!("Symbol '{}' is too long. Maximum 10 characters allowed.", args.symbol));
+/// An `--operator` flag, or `LAUNCHER_OPERATOR`
+pub fn resolve_operator(flag: Option<String>) -> Option<String> {
+    flag.or_else(|| std::env::var("LAUNCHER_OPERATOR").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+fn parse_not_before(value: &str) -> Result<SystemTime, String> {
+    parse_timestamp(value).map_err(|e| e.to_string())
+}
+
+fn parse_upload_filename_arg(value: &str) -> Result<String, String> {
+    parse_upload_filename(value).map_err(|e| e.to_string())
+}
+
+fn parse_link_url_arg(value: &str) -> Result<String, String> {
+    parse_link_url(value).map_err(|e| e.to_string())
+}
+
+/// `--dev-buy` SOL amount as lamports
+fn parse_dev_buy(value: &str) -> Result<u64, String> {
+    match value.trim().parse::<f64>() {
+        Ok(sol) if sol.is_finite() && sol > 0.0 => Ok((sol * LAMPORTS_PER_SOL) as u64),
+        _ => Err(format!("expected a positive SOL amount, got '{}'", value)),
     }
-    
+}
+
+/// A creator must be a real wallet; the default (system program) address would lock creator fees away
+fn parse_creator(value: &str) -> Result<Pubkey, String> {
+    let creator = value.trim().parse::<Pubkey>().map_err(|e| format!("invalid pubkey '{}': {}", value, e))?;
+    if creator == Pubkey::default() {
+        return Err("creator cannot be the default (all-zero) address".to_string());
+    }
+    Ok(creator)
+}
+
+/// Dispatch parsed arguments to the requested command (token creation by default)
+pub async fn handle_command(args: Args) -> Result<()> {
+    match args.command {
+        Some(Command::GlobalConfig) => handle_global_config().await,
+        Some(Command::Upload(upload_args)) => handle_upload(upload_args).await,
+        Some(Command::Derive(derive_args)) => handle_derive(derive_args).await,
+        Some(Command::AuditVerify { path }) => handle_audit_verify(path),
+        Some(Command::Airdrop { sol }) => handle_airdrop(sol, None).await,
+        Some(Command::NewWallet { name, fund, from }) => handle_new_wallet(&name, fund, from.as_ref()).await,
+        Some(Command::Nonce { action }) => handle_nonce(action).await,
+        Some(Command::Lint(lint_args)) => handle_lint(lint_args),
+        Some(Command::NameCheck { symbol, name, hours, json }) => handle_name_check(&symbol, name, hours, json).await,
+        Some(Command::PrepareImages { dir, out_dir, report }) => handle_prepare_images(&dir, out_dir, report),
+        Some(Command::Quote { mint, sol, tokens, slippage_bps, jupiter }) => handle_quote(mint, sol, tokens, slippage_bps, jupiter).await,
+        Some(Command::Buy { mint, sol, slippage_bps, wallet, operator }) => handle_buy(mint, sol, slippage_bps, wallet.as_ref(), resolve_operator(operator)).await,
+        Some(Command::UpdateMetadata { mint, uri, name, wallet, operator }) => handle_update_metadata(mint, uri, name, wallet.as_ref(), resolve_operator(operator)).await,
+        Some(Command::VerifyAuthorities { mint, json }) => handle_verify_authorities(mint, json).await,
+        Some(Command::Sell { mint, tokens, all, slippage_bps, wallet, operator }) => handle_sell(mint, tokens, all, slippage_bps, wallet.as_ref(), resolve_operator(operator)).await,
+        Some(Command::VanityWorker { socket, listen }) => {
+            let socket = socket.unwrap_or_else(worker_socket_path);
+            tokio::task::spawn_blocking(move || run_worker(&socket, listen.as_deref())).await?
+        }
+        Some(Command::VanityGrind { submit_to, worker_id }) => {
+            let key = FleetKey::from_env()?;
+            let worker_id = worker_id
+                .or_else(|| std::env::var("HOSTNAME").ok())
+                .unwrap_or_else(|| "grinder".to_string());
+            tokio::task::spawn_blocking(move || run_remote_grinder(&submit_to, &worker_id, &key)).await?
+        }
+        Some(Command::VanityStats { json, prometheus }) => handle_vanity_stats(json, prometheus),
+        Some(Command::LocalnetTest { create, ledger, sol }) => handle_localnet_test(create, &ledger, sol).await,
+        Some(Command::LaunchAndWatch { create, duration, sign_receipt }) => handle_launch_and_watch(create, duration, sign_receipt).await,
+        Some(Command::Daemon { listen, register_discord_commands, read_only }) => {
+            init_read_only(read_only);
+            handle_daemon(&listen, register_discord_commands).await
+        }
+        Some(Command::Serve { listen, read_only }) => {
+            init_read_only(read_only);
+            handle_serve(&listen).await
+        }
+        Some(Command::VerifyReceipt { path }) => handle_verify_receipt(&path),
+        Some(Command::History { limit, market_data, json }) => handle_history(limit, market_data, json).await,
+        Some(Command::Portfolio { wallet, market_data, json }) => handle_portfolio(wallet.as_ref(), market_data, json).await,
+        Some(Command::FundingGraph { mint, buyers, format, out }) => handle_funding_graph(mint, buyers, &format, out).await,
+        Some(Command::VerifyPins { mint, repin, json }) => handle_verify_pins(mint, repin, json).await,
+        Some(Command::CompareDryRun { symbol, name, uri, targets, creator, json }) => handle_compare_dry_run(symbol, name, uri, &targets, creator, json).await,
+        Some(Command::VerifyMetadata { mint, signature, json }) => handle_verify_metadata(mint, &signature, json).await,
+        Some(Command::WalletReport { wallets, max_signatures, json }) => handle_wallet_report(&wallets, max_signatures, json).await,
+        Some(Command::FirstBuyers { mint, count, max_signatures, json }) => handle_first_buyers(mint, count, max_signatures, json).await,
+        Some(Command::Holders { mint, min_balance, out }) => handle_holders(mint, min_balance, out).await,
+        Some(Command::Watch { mints, interval, duration }) => handle_watch(mints, interval, duration).await,
+        Some(Command::SquadsStatus { multisig, index, wait }) => handle_squads_status(multisig, index, wait).await,
+        Some(Command::Create(create)) => handle_token_creation(create).await.map(|_| ()),
+        Some(Command::Vanity { action: VanityCommand::Generate { count, prefix, suffix, ignore_case, out_dir } }) => {
+            let pattern = VanityPattern::resolve(prefix, suffix, ignore_case)?;
+            tokio::task::spawn_blocking(move || handle_vanity_generate(count, &pattern, out_dir.as_deref())).await?
+        }
+        Some(Command::Status { json }) => handle_status(json).await,
+        Some(Command::Balance { wallet }) => handle_balance(wallet.as_ref()).await,
+        None => handle_token_creation(args.create).await.map(|_| ()),
+    }
+}
+
+pub async fn handle_global_config() -> Result<()> {
+    let creator = TokenCreator::new()?;
+    let global = creator.fetch_global_account().await?;
+    let version = creator.resolve_program_version().await?;
+
+    println!("pump.fun global config ({})", creator.get_global_pda());
+    println!("  Program version:                {:?}", version);
+    println!("  Authority:                      {}", global.authority);
+    println!("  Fee recipient:                  {}", global.fee_recipient);
+    println!("  Fee basis points:               {}", global.fee_basis_points);
+    if let Some(creator_fee) = global.creator_fee_basis_points {
+        println!("  Creator fee basis points:       {}", creator_fee);
+    }
+    println!("  Initial virtual token reserves: {:.0} tokens", global.initial_virtual_token_reserves as f64 / TOKEN_UNIT);
+    println!("  Initial virtual SOL reserves:   {:.4} SOL", global.initial_virtual_sol_reserves as f64 / LAMPORTS_PER_SOL);
+    println!("  Initial real token reserves:    {:.0} tokens", global.initial_real_token_reserves as f64 / TOKEN_UNIT);
+    println!("  Token total supply:             {:.0} tokens", global.token_total_supply as f64 / TOKEN_UNIT);
+    if let Some(migration_fee) = global.pool_migration_fee {
+        println!("  Pool migration fee:             {:.4} SOL", migration_fee as f64 / LAMPORTS_PER_SOL);
+    }
+    if !global.fee_recipients.is_empty() {
+        println!("  Additional fee recipients:");
+        for recipient in &global.fee_recipients {
+            println!("    {}", recipient);
+        }
+    }
+    println!("  Launch price:                   {:.10} SOL per token", global.initial_price_lamports() / LAMPORTS_PER_SOL);
+    println!("  First buy of 1 SOL receives:    {:.0} tokens", global.initial_curve(None).quote_buy(LAMPORTS_PER_SOL as u64, global.total_fee_basis_points()) as f64 / TOKEN_UNIT);
+
+    Ok(())
+}
+
+pub async fn handle_upload(args: UploadArgs) -> Result<()> {
+    let symbol = args.symbol.to_uppercase();
+    let token_name = args.name.clone()
+        .unwrap_or_else(|| DEFAULT_NAME_TEMPLATE.replace("{}", &symbol));
+    let description = args.description.clone()
+        .unwrap_or_else(|| DEFAULT_DESCRIPTION_TEMPLATE.replace("{}", &symbol));
+    validate_token_fields(&symbol, &token_name, &description)?;
+    warn_name_collisions(&token_name, &symbol, &LaunchWarnings::default()).await;
+
+    let image_path = match &args.image_url {
+        Some(url) => Some(fetch_image_url(url).await?),
+        None => args.image.clone(),
+    };
+    let metadata_uri = upload_metadata_to_pumpfun(&token_name, &symbol, &description, image_path.as_deref(), &args.metadata.to_form()?).await?;
+
+    println!("Name: {}", token_name);
+    println!("Symbol: {}", symbol);
+    println!("Description: {}", description);
+    println!("Metadata URI: {}", metadata_uri);
+    Ok(())
+}
+
+pub async fn handle_derive(args: DeriveArgs) -> Result<()> {
+    let program = ProgramConfig::from_env()?;
+    let mint = args.mint;
+
+    // Fall back to the on-chain bonding curve for the creator when an RPC endpoint is configured
+    let creator = match args.creator {
+        Some(creator) => Some(creator),
+        None => match rpc_url_from_env() {
+            Ok(rpc_url) => {
+                let rpc = RpcClient::new(rpc_url);
+                match rpc.get_account_data(&program.bonding_curve_pda(&mint)).await {
+                    Ok(data) => BondingCurveAccount::from_account_data(&data)?.creator,
+                    Err(e) => {
+                        log::warn!("Could not read bonding curve for creator lookup: {}", e);
+                        None
+                    }
+                }
+            }
+            Err(_) => None,
+        },
+    };
+
+    println!("PDAs for mint {} (program {})", mint, program.program_id);
+    println!("  Global:                   {}", program.global_pda());
+    println!("  Bonding curve:            {}", program.bonding_curve_pda(&mint));
+    println!("  Associated bonding curve: {}", program.associated_bonding_curve(&mint));
+    println!("  Metadata:                 {}", program.metadata_pda(&mint));
+    println!("  Mint authority:           {}", program.mint_authority_pda());
+    println!("  Event authority:          {}", program.event_authority_pda());
+    match creator {
+        Some(creator) => {
+            println!("  Creator:                  {}", creator);
+            println!("  Creator vault:            {}", program.creator_vault_pda(&creator));
+        }
+        None => println!("  Creator vault:            n/a (pass --creator or configure an RPC endpoint)"),
+    }
+
+    Ok(())
+}
+
+pub fn handle_audit_verify(path: Option<String>) -> Result<()> {
+    let audit_log = match path {
+        Some(path) => AuditLog::new(path),
+        None => AuditLog::from_env()
+            .ok_or_else(|| anyhow::anyhow!("No audit log given. Pass --path or set AUDIT_LOG_PATH"))?,
+    };
+    let entries = audit_log.verify()?;
+    println!("Audit log {} is intact ({} entries)", audit_log.path(), entries);
+    Ok(())
+}
+
+pub async fn handle_watch(mints: Vec<Pubkey>, interval: u64, duration: Option<u64>) -> Result<()> {
+    let mints = if mints.is_empty() { TxJournal::from_env().launched_mints()? } else { mints };
+    if mints.is_empty() {
+        return Err(anyhow::anyhow!("No mints to watch; pass mints or launch a token first"));
+    }
+    Watcher::new(mints, Notifier::from_env(), Duration::from_secs(interval.max(1)))
+        .run(duration.map(Duration::from_secs))
+        .await
+}
+
+pub async fn handle_holders(mint: Pubkey, min_balance: f64, out: Option<String>) -> Result<()> {
+    let rpc_url = rpc_url_from_env()?;
+    let rpc = RpcClient::new(rpc_url.clone());
+    let program = ProgramConfig::from_env()?;
+
+    let supply: u64 = rpc.get_token_supply(&mint).await?.amount.parse()?;
+    let balances = fetch_token_balances(&rpc, &rpc_url, &mint).await?;
+    let labels = HashMap::from([(program.bonding_curve_pda(&mint), "bonding_curve".to_string())]);
+    let holders = build_snapshot(&balances, supply, (min_balance * TOKEN_UNIT) as u64, &labels);
+    let csv = snapshot_to_csv(&holders);
+
+    match out {
+        Some(path) => {
+            std::fs::write(&path, csv)?;
+            println!("Wrote {} holders of {} to {}", holders.len(), mint, path);
+        }
+        None => print!("{}", csv),
+    }
+    Ok(())
+}
+
+pub async fn handle_localnet_test(create: CreateArgs, ledger: &str, sol: f64) -> Result<()> {
+    if create.squads_multisig.is_some() {
+        return Err(anyhow::anyhow!("localnet-test does not support Squads launches"));
+    }
+    if std::env::var("DRY_RUN").is_ok_and(|v| v.eq_ignore_ascii_case("true")) {
+        return Err(anyhow::anyhow!("localnet-test sends real transactions to the local validator; unset DRY_RUN"));
+    }
+    set_cluster(SolanaCluster::Localnet);
+    let _validator = LocalValidator::start(ledger, &ProgramConfig::from_env()?).await?;
+    handle_airdrop(sol, create.wallet.as_ref()).await?;
+
+    let LaunchResult { signature, mint, .. } = handle_token_creation(create).await?
+        .ok_or_else(|| anyhow::anyhow!("Launch did not produce a confirmed transaction"))?;
+
+    // Check the launch actually initialized the curve, not just that a signature confirmed
+    let creator = TokenCreator::new()?;
+    let curve = creator.fetch_bonding_curve(&mint).await?;
+    let supply = creator.rpc().get_token_supply(&mint).await?;
+    println!("Localnet launch succeeded");
+    println!("  Transaction:   {}", signature);
+    println!("  Mint:          {} (supply {})", mint, supply.ui_amount_string);
+    println!("  Bonding curve: {} (virtual SOL {}, complete {})",
+             creator.get_bonding_curve_pda(&mint), curve.virtual_sol_reserves, curve.complete);
+    Ok(())
+}
+
+pub async fn handle_launch_and_watch(create: CreateArgs, duration: u64, sign_receipt: bool) -> Result<()> {
+    let (name, symbol) = (create.get_token_name(), create.get_symbol().to_uppercase());
+    let (wallet, operator) = (create.wallet.clone(), create.get_operator());
+    let Some(LaunchResult { signature, mint, launch_dir, .. }) = handle_token_creation(create).await? else {
+        log::info!("No confirmed launch to watch");
+        return Ok(());
+    };
+
+    let creator = TokenCreator::with_wallet(wallet.as_ref())?;
+    let mut report = LaunchReport::new(&mint, &name, &symbol, &signature)?;
+    report.operator = operator;
+    if sign_receipt {
+        report.sign(creator.payer())?;
+    }
+    match creator.verify_authorities(&mint).await {
+        Ok(authorities) => {
+            for anomaly in &authorities.anomalies {
+                log::warn!("Authority anomaly on {}: {}", mint, anomaly);
+            }
+            report.authorities = Some(authorities);
+        }
+        Err(e) => log::warn!("Could not verify the authorities of {}: {}", mint, e),
+    }
+    let path = report.write()?;
+    log::info!("Launch report written to {}", path.display());
+    if let Some(dir) = &launch_dir {
+        report.write_to(&dir.report_path())?;
+    }
+
+    let global = creator.fetch_global_account().await?;
+    let summary = watch_launch(
+        creator.rpc(),
+        &websocket_url_from_env()?,
+        &mint,
+        &creator.get_bonding_curve_pda(&mint),
+        global.initial_real_token_reserves,
+        Duration::from_secs(duration),
+    ).await?;
+
+    let sol_usd = sol_usd_price().await;
+    println!("Watch summary for {} ({}s)", mint, summary.duration_secs);
+    println!("  Trades:        {} ({} buys, {} sells)", summary.trades, summary.buys, summary.sells);
+    println!("  Unique buyers: {}", summary.unique_buyers);
+    println!("  Volume:        {}", format_sol(summary.volume_lamports, sol_usd));
+    println!("  Peak mcap:     {}", format_sol(summary.peak_market_cap_lamports, sol_usd));
+    println!("  Final mcap:    {}", format_sol(summary.final_market_cap_lamports, sol_usd));
+    println!("  Curve:         {:.1}%{}", summary.final_progress_percent, if summary.complete { " (complete)" } else { "" });
+
+    report.watch = Some(summary);
+    if let Some(dir) = &launch_dir {
+        report.write_to(&dir.report_path())?;
+        // The directory itself was uploaded when the launch confirmed
+        if let Ok(Some(store)) = ArtifactStore::from_env() {
+            store.upload_file(dir, &dir.report_path()).await;
+        }
+    }
+    println!("Report: {}", report.write()?.display());
+    Ok(())
+}
+
+pub async fn handle_daemon(listen: &str, register_discord_commands: bool) -> Result<()> {
+    let discord = DiscordConfig::from_env()?;
+    // A Discord-only daemon needs no trigger file
+    let triggers = match (load_triggers(), &discord) {
+        (Ok(triggers), _) => triggers,
+        (Err(e), Some(_)) => {
+            log::info!("No webhook triggers loaded: {}", e);
+            Vec::new()
+        }
+        (Err(e), None) => return Err(e),
+    };
+    if register_discord_commands {
+        let config = discord.as_ref().ok_or_else(|| anyhow::anyhow!("Set DISCORD_APPLICATION_ID to register Discord commands"))?;
+        register_commands(config).await?;
+        log::info!("Registered Discord slash commands");
+    }
+    // Fired drafts launch through the global pool, and /readyz reports its state
+    if VanityConfig::from_env().enabled {
+        init_global_vanity_pool(VanityPattern::from_env())?;
+    }
+    let health = HealthChecker::from_env()?;
+    // Launches run one at a time, in the order their triggers fired
+    let (queue, mut fired) = tokio::sync::mpsc::channel(16);
+    let listen = listen.to_string();
+    let server = tokio::spawn(async move { serve_triggers(&listen, triggers, discord, health, queue).await });
+
+    while let Some(FiredTrigger { trigger, draft }) = fired.recv().await {
+        let argv = std::iter::once("pumpfun-launcher".to_string()).chain(draft.to_args());
+        let create = match Args::try_parse_from(argv) {
+            Ok(args) => args.create,
+            Err(e) => {
+                log::error!("Draft fired by {} is not a valid launch: {}", trigger, e);
+                continue;
+            }
+        };
+        match handle_token_creation(create).await {
+            Ok(Some(LaunchResult { signature, mint, .. })) => log::info!("Trigger {} launched {} in {}", trigger, mint, signature),
+            Ok(None) => log::info!("Trigger {} finished without a confirmed launch", trigger),
+            Err(e) => log::error!("Launch fired by {} failed: {}", trigger, e),
+        }
+    }
+    server.await?
+}
+
+pub async fn handle_serve(listen: &str) -> Result<()> {
+    let api_key = SecureApiKey::load(API_KEY_VAR)
+        .map_err(|e| anyhow::anyhow!("Set {} to the key API clients must send: {}", API_KEY_VAR, e))?;
+    if VanityConfig::from_env().enabled {
+        init_global_vanity_pool(VanityPattern::from_env())?;
+    }
+    // Launches run one at a time, in the order they were requested
+    let (queue, mut queued) = tokio::sync::mpsc::channel(16);
+    let launches = ApiLaunches::default();
+    let listen = listen.to_string();
+    let server_launches = launches.clone();
+    let server = tokio::spawn(async move { serve_api(&listen, api_key, server_launches, queue).await });
+
+    while let Some(QueuedLaunch { id, create }) = queued.recv().await {
+        launches.update(id, |launch| launch.status = LaunchState::Running);
+        let result = handle_token_creation(create).await;
+        launches.update(id, |launch| match result {
+            Ok(Some(LaunchResult { signature, mint, .. })) => {
+                log::info!("API launch {} launched {} in {}", id, mint, signature);
+                launch.status = LaunchState::Confirmed;
+                launch.mint = Some(mint.to_string());
+                launch.signature = Some(signature.to_string());
+            }
+            Ok(None) => launch.status = LaunchState::Finished,
+            Err(e) => {
+                log::error!("API launch {} failed: {}", id, e);
+                launch.status = LaunchState::Failed;
+                launch.error = Some(e.to_string());
+            }
+        });
+    }
+    server.await?
+}
+
+pub fn handle_verify_receipt(path: &str) -> Result<()> {
+    let report = LaunchReport::read(Path::new(path))?;
+    let signer = report.verify_receipt()?;
+    println!("Receipt valid: {} (${}, mint {}) was launched by {} in {}",
+             report.name, report.symbol, report.mint, signer, report.signature);
+    Ok(())
+}
+
+/// "curve 42.1%", "migrated" or "not pump.fun", with the market data of migrated tokens when `enrich` is set
+async fn token_market_state(creator: &TokenCreator, mint: &Pubkey, initial_real_token_reserves: u64, enrich: bool) -> (String, Option<BondingCurveAccount>, Option<MarketData>) {
+    match creator.fetch_bonding_curve(mint).await {
+        Ok(curve) if curve.complete => ("migrated".to_string(), Some(curve), if enrich { market_data(mint).await } else { None }),
+        Ok(curve) => (format!("curve {:.1}%", curve.progress_percent(initial_real_token_reserves)), Some(curve), None),
+        Err(_) => ("not pump.fun".to_string(), None, None),
+    }
+}
+
+pub async fn handle_history(limit: usize, enrich: bool, json: bool) -> Result<()> {
+    let reports = LaunchReport::list()?;
+    let creator = TokenCreator::new()?;
+    let global = creator.fetch_global_account().await?;
+    let mut launches = Vec::new();
+    for report in reports.into_iter().take(limit) {
+        let state = match report.mint.parse::<Pubkey>() {
+            Ok(mint) => token_market_state(&creator, &mint, global.initial_real_token_reserves, enrich).await,
+            Err(_) => ("invalid mint".to_string(), None, None),
+        };
+        launches.push((report, state));
+    }
+
+    if json {
+        let launches: Vec<_> = launches.iter().map(|(report, (status, _, market))| serde_json::json!({
+            "mint": report.mint,
+            "symbol": report.symbol,
+            "name": report.name,
+            "launched_at": report.launched_at,
+            "operator": report.operator,
+            "status": status,
+            "market": market,
+        })).collect();
+        println!("{}", serde_json::to_string_pretty(&launches)?);
+        return Ok(());
+    }
+    if launches.is_empty() {
+        println!("No launch reports in {}", LaunchReport::dir().display());
+    }
+    for (report, (status, _, market)) in &launches {
+        println!("{} ${:<10} {} {}  {}", report.launched_at, report.symbol, report.mint, report.operator.as_deref().unwrap_or("-"), status);
+        if let Some(market) = market {
+            println!("    {}", format_market_data(market));
+        }
+    }
+    Ok(())
+}
+
+pub async fn handle_portfolio(wallet: Option<&WalletSpec>, enrich: bool, json: bool) -> Result<()> {
+    let creator = TokenCreator::with_wallet(wallet)?;
+    let owner = creator.get_wallet_address();
+    let tokens = fetch_wallet_tokens(creator.rpc(), &owner).await?;
+    let global = creator.fetch_global_account().await?;
+    let sol_usd = sol_usd_price().await;
+    let mut holdings = Vec::new();
+    for (mint, amount) in tokens {
+        let (status, curve, market) = token_market_state(&creator, &mint, global.initial_real_token_reserves, enrich).await;
+        // pump.fun tokens have 6 decimals; other mints are shown as raw amounts
+        let tokens = curve.as_ref().map(|_| amount as f64 / TOKEN_UNIT);
+        let value_usd = match (&curve, &market) {
+            (Some(curve), _) if !curve.complete => sol_usd.map(|price| curve.price_lamports() / LAMPORTS_PER_SOL * price * amount as f64 / TOKEN_UNIT),
+            (Some(_), Some(market)) => market.price_usd.map(|price| price * amount as f64 / TOKEN_UNIT),
+            _ => None,
+        };
+        holdings.push((mint, amount, tokens, status, value_usd, market));
+    }
+
+    if json {
+        let holdings: Vec<_> = holdings.iter().map(|(mint, amount, tokens, status, value_usd, market)| serde_json::json!({
+            "mint": mint.to_string(),
+            "amount": amount,
+            "tokens": tokens,
+            "status": status,
+            "value_usd": value_usd,
+            "market": market,
+        })).collect();
+        println!("{}", serde_json::to_string_pretty(&holdings)?);
+        return Ok(());
+    }
+    println!("Portfolio of {} ({} tokens)", owner, holdings.len());
+    for (mint, amount, tokens, status, value_usd, market) in &holdings {
+        let amount = tokens.map(|tokens| format!("{:.0}", tokens)).unwrap_or_else(|| format!("{} raw", amount));
+        let value = value_usd.map(|usd| format!("~${:.2}", usd)).unwrap_or_default();
+        println!("  {} {:>20}  {:<14} {}", mint, amount, status, value);
+        if let Some(market) = market {
+            println!("    {}", format_market_data(market));
+        }
+    }
+    Ok(())
+}
+
+pub async fn handle_first_buyers(mint: Pubkey, count: usize, max_signatures: usize, json: bool) -> Result<()> {
+    let rpc = RpcClient::new(rpc_url_from_env()?);
+    let program = ProgramConfig::from_env()?;
+    let buys = first_buyers(&rpc, &mint, &program.bonding_curve_pda(&mint), count, max_signatures).await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&buys)?);
+        return Ok(());
+    }
+    println!("{:<4} {:<10} {:<44} {:>16} {:>12}  flags", "#", "slot", "buyer", "tokens", "SOL");
+    for (index, buy) in buys.iter().enumerate() {
+        println!("{:<4} {:<10} {:<44} {:>16.0} {:>12.4}  {}",
+                 index + 1, buy.slot, buy.buyer, buy.tokens as f64 / TOKEN_UNIT,
+                 buy.sol_lamports as f64 / LAMPORTS_PER_SOL, buy.flags.join(","));
+    }
+    let flagged = buys.iter().filter(|b| !b.flags.is_empty()).count();
+    println!("{} of {} buys flagged", flagged, buys.len());
+    Ok(())
+}
+
+pub async fn handle_funding_graph(mint: Pubkey, buyer_count: usize, format: &str, out: Option<String>) -> Result<()> {
+    if format != "json" && format != "dot" {
+        return Err(anyhow::anyhow!("Unknown format '{}' (expected json or dot)", format));
+    }
+    let rpc = RpcClient::new(rpc_url_from_env()?);
+    let program = ProgramConfig::from_env()?;
+    let buys = first_buyers(&rpc, &mint, &program.bonding_curve_pda(&mint), buyer_count, 20_000).await?;
+    let mut seen = std::collections::HashSet::new();
+    let buyers: Vec<String> = buys.into_iter().map(|b| b.buyer).filter(|b| seen.insert(b.clone())).collect();
+
+    let graph = analyze_buyers(&rpc, &buyers).await?;
+    let rendered = if format == "dot" { to_dot(&graph) } else { serde_json::to_string_pretty(&graph)? };
+    match out {
+        Some(path) => {
+            std::fs::write(&path, rendered)?;
+            println!("{} clusters among {} buyers written to {}", graph.clusters.len(), buyers.len(), path);
+        }
+        None => println!("{}", rendered),
+    }
+    Ok(())
+}
+
+pub async fn handle_verify_pins(mint: Pubkey, repin: bool, json: bool) -> Result<()> {
+    let rpc = RpcClient::new(rpc_url_from_env()?);
+    let uri = onchain_metadata_uri(&rpc, &ProgramConfig::from_env()?, &mint).await?;
+    let reports = verify_pins(&uri, &mint.to_string(), repin).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    } else {
+        for report in &reports {
+            println!("{} {} ({})", report.asset, report.uri, report.cid.as_deref().unwrap_or("not an IPFS URI"));
+            for check in &report.checks {
+                println!("  {:<8} {} {}", if check.retrievable { "ok" } else { "MISSING" }, check.gateway, check.detail);
+            }
+            if let Some(repinned) = report.repinned {
+                println!("  re-pinned to Pinata: {}", if repinned { "yes" } else { "failed" });
+            }
+        }
+    }
+    if reports.iter().any(|r| r.cid.is_some() && !r.retrievable()) {
+        return Err(anyhow::anyhow!("Some launch assets are not retrievable from any checked gateway"));
+    }
+    Ok(())
+}
+
+pub async fn handle_compare_dry_run(symbol: String, name: Option<String>, uri: String, targets: &[String], creator: Option<Pubkey>, json: bool) -> Result<()> {
+    let targets = targets.iter().map(|t| t.parse::<SimulationTarget>()).collect::<Result<Vec<_>>>()?;
+    if targets.len() < 2 {
+        return Err(anyhow::anyhow!("Give at least two --target endpoints to compare"));
+    }
+    let symbol = symbol.to_uppercase();
+    let name = name.unwrap_or_else(|| DEFAULT_NAME_TEMPLATE.replace("{}", &symbol));
+    let description = DEFAULT_DESCRIPTION_TEMPLATE.replace("{}", &symbol);
+    validate_token_fields(&symbol, &name, &description)?;
+    let metadata = PreparedMetadata { name, symbol, description, uri };
+    let outcomes = compare_dry_runs(&TokenCreator::new()?, &targets, &metadata, creator).await?;
+    let differences = differences(&outcomes);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&serde_json::json!({ "outcomes": outcomes, "differences": differences }))?);
+    } else {
+        for outcome in &outcomes {
+            println!("{}: {} (program version {}, {} CU, {} bytes)",
+                outcome.target,
+                if outcome.success { "ok".to_string() } else { format!("FAILED {}", outcome.error.as_deref().unwrap_or("")) },
+                outcome.program_version.as_deref().unwrap_or("unresolved"),
+                outcome.units_consumed.map(|u| u.to_string()).unwrap_or_else(|| "?".to_string()),
+                outcome.size_bytes.map(|b| b.to_string()).unwrap_or_else(|| "?".to_string()));
+        }
+        if differences.is_empty() {
+            println!("No differences");
+        } else {
+            println!("Differences:");
+            for difference in &differences {
+                println!("  {}", difference);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub async fn handle_verify_metadata(mint: Pubkey, signature: &str, json: bool) -> Result<()> {
+    let rpc = RpcClient::new(rpc_url_from_env()?);
+    let check = verify_commitment(&rpc, &ProgramConfig::from_env()?, &mint, signature).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&check)?);
+    } else {
+        println!("Metadata URI: {}", check.uri);
+        println!("Committed:    {}", check.committed.as_deref().unwrap_or("(no commitment memo)"));
+        println!("Current:      {}", check.current);
+    }
+    match &check.committed {
+        None => Err(anyhow::anyhow!("Launch transaction {} carries no metadata commitment", signature)),
+        Some(_) if !check.matches() => Err(anyhow::anyhow!("Metadata of {} changed since launch", mint)),
+        Some(_) => Ok(()),
+    }
+}
+
+pub async fn handle_wallet_report(wallets: &[String], max_signatures: usize, json: bool) -> Result<()> {
+    let addresses = if wallets.is_empty() {
+        vec![TokenCreator::new()?.get_wallet_address()]
+    } else {
+        wallets.iter()
+            .map(|wallet| match wallet.parse::<Pubkey>() {
+                Ok(address) => Ok(address),
+                Err(_) => Ok(wallet.parse::<WalletSpec>()?.load()?.pubkey()),
+            })
+            .collect::<Result<Vec<_>>>()?
+    };
+    let rpc = RpcClient::new(rpc_url_from_env()?);
+    let mut reports = Vec::new();
+    for address in &addresses {
+        reports.push(build_report(&rpc, address, max_signatures).await?);
+    }
+    if json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    } else {
+        for report in &reports {
+            println!("{}", report.to_text());
+        }
+    }
+    Ok(())
+}
+
+pub fn handle_lint(args: LintArgs) -> Result<()> {
+    let mut metadata = match &args.metadata_file {
+        Some(path) => TokenMetadata::from_file(path)?,
+        None => TokenMetadata::default(),
+    };
+    for (flag, field) in [
+        (args.symbol, &mut metadata.symbol),
+        (args.name, &mut metadata.name),
+        (args.description, &mut metadata.description),
+        (args.image, &mut metadata.image),
+        (args.twitter, &mut metadata.twitter),
+        (args.telegram, &mut metadata.telegram),
+        (args.website, &mut metadata.website),
+    ] {
+        if flag.is_some() {
+            *field = flag;
+        }
+    }
+
+    let report = lint_metadata(&metadata);
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        for finding in &report.findings {
+            let severity = if finding.severity == Severity::Error { "error" } else { "warning" };
+            println!("{:<8} {:<12} {}", severity, finding.field, finding.message);
+        }
+        println!("{} findings, completeness {}/100", report.findings.len(), report.completeness);
+    }
+    if report.has_errors() {
+        return Err(anyhow::anyhow!("Metadata has errors that would block or misrepresent the launch"));
+    }
+    Ok(())
+}
+
+pub async fn handle_name_check(symbol: &str, name: Option<String>, hours: u64, json: bool) -> Result<()> {
+    let name = name.unwrap_or_else(|| symbol.to_string());
+    let recent = fetch_recent_tokens(hours).await?;
+    let collisions = find_collisions(&name, symbol, &recent, threshold_from_env()?);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&collisions)?);
+        return Ok(());
+    }
+    println!("{:<44} {:<12} {:<32} {:>6} {:>6}", "mint", "symbol", "name", "name%", "tick%");
+    for collision in &collisions {
+        println!("{:<44} {:<12} {:<32} {:>6.0} {:>6.0}", collision.mint, collision.symbol, collision.name,
+                 collision.name_similarity * 100.0, collision.symbol_similarity * 100.0);
+    }
+    println!("{} near-duplicates among {} tokens launched in the last {}h", collisions.len(), recent.len(), hours);
+    Ok(())
+}
+
+pub fn handle_vanity_stats(json: bool, prometheus: bool) -> Result<()> {
+    let file = StatsFile::from_env();
+    let stats = file.load()?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+        return Ok(());
+    }
+    if prometheus {
+        print!("{}", stats.to_exposition());
+        return Ok(());
+    }
+    if stats.patterns.is_empty() {
+        println!("No vanity grinding recorded in {} yet", file.path().display());
+        return Ok(());
+    }
+    println!("{:<16} {:>16} {:>8} {:>10} {:>16}", "pattern", "attempts", "found", "hours", "attempts/addr");
+    let total = stats.total();
+    for (pattern, row) in stats.patterns.iter().map(|(p, row)| (p.as_str(), row)).chain(std::iter::once(("total", &total))) {
+        let per_address = row.attempts_per_address().map(|n| format!("{:.0}", n)).unwrap_or_else(|| "-".to_string());
+        println!("{:<16} {:>16} {:>8} {:>10.1} {:>16}", pattern, row.attempts, row.found, row.grind_secs / 3600.0, per_address);
+    }
+    let days = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs().saturating_sub(stats.since) as f64 / 86_400.0;
+    println!("Recorded over the last {:.1} days in {}", days, file.path().display());
+    Ok(())
+}
+
+/// Grind `count` addresses up front, so launches can take them from the store without waiting
+pub fn handle_vanity_generate(count: usize, pattern: &VanityPattern, out_dir: Option<&str>) -> Result<()> {
+    let store = VanityStore::from_env()?;
+    if store.is_none() && out_dir.is_none() {
+        return Err(anyhow::anyhow!("Nowhere to keep the keypairs: set VANITY_STORE_SECRET or pass --out-dir"));
+    }
+    let threads = num_cpus::get();
+    let stats = StatsFile::from_env();
+    println!("Grinding {} '{}' address(es) on {} threads (~{:?} each)", count, pattern, threads, expected_search_time(pattern, threads));
+    for n in 1..=count {
+        let result = VanityAddressPool::find_vanity_address(pattern, threads)?;
+        if let Err(e) = stats.record(&pattern.to_string(), result.attempts, result.elapsed, true) {
+            log::warn!("Failed to update vanity stats {}: {}", stats.path().display(), e);
+        }
+        let address = result.keypair.pubkey();
+        if let Some(store) = &store {
+            store.add(&result.keypair)?;
+        }
+        if let Some(dir) = out_dir {
+            write_keypair_file(Path::new(dir), &address.to_string(), &result.keypair)?;
+        }
+        println!("{}/{} {} ({} attempts in {:.1}s)", n, count, address, result.attempts, result.elapsed.as_secs_f64());
+    }
+    if let Some(store) = &store {
+        println!("Stored in {}", store.path().display());
+    }
+    Ok(())
+}
+
+/// Pool state as seen from outside a launch: the worker's pool, or what the store holds for the next launch
+pub fn vanity_pool_summary(config: &VanityConfig) -> Result<serde_json::Value> {
+    if !config.enabled {
+        return Ok(serde_json::json!({ "enabled": false }));
+    }
+    let mut summary = serde_json::json!({
+        "enabled": true,
+        "pattern": config.pattern.to_string(),
+        "mode": if config.worker { "worker" } else { "in-process" },
+    });
+    if config.worker {
+        // Only ask a running worker; `WorkerClient::from_env` would spawn one
+        summary["worker"] = match WorkerClient::new(worker_socket_path()).request(&WorkerRequest::Status) {
+            Ok(status) => serde_json::json!({ "ready": status.count, "generating": status.generating }),
+            Err(e) => serde_json::json!({ "error": format!("not reachable: {}", e) }),
+        };
+    }
+    if let Some(store) = VanityStore::from_env()? {
+        let stored = store.load()?;
+        let matching = stored.iter().filter(|addr| config.pattern.matches(&addr.address.to_string())).count();
+        summary["stored"] = serde_json::json!({ "matching": matching, "total": stored.len(), "path": store.path().display().to_string() });
+    }
+    let lifetime = StatsFile::from_env().load()?.total();
+    summary["lifetime"] = serde_json::json!({ "attempts": lifetime.attempts, "found": lifetime.found });
+    Ok(summary)
+}
+
+pub async fn handle_status(json: bool) -> Result<()> {
+    let creator = TokenCreator::new()?;
+    let wallet = creator.get_wallet_address();
+    let rpc = creator.rpc();
+    let (slot, balance) = tokio::join!(rpc.get_slot(), rpc.get_balance(&wallet));
+    let vanity = vanity_pool_summary(&VanityConfig::from_env())?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&serde_json::json!({
+            "wallet": wallet.to_string(),
+            "balance_lamports": balance.as_ref().ok(),
+            "slot": slot.as_ref().ok(),
+            "rpc_error": slot.as_ref().err().map(|e| e.to_string()),
+            "vanity": vanity,
+        }))?);
+        return Ok(());
+    }
+
+    println!("Wallet:  {}", wallet);
+    match balance {
+        Ok(lamports) => println!("Balance: {}", format_sol(lamports, sol_usd_price().await)),
+        Err(e) => println!("Balance: unavailable ({})", e),
+    }
+    match slot {
+        Ok(slot) => println!("RPC:     ok (slot {})", slot),
+        Err(e) => println!("RPC:     unreachable ({})", e),
+    }
+    if vanity["enabled"] == false {
+        println!("Vanity:  disabled");
+        return Ok(());
+    }
+    println!("Vanity:  '{}' ({})", vanity["pattern"].as_str().unwrap_or_default(), vanity["mode"].as_str().unwrap_or_default());
+    if let Some(worker) = vanity.get("worker") {
+        match worker.get("error") {
+            Some(error) => println!("  worker: {}", error.as_str().unwrap_or_default()),
+            None => println!("  worker: {} ready, generation {}", worker["ready"],
+                             if worker["generating"] == true { "running" } else { "stopped" }),
+        }
+    }
+    if let Some(stored) = vanity.get("stored") {
+        println!("  store:  {} ready ({} total) in {}", stored["matching"], stored["total"], stored["path"].as_str().unwrap_or_default());
+    }
+    println!("  lifetime: {} attempts, {} found", vanity["lifetime"]["attempts"], vanity["lifetime"]["found"]);
+    Ok(())
+}
+
+pub async fn handle_balance(wallet: Option<&WalletSpec>) -> Result<()> {
+    let creator = TokenCreator::with_wallet(wallet)?;
+    let address = creator.get_wallet_address();
+    let lamports = creator.rpc().get_balance(&address).await?;
+    println!("{}: {}", address, format_sol(lamports, sol_usd_price().await));
+    Ok(())
+}
+
+pub fn handle_prepare_images(dir: &str, out_dir: Option<String>, report: Option<String>) -> Result<()> {
+    let dir = Path::new(dir);
+    let out_dir = out_dir.map(PathBuf::from).unwrap_or_else(|| dir.join("prepared"));
+    let report_path = report.map(PathBuf::from).unwrap_or_else(|| dir.join("image-report.json"));
+    let reports = prepare_images(dir, &out_dir)?;
+
+    for report in &reports {
+        let size = match (report.width, report.height) {
+            (Some(width), Some(height)) => format!("{}x{}", width, height),
+            _ => "-".to_string(),
+        };
+        let status = if report.issues.is_empty() { "ok".to_string() } else { report.issues.join("; ") };
+        println!("{:<40} {:<12} {:>10}  {}", report.file, size, report.bytes, status);
+        if let Some(fixed) = &report.fixed {
+            println!("{:<40} -> {}", "", fixed);
+        }
+    }
+    std::fs::write(&report_path, serde_json::to_string_pretty(&reports)?)?;
+    let needs_fixing = reports.iter().filter(|r| !r.issues.is_empty()).count();
+    let fixed = reports.iter().filter(|r| r.fixed.is_some()).count();
+    println!("{} of {} files need fixing ({} normalized); report written to {}",
+             needs_fixing, reports.len(), fixed, report_path.display());
+    Ok(())
+}
+
+pub async fn handle_quote(mint: Pubkey, sol: Option<f64>, tokens: Option<f64>, slippage_bps: u64, jupiter: bool) -> Result<()> {
+    if sol.or(tokens).is_some_and(|amount| !amount.is_finite() || amount <= 0.0) {
+        return Err(anyhow::anyhow!("--sol and --tokens must be positive"));
+    }
+    let creator = TokenCreator::new()?;
+    let sol_usd = sol_usd_price().await;
+    // Jupiter is used when forced, or as a fallback when the token cannot be quoted on pump.fun/PumpSwap
+    let fallback = if jupiter { Some(Jupiter::from_env()?) } else { Jupiter::fallback_from_env()? };
+    match (sol, tokens) {
+        (Some(sol), _) => {
+            let lamports = (sol * LAMPORTS_PER_SOL) as u64;
+            let order = match (jupiter, creator.build_buy_instructions(&mint, lamports, slippage_bps).await) {
+                (false, Ok((_, order))) => order,
+                (_, native) => match &fallback {
+                    Some(jupiter) => {
+                        if let Err(e) = native {
+                            log::warn!("Native quote failed ({}), asking Jupiter", e);
+                        }
+                        jupiter.buy_order(&mint, lamports, slippage_bps).await?.0
+                    }
+                    None => native?.1,
+                },
+            };
+            println!("Buy {} with {} on {:?}", mint, format_sol(order.sol_lamports, sol_usd), order.venue);
+            println!("  Tokens out:    {:.0}", order.token_amount as f64 / TOKEN_UNIT);
+            println!("  Max SOL cost:  {}", format_sol(order.max_sol_cost, sol_usd));
+        }
+        (None, Some(tokens)) => {
+            let amount = (tokens * TOKEN_UNIT) as u64;
+            let order = match (jupiter, creator.build_sell_instructions(&mint, amount, slippage_bps).await) {
+                (false, Ok((_, order))) => order,
+                (_, native) => match &fallback {
+                    Some(jupiter) => {
+                        if let Err(e) = native {
+                            log::warn!("Native quote failed ({}), asking Jupiter", e);
+                        }
+                        jupiter.sell_order(&mint, amount, slippage_bps).await?.0
+                    }
+                    None => native?.1,
+                },
+            };
+            println!("Sell {:.0} tokens of {} on {:?}", tokens, mint, order.venue);
+            println!("  Expected SOL:  {}", format_sol(order.expected_sol, sol_usd));
+            println!("  Min SOL out:   {}", format_sol(order.min_sol_output, sol_usd));
+        }
+        (None, None) => return Err(anyhow::anyhow!("Pass --sol to quote a buy or --tokens to quote a sell")),
+    }
+    Ok(())
+}
+
+pub async fn handle_buy(mint: Pubkey, sol: f64, slippage_bps: u64, wallet: Option<&WalletSpec>, operator: Option<String>) -> Result<()> {
+    if !sol.is_finite() || sol <= 0.0 {
+        return Err(anyhow::anyhow!("--sol must be positive"));
+    }
+    let creator = TokenCreator::with_wallet(wallet)?;
+    let lamports = (sol * LAMPORTS_PER_SOL) as u64;
+    let sol_usd = sol_usd_price().await;
+    if std::env::var("DRY_RUN").is_ok_and(|v| v.eq_ignore_ascii_case("true")) {
+        let (_, order) = creator.build_checked_buy_instructions(&mint, lamports, slippage_bps).await?;
+        println!("DRY RUN: would buy {:.0} tokens of {} for at most {} on {:?}",
+                 order.token_amount as f64 / TOKEN_UNIT, mint, format_sol(order.max_sol_cost, sol_usd), order.venue);
+        return Ok(());
+    }
+    let (signature, order) = creator.buy_token(&mint, lamports, slippage_bps, operator.as_deref()).await?;
+    println!("Bought {:.0} tokens of {} for at most {} on {:?}",
+             order.token_amount as f64 / TOKEN_UNIT, mint, format_sol(order.max_sol_cost, sol_usd), order.venue);
+    println!("Transaction: {}", signature);
+    Ok(())
+}
+
+pub async fn handle_update_metadata(mint: Pubkey, uri: Option<String>, name: Option<String>, wallet: Option<&WalletSpec>, operator: Option<String>) -> Result<()> {
+    let creator = TokenCreator::with_wallet(wallet)?;
+    let current = creator.fetch_metadata_account(&mint).await?;
+    println!("Metadata of {} (update authority {})", mint, current.update_authority);
+    if let Some(name) = &name {
+        println!("  Name: {} -> {}", current.name, name);
+    }
+    if let Some(uri) = &uri {
+        println!("  URI:  {} -> {}", current.uri, uri);
+    }
+    if std::env::var("DRY_RUN").is_ok_and(|v| v.eq_ignore_ascii_case("true")) {
+        if current.update_authority != creator.get_wallet_address() {
+            return Err(anyhow::anyhow!("The update authority is {}, not this wallet ({})", current.update_authority, creator.get_wallet_address()));
+        }
+        let metadata_program = creator.program_config().metadata_program_id;
+        update_metadata_instruction(&metadata_program, &creator.get_metadata_pda(&mint), &current, name.as_deref(), uri.as_deref())?;
+        println!("DRY RUN: not sending the update");
+        return Ok(());
+    }
+    let signature = creator.update_metadata(&mint, name.as_deref(), uri.as_deref(), operator.as_deref()).await?;
+    println!("Transaction: {}", signature);
+    Ok(())
+}
+
+pub async fn handle_verify_authorities(mint: Pubkey, json: bool) -> Result<()> {
+    let creator = TokenCreator::new()?;
+    let authorities = creator.verify_authorities(&mint).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&authorities)?);
+    } else {
+        let or_none = |authority: &Option<String>| authority.clone().unwrap_or_else(|| "none".to_string());
+        println!("Authorities of {}", mint);
+        println!("  Mint authority:     {}", or_none(&authorities.mint_authority));
+        println!("  Freeze authority:   {}", or_none(&authorities.freeze_authority));
+        println!("  Update authority:   {}{}", authorities.update_authority, if authorities.metadata_mutable { "" } else { " (immutable)" });
+        for anomaly in &authorities.anomalies {
+            println!("  ANOMALY: {}", anomaly);
+        }
+    }
+
+    let path = LaunchReport::path_for(&mint.to_string());
+    if path.exists() {
+        let mut report = LaunchReport::read(&path)?;
+        report.authorities = Some(authorities.clone());
+        report.write_to(&path)?;
+        log::info!("Recorded the authority check in {}", path.display());
+    }
+    if !authorities.is_clean() {
+        return Err(anyhow::anyhow!("Authorities of {} are not in the expected state ({} anomalies)", mint, authorities.anomalies.len()));
+    }
+    Ok(())
+}
+
+pub async fn handle_sell(mint: Pubkey, tokens: Option<f64>, all: bool, slippage_bps: u64, wallet: Option<&WalletSpec>, operator: Option<String>) -> Result<()> {
+    let amount = match tokens {
+        _ if all => SellAmount::All,
+        Some(tokens) if tokens.is_finite() && tokens > 0.0 => SellAmount::Tokens((tokens * TOKEN_UNIT) as u64),
+        _ => return Err(anyhow::anyhow!("--tokens must be positive")),
+    };
+    let creator = TokenCreator::with_wallet(wallet)?;
+    let sol_usd = sol_usd_price().await;
+    if std::env::var("DRY_RUN").is_ok_and(|v| v.eq_ignore_ascii_case("true")) {
+        let token_amount = amount.resolve(creator.token_balance(&mint).await?)?;
+        let (_, order) = creator.build_sell_instructions(&mint, token_amount, slippage_bps).await?;
+        println!("DRY RUN: would sell {:.0} tokens of {} for at least {} (expected {}) on {:?}",
+                 order.token_amount as f64 / TOKEN_UNIT, mint, format_sol(order.min_sol_output, sol_usd),
+                 format_sol(order.expected_sol, sol_usd), order.venue);
+        return Ok(());
+    }
+    let (signature, order) = creator.sell_token(&mint, amount, slippage_bps, operator.as_deref()).await?;
+    println!("Sold {:.0} tokens of {} for at least {} on {:?}",
+             order.token_amount as f64 / TOKEN_UNIT, mint, format_sol(order.min_sol_output, sol_usd), order.venue);
+    println!("Transaction: {}", signature);
+    Ok(())
+}
+
+pub async fn handle_new_wallet(name: &str, fund: Option<f64>, from: Option<&WalletSpec>) -> Result<()> {
+    if fund.is_some_and(|sol| !sol.is_finite() || sol <= 0.0) {
+        return Err(anyhow::anyhow!("--fund must be positive"));
+    }
+    let dir = std::env::var("WALLETS_DIR").unwrap_or_else(|_| DEFAULT_WALLETS_DIR.to_string());
+    let (wallet, path) = create_wallet_file(std::path::Path::new(&dir), name)?;
+    println!("Created wallet {} in {}", wallet.pubkey(), path.display());
+    println!("Launch from it with --wallet {}", path.display());
+
+    if let Some(sol) = fund {
+        // The caller chose the amount explicitly, so TOPUP_MAX_SOL does not apply
+        let funding = FundingWallet::new(load_wallet(from)?, u64::MAX);
+        let rpc = RpcClient::new(rpc_url_from_env()?);
+        let signature = funding.top_up(&rpc, &wallet.pubkey(), (sol * LAMPORTS_PER_SOL) as u64, sol_usd_price().await, resolve_operator(None).as_deref()).await?;
+        println!("Funded with {} SOL from {}: {}", sol, funding.address(), signature);
+    }
+    Ok(())
+}
+
+pub async fn handle_nonce(action: NonceCommand) -> Result<()> {
+    let creator = TokenCreator::new()?;
+    let payer = creator.payer();
+    match action {
+        NonceCommand::Create => {
+            if let Some(existing) = configured_nonce_account()? {
+                return Err(anyhow::anyhow!("Nonce account {} is already configured; close it first", existing));
+            }
+            let (address, signature) = create_nonce_account(&creator, &payer.pubkey(), resolve_operator(None).as_deref()).await?;
+            NonceAccountConfig { address: address.to_string(), authority: payer.pubkey().to_string() }.save()?;
+            println!("Created nonce account {} (authority {}): {}", address, payer.pubkey(), signature);
+        }
+        NonceCommand::Show => {
+            let address = configured_nonce_account()?
+                .ok_or_else(|| anyhow::anyhow!("No nonce account configured; run `nonce create` or set NONCE_ACCOUNT"))?;
+            let state = fetch_nonce_state(creator.rpc(), &address).await?;
+            println!("Nonce account: {}", address);
+            println!("  Authority:   {}", state.authority);
+            println!("  Nonce:       {}", state.blockhash);
+            println!("  Fee:         {} lamports per signature", state.lamports_per_signature);
+            println!("  Balance:     {:.6} SOL", creator.rpc().get_balance(&address).await? as f64 / LAMPORTS_PER_SOL);
+        }
+        NonceCommand::Close => {
+            let address = configured_nonce_account()?
+                .ok_or_else(|| anyhow::anyhow!("No nonce account configured"))?;
+            let signature = close_nonce_account(&creator, &address, resolve_operator(None).as_deref()).await?;
+            NonceAccountConfig::remove()?;
+            println!("Closed nonce account {}: {}", address, signature);
+        }
+    }
+    Ok(())
+}
+
+/// Airdrop `sol` to `wallet`, or the PRIVATE_KEY wallet when `None`
+pub async fn handle_airdrop(sol: f64, wallet: Option<&WalletSpec>) -> Result<()> {
+    const MAINNET_GENESIS_HASH: &str = "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d";
+    const MAX_ATTEMPTS: u32 = 5;
+
+    if !sol.is_finite() || sol <= 0.0 {
+        return Err(anyhow::anyhow!("--sol must be positive"));
+    }
+    let creator = TokenCreator::with_wallet(wallet)?;
+    let rpc = creator.rpc();
+    let wallet = creator.get_wallet_address();
+    if rpc.get_genesis_hash().await?.to_string() == MAINNET_GENESIS_HASH {
+        return Err(anyhow::anyhow!("Airdrops are not available on mainnet; point HELIUS_API_KEY at a devnet or testnet RPC"));
+    }
+
+    let lamports = (sol * LAMPORTS_PER_SOL) as u64;
+    let mut attempt = 1;
+    let signature = loop {
+        match rpc.request_airdrop(&wallet, lamports).await {
+            Ok(signature) => break signature,
+            // Faucets rate limit aggressively; back off and retry
+            Err(e) if attempt < MAX_ATTEMPTS => {
+                let delay = Duration::from_secs(2u64.pow(attempt));
+                log::warn!("Airdrop request failed ({}), retrying in {}s", e, delay.as_secs());
+                sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(anyhow::anyhow!("Airdrop failed after {} attempts: {}", MAX_ATTEMPTS, e)),
+        }
+    };
+
+    println!("Requested {} SOL for {}: {}", sol, wallet, signature);
+    for _ in 0..30 {
+        if rpc.confirm_transaction(&signature).await? {
+            println!("Airdrop confirmed. Balance: {:.4} SOL", creator.get_wallet_balance().await?);
+            return Ok(());
+        }
+        sleep(Duration::from_secs(1)).await;
+    }
+    Err(anyhow::anyhow!("Airdrop {} was not confirmed within 30s", signature))
+}
+
+pub async fn handle_squads_status(multisig: Pubkey, index: u64, wait: bool) -> Result<()> {
+    let rpc = RpcClient::new(rpc_url_from_env()?);
+    let squads = SquadsConfig::new(multisig, 0);
+    let threshold = fetch_multisig(&rpc, &multisig).await?.threshold;
+    let mut last_status = None;
+
+    loop {
+        let proposal = fetch_proposal(&rpc, &squads, index).await?;
+        if last_status != Some(proposal.status) {
+            println!("Proposal {} (index {}): {:?}, approvals {}/{}, rejections {}",
+                     squads.proposal_pda(index), index, proposal.status,
+                     proposal.approved.len(), threshold, proposal.rejected.len());
+            last_status = Some(proposal.status);
+        }
+        if !wait || proposal.status.is_final() {
+            return Ok(());
+        }
+        sleep(Duration::from_secs(10)).await;
+    }
+}
+
+/// Propose the launch to a Squads multisig instead of sending it from the payer wallet
+async fn handle_squads_creation(args: &CreateArgs, squads: SquadsConfig) -> Result<()> {
+    let symbol = args.get_symbol().to_uppercase();
+    let creator = TokenCreator::with_wallet(args.wallet.as_ref())?;
+    let proposal = creator.create_token_via_squads(
+        args.get_token_name(),
+        symbol.clone(),
+        args.get_description(),
+        args.get_image_path(),
+        &args.metadata.to_form()?,
+        &squads,
+        args.get_operator().as_deref(),
+    ).await?;
+
+    log::info!("{} launch proposed to Squads multisig {}", symbol, squads.multisig);
+    log::info!("Transaction index: {}", proposal.transaction_index);
+    log::info!("Vault transaction: {}", proposal.transaction);
+    log::info!("Proposal: {}", proposal.proposal);
+    log::info!("Vault (payer/creator): {}", proposal.vault);
+    log::info!("Mint on execution: {}", proposal.mint);
+    log::info!("Metadata URI: {}", proposal.metadata_uri);
+    log::info!("Transaction: {}", proposal.signature);
+    log::info!("Track with: squads-status --multisig {} --index {} --wait", squads.multisig, proposal.transaction_index);
+    Ok(())
+}
+
+/// Launch a token, returning the signature and mint once confirmed (`None` when proposed to a multisig or simulated)
+pub async fn handle_token_creation(mut args: CreateArgs) -> Result<Option<LaunchResult>> {
+    log::info!("Processing token creation...");
+    ensure_writable("launch a token")?;
+    let symbol = args.get_symbol();
     let token_name = args.get_token_name();
     let description = args.get_description();
+    
+    validate_token_fields(&symbol, &token_name, &description)?;
+    progress("started", serde_json::json!({ "symbol": symbol.to_uppercase(), "name": token_name }));
+    
+    // Fetch remote artwork up front so everything below works with a local path
+    if let Some(url) = args.image_url.take() {
+        args.image = Some(fetch_image_url(&url).await?);
+    }
     let image_path = args.get_image_path();
     
-    // Validate token name length (Metaplex name limit is typically 32 characters)
-    if token_name.len() > 32 {
-        return Err(anyhow::anyhow!("Token name '{}' is too long. Maximum 32 characters allowed.", token_name));
+    if let Some(multisig) = args.squads_multisig {
+        handle_squads_creation(&args, SquadsConfig::new(multisig, args.squads_vault_index)).await?;
+        return Ok(None);
     }
     
     log::info!("Creating token with symbol: {}, name: {}, description: {}", 
-               args.symbol, token_name, description);
+               symbol, token_name, description);
     
     // Check wallet balance first
-    let creator = TokenCreator::new();
-    let wallet_balance = creator.get_wallet_balance().await?;
+    let creator = TokenCreator::with_wallet(args.wallet.as_ref())?;
     let wallet_address = creator.get_wallet_address();
     
-    // Early exit if wallet balance is below minimum required lamports
-    let required_sol = MIN_REQUIRED_LAMPORTS as f64 / LAMPORTS_PER_SOL;
-    if wallet_balance < required_sol {
-        return Err(anyhow::anyhow!(
-            "Insufficient wallet balance. Current: {:.4} SOL, Required: {:.4} SOL. Please add more SOL to your wallet.",
-            wallet_balance,
-            required_sol
-        ));
-    }
+    // Early exit (or top-up) if the wallet cannot cover the launch, before waiting on vanity addresses
+    let dry_run = std::env::var("DRY_RUN").unwrap_or_else(|_| "false".to_string()).to_lowercase() == "true";
+    let options = args.get_launch_options()?;
+    // The same estimate as the launch's own check, so a top-up here leaves nothing for that one to do
+    let cost = creator.launch_cost(&options).await?;
+    let wallet_balance = creator.ensure_launch_balance(&cost, sol_usd_price().await, dry_run, options.operator.as_deref()).await? as f64 / LAMPORTS_PER_SOL;
     
     // Check vanity status
     let (_has_vanity, _pool_size) = creator.get_vanity_status();
@@ -112,13 +2141,17 @@ pub async fn handle_token_creation(args: Args) -> Result<()> {
                     break;
                 }
                 
-                if is_generating {
-                    log::info!("Still generating vanity addresses... (generated: {}, generating: true)", generated_count);
-                } else {
-                    log::info!("Vanity address generation not running. Generated: {}", generated_count);
-                }
+                // Nothing will ever fill the pool when generation is not running, so don't wait on it
+                let eta = get_global_vanity_pool().filter(|_| is_generating).and_then(|pool| pool.estimated_wait());
+                let Some(eta) = eta else {
+                    log::warn!("Vanity address generation is not running (generated: {}); launching with a random mint address", generated_count);
+                    break;
+                };
+                log::info!("Still generating vanity addresses... (generated: {}, next expected in ~{}s)",
+                    generated_count, eta.as_secs());
                 
                 log::info!("Waiting 30 seconds before next check...");
+                progress("waiting_for_vanity", serde_json::json!({ "generated": generated_count }));
                 sleep(Duration::from_secs(30)).await;
             }
         } else {
@@ -142,25 +2175,76 @@ pub async fn handle_token_creation(args: Args) -> Result<()> {
     }
     
     // Create token using TokenCreator
-    let (signature, mint_address) = creator.create_token(
+    let started = std::time::Instant::now();
+    let outcome = creator.create_token(
         token_name.clone(),
-        args.symbol.to_uppercase(), // Symbol is always uppercase
+        symbol.to_uppercase(), // Symbol is always uppercase
         description.clone(),
         image_path, // Pass the image path (None if no image provided)
-    ).await?;
+        &options,
+    ).await;
+    let cluster = selected_cluster()?.map(|c| format!("{:?}", c).to_lowercase()).unwrap_or_else(|| "custom".to_string());
+    push_launch_metrics(&LaunchMetrics {
+        symbol: symbol.to_uppercase(),
+        cluster: cluster.clone(),
+        success: outcome.is_ok(),
+        dry_run,
+        duration: started.elapsed(),
+        warnings: options.warnings.list().len(),
+    }).await;
+    let result = outcome?;
+    let (signature, mint_address) = (result.signature, result.mint);
     
     // Print success message with vanity status
     if is_vanity_enabled && final_has_vanity {
-        log::info!("{} deployed successfully with vanity address!", args.symbol.to_uppercase());
+        log::info!("{} deployed successfully with vanity address!", symbol.to_uppercase());
     } else {
-        log::info!("{} deployed successfully!", args.symbol.to_uppercase());
+        log::info!("{} deployed successfully!", symbol.to_uppercase());
     }
     
     log::info!("Name: {}", token_name);
-    log::info!("Symbol: {}", args.symbol.to_uppercase());
+    log::info!("Symbol: {}", symbol.to_uppercase());
     log::info!("Description: {}", description);
     log::info!("Contract: {}", mint_address);
     log::info!("Transaction: {}", signature);
+    for warning in &result.warnings {
+        log::info!("Warning [{}] {}: {}", warning.severity.label(), warning.code, warning.message);
+    }
     
-    Ok(())
+    if dry_run {
+        return Ok(None);
+    }
+    let notifier = Notifier::from_env();
+    notifier.notify(Notification::new(
+        "launch_confirmed",
+        Some(mint_address.to_string()),
+        format!("{} launched at {} ({})", symbol.to_uppercase(), mint_address, signature),
+    )).await;
+    LaunchExporter::from_env().export(&LaunchRow::new(
+        &symbol.to_uppercase(), &token_name, &mint_address.to_string(), &signature.to_string(),
+        &wallet_address.to_string(), &cluster, options.operator.as_deref(),
+    )).await;
+    if let Some(dir) = &result.launch_dir {
+        match ArtifactStore::from_env() {
+            Ok(Some(store)) => store.upload_launch_dir(dir).await,
+            Ok(None) => {}
+            Err(e) => log::warn!("Not uploading launch artifacts: {}", e),
+        }
+    }
+    verify_launch_pins(creator.rpc(), creator.program_config(), &mint_address, &symbol.to_uppercase()).await;
+    // Monitoring is best-effort; the launch itself already succeeded. It runs in the background so
+    // a websocket monitor does not hold up the next queued launch.
+    match MintMonitor::from_env() {
+        Ok(MintMonitor::Off) => {}
+        Ok(monitor) => {
+            tokio::spawn(async move {
+                if let Err(e) = monitor.start(&mint_address, &notifier).await {
+                    log::warn!("Failed to start monitoring {}: {}", mint_address, e);
+                }
+            });
+        }
+        Err(e) => log::warn!("Not monitoring {}: {}", mint_address, e),
+    }
+    
+    Ok(Some(result))
 }
\ No newline at end of file
@@ -0,0 +1,189 @@
+use anyhow::Result;
+use log::{info, warn};
+use serde::Serialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use std::env;
+use std::time::Duration;
+
+use crate::create_token::ProgramConfig;
+use crate::secure_credentials::SecureApiKey;
+
+const PINATA_PIN_BY_HASH_URL: &str = "https://api.pinata.cloud/pinning/pinByHash";
+/// Gateways asked for each CID unless `PIN_CHECK_GATEWAYS` is set
+pub const DEFAULT_PIN_CHECK_GATEWAYS: &[&str] = &["https://ipfs.io", "https://dweb.link", "https://gateway.pinata.cloud"];
+
+/// Whether one gateway served a CID
+#[derive(Debug, Clone, Serialize)]
+pub struct GatewayCheck {
+    pub gateway: String,
+    pub retrievable: bool,
+    pub detail: String,
+}
+
+/// Retrievability of one launch asset
+#[derive(Debug, Clone, Serialize)]
+pub struct PinReport {
+    /// "metadata" or "image"
+    pub asset: String,
+    pub uri: String,
+    pub cid: Option<String>,
+    pub checks: Vec<GatewayCheck>,
+    /// Set when re-pinning was requested
+    pub repinned: Option<bool>,
+}
+
+impl PinReport {
+    pub fn retrievable(&self) -> bool {
+        self.checks.iter().any(|c| c.retrievable)
+    }
+}
+
+/// CID (with any path) of an `ipfs://`, path-gateway (`/ipfs/<cid>`) or subdomain-gateway URI
+pub fn extract_cid(uri: &str) -> Option<String> {
+    if let Some(rest) = uri.strip_prefix("ipfs://") {
+        return Some(rest.trim_start_matches("ipfs/").to_string()).filter(|c| !c.is_empty());
+    }
+    let url = url::Url::parse(uri).ok()?;
+    if let Some(rest) = url.path().strip_prefix("/ipfs/") {
+        return Some(rest.trim_end_matches('/').to_string()).filter(|c| !c.is_empty());
+    }
+    let host = url.host_str()?;
+    let (cid, _) = host.split_once(".ipfs.")?;
+    let path = url.path().trim_matches('/');
+    Some(if path.is_empty() { cid.to_string() } else { format!("{}/{}", cid, path) })
+}
+
+/// URI stored in a Metaplex metadata account (after key, update authority, mint, name and symbol)
+pub fn parse_metaplex_uri(data: &[u8]) -> Option<String> {
+    let mut offset = 1 + 32 + 32;
+    let mut read_string = || {
+        let len = u32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?) as usize;
+        let value = data.get(offset + 4..offset + 4 + len)?;
+        offset += 4 + len;
+        Some(String::from_utf8_lossy(value).trim_end_matches('\0').to_string())
+    };
+    let (_name, _symbol) = (read_string()?, read_string()?);
+    read_string()
+}
+
+fn gateways_from_env() -> Vec<String> {
+    match env::var("PIN_CHECK_GATEWAYS") {
+        Ok(list) => list.split(',').map(|g| g.trim().trim_end_matches('/').to_string()).filter(|g| !g.is_empty()).collect(),
+        Err(_) => DEFAULT_PIN_CHECK_GATEWAYS.iter().map(|g| g.to_string()).collect(),
+    }
+}
+
+async fn check_gateways(client: &reqwest::Client, cid: &str, gateways: &[String]) -> Vec<GatewayCheck> {
+    let mut checks = Vec::new();
+    for gateway in gateways {
+        let (retrievable, detail) = match client.head(format!("{}/ipfs/{}", gateway, cid)).send().await {
+            Ok(response) => (response.status().is_success(), response.status().to_string()),
+            Err(e) => (false, e.to_string()),
+        };
+        checks.push(GatewayCheck { gateway: gateway.clone(), retrievable, detail });
+    }
+    checks
+}
+
+/// Ask Pinata to pin `cid` to the account of `PINATA_JWT`
+pub async fn repin_to_pinata(client: &reqwest::Client, cid: &str, name: &str) -> Result<()> {
+    let jwt = SecureApiKey::load("PINATA_JWT")?;
+    // pinByHash takes the root CID; paths inside it are covered by pinning the root
+    let root = cid.split('/').next().unwrap_or(cid);
+    client.post(PINATA_PIN_BY_HASH_URL)
+        .bearer_auth(jwt.expose_secret())
+        .json(&serde_json::json!({ "hashToPin": root, "pinataMetadata": { "name": name } }))
+        .send().await?
+        .error_for_status()?;
+    Ok(())
+}
+
+/// Check the metadata at `metadata_uri` and the image it points to on public gateways, re-pinning
+/// both to Pinata when `repin` is set
+pub async fn verify_pins(metadata_uri: &str, label: &str, repin: bool) -> Result<Vec<PinReport>> {
+    let client = reqwest::Client::builder().timeout(Duration::from_secs(20)).build()?;
+    let gateways = gateways_from_env();
+    let mut assets = vec![("metadata".to_string(), metadata_uri.to_string())];
+    match client.get(metadata_uri).send().await.and_then(|r| r.error_for_status()) {
+        Ok(response) => {
+            let metadata: serde_json::Value = response.json().await?;
+            if let Some(image) = metadata["image"].as_str() {
+                assets.push(("image".to_string(), image.to_string()));
+            }
+        }
+        Err(e) => warn!("Cannot read metadata at {}: {}", metadata_uri, e),
+    }
+
+    let mut reports = Vec::new();
+    for (asset, uri) in assets {
+        let cid = extract_cid(&uri);
+        let checks = match &cid {
+            Some(cid) => check_gateways(&client, cid, &gateways).await,
+            None => Vec::new(),
+        };
+        let repinned = match (&cid, repin) {
+            (Some(cid), true) => Some(match repin_to_pinata(&client, cid, &format!("{} {}", label, asset)).await {
+                Ok(()) => {
+                    info!("Re-pinned {} {} to Pinata", asset, cid);
+                    true
+                }
+                Err(e) => {
+                    warn!("Failed to re-pin {} {} to Pinata: {}", asset, cid, e);
+                    false
+                }
+            }),
+            _ => None,
+        };
+        reports.push(PinReport { asset, uri, cid, checks, repinned });
+    }
+    Ok(reports)
+}
+
+/// Metadata URI recorded on chain for `mint`
+pub async fn onchain_metadata_uri(rpc: &RpcClient, program: &ProgramConfig, mint: &Pubkey) -> Result<String> {
+    let data = rpc.get_account_data(&program.metadata_pda(mint)).await?;
+    parse_metaplex_uri(&data).ok_or_else(|| anyhow::anyhow!("Cannot read the metadata account of {}", mint))
+}
+
+/// After a launch, verify (and with `REPIN_TO_PINATA=true` re-pin) its assets when `VERIFY_PINS=true`.
+/// Never fails the launch.
+pub async fn verify_launch_pins(rpc: &RpcClient, program: &ProgramConfig, mint: &Pubkey, symbol: &str) {
+    let enabled = |var: &str| env::var(var).map(|v| v.trim().eq_ignore_ascii_case("true")).unwrap_or(false);
+    if !enabled("VERIFY_PINS") {
+        return;
+    }
+    let result = async {
+        let uri = onchain_metadata_uri(rpc, program, mint).await?;
+        verify_pins(&uri, symbol, enabled("REPIN_TO_PINATA")).await
+    }.await;
+    match result {
+        Ok(reports) => {
+            for report in reports.iter().filter(|r| !r.retrievable()) {
+                warn!("{} {} is not retrievable from any checked gateway", report.asset, report.uri);
+            }
+        }
+        Err(e) => warn!("Could not verify pins of {}: {}", mint, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_cid() {
+        assert_eq!(extract_cid("https://ipfs.io/ipfs/QmAbc").as_deref(), Some("QmAbc"));
+        assert_eq!(extract_cid("https://cf-ipfs.com/ipfs/QmAbc/image.png").as_deref(), Some("QmAbc/image.png"));
+        assert_eq!(extract_cid("ipfs://bafyabc").as_deref(), Some("bafyabc"));
+        assert_eq!(extract_cid("https://bafyabc.ipfs.dweb.link/").as_deref(), Some("bafyabc"));
+        assert_eq!(extract_cid("https://example.com/token.json"), None);
+
+        let mut data = vec![4u8; 65];
+        for value in ["PVE Token", "PVE", "https://ipfs.io/ipfs/QmAbc\0\0"] {
+            data.extend_from_slice(&(value.len() as u32).to_le_bytes());
+            data.extend_from_slice(value.as_bytes());
+        }
+        assert_eq!(parse_metaplex_uri(&data).as_deref(), Some("https://ipfs.io/ipfs/QmAbc"));
+    }
+}
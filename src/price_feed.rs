@@ -0,0 +1,138 @@
+use anyhow::Result;
+use log::warn;
+use std::env;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::create_token::LAMPORTS_PER_SOL;
+
+/// Pyth Hermes endpoint and the SOL/USD price feed id
+pub const PYTH_HERMES_URL: &str = "https://hermes.pyth.network";
+pub const PYTH_SOL_USD_FEED_ID: &str = "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
+/// Default REST oracle when `SOL_PRICE_FEED=rest` and no URL is given
+pub const DEFAULT_REST_PRICE_URL: &str = "https://api.coingecko.com/api/v3/simple/price?ids=solana&vs_currencies=usd";
+const DEFAULT_REST_JSON_POINTER: &str = "/solana/usd";
+const PRICE_CACHE_TTL: Duration = Duration::from_secs(60);
+
+// Last fetched price, shared by every caller in the process
+static PRICE_CACHE: LazyLock<Mutex<Option<(Instant, f64)>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Where SOL/USD prices come from, selected by `SOL_PRICE_FEED`
+#[derive(Debug, Clone, PartialEq)]
+pub enum PriceFeed {
+    /// Pyth Hermes
+    Pyth { url: String, feed_id: String },
+    /// Any JSON endpoint; the price is read at `json_pointer`
+    Rest { url: String, json_pointer: String },
+    /// No USD values are shown (default)
+    Disabled,
+}
+
+impl PriceFeed {
+    /// Read `SOL_PRICE_FEED` (pyth/rest/off), `SOL_PRICE_URL`, `SOL_PRICE_FEED_ID` and `SOL_PRICE_JSON_POINTER`
+    pub fn from_env() -> Result<Self> {
+        let source = env::var("SOL_PRICE_FEED").unwrap_or_default();
+        match source.trim().to_lowercase().as_str() {
+            "pyth" => Ok(PriceFeed::Pyth {
+                url: env::var("SOL_PRICE_URL").unwrap_or_else(|_| PYTH_HERMES_URL.to_string()),
+                feed_id: env::var("SOL_PRICE_FEED_ID").unwrap_or_else(|_| PYTH_SOL_USD_FEED_ID.to_string()),
+            }),
+            "rest" => Ok(PriceFeed::Rest {
+                url: env::var("SOL_PRICE_URL").unwrap_or_else(|_| DEFAULT_REST_PRICE_URL.to_string()),
+                json_pointer: env::var("SOL_PRICE_JSON_POINTER").unwrap_or_else(|_| DEFAULT_REST_JSON_POINTER.to_string()),
+            }),
+            "" | "off" | "none" | "disabled" | "false" => Ok(PriceFeed::Disabled),
+            other => Err(anyhow::anyhow!("Unknown SOL_PRICE_FEED '{}' (expected pyth, rest or off)", other)),
+        }
+    }
+
+    /// Fetch the current SOL/USD price from the configured source
+    pub async fn fetch_sol_usd(&self) -> Result<Option<f64>> {
+        let client = reqwest::Client::builder().timeout(Duration::from_secs(5)).build()?;
+        match self {
+            PriceFeed::Pyth { url, feed_id } => {
+                let response: serde_json::Value = client
+                    .get(format!("{}/v2/updates/price/latest", url.trim_end_matches('/')))
+                    .query(&[("ids[]", feed_id.as_str()), ("parsed", "true")])
+                    .send().await?
+                    .error_for_status()?
+                    .json().await?;
+                parse_pyth_price(&response).map(Some)
+            }
+            PriceFeed::Rest { url, json_pointer } => {
+                let response: serde_json::Value = client.get(url).send().await?.error_for_status()?.json().await?;
+                let value = response.pointer(json_pointer)
+                    .ok_or_else(|| anyhow::anyhow!("No value at {} in price response", json_pointer))?;
+                let price = value.as_f64()
+                    .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+                    .ok_or_else(|| anyhow::anyhow!("Price at {} is not a number", json_pointer))?;
+                Ok(Some(price))
+            }
+            PriceFeed::Disabled => Ok(None),
+        }
+    }
+}
+
+/// Price from a Hermes `/v2/updates/price/latest?parsed=true` response
+fn parse_pyth_price(response: &serde_json::Value) -> Result<f64> {
+    let price = &response["parsed"][0]["price"];
+    let mantissa: i64 = price["price"].as_str()
+        .and_then(|p| p.parse().ok())
+        .ok_or_else(|| anyhow::anyhow!("Missing price in Pyth response"))?;
+    let expo = price["expo"].as_i64()
+        .ok_or_else(|| anyhow::anyhow!("Missing expo in Pyth response"))?;
+    Ok(mantissa as f64 * 10f64.powi(expo as i32))
+}
+
+/// Current SOL/USD price, cached for a minute. `None` unless `SOL_PRICE_FEED` is set, or when the feed is unavailable.
+pub async fn sol_usd_price() -> Option<f64> {
+    if let Some((fetched_at, price)) = *PRICE_CACHE.lock().unwrap() {
+        if fetched_at.elapsed() < PRICE_CACHE_TTL {
+            return Some(price);
+        }
+    }
+
+    let feed = match PriceFeed::from_env() {
+        Ok(feed) => feed,
+        Err(e) => {
+            warn!("{}; USD values disabled", e);
+            return None;
+        }
+    };
+    match feed.fetch_sol_usd().await {
+        Ok(Some(price)) => {
+            *PRICE_CACHE.lock().unwrap() = Some((Instant::now(), price));
+            Some(price)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            warn!("Failed to fetch SOL/USD price: {}", e);
+            None
+        }
+    }
+}
+
+/// "0.0155 SOL (~$2.33)", or just the SOL amount when no price is available
+pub fn format_sol(lamports: u64, sol_usd: Option<f64>) -> String {
+    let sol = lamports as f64 / LAMPORTS_PER_SOL;
+    match sol_usd {
+        Some(price) => format!("{} SOL (~${:.2})", sol, sol * price),
+        None => format!("{} SOL", sol),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pyth_price_and_format() {
+        let response = serde_json::json!({
+            "parsed": [{ "price": { "price": "15012345678", "conf": "1000", "expo": -8, "publish_time": 0 } }]
+        });
+        let price = parse_pyth_price(&response).unwrap();
+        assert!((price - 150.12345678).abs() < 1e-9);
+        assert_eq!(format_sol(500_000_000, Some(price)), "0.5 SOL (~$75.06)");
+        assert_eq!(format_sol(500_000_000, None), "0.5 SOL");
+    }
+}
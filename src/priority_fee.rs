@@ -0,0 +1,156 @@
+use anyhow::Result;
+use log::{info, warn};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use std::env;
+use std::str::FromStr;
+
+use crate::extra_instructions::{compute_unit_limit_instruction, compute_unit_price_instruction};
+use crate::tx_size::TransactionComponent;
+
+/// Compute units requested unless `--compute-units` or `COMPUTE_UNIT_LIMIT` is set; a create with a
+/// dev buy uses well under this, and the fee is charged on the requested limit, not the units used
+pub const DEFAULT_COMPUTE_UNIT_LIMIT: u32 = 300_000;
+/// Percentile of recent prioritization fees the automatic price pays, unless `PRIORITY_FEE_PERCENTILE` is set
+pub const DEFAULT_PRIORITY_FEE_PERCENTILE: u8 = 75;
+/// Cap on the automatic price in micro-lamports per compute unit, unless `PRIORITY_FEE_MAX_MICROLAMPORTS` is set
+pub const DEFAULT_PRIORITY_FEE_MAX_MICROLAMPORTS: u64 = 1_000_000;
+
+/// Compute unit price: estimated from recent fees or fixed in micro-lamports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriorityFee {
+    Auto,
+    Fixed(u64),
+}
+
+impl FromStr for PriorityFee {
+    type Err = anyhow::Error;
+
+    /// `auto` or a price in micro-lamports per compute unit
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("auto") {
+            return Ok(Self::Auto);
+        }
+        s.parse().map(Self::Fixed)
+            .map_err(|_| anyhow::anyhow!("Invalid priority fee '{}' (expected 'auto' or micro-lamports per compute unit)", s))
+    }
+}
+
+/// Compute budget instructions prepended to the launch transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComputeBudget {
+    pub unit_limit: u32,
+    pub priority_fee: PriorityFee,
+    /// Percentile of recent fees paid by [`PriorityFee::Auto`]
+    pub percentile: u8,
+    /// Most [`PriorityFee::Auto`] pays per compute unit
+    pub max_micro_lamports: u64,
+}
+
+impl ComputeBudget {
+    /// Flags first, then `PRIORITY_FEE` (default `auto`), `COMPUTE_UNIT_LIMIT`, `PRIORITY_FEE_PERCENTILE`
+    /// and `PRIORITY_FEE_MAX_MICROLAMPORTS`
+    pub fn resolve(priority_fee: Option<PriorityFee>, compute_units: Option<u32>) -> Result<Self> {
+        fn read<T: FromStr>(var: &str) -> Result<Option<T>> {
+            env::var(var).ok()
+                .map(|value| value.trim().parse().map_err(|_| anyhow::anyhow!("Invalid {} '{}'", var, value)))
+                .transpose()
+        }
+        let priority_fee = match priority_fee {
+            Some(fee) => fee,
+            None => env::var("PRIORITY_FEE").ok().map(|v| v.parse()).transpose()?.unwrap_or(PriorityFee::Auto),
+        };
+        let unit_limit = match compute_units {
+            Some(units) => units,
+            None => read("COMPUTE_UNIT_LIMIT")?.unwrap_or(DEFAULT_COMPUTE_UNIT_LIMIT),
+        };
+        if unit_limit == 0 || unit_limit > 1_400_000 {
+            return Err(anyhow::anyhow!("Compute unit limit must be between 1 and 1400000, got {}", unit_limit));
+        }
+        let percentile = read("PRIORITY_FEE_PERCENTILE")?.unwrap_or(DEFAULT_PRIORITY_FEE_PERCENTILE);
+        if percentile > 100 {
+            return Err(anyhow::anyhow!("PRIORITY_FEE_PERCENTILE must be at most 100, got {}", percentile));
+        }
+        Ok(Self {
+            unit_limit,
+            priority_fee,
+            percentile,
+            max_micro_lamports: read("PRIORITY_FEE_MAX_MICROLAMPORTS")?.unwrap_or(DEFAULT_PRIORITY_FEE_MAX_MICROLAMPORTS),
+        })
+    }
+
+    /// Price for the next attempt; an estimate that fails falls back to no priority fee
+    pub async fn unit_price(&self, rpc: &RpcClient, writable_accounts: &[Pubkey]) -> u64 {
+        match self.priority_fee {
+            PriorityFee::Fixed(price) => price,
+            PriorityFee::Auto => match rpc.get_recent_prioritization_fees(writable_accounts).await {
+                Ok(fees) => {
+                    let fees: Vec<u64> = fees.iter().map(|fee| fee.prioritization_fee).collect();
+                    let price = fee_percentile(&fees, self.percentile).min(self.max_micro_lamports);
+                    info!("Priority fee: {} micro-lamports/CU (p{} of {} recent slots)", price, self.percentile, fees.len());
+                    price
+                }
+                Err(e) => {
+                    warn!("Cannot estimate the priority fee ({}); sending without one", e);
+                    0
+                }
+            },
+        }
+    }
+
+    /// Most the priority fee can cost in lamports
+    pub fn max_fee_lamports(&self) -> u64 {
+        let price = match self.priority_fee {
+            PriorityFee::Fixed(price) => price,
+            PriorityFee::Auto => self.max_micro_lamports,
+        };
+        (price as u128 * self.unit_limit as u128).div_ceil(1_000_000) as u64
+    }
+
+    pub fn components(&self, unit_price: u64) -> Vec<TransactionComponent> {
+        let mut components = vec![TransactionComponent::new(
+            format!("compute unit limit {}", self.unit_limit), compute_unit_limit_instruction(self.unit_limit))];
+        if unit_price > 0 {
+            components.push(TransactionComponent::new(
+                format!("compute unit price {} micro-lamports", unit_price), compute_unit_price_instruction(unit_price)));
+        }
+        components
+    }
+}
+
+/// Nearest-rank `percentile` of `fees`, 0 when there are none
+pub fn fee_percentile(fees: &[u64], percentile: u8) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+    let mut sorted = fees.to_vec();
+    sorted.sort_unstable();
+    let rank = (percentile as usize * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extra_instructions::priority_fee_lamports;
+
+    #[test]
+    fn test_compute_budget() {
+        assert_eq!("auto".parse::<PriorityFee>().unwrap(), PriorityFee::Auto);
+        assert_eq!("25000".parse::<PriorityFee>().unwrap(), PriorityFee::Fixed(25_000));
+        assert!("fast".parse::<PriorityFee>().is_err());
+
+        let fees = [0, 0, 100, 200, 300, 400, 500, 600, 700, 10_000];
+        assert_eq!(fee_percentile(&fees, 75), 600);
+        assert_eq!(fee_percentile(&fees, 100), 10_000);
+        assert_eq!(fee_percentile(&fees, 0), 0);
+        assert_eq!(fee_percentile(&[], 75), 0);
+
+        let budget = ComputeBudget { unit_limit: 200_000, priority_fee: PriorityFee::Fixed(5_000_000), percentile: 75, max_micro_lamports: 1 };
+        assert_eq!(budget.max_fee_lamports(), 1_000_000);
+        assert_eq!(priority_fee_lamports(&budget.components(5_000_000)), Some(budget.max_fee_lamports()));
+        assert_eq!(budget.components(0).len(), 1);
+        assert!(ComputeBudget::resolve(None, Some(0)).is_err());
+    }
+}
@@ -0,0 +1,54 @@
+use serde_json::json;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static PROGRESS_JSON: AtomicBool = AtomicBool::new(false);
+
+/// Emit progress events for the rest of the process (`--progress-json` or `PROGRESS_JSON=true`)
+pub fn enable_progress_json() {
+    PROGRESS_JSON.store(true, Ordering::Relaxed);
+}
+
+pub fn progress_json_enabled() -> bool {
+    PROGRESS_JSON.load(Ordering::Relaxed)
+        || std::env::var("PROGRESS_JSON").is_ok_and(|v| v.eq_ignore_ascii_case("true"))
+}
+
+/// One progress event as a single JSON line: `{"event":"progress","stage":..,"timestamp":..,<fields>}`
+pub fn progress_line(stage: &str, fields: serde_json::Value) -> String {
+    let mut event = json!({
+        "event": "progress",
+        "stage": stage,
+        "timestamp": SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0),
+    });
+    if let (Some(event), serde_json::Value::Object(fields)) = (event.as_object_mut(), fields) {
+        event.extend(fields);
+    }
+    event.to_string()
+}
+
+/// Write a progress event to stderr when enabled; human-readable logs are unaffected
+pub fn progress(stage: &str, fields: serde_json::Value) {
+    if !progress_json_enabled() {
+        return;
+    }
+    let mut stderr = std::io::stderr().lock();
+    let _ = writeln!(stderr, "{}", progress_line(stage, fields));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_line() {
+        let line = progress_line("metadata_uploaded", json!({ "uri": "https://ipfs.io/x" }));
+        let event: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(event["event"], "progress");
+        assert_eq!(event["stage"], "metadata_uploaded");
+        assert_eq!(event["uri"], "https://ipfs.io/x");
+        assert!(event["timestamp"].as_u64().unwrap() > 0);
+        assert!(!line.contains('\n'));
+    }
+}
@@ -0,0 +1,430 @@
+use anyhow::Result;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// Account discriminators from the IDL
+const GLOBAL_ACCOUNT_DISCRIMINATOR: &[u8] = &[167, 232, 232, 177, 200, 108, 114, 127];
+const BONDING_CURVE_DISCRIMINATOR: &[u8] = &[23, 183, 248, 55, 96, 216, 172, 96];
+
+/// pump.fun tokens are minted with 6 decimals
+pub const TOKEN_DECIMALS: u8 = 6;
+pub const TOKEN_UNIT: f64 = 1_000_000.0;
+
+/// Minimal little-endian reader for Anchor account data
+pub(crate) struct AccountReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> AccountReader<'a> {
+    pub(crate) fn new(data: &'a [u8], discriminator: &[u8], name: &str) -> Result<Self> {
+        if data.len() < discriminator.len() || &data[..discriminator.len()] != discriminator {
+            return Err(anyhow::anyhow!("Account data is not a {} account", name));
+        }
+        Ok(Self { data, offset: discriminator.len() })
+    }
+
+    pub(crate) fn remaining(&self) -> usize {
+        self.data.len().saturating_sub(self.offset)
+    }
+
+    pub(crate) fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.remaining() < len {
+            return Err(anyhow::anyhow!("Account data truncated at offset {}", self.offset));
+        }
+        let slice = &self.data[self.offset..self.offset + len];
+        self.offset += len;
+        Ok(slice)
+    }
+
+    pub(crate) fn read_bool(&mut self) -> Result<bool> {
+        Ok(self.take(1)?[0] != 0)
+    }
+
+    pub(crate) fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub(crate) fn read_u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    pub(crate) fn read_u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    pub(crate) fn read_u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    pub(crate) fn read_i64(&mut self) -> Result<i64> {
+        Ok(i64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    pub(crate) fn read_pubkey(&mut self) -> Result<Pubkey> {
+        Ok(Pubkey::new_from_array(self.take(32)?.try_into().unwrap()))
+    }
+
+    /// Borsh `Vec<Pubkey>` (u32 length prefix)
+    pub(crate) fn read_pubkey_vec(&mut self) -> Result<Vec<Pubkey>> {
+        let len = self.read_u32()? as usize;
+        (0..len).map(|_| self.read_pubkey()).collect()
+    }
+}
+
+/// Decoded pump.fun `Global` account (fees, initial curve reserves, fee recipients).
+///
+/// Fields added by later program versions are optional so older deployments still decode.
+#[derive(Debug, Clone)]
+pub struct GlobalAccount {
+    pub initialized: bool,
+    pub authority: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub initial_virtual_token_reserves: u64,
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_real_token_reserves: u64,
+    pub token_total_supply: u64,
+    pub fee_basis_points: u64,
+    pub withdraw_authority: Pubkey,
+    pub enable_migrate: bool,
+    pub pool_migration_fee: Option<u64>,
+    pub creator_fee_basis_points: Option<u64>,
+    pub fee_recipients: Vec<Pubkey>,
+}
+
+impl GlobalAccount {
+    pub fn from_account_data(data: &[u8]) -> Result<Self> {
+        let mut reader = AccountReader::new(data, GLOBAL_ACCOUNT_DISCRIMINATOR, "Global")?;
+
+        let mut global = GlobalAccount {
+            initialized: reader.read_bool()?,
+            authority: reader.read_pubkey()?,
+            fee_recipient: reader.read_pubkey()?,
+            initial_virtual_token_reserves: reader.read_u64()?,
+            initial_virtual_sol_reserves: reader.read_u64()?,
+            initial_real_token_reserves: reader.read_u64()?,
+            token_total_supply: reader.read_u64()?,
+            fee_basis_points: reader.read_u64()?,
+            withdraw_authority: reader.read_pubkey()?,
+            enable_migrate: reader.read_bool()?,
+            pool_migration_fee: None,
+            creator_fee_basis_points: None,
+            fee_recipients: Vec::new(),
+        };
+
+        if reader.remaining() >= 8 {
+            global.pool_migration_fee = Some(reader.read_u64()?);
+        }
+        if reader.remaining() >= 8 {
+            global.creator_fee_basis_points = Some(reader.read_u64()?);
+        }
+        if reader.remaining() >= 32 * 7 {
+            for _ in 0..7 {
+                global.fee_recipients.push(reader.read_pubkey()?);
+            }
+        }
+
+        Ok(global)
+    }
+
+    /// Total fee charged on trades, in basis points (protocol + creator)
+    pub fn total_fee_basis_points(&self) -> u64 {
+        self.fee_basis_points.saturating_add(self.creator_fee_basis_points.unwrap_or(0))
+    }
+
+    /// Price of one whole token on a freshly created curve, in lamports
+    pub fn initial_price_lamports(&self) -> f64 {
+        if self.initial_virtual_token_reserves == 0 {
+            return 0.0;
+        }
+        self.initial_virtual_sol_reserves as f64 / self.initial_virtual_token_reserves as f64 * TOKEN_UNIT
+    }
+
+    /// Accounts a buy may pay its protocol fee to: the primary recipient plus the rotation list
+    pub fn fee_recipient_candidates(&self) -> Vec<Pubkey> {
+        let mut candidates = vec![self.fee_recipient];
+        for recipient in &self.fee_recipients {
+            if *recipient != Pubkey::default() && !candidates.contains(recipient) {
+                candidates.push(*recipient);
+            }
+        }
+        candidates
+    }
+
+    /// The bonding curve a create instruction initializes under this config
+    pub fn initial_curve(&self, creator: Option<Pubkey>) -> BondingCurveAccount {
+        BondingCurveAccount {
+            virtual_token_reserves: self.initial_virtual_token_reserves,
+            virtual_sol_reserves: self.initial_virtual_sol_reserves,
+            real_token_reserves: self.initial_real_token_reserves,
+            real_sol_reserves: 0,
+            token_total_supply: self.token_total_supply,
+            complete: false,
+            creator,
+        }
+    }
+}
+
+/// Fee recipients read from the global account, reused until `ttl` passes or a buy fails.
+///
+/// Lookups rotate through the candidates the same way the pump.fun frontend spreads buys
+/// over its fee accounts.
+pub struct FeeRecipientCache {
+    entry: Mutex<Option<(Vec<Pubkey>, Instant)>>,
+    next: AtomicUsize,
+    ttl: Duration,
+}
+
+impl FeeRecipientCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self { entry: Mutex::new(None), next: AtomicUsize::new(0), ttl }
+    }
+
+    /// Next cached recipient, or `None` when the cache is empty or stale
+    pub fn get(&self) -> Option<Pubkey> {
+        let entry = self.entry.lock().unwrap();
+        let (candidates, fetched_at) = entry.as_ref()?;
+        if candidates.is_empty() || fetched_at.elapsed() >= self.ttl {
+            return None;
+        }
+        Some(candidates[self.next.fetch_add(1, Ordering::Relaxed) % candidates.len()])
+    }
+
+    pub fn set(&self, candidates: Vec<Pubkey>) {
+        *self.entry.lock().unwrap() = Some((candidates, Instant::now()));
+    }
+
+    /// Forget the cached recipients so the next lookup re-reads the global account
+    pub fn invalidate(&self) {
+        *self.entry.lock().unwrap() = None;
+    }
+}
+
+/// Decoded pump.fun `BondingCurve` account.
+///
+/// `creator` is only present on program versions with creator vaults.
+#[derive(Debug, Clone)]
+pub struct BondingCurveAccount {
+    pub virtual_token_reserves: u64,
+    pub virtual_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub token_total_supply: u64,
+    pub complete: bool,
+    pub creator: Option<Pubkey>,
+}
+
+impl BondingCurveAccount {
+    pub fn from_account_data(data: &[u8]) -> Result<Self> {
+        let mut reader = AccountReader::new(data, BONDING_CURVE_DISCRIMINATOR, "BondingCurve")?;
+
+        let mut curve = BondingCurveAccount {
+            virtual_token_reserves: reader.read_u64()?,
+            virtual_sol_reserves: reader.read_u64()?,
+            real_token_reserves: reader.read_u64()?,
+            real_sol_reserves: reader.read_u64()?,
+            token_total_supply: reader.read_u64()?,
+            complete: reader.read_bool()?,
+            creator: None,
+        };
+
+        if reader.remaining() >= 32 {
+            curve.creator = Some(reader.read_pubkey()?);
+        }
+
+        Ok(curve)
+    }
+
+    /// Current price of one whole token, in lamports
+    pub fn price_lamports(&self) -> f64 {
+        if self.virtual_token_reserves == 0 {
+            return 0.0;
+        }
+        self.virtual_sol_reserves as f64 / self.virtual_token_reserves as f64 * TOKEN_UNIT
+    }
+
+    /// Raw tokens received for spending `sol_lamports` on this curve, after `fee_basis_points`
+    pub fn quote_buy(&self, sol_lamports: u64, fee_basis_points: u64) -> u64 {
+        let fee = sol_lamports as u128 * fee_basis_points as u128 / 10_000;
+        let sol_in = (sol_lamports as u128).saturating_sub(fee);
+        let virtual_sol = self.virtual_sol_reserves as u128;
+        if virtual_sol + sol_in == 0 {
+            return 0;
+        }
+        let tokens_out = self.virtual_token_reserves as u128 * sol_in / (virtual_sol + sol_in);
+        tokens_out.min(self.real_token_reserves as u128) as u64
+    }
+
+    /// Lamports, fees included, that buy every remaining real token of this curve. `None` when no
+    /// amount does: the fee takes the whole buy or the virtual reserves cannot cover the real ones.
+    pub fn buy_out_cost(&self, fee_basis_points: u64) -> Option<u64> {
+        let virtual_tokens = self.virtual_token_reserves as u128;
+        let real_tokens = self.real_token_reserves as u128;
+        if fee_basis_points >= 10_000 || virtual_tokens <= real_tokens {
+            return None;
+        }
+        let sol_in = (self.virtual_sol_reserves as u128 * real_tokens).div_ceil(virtual_tokens - real_tokens);
+        let gross = (sol_in * 10_000).div_ceil(10_000 - fee_basis_points as u128);
+        u64::try_from(gross).ok()
+    }
+
+    /// Lamports received for selling `token_amount` raw tokens to this curve, after `fee_basis_points`
+    pub fn quote_sell(&self, token_amount: u64, fee_basis_points: u64) -> u64 {
+        let virtual_tokens = self.virtual_token_reserves as u128;
+        if virtual_tokens + token_amount as u128 == 0 {
+            return 0;
+        }
+        let sol_out = self.virtual_sol_reserves as u128 * token_amount as u128 / (virtual_tokens + token_amount as u128);
+        let sol_out = sol_out.min(self.real_sol_reserves as u128);
+        sol_out.saturating_sub(sol_out * fee_basis_points as u128 / 10_000) as u64
+    }
+
+    /// Market cap of the full supply at the current price, in lamports
+    pub fn market_cap_lamports(&self) -> u64 {
+        if self.virtual_token_reserves == 0 {
+            return 0;
+        }
+        (self.virtual_sol_reserves as u128 * self.token_total_supply as u128 / self.virtual_token_reserves as u128) as u64
+    }
+
+    /// Share of the curve's sellable tokens already bought, 0-100
+    pub fn progress_percent(&self, initial_real_token_reserves: u64) -> f64 {
+        if self.complete {
+            return 100.0;
+        }
+        if initial_real_token_reserves == 0 {
+            return 0.0;
+        }
+        let sold = initial_real_token_reserves.saturating_sub(self.real_token_reserves);
+        sold as f64 * 100.0 / initial_real_token_reserves as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_recipient_cache_rotates_and_invalidates() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let cache = FeeRecipientCache::new(Duration::from_secs(60));
+        assert_eq!(cache.get(), None);
+
+        cache.set(vec![a, b]);
+        assert_eq!((cache.get(), cache.get(), cache.get()), (Some(a), Some(b), Some(a)));
+
+        cache.invalidate();
+        assert_eq!(cache.get(), None);
+
+        let expired = FeeRecipientCache::new(Duration::ZERO);
+        expired.set(vec![a]);
+        assert_eq!(expired.get(), None);
+    }
+
+    /// Global account bytes as the Legacy program wrote them (146 bytes), optionally followed by
+    /// the pool migration and creator fees of the CreatorVault layout (162 bytes)
+    fn global_fixture(creator_vault: bool) -> Vec<u8> {
+        let mut data = GLOBAL_ACCOUNT_DISCRIMINATOR.to_vec();
+        data.push(1);
+        data.extend_from_slice(&[1; 32]);
+        data.extend_from_slice(&[2; 32]);
+        for value in [1_073_000_000_000_000u64, 30_000_000_000, 793_100_000_000_000, 1_000_000_000_000_000, 95] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(&[3; 32]);
+        data.push(1);
+        if creator_vault {
+            data.extend_from_slice(&15_000_000u64.to_le_bytes());
+            data.extend_from_slice(&5u64.to_le_bytes());
+        }
+        data
+    }
+
+    fn bonding_curve_fixture(creator: Option<[u8; 32]>) -> Vec<u8> {
+        let mut data = BONDING_CURVE_DISCRIMINATOR.to_vec();
+        for value in [1_000_000_000_000_000u64, 32_190_000_000, 723_100_000_000_000, 2_190_000_000, 1_000_000_000_000_000] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.push(0);
+        if let Some(creator) = creator {
+            data.extend_from_slice(&creator);
+        }
+        data
+    }
+
+    #[test]
+    fn test_decode_account_layouts() {
+        use crate::create_token::ProgramVersion;
+
+        let legacy = global_fixture(false);
+        assert_eq!(legacy.len(), 146);
+        assert_eq!(ProgramVersion::detect_from_global(&legacy), ProgramVersion::Legacy);
+        let global = GlobalAccount::from_account_data(&legacy).unwrap();
+        assert!(global.initialized && global.enable_migrate);
+        assert_eq!((global.authority, global.fee_recipient, global.withdraw_authority),
+                   (Pubkey::new_from_array([1; 32]), Pubkey::new_from_array([2; 32]), Pubkey::new_from_array([3; 32])));
+        assert_eq!(global.initial_virtual_token_reserves, 1_073_000_000_000_000);
+        assert_eq!(global.initial_virtual_sol_reserves, 30_000_000_000);
+        assert_eq!(global.initial_real_token_reserves, 793_100_000_000_000);
+        assert_eq!(global.token_total_supply, 1_000_000_000_000_000);
+        assert_eq!((global.pool_migration_fee, global.creator_fee_basis_points), (None, None));
+        assert_eq!(global.total_fee_basis_points(), 95);
+
+        let creator_vault = global_fixture(true);
+        assert_eq!(creator_vault.len(), 162);
+        assert_eq!(ProgramVersion::detect_from_global(&creator_vault), ProgramVersion::CreatorVault);
+        let global = GlobalAccount::from_account_data(&creator_vault).unwrap();
+        assert_eq!((global.pool_migration_fee, global.creator_fee_basis_points), (Some(15_000_000), Some(5)));
+        assert_eq!(global.total_fee_basis_points(), 100);
+        assert!(global.fee_recipients.is_empty());
+        assert!(GlobalAccount::from_account_data(&creator_vault[..100]).is_err());
+        assert!(GlobalAccount::from_account_data(&bonding_curve_fixture(None)).is_err());
+
+        let legacy_curve = bonding_curve_fixture(None);
+        assert_eq!(legacy_curve.len(), 49);
+        let curve = BondingCurveAccount::from_account_data(&legacy_curve).unwrap();
+        assert_eq!((curve.virtual_token_reserves, curve.virtual_sol_reserves), (1_000_000_000_000_000, 32_190_000_000));
+        assert_eq!((curve.real_token_reserves, curve.real_sol_reserves), (723_100_000_000_000, 2_190_000_000));
+        assert_eq!(curve.token_total_supply, 1_000_000_000_000_000);
+        assert!(!curve.complete);
+        assert_eq!(curve.creator, None);
+
+        let creator_vault_curve = bonding_curve_fixture(Some([7; 32]));
+        assert_eq!(creator_vault_curve.len(), 81);
+        let curve = BondingCurveAccount::from_account_data(&creator_vault_curve).unwrap();
+        assert_eq!(curve.creator, Some(Pubkey::new_from_array([7; 32])));
+        assert_eq!(curve.market_cap_lamports(), 32_190_000_000);
+        assert!(BondingCurveAccount::from_account_data(&legacy_curve[..40]).is_err());
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_curve_quotes_stay_within_reserves(
+            virtual_tokens in 0..=u64::MAX, virtual_sol in 0..=u64::MAX, real_tokens in 0..=u64::MAX, real_sol in 0..=u64::MAX,
+            amount in 0..=u64::MAX, more in 0..=u64::MAX, fee_bps in 0u64..=20_000,
+        ) {
+            let curve = BondingCurveAccount {
+                virtual_token_reserves: virtual_tokens,
+                virtual_sol_reserves: virtual_sol,
+                real_token_reserves: real_tokens,
+                real_sol_reserves: real_sol,
+                token_total_supply: virtual_tokens,
+                complete: false,
+                creator: None,
+            };
+            let bought = curve.quote_buy(amount, fee_bps);
+            proptest::prop_assert!(bought <= real_tokens && bought <= virtual_tokens);
+            let sold = curve.quote_sell(amount, fee_bps);
+            proptest::prop_assert!(sold <= real_sol && sold <= virtual_sol);
+            // Paying or selling more never gets less back
+            let larger = amount.saturating_add(more);
+            proptest::prop_assert!(curve.quote_buy(larger, fee_bps) >= bought);
+            proptest::prop_assert!(curve.quote_sell(larger, fee_bps) >= sold);
+            if fee_bps >= 10_000 {
+                proptest::prop_assert_eq!((bought, sold), (0, 0));
+            }
+        }
+    }
+}
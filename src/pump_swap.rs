@@ -0,0 +1,340 @@
+use anyhow::Result;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use std::str::FromStr;
+
+use crate::create_token::{ProgramConfig, SYSTEM_PROGRAM_ID, get_associated_token_address};
+use crate::funding::system_transfer_instruction;
+use crate::pump_accounts::AccountReader;
+use crate::trade::{BuyOrder, SellOrder, Venue, create_associated_token_account_idempotent};
+
+/// PumpSwap AMM, where pump.fun tokens trade once their bonding curve completes
+pub const PUMP_SWAP_PROGRAM_ID: &str = "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA";
+pub const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+const POOL_DISCRIMINATOR: &[u8] = &[241, 154, 109, 4, 17, 177, 109, 188];
+const GLOBAL_CONFIG_DISCRIMINATOR: &[u8] = &[149, 8, 156, 202, 160, 252, 176, 217];
+const BUY_INSTRUCTION_DISCRIMINATOR: &[u8] = &[102, 6, 61, 18, 1, 218, 235, 234];
+const SELL_INSTRUCTION_DISCRIMINATOR: &[u8] = &[51, 230, 133, 164, 1, 127, 131, 173];
+const POOL_SEED: &[u8] = b"pool";
+const POOL_AUTHORITY_SEED: &[u8] = b"pool-authority";
+const GLOBAL_CONFIG_SEED: &[u8] = b"global_config";
+const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+const CREATOR_VAULT_SEED: &[u8] = b"creator_vault";
+// SPL token instruction indices
+const TOKEN_CLOSE_ACCOUNT: u8 = 9;
+const TOKEN_SYNC_NATIVE: u8 = 17;
+// Offset of the amount in an SPL token account (after mint and owner)
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+
+fn program_id() -> Pubkey {
+    Pubkey::from_str(PUMP_SWAP_PROGRAM_ID).unwrap()
+}
+
+fn wsol_mint() -> Pubkey {
+    Pubkey::from_str(WSOL_MINT).unwrap()
+}
+
+fn token_program_id() -> Pubkey {
+    Pubkey::new_from_array(spl_token::ID.to_bytes())
+}
+
+/// Canonical pool pump.fun migrates `mint` into: index 0, created by the pump program's pool authority
+pub fn canonical_pool_pda(program: &ProgramConfig, mint: &Pubkey) -> Pubkey {
+    let pool_authority = Pubkey::find_program_address(&[POOL_AUTHORITY_SEED, mint.as_ref()], &program.program_id).0;
+    let index = 0u16.to_le_bytes();
+    Pubkey::find_program_address(
+        &[POOL_SEED, &index, pool_authority.as_ref(), mint.as_ref(), wsol_mint().as_ref()],
+        &program_id(),
+    ).0
+}
+
+fn global_config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[GLOBAL_CONFIG_SEED], &program_id()).0
+}
+
+fn event_authority_pda() -> Pubkey {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &program_id()).0
+}
+
+fn coin_creator_vault_authority(coin_creator: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[CREATOR_VAULT_SEED, coin_creator.as_ref()], &program_id()).0
+}
+
+/// Decoded PumpSwap `Pool` account
+#[derive(Debug, Clone)]
+pub struct PoolAccount {
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub pool_base_token_account: Pubkey,
+    pub pool_quote_token_account: Pubkey,
+    /// Receives creator fees; only present on pools created after creator fees were introduced
+    pub coin_creator: Option<Pubkey>,
+}
+
+impl PoolAccount {
+    pub fn from_account_data(data: &[u8]) -> Result<Self> {
+        let mut reader = AccountReader::new(data, POOL_DISCRIMINATOR, "Pool")?;
+        let _bump = reader.read_u8()?;
+        let _index = reader.read_u16()?;
+        let _creator = reader.read_pubkey()?;
+        let base_mint = reader.read_pubkey()?;
+        let quote_mint = reader.read_pubkey()?;
+        let _lp_mint = reader.read_pubkey()?;
+        let pool_base_token_account = reader.read_pubkey()?;
+        let pool_quote_token_account = reader.read_pubkey()?;
+        let _lp_supply = reader.read_u64()?;
+        let coin_creator = if reader.remaining() >= 32 { Some(reader.read_pubkey()?) } else { None };
+        Ok(Self { base_mint, quote_mint, pool_base_token_account, pool_quote_token_account, coin_creator })
+    }
+}
+
+/// Decoded PumpSwap `GlobalConfig` account (fees and protocol fee recipients)
+#[derive(Debug, Clone)]
+pub struct AmmGlobalConfig {
+    pub lp_fee_basis_points: u64,
+    pub protocol_fee_basis_points: u64,
+    pub protocol_fee_recipients: Vec<Pubkey>,
+    pub coin_creator_fee_basis_points: u64,
+}
+
+impl AmmGlobalConfig {
+    pub fn from_account_data(data: &[u8]) -> Result<Self> {
+        let mut reader = AccountReader::new(data, GLOBAL_CONFIG_DISCRIMINATOR, "GlobalConfig")?;
+        let _admin = reader.read_pubkey()?;
+        let lp_fee_basis_points = reader.read_u64()?;
+        let protocol_fee_basis_points = reader.read_u64()?;
+        let _disable_flags = reader.read_u8()?;
+        let protocol_fee_recipients = (0..8).map(|_| reader.read_pubkey()).collect::<Result<Vec<_>>>()?;
+        let coin_creator_fee_basis_points = if reader.remaining() >= 8 { reader.read_u64()? } else { 0 };
+        Ok(Self { lp_fee_basis_points, protocol_fee_basis_points, protocol_fee_recipients, coin_creator_fee_basis_points })
+    }
+
+    pub fn total_fee_basis_points(&self) -> u64 {
+        self.lp_fee_basis_points + self.protocol_fee_basis_points + self.coin_creator_fee_basis_points
+    }
+
+    fn protocol_fee_recipient(&self) -> Result<Pubkey> {
+        self.protocol_fee_recipients.iter().copied()
+            .find(|recipient| *recipient != Pubkey::default())
+            .ok_or_else(|| anyhow::anyhow!("PumpSwap global config lists no protocol fee recipient"))
+    }
+}
+
+/// A migrated token's pool with its current reserves
+#[derive(Debug, Clone)]
+pub struct PoolState {
+    pub address: Pubkey,
+    pub pool: PoolAccount,
+    pub config: AmmGlobalConfig,
+    pub base_reserve: u64,
+    pub quote_reserve: u64,
+}
+
+impl PoolState {
+    /// Raw tokens received for `quote_lamports` of SOL, after fees
+    pub fn quote_buy(&self, quote_lamports: u64) -> u64 {
+        let fee = quote_lamports as u128 * self.config.total_fee_basis_points() as u128 / 10_000;
+        let quote_in = (quote_lamports as u128).saturating_sub(fee);
+        if self.quote_reserve as u128 + quote_in == 0 {
+            return 0;
+        }
+        (self.base_reserve as u128 * quote_in / (self.quote_reserve as u128 + quote_in)) as u64
+    }
+
+    /// Lamports received for selling `base_amount` raw tokens, after fees
+    pub fn quote_sell(&self, base_amount: u64) -> u64 {
+        if self.base_reserve as u128 + base_amount as u128 == 0 {
+            return 0;
+        }
+        let quote_out = self.quote_reserve as u128 * base_amount as u128 / (self.base_reserve as u128 + base_amount as u128);
+        quote_out.saturating_sub(quote_out * self.config.total_fee_basis_points() as u128 / 10_000) as u64
+    }
+
+    pub fn buy_order(&self, sol_lamports: u64, slippage_bps: u64) -> Result<BuyOrder> {
+        let token_amount = self.quote_buy(sol_lamports);
+        if token_amount == 0 {
+            return Err(anyhow::anyhow!("{} lamports buys no tokens from pool {}", sol_lamports, self.address));
+        }
+        Ok(BuyOrder::new(Venue::PumpSwap, sol_lamports, token_amount, slippage_bps))
+    }
+
+    pub fn sell_order(&self, token_amount: u64, slippage_bps: u64) -> Result<SellOrder> {
+        if token_amount == 0 {
+            return Err(anyhow::anyhow!("Nothing to sell"));
+        }
+        Ok(SellOrder::new(Venue::PumpSwap, token_amount, self.quote_sell(token_amount), slippage_bps))
+    }
+}
+
+fn token_account_amount(data: &[u8]) -> Result<u64> {
+    data.get(TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+        .ok_or_else(|| anyhow::anyhow!("Not a token account"))
+}
+
+/// Read the canonical pool of a migrated `mint` and its reserves
+pub async fn fetch_pool_state(rpc: &RpcClient, program: &ProgramConfig, mint: &Pubkey) -> Result<PoolState> {
+    let address = canonical_pool_pda(program, mint);
+    let pool = rpc.get_account_data(&address).await
+        .map_err(|e| anyhow::anyhow!("No PumpSwap pool {} for {}: {}", address, mint, e))?;
+    let pool = PoolAccount::from_account_data(&pool)?;
+    let config = AmmGlobalConfig::from_account_data(&rpc.get_account_data(&global_config_pda()).await?)?;
+    let reserves = rpc.get_multiple_accounts(&[pool.pool_base_token_account, pool.pool_quote_token_account]).await?;
+    let reserve = |index: usize| -> Result<u64> {
+        let account = reserves[index].as_ref().ok_or_else(|| anyhow::anyhow!("Pool {} token account is missing", address))?;
+        token_account_amount(&account.data)
+    };
+    Ok(PoolState { address, base_reserve: reserve(0)?, quote_reserve: reserve(1)?, pool, config })
+}
+
+fn swap_instruction(state: &PoolState, user: &Pubkey, data: Vec<u8>) -> Result<Instruction> {
+    let wsol = wsol_mint();
+    let fee_recipient = state.config.protocol_fee_recipient()?;
+    let coin_creator = state.pool.coin_creator.unwrap_or_default();
+    let vault_authority = coin_creator_vault_authority(&coin_creator);
+    Ok(Instruction {
+        program_id: program_id(),
+        accounts: vec![
+            AccountMeta::new_readonly(state.address, false),
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(global_config_pda(), false),
+            AccountMeta::new_readonly(state.pool.base_mint, false),
+            AccountMeta::new_readonly(state.pool.quote_mint, false),
+            AccountMeta::new(get_associated_token_address(user, &state.pool.base_mint), false),
+            AccountMeta::new(get_associated_token_address(user, &wsol), false),
+            AccountMeta::new(state.pool.pool_base_token_account, false),
+            AccountMeta::new(state.pool.pool_quote_token_account, false),
+            AccountMeta::new_readonly(fee_recipient, false),
+            AccountMeta::new(get_associated_token_address(&fee_recipient, &wsol), false),
+            AccountMeta::new_readonly(token_program_id(), false),
+            AccountMeta::new_readonly(token_program_id(), false),
+            AccountMeta::new_readonly(Pubkey::from_str(SYSTEM_PROGRAM_ID).unwrap(), false),
+            AccountMeta::new_readonly(Pubkey::new_from_array(spl_associated_token_account::ID.to_bytes()), false),
+            AccountMeta::new_readonly(event_authority_pda(), false),
+            AccountMeta::new_readonly(program_id(), false),
+            AccountMeta::new(get_associated_token_address(&vault_authority, &wsol), false),
+            AccountMeta::new_readonly(vault_authority, false),
+        ],
+        data,
+    })
+}
+
+fn swap_data(discriminator: &[u8], base_amount: u64, quote_limit: u64) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    data.extend_from_slice(&base_amount.to_le_bytes());
+    data.extend_from_slice(&quote_limit.to_le_bytes());
+    data
+}
+
+/// Close the user's wrapped SOL account, returning its lamports
+fn close_wsol_instruction(user: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: token_program_id(),
+        accounts: vec![
+            AccountMeta::new(get_associated_token_address(user, &wsol_mint()), false),
+            AccountMeta::new(*user, false),
+            AccountMeta::new_readonly(*user, true),
+        ],
+        data: vec![TOKEN_CLOSE_ACCOUNT],
+    }
+}
+
+/// Wrap `order.max_sol_cost` lamports, buy from the pool, then unwrap what was not spent
+pub fn buy_instructions(state: &PoolState, user: &Pubkey, order: &BuyOrder) -> Result<Vec<Instruction>> {
+    let wsol = wsol_mint();
+    let wsol_account = get_associated_token_address(user, &wsol);
+    Ok(vec![
+        create_associated_token_account_idempotent(user, user, &state.pool.base_mint),
+        create_associated_token_account_idempotent(user, user, &wsol),
+        system_transfer_instruction(user, &wsol_account, order.max_sol_cost),
+        Instruction {
+            program_id: token_program_id(),
+            accounts: vec![AccountMeta::new(wsol_account, false)],
+            data: vec![TOKEN_SYNC_NATIVE],
+        },
+        swap_instruction(state, user, swap_data(BUY_INSTRUCTION_DISCRIMINATOR, order.token_amount, order.max_sol_cost))?,
+        close_wsol_instruction(user),
+    ])
+}
+
+/// Sell to the pool into a temporary wrapped SOL account, then unwrap the proceeds
+pub fn sell_instructions(state: &PoolState, user: &Pubkey, order: &SellOrder) -> Result<Vec<Instruction>> {
+    Ok(vec![
+        create_associated_token_account_idempotent(user, user, &wsol_mint()),
+        swap_instruction(state, user, swap_data(SELL_INSTRUCTION_DISCRIMINATOR, order.token_amount, order.min_sol_output))?,
+        close_wsol_instruction(user),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_quotes_and_buy_layout() {
+        let mint = Pubkey::new_unique();
+        let state = PoolState {
+            address: Pubkey::new_unique(),
+            pool: PoolAccount {
+                base_mint: mint,
+                quote_mint: wsol_mint(),
+                pool_base_token_account: Pubkey::new_unique(),
+                pool_quote_token_account: Pubkey::new_unique(),
+                coin_creator: Some(Pubkey::new_unique()),
+            },
+            config: AmmGlobalConfig {
+                lp_fee_basis_points: 20,
+                protocol_fee_basis_points: 5,
+                protocol_fee_recipients: vec![Pubkey::default(), Pubkey::new_unique()],
+                coin_creator_fee_basis_points: 5,
+            },
+            base_reserve: 200_000_000_000_000,
+            quote_reserve: 85_000_000_000,
+        };
+
+        // Round trip loses the fee twice, never gains
+        let tokens = state.quote_buy(1_000_000_000);
+        assert!(tokens > 0);
+        assert!(state.quote_sell(tokens) < 1_000_000_000);
+
+        let order = state.buy_order(1_000_000_000, 100).unwrap();
+        assert_eq!((order.venue, order.max_sol_cost), (Venue::PumpSwap, 1_010_000_000));
+        let instructions = buy_instructions(&state, &Pubkey::new_unique(), &order).unwrap();
+        let swap = &instructions[4];
+        assert_eq!(swap.program_id, program_id());
+        assert_eq!(swap.accounts[9].pubkey, state.config.protocol_fee_recipients[1]);
+        assert_eq!(u64::from_le_bytes(swap.data[8..16].try_into().unwrap()), order.token_amount);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_pool_quotes_stay_within_reserves(
+            base_reserve in 0..=u64::MAX, quote_reserve in 0..=u64::MAX, amount in 0..=u64::MAX, fee_bps in 0u64..=20_000,
+        ) {
+            let state = PoolState {
+                address: Pubkey::default(),
+                pool: PoolAccount {
+                    base_mint: Pubkey::default(),
+                    quote_mint: wsol_mint(),
+                    pool_base_token_account: Pubkey::default(),
+                    pool_quote_token_account: Pubkey::default(),
+                    coin_creator: None,
+                },
+                config: AmmGlobalConfig {
+                    lp_fee_basis_points: fee_bps,
+                    protocol_fee_basis_points: 0,
+                    protocol_fee_recipients: Vec::new(),
+                    coin_creator_fee_basis_points: 0,
+                },
+                base_reserve,
+                quote_reserve,
+            };
+            proptest::prop_assert!(state.quote_buy(amount) <= base_reserve);
+            proptest::prop_assert!(state.quote_sell(amount) <= quote_reserve);
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use anyhow::Result;
+use log::warn;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::api_server::check_api_key;
+use crate::secure_credentials::SecureApiKey;
+
+/// Secret holding the key `/admin/read-only` requests must send
+pub const ADMIN_KEY_VAR: &str = "LAUNCHER_ADMIN_KEY";
+
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// Whether anything that signs or spends is currently refused
+pub fn is_read_only() -> bool {
+    READ_ONLY.load(Ordering::SeqCst)
+}
+
+pub fn set_read_only(enabled: bool) {
+    set_flag(&READ_ONLY, enabled);
+}
+
+fn set_flag(flag: &AtomicBool, enabled: bool) {
+    if flag.swap(enabled, Ordering::SeqCst) != enabled {
+        warn!("Read-only mode {}", if enabled { "enabled: launches, trades and transfers are refused" } else { "disabled" });
+    }
+}
+
+/// Start in read-only mode when `--read-only` is passed or `LAUNCHER_READ_ONLY=true`
+pub fn init_read_only(flag: bool) {
+    let from_env = std::env::var("LAUNCHER_READ_ONLY").is_ok_and(|v| v.trim().eq_ignore_ascii_case("true"));
+    set_read_only(flag || from_env);
+}
+
+/// Refuse `action` (e.g. "launch a token") while read-only mode is on
+pub fn ensure_writable(action: &str) -> Result<()> {
+    if is_read_only() {
+        return Err(anyhow::anyhow!("The launcher is in read-only mode; refusing to {}", action));
+    }
+    Ok(())
+}
+
+/// `GET` or `PUT`/`POST` (`{"enabled": bool}`) of `/admin/read-only`, authorized by `LAUNCHER_ADMIN_KEY`
+pub(crate) fn handle_admin_request(method: &str, headers: &HashMap<String, String>, body: &[u8]) -> (&'static str, serde_json::Value) {
+    let admin_key = SecureApiKey::load(ADMIN_KEY_VAR).ok();
+    admin_request(admin_key.as_ref().map(SecureApiKey::expose_secret), &READ_ONLY, method, headers, body)
+}
+
+/// [`handle_admin_request`] against `flag`, authorized by `admin_key` (`None` when the admin API is disabled)
+fn admin_request(admin_key: Option<&str>, flag: &AtomicBool, method: &str, headers: &HashMap<String, String>, body: &[u8]) -> (&'static str, serde_json::Value) {
+    let Some(admin_key) = admin_key else {
+        return ("404 Not Found", serde_json::json!({ "message": format!("Admin API disabled: set {}", ADMIN_KEY_VAR) }));
+    };
+    if let Err(e) = check_api_key(admin_key, headers) {
+        return ("401 Unauthorized", serde_json::json!({ "message": e.to_string() }));
+    }
+    match method {
+        "GET" => {}
+        "PUT" | "POST" => match serde_json::from_slice::<serde_json::Value>(body).ok().and_then(|body| body["enabled"].as_bool()) {
+            Some(enabled) => set_flag(flag, enabled),
+            None => return ("400 Bad Request", serde_json::json!({ "message": "Expected {\"enabled\": true|false}" })),
+        },
+        _ => return ("405 Method Not Allowed", serde_json::json!({ "message": "Use GET, PUT or POST" })),
+    }
+    ("200 OK", serde_json::json!({ "read_only": flag.load(Ordering::SeqCst) }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_admin_toggle() {
+        let flag = AtomicBool::new(false);
+        let headers = HashMap::from([("x-api-key".to_string(), "admin-k3y".to_string())]);
+        assert_eq!(admin_request(None, &flag, "GET", &headers, b"").0, "404 Not Found");
+        assert_eq!(admin_request(Some("admin-k3y"), &flag, "PUT", &HashMap::new(), br#"{"enabled":true}"#).0, "401 Unauthorized");
+        assert!(!flag.load(Ordering::SeqCst));
+
+        assert_eq!(admin_request(Some("admin-k3y"), &flag, "PUT", &headers, br#"{"enabled":true}"#).1["read_only"], true);
+        assert!(flag.load(Ordering::SeqCst));
+        assert_eq!(admin_request(Some("admin-k3y"), &flag, "POST", &headers, b"{}").0, "400 Bad Request");
+        assert_eq!(admin_request(Some("admin-k3y"), &flag, "DELETE", &headers, b"").0, "405 Method Not Allowed");
+        assert_eq!(admin_request(Some("admin-k3y"), &flag, "POST", &headers, br#"{"enabled":false}"#).1["read_only"], false);
+        assert!(!flag.load(Ordering::SeqCst));
+    }
+}
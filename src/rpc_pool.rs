@@ -0,0 +1,210 @@
+use log::{info, warn};
+use solana_client::client_error::{ClientError, ClientErrorKind, Result as ClientResult};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcSendTransactionConfig;
+use solana_client::rpc_request::RpcError;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::{signature::Signature, transaction::Transaction};
+use std::future::Future;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Seconds a failing endpoint is tried last unless `RPC_ENDPOINT_COOLDOWN_SECS` is set
+pub const DEFAULT_RPC_ENDPOINT_COOLDOWN_SECS: u64 = 30;
+
+struct Endpoint {
+    /// Host only: endpoint URLs usually carry an API key
+    label: String,
+    client: Arc<RpcClient>,
+    failures: AtomicU32,
+    down_until: Mutex<Option<Instant>>,
+}
+
+impl Endpoint {
+    fn is_healthy(&self) -> bool {
+        self.down_until.lock().unwrap().is_none_or(|until| Instant::now() >= until)
+    }
+}
+
+/// Health of one endpoint, for status output
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct EndpointStatus {
+    pub endpoint: String,
+    pub healthy: bool,
+    pub consecutive_failures: u32,
+}
+
+/// RPC endpoints in order of preference; a failing endpoint is skipped for a cooldown while the
+/// others serve requests
+pub struct RpcPool {
+    endpoints: Vec<Endpoint>,
+    cooldown: Duration,
+}
+
+/// Failures of the endpoint rather than of the request: worth retrying elsewhere
+pub fn is_endpoint_failure(error: &ClientError) -> bool {
+    matches!(error.kind(), ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_) | ClientErrorKind::RpcError(RpcError::RpcRequestError(_)))
+}
+
+fn endpoint_label(url: &str) -> String {
+    url::Url::parse(url).ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| "rpc".to_string())
+}
+
+impl RpcPool {
+    /// `urls[0]` is the primary endpoint
+    pub fn new(urls: Vec<String>, commitment: CommitmentConfig) -> Self {
+        let cooldown = std::env::var("RPC_ENDPOINT_COOLDOWN_SECS").ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_RPC_ENDPOINT_COOLDOWN_SECS);
+        let endpoints = urls.into_iter().map(|url| Endpoint {
+            label: endpoint_label(&url),
+            client: Arc::new(RpcClient::new_with_commitment(url, commitment)),
+            failures: AtomicU32::new(0),
+            down_until: Mutex::new(None),
+        }).collect();
+        Self { endpoints, cooldown: Duration::from_secs(cooldown) }
+    }
+
+    /// Fallback endpoints from the comma-separated `RPC_FALLBACK_URLS`
+    pub fn fallback_urls_from_env() -> Vec<String> {
+        std::env::var("RPC_FALLBACK_URLS").unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    pub fn primary(&self) -> Arc<RpcClient> {
+        self.endpoints[0].client.clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+
+    pub fn status(&self) -> Vec<EndpointStatus> {
+        self.endpoints.iter().map(|endpoint| EndpointStatus {
+            endpoint: endpoint.label.clone(),
+            healthy: endpoint.is_healthy(),
+            consecutive_failures: endpoint.failures.load(Ordering::Relaxed),
+        }).collect()
+    }
+
+    /// Healthy endpoints in configured order, then the ones cooling down as a last resort
+    fn ordered(&self) -> impl Iterator<Item = &Endpoint> {
+        let (healthy, cooling): (Vec<_>, Vec<_>) = self.endpoints.iter().partition(|endpoint| endpoint.is_healthy());
+        healthy.into_iter().chain(cooling)
+    }
+
+    fn record(&self, endpoint: &Endpoint, result: Result<(), &ClientError>) {
+        match result {
+            Ok(()) => {
+                if endpoint.failures.swap(0, Ordering::Relaxed) > 0 {
+                    info!("RPC endpoint {} recovered", endpoint.label);
+                }
+                *endpoint.down_until.lock().unwrap() = None;
+            }
+            Err(e) => {
+                let failures = endpoint.failures.fetch_add(1, Ordering::Relaxed) + 1;
+                *endpoint.down_until.lock().unwrap() = Some(Instant::now() + self.cooldown);
+                warn!("RPC endpoint {} failed ({} in a row): {}", endpoint.label, failures, e);
+            }
+        }
+    }
+
+    /// Run `op` against one endpoint after another until one answers; request errors such as a
+    /// rejected transaction are returned without trying the others
+    pub async fn with_failover<T, F, Fut>(&self, op: F) -> ClientResult<T>
+    where
+        F: Fn(Arc<RpcClient>) -> Fut,
+        Fut: Future<Output = ClientResult<T>>,
+    {
+        let mut last_error = None;
+        for endpoint in self.ordered() {
+            match op(endpoint.client.clone()).await {
+                Ok(value) => {
+                    self.record(endpoint, Ok(()));
+                    return Ok(value);
+                }
+                Err(e) if is_endpoint_failure(&e) => {
+                    self.record(endpoint, Err(&e));
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| RpcError::ForUser("no RPC endpoints configured".to_string()).into()))
+    }
+
+    /// Send `transaction` through every endpoint at once; the same signed transaction lands at most once
+    pub async fn send_transaction(&self, transaction: &Transaction, config: RpcSendTransactionConfig) -> ClientResult<Signature> {
+        let sends = self.endpoints.iter().map(|endpoint| async move {
+            (endpoint, endpoint.client.send_transaction_with_config(transaction, config).await)
+        });
+        let mut errors = Vec::new();
+        let mut sent = None;
+        for (endpoint, result) in futures::future::join_all(sends).await {
+            match result {
+                Ok(signature) => {
+                    self.record(endpoint, Ok(()));
+                    sent.get_or_insert(signature);
+                }
+                Err(e) if is_endpoint_failure(&e) => {
+                    self.record(endpoint, Err(&e));
+                    errors.push(e);
+                }
+                // A rejection says something about the transaction, so it is what the caller needs to see
+                Err(e) => errors.insert(0, e),
+            }
+        }
+        match sent {
+            Some(signature) => Ok(signature),
+            None => Err(errors.into_iter().next()
+                .unwrap_or_else(|| RpcError::ForUser("no RPC endpoints configured".to_string()).into())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_failover_skips_dead_endpoints() {
+        // Nothing listens on port 1, so the first endpoint fails at the transport level
+        let pool = RpcPool::new(vec!["http://127.0.0.1:1".to_string(), "http://127.0.0.1:2".to_string()], CommitmentConfig::confirmed());
+        assert_eq!(endpoint_label("https://mainnet.helius-rpc.com/?api-key=secret"), "mainnet.helius-rpc.com");
+
+        let calls = AtomicU32::new(0);
+        let result = pool.with_failover(|rpc| {
+            let call = calls.fetch_add(1, Ordering::SeqCst);
+            async move {
+                match call {
+                    0 => rpc.get_slot().await,
+                    _ => Ok(42),
+                }
+            }
+        }).await;
+        assert_eq!(result.unwrap(), 42);
+        let status = pool.status();
+        assert!(!status[0].healthy && status[0].consecutive_failures == 1);
+        assert!(status[1].healthy);
+
+        // The failed endpoint is now tried last, and a request error stops the failover
+        let tried = Mutex::new(Vec::new());
+        let result: ClientResult<u64> = pool.with_failover(|rpc| {
+            tried.lock().unwrap().push(rpc.url());
+            async { Err(RpcError::ForUser("bad request".to_string()).into()) }
+        }).await;
+        assert!(result.is_err());
+        assert_eq!(*tried.lock().unwrap(), vec!["http://127.0.0.1:2".to_string()]);
+    }
+}
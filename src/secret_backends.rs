@@ -0,0 +1,449 @@
+use anyhow::Result;
+use hmac::{Hmac, Mac};
+use log::info;
+use secrecy::Secret;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::env;
+use std::time::{SystemTime, UNIX_EPOCH};
+use zeroize::Zeroize;
+
+use crate::secure_credentials::install_provider_secrets;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Where launcher secrets (`PRIVATE_KEY`, `HELIUS_API_KEY`, ...) are loaded from at startup
+#[derive(Debug, Clone)]
+pub enum SecretsBackend {
+    /// Plain environment variables / .env (default)
+    Env,
+    /// AWS Secrets Manager secret whose `SecretString` is a JSON object of name -> value
+    AwsSecretsManager(AwsSecretsManagerConfig),
+    /// HashiCorp Vault KV secret whose data is a map of name -> value
+    Vault(VaultConfig),
+    /// External commands (`op read ...`, `pass show ...`), one per secret, whose stdout is the value
+    Command(CommandConfig),
+}
+
+#[derive(Debug, Clone)]
+pub struct AwsSecretsManagerConfig {
+    pub region: String,
+    pub secret_id: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct VaultConfig {
+    pub address: String,
+    /// API path of the secret, e.g. `secret/data/pumpfun` for KV v2
+    pub secret_path: String,
+    pub namespace: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CommandConfig {
+    /// Secret name -> shell command printing the secret on stdout
+    pub commands: Vec<(String, String)>,
+}
+
+// Environment prefix for per-secret commands, e.g. SECRET_COMMAND_PRIVATE_KEY="op read op://..."
+const SECRET_COMMAND_PREFIX: &str = "SECRET_COMMAND_";
+
+fn required_env(name: &str) -> Result<String> {
+    env::var(name).map_err(|_| anyhow::anyhow!("{} must be set for the selected secrets backend", name))
+}
+
+impl SecretsBackend {
+    /// Select the backend from `SECRETS_BACKEND` (env, aws, vault, command)
+    pub fn from_env() -> Result<Self> {
+        let backend = env::var("SECRETS_BACKEND").unwrap_or_else(|_| "env".to_string());
+        match backend.trim().to_lowercase().as_str() {
+            "" | "env" => Ok(SecretsBackend::Env),
+            "aws" | "aws-secrets-manager" => Ok(SecretsBackend::AwsSecretsManager(AwsSecretsManagerConfig {
+                region: env::var("AWS_REGION").or_else(|_| required_env("AWS_DEFAULT_REGION"))?,
+                secret_id: required_env("AWS_SECRET_ID")?,
+            })),
+            "vault" => Ok(SecretsBackend::Vault(VaultConfig {
+                address: required_env("VAULT_ADDR")?.trim_end_matches('/').to_string(),
+                secret_path: required_env("VAULT_SECRET_PATH")?.trim_matches('/').to_string(),
+                namespace: env::var("VAULT_NAMESPACE").ok(),
+            })),
+            "command" => {
+                let mut commands: Vec<(String, String)> = env::vars()
+                    .filter_map(|(name, command)| {
+                        name.strip_prefix(SECRET_COMMAND_PREFIX).map(|secret| (secret.to_string(), command))
+                    })
+                    .collect();
+                commands.sort();
+                if commands.is_empty() {
+                    return Err(anyhow::anyhow!(
+                        "SECRETS_BACKEND=command requires at least one {}<NAME> variable", SECRET_COMMAND_PREFIX
+                    ));
+                }
+                Ok(SecretsBackend::Command(CommandConfig { commands }))
+            }
+            other => Err(anyhow::anyhow!("Unknown SECRETS_BACKEND '{}' (expected env, aws, vault or command)", other)),
+        }
+    }
+
+    /// Fetch all secrets from the backend as name -> value
+    pub async fn fetch_secrets(&self) -> Result<HashMap<String, Secret<String>>> {
+        let values = match self {
+            SecretsBackend::Env => return Ok(HashMap::new()),
+            SecretsBackend::Command(config) => return fetch_command_secrets(config).await,
+            SecretsBackend::AwsSecretsManager(config) => fetch_aws_secret(config).await?,
+            SecretsBackend::Vault(config) => fetch_vault_secret(config).await?,
+        };
+        secrets_from_json(&values)
+    }
+}
+
+/// Load secrets from the configured backend and make them available to `secure_credentials`
+pub async fn load_secrets_from_backend() -> Result<()> {
+    let backend = SecretsBackend::from_env()?;
+    if matches!(backend, SecretsBackend::Env) {
+        return Ok(());
+    }
+
+    let secrets = backend.fetch_secrets().await?;
+    let mut names: Vec<&String> = secrets.keys().collect();
+    names.sort();
+    info!("Loaded {} secrets from {} backend: {:?}", secrets.len(), backend_name(&backend), names);
+    install_provider_secrets(secrets)
+}
+
+fn backend_name(backend: &SecretsBackend) -> &'static str {
+    match backend {
+        SecretsBackend::Env => "env",
+        SecretsBackend::AwsSecretsManager(_) => "aws",
+        SecretsBackend::Vault(_) => "vault",
+        SecretsBackend::Command(_) => "command",
+    }
+}
+
+/// Turn a JSON object of string values into secrets, ignoring non-string fields
+fn secrets_from_json(value: &serde_json::Value) -> Result<HashMap<String, Secret<String>>> {
+    let object = value.as_object()
+        .ok_or_else(|| anyhow::anyhow!("Secret payload must be a JSON object of name -> value"))?;
+    Ok(object
+        .iter()
+        .filter_map(|(name, value)| value.as_str().map(|v| (name.clone(), Secret::new(v.to_string()))))
+        .collect())
+}
+
+// ---------------------------------------------------------------------------
+// External commands (1Password CLI, pass, ...)
+// ---------------------------------------------------------------------------
+
+async fn fetch_command_secrets(config: &CommandConfig) -> Result<HashMap<String, Secret<String>>> {
+    let mut secrets = HashMap::new();
+
+    for (name, command) in &config.commands {
+        // The command itself is not secret, but never log its output
+        info!("Fetching secret {} via external command", name);
+        let output = tokio::process::Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .stdin(std::process::Stdio::null())
+            .stderr(std::process::Stdio::inherit())
+            .output()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to run secret command for {}: {}", name, e))?;
+
+        let mut stdout = output.stdout;
+        if !output.status.success() {
+            stdout.zeroize();
+            return Err(anyhow::anyhow!("Secret command for {} exited with {}", name, output.status));
+        }
+
+        let value = String::from_utf8(stdout)
+            .map_err(|_| anyhow::anyhow!("Secret command for {} printed non UTF-8 output", name))?;
+        let trimmed = value.trim().to_string();
+        let mut value = value;
+        value.zeroize();
+        if trimmed.is_empty() {
+            return Err(anyhow::anyhow!("Secret command for {} printed an empty value", name));
+        }
+        secrets.insert(name.clone(), Secret::new(trimmed));
+    }
+
+    Ok(secrets)
+}
+
+// ---------------------------------------------------------------------------
+// HashiCorp Vault
+// ---------------------------------------------------------------------------
+
+/// Vault token from `VAULT_TOKEN`, or an AppRole login with `VAULT_ROLE_ID` / `VAULT_SECRET_ID`
+async fn vault_token(client: &reqwest::Client, config: &VaultConfig) -> Result<String> {
+    if let Ok(token) = env::var("VAULT_TOKEN") {
+        return Ok(token);
+    }
+
+    let role_id = required_env("VAULT_ROLE_ID")
+        .map_err(|_| anyhow::anyhow!("Set VAULT_TOKEN or VAULT_ROLE_ID/VAULT_SECRET_ID for Vault auth"))?;
+    let secret_id = required_env("VAULT_SECRET_ID")?;
+
+    let mut request = client
+        .post(format!("{}/v1/auth/approle/login", config.address))
+        .json(&serde_json::json!({ "role_id": role_id, "secret_id": secret_id }));
+    if let Some(namespace) = &config.namespace {
+        request = request.header("X-Vault-Namespace", namespace);
+    }
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("Vault AppRole login failed: {}", response.status()));
+    }
+    let body: serde_json::Value = response.json().await?;
+    body["auth"]["client_token"]
+        .as_str()
+        .map(String::from)
+        .ok_or_else(|| anyhow::anyhow!("Vault AppRole login response has no client_token"))
+}
+
+async fn fetch_vault_secret(config: &VaultConfig) -> Result<serde_json::Value> {
+    let client = reqwest::Client::new();
+    let token = vault_token(&client, config).await?;
+
+    let mut request = client
+        .get(format!("{}/v1/{}", config.address, config.secret_path))
+        .header("X-Vault-Token", token);
+    if let Some(namespace) = &config.namespace {
+        request = request.header("X-Vault-Namespace", namespace);
+    }
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("Vault read of {} failed: {}", config.secret_path, response.status()));
+    }
+
+    let body: serde_json::Value = response.json().await?;
+    // KV v2 nests the secret under data.data, KV v1 directly under data
+    if body["data"]["data"].is_object() {
+        Ok(body["data"]["data"].clone())
+    } else {
+        Ok(body["data"].clone())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// AWS Secrets Manager
+// ---------------------------------------------------------------------------
+
+pub(crate) struct AwsCredentials {
+    pub(crate) access_key_id: String,
+    pub(crate) secret_access_key: String,
+    pub(crate) session_token: Option<String>,
+}
+
+/// Resolve IAM credentials: static env keys, then the ECS task role, then the EC2 instance role (IMDSv2)
+pub(crate) async fn aws_credentials(client: &reqwest::Client) -> Result<AwsCredentials> {
+    if let (Ok(access_key_id), Ok(secret_access_key)) = (env::var("AWS_ACCESS_KEY_ID"), env::var("AWS_SECRET_ACCESS_KEY")) {
+        return Ok(AwsCredentials {
+            access_key_id,
+            secret_access_key,
+            session_token: env::var("AWS_SESSION_TOKEN").ok(),
+        });
+    }
+
+    let credentials: serde_json::Value = if let Ok(relative_uri) = env::var("AWS_CONTAINER_CREDENTIALS_RELATIVE_URI") {
+        client.get(format!("http://169.254.170.2{}", relative_uri)).send().await?.json().await?
+    } else {
+        let imds = "http://169.254.169.254/latest";
+        let token = client
+            .put(format!("{}/api/token", imds))
+            .header("X-aws-ec2-metadata-token-ttl-seconds", "300")
+            .timeout(std::time::Duration::from_secs(2))
+            .send().await?
+            .text().await?;
+        let role = client
+            .get(format!("{}/meta-data/iam/security-credentials/", imds))
+            .header("X-aws-ec2-metadata-token", &token)
+            .send().await?
+            .text().await?;
+        client
+            .get(format!("{}/meta-data/iam/security-credentials/{}", imds, role.trim()))
+            .header("X-aws-ec2-metadata-token", &token)
+            .send().await?
+            .json().await?
+    };
+
+    let field = |name: &str| credentials[name].as_str().map(String::from)
+        .ok_or_else(|| anyhow::anyhow!("AWS credentials response missing {}", name));
+    Ok(AwsCredentials {
+        access_key_id: field("AccessKeyId")?,
+        secret_access_key: field("SecretAccessKey")?,
+        session_token: field("Token").ok(),
+    })
+}
+
+pub(crate) fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub(crate) fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// UTC (`YYYYMMDD`, `YYYYMMDDTHHMMSSZ`) for a unix timestamp
+pub(crate) fn amz_dates(unix_secs: u64) -> (String, String) {
+    let days = (unix_secs / 86_400) as i64;
+    let secs_of_day = unix_secs % 86_400;
+
+    // Civil-from-days conversion (proleptic Gregorian calendar)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    let date = format!("{:04}{:02}{:02}", year, month, day);
+    let datetime = format!(
+        "{}T{:02}{:02}{:02}Z",
+        date, secs_of_day / 3600, (secs_of_day % 3600) / 60, secs_of_day % 60
+    );
+    (date, datetime)
+}
+
+/// A request to sign with AWS Signature Version 4. Header names are lowercase; the query string is empty.
+pub(crate) struct SigV4Request<'a> {
+    pub(crate) method: &'a str,
+    pub(crate) path: &'a str,
+    pub(crate) headers: Vec<(&'a str, String)>,
+    /// Hex SHA-256 of the body
+    pub(crate) payload_hash: &'a str,
+    pub(crate) region: &'a str,
+    pub(crate) service: &'a str,
+    /// `YYYYMMDDTHHMMSSZ`, also sent as `x-amz-date`
+    pub(crate) amz_date: &'a str,
+}
+
+/// Canonical request and signed header list of `request`
+fn canonical_request(request: &SigV4Request) -> (String, String) {
+    // Canonical headers must be sorted by name
+    let mut headers = request.headers.iter().collect::<Vec<_>>();
+    headers.sort_by(|a, b| a.0.cmp(b.0));
+    let canonical_headers: String = headers.iter().map(|(name, value)| format!("{}:{}\n", name, value.trim())).collect();
+    let signed_headers = headers.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(";");
+    let canonical_request = format!(
+        "{}\n{}\n\n{}\n{}\n{}",
+        request.method, request.path, canonical_headers, signed_headers, request.payload_hash
+    );
+    (canonical_request, signed_headers)
+}
+
+/// `Authorization` header of `request` signed with `credentials`
+pub(crate) fn sign_v4(request: &SigV4Request, credentials: &AwsCredentials) -> String {
+    let date = &request.amz_date[..8];
+    let (canonical_request, signed_headers) = canonical_request(request);
+
+    let scope = format!("{}/{}/{}/aws4_request", date, request.region, request.service);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        request.amz_date, scope, hex_encode(&Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let k_date = hmac_sha256(format!("AWS4{}", credentials.secret_access_key).as_bytes(), date.as_bytes());
+    let k_region = hmac_sha256(&k_date, request.region.as_bytes());
+    let k_service = hmac_sha256(&k_region, request.service.as_bytes());
+    let k_signing = hmac_sha256(&k_service, b"aws4_request");
+    let signature = hex_encode(&hmac_sha256(&k_signing, string_to_sign.as_bytes()));
+
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        credentials.access_key_id, scope, signed_headers, signature
+    )
+}
+
+async fn fetch_aws_secret(config: &AwsSecretsManagerConfig) -> Result<serde_json::Value> {
+    let client = reqwest::Client::new();
+    let credentials = aws_credentials(&client).await?;
+
+    let service = "secretsmanager";
+    let host = format!("{}.{}.amazonaws.com", service, config.region);
+    let target = "secretsmanager.GetSecretValue";
+    let content_type = "application/x-amz-json-1.1";
+    let body = serde_json::json!({ "SecretId": config.secret_id }).to_string();
+    let (_, amz_date) = amz_dates(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs());
+
+    let mut headers = vec![
+        ("content-type", content_type.to_string()),
+        ("host", host.clone()),
+        ("x-amz-date", amz_date.clone()),
+        ("x-amz-target", target.to_string()),
+    ];
+    if let Some(token) = &credentials.session_token {
+        headers.push(("x-amz-security-token", token.clone()));
+    }
+    let payload_hash = hex_encode(&Sha256::digest(body.as_bytes()));
+    let authorization = sign_v4(&SigV4Request {
+        method: "POST",
+        path: "/",
+        headers,
+        payload_hash: &payload_hash,
+        region: &config.region,
+        service,
+        amz_date: &amz_date,
+    }, &credentials);
+
+    let mut request = client
+        .post(format!("https://{}/", host))
+        .header("Content-Type", content_type)
+        .header("X-Amz-Date", &amz_date)
+        .header("X-Amz-Target", target)
+        .header("Authorization", authorization)
+        .body(body);
+    if let Some(token) = &credentials.session_token {
+        request = request.header("X-Amz-Security-Token", token);
+    }
+
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(anyhow::anyhow!("AWS Secrets Manager GetSecretValue failed: {} {}", status, text));
+    }
+
+    let result: serde_json::Value = response.json().await?;
+    let secret_string = result["SecretString"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Secret {} has no SecretString (binary secrets are not supported)", config.secret_id))?;
+    Ok(serde_json::from_str(secret_string)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amz_dates_and_signing() {
+        assert_eq!(amz_dates(0), ("19700101".to_string(), "19700101T000000Z".to_string()));
+        assert_eq!(amz_dates(1_709_251_199), ("20240229".to_string(), "20240229T235959Z".to_string()));
+
+        // get-vanilla from AWS's published Signature Version 4 test suite
+        let credentials = AwsCredentials {
+            access_key_id: "AKIDEXAMPLE".to_string(),
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+            session_token: None,
+        };
+        let empty_hash = hex_encode(&Sha256::digest(b""));
+        let request = SigV4Request {
+            method: "GET",
+            path: "/",
+            headers: vec![("x-amz-date", "20150830T123600Z".to_string()), ("host", "example.amazonaws.com".to_string())],
+            payload_hash: &empty_hash,
+            region: "us-east-1",
+            service: "service",
+            amz_date: "20150830T123600Z",
+        };
+        assert_eq!(canonical_request(&request).0, "GET\n/\n\nhost:example.amazonaws.com\nx-amz-date:20150830T123600Z\n\nhost;x-amz-date\ne3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(
+            sign_v4(&request, &credentials),
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, SignedHeaders=host;x-amz-date, Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+        );
+    }
+}
@@ -1,10 +1,94 @@
 use secrecy::{Secret, ExposeSecret};
 use zeroize::{Zeroize, ZeroizeOnDrop};
 use anyhow::Result;
+use bip39::{Language, Mnemonic};
+use solana_derivation_path::DerivationPath;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::keypair::generate_seed_from_seed_phrase_and_passphrase;
+use solana_sdk::signer::SeedDerivable;
+use std::collections::HashMap;
 use std::env;
+use std::io::Read;
+use std::path::Path;
+use std::sync::{LazyLock, RwLock};
+
+/// Derivation path of mnemonics without `<VAR>_DERIVATION_PATH`, the one Phantom and `solana-keygen` use
+pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/501'/0'/0'";
+
+// Secrets supplied at startup (secrets backend, stdin, file descriptor), consulted before the environment
+static PROVIDER_SECRETS: LazyLock<RwLock<HashMap<String, Secret<String>>>> = LazyLock::new(Default::default);
+
+/// Install secrets loaded from a secrets backend or other startup source
+pub fn install_provider_secrets(secrets: HashMap<String, Secret<String>>) -> Result<()> {
+    PROVIDER_SECRETS.write().unwrap().extend(secrets);
+    Ok(())
+}
+
+/// Read a secret from `reader` (trimmed), zeroing the intermediate buffer
+pub fn read_secret_from<R: Read>(mut reader: R) -> Result<Secret<String>> {
+    let mut buffer = String::new();
+    reader.read_to_string(&mut buffer)?;
+    let value = buffer.trim().to_string();
+    buffer.zeroize();
+    if value.is_empty() {
+        return Err(anyhow::anyhow!("No secret provided on input"));
+    }
+    Ok(Secret::new(value))
+}
+
+/// Read `var_name` from stdin (as piped by an orchestrator) and install it as a provider secret
+pub fn install_secret_from_stdin(var_name: &str) -> Result<()> {
+    let secret = read_secret_from(std::io::stdin().lock())?;
+    PROVIDER_SECRETS.write().unwrap().insert(var_name.to_string(), secret);
+    Ok(())
+}
+
+/// Read `var_name` from an inherited file descriptor and install it as a provider secret
+#[cfg(unix)]
+pub fn install_secret_from_fd(var_name: &str, fd: i32) -> Result<()> {
+    use std::os::unix::io::FromRawFd;
+    if fd < 0 {
+        return Err(anyhow::anyhow!("Invalid file descriptor {}", fd));
+    }
+    // Safety: the descriptor is handed to us by the parent process for exclusive use and closed on drop
+    let file = unsafe { std::fs::File::from_raw_fd(fd) };
+    let secret = read_secret_from(file)?;
+    PROVIDER_SECRETS.write().unwrap().insert(var_name.to_string(), secret);
+    Ok(())
+}
+
+/// Read `var_name` from a file (e.g. a solana-keygen keypair) and install it as a provider secret
+pub fn install_secret_from_file(var_name: &str, path: &Path) -> Result<()> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| anyhow::anyhow!("Cannot open {}: {}", path.display(), e))?;
+    let secret = read_secret_from(file)?;
+    PROVIDER_SECRETS.write().unwrap().insert(var_name.to_string(), secret);
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn install_secret_from_fd(_var_name: &str, _fd: i32) -> Result<()> {
+    Err(anyhow::anyhow!("Reading secrets from a file descriptor is only supported on unix"))
+}
+
+/// Whether `var_name` was installed from a secrets backend, stdin or a file descriptor
+pub fn is_provided_secret(var_name: &str) -> bool {
+    PROVIDER_SECRETS.read().unwrap().contains_key(var_name)
+}
+
+/// Look up a secret by name: backend-provided secrets first, then the environment
+fn lookup_secret(var_name: &str) -> Result<Secret<String>> {
+    if let Some(secret) = PROVIDER_SECRETS.read().unwrap().get(var_name) {
+        return Ok(secret.clone());
+    }
+    env::var(var_name)
+        .map(Secret::new)
+        .map_err(|_| anyhow::anyhow!("{} must be set in environment or secrets backend", var_name))
+}
 
 /// Secure wrapper for private key that automatically zeroes memory on drop
 pub struct SecurePrivateKey {
+    var_name: String,
     private_key: Secret<String>,
 }
 
@@ -24,22 +108,79 @@ impl SecurePrivateKey {
             .map_err(|_| anyhow::anyhow!("{} must be set in environment", var_name))?;
         
         Ok(Self {
+            var_name: var_name.to_string(),
             private_key: Secret::new(private_key),
         })
     }
     
+    /// Load private key from the configured secrets backend, falling back to the environment
+    pub fn load(var_name: &str) -> Result<Self> {
+        Ok(Self {
+            var_name: var_name.to_string(),
+            private_key: lookup_secret(var_name)?,
+        })
+    }
+    
     /// Get the private key for use (exposes it temporarily)
     pub fn expose_secret(&self) -> &str {
         self.private_key.expose_secret()
     }
     
-    /// Convert to bytes for keypair creation
+    /// Convert to bytes for keypair creation. Accepts a base58 key, a solana-keygen JSON byte array
+    /// or a BIP39 mnemonic, derived with `<VAR>_PASSPHRASE` and `<VAR>_DERIVATION_PATH`.
     pub fn to_bytes(&self) -> Result<Vec<u8>> {
-        let key_str = self.private_key.expose_secret();
+        let key_str = self.private_key.expose_secret().trim();
+        if key_str.starts_with('[') {
+            return serde_json::from_str::<Vec<u8>>(key_str)
+                .map_err(|e| anyhow::anyhow!("Invalid keypair JSON: {}", e));
+        }
+        if key_str.contains(char::is_whitespace) {
+            return self.mnemonic_to_bytes(key_str);
+        }
         bs58::decode(key_str)
             .into_vec()
             .map_err(|e| anyhow::anyhow!("Invalid private key format: {}", e))
     }
+
+    fn mnemonic_to_bytes(&self, phrase: &str) -> Result<Vec<u8>> {
+        let mut words = phrase.split_whitespace().map(str::to_lowercase).collect::<Vec<_>>();
+        if ![12, 15, 18, 21, 24].contains(&words.len()) || !words.iter().all(|w| w.chars().all(|c| c.is_ascii_lowercase())) {
+            let count = words.len();
+            words.zeroize();
+            return Err(anyhow::anyhow!("Invalid mnemonic: expected 12, 15, 18, 21 or 24 words, got {}", count));
+        }
+        let mut normalized = words.join(" ");
+        words.zeroize();
+        // A mistyped word would otherwise silently derive a different, empty wallet
+        if let Err(e) = Mnemonic::parse_in_normalized(Language::English, &normalized) {
+            normalized.zeroize();
+            return Err(anyhow::anyhow!("Invalid mnemonic: {} (words must come from the BIP39 English list)", e));
+        }
+
+        let passphrase = lookup_secret(&format!("{}_PASSPHRASE", self.var_name)).ok();
+        let path_var = format!("{}_DERIVATION_PATH", self.var_name);
+        let path = lookup_secret(&path_var).map(|path| path.expose_secret().trim().to_string())
+            .unwrap_or_else(|_| DEFAULT_DERIVATION_PATH.to_string());
+        let derivation_path = if path.starts_with('m') {
+            DerivationPath::from_absolute_path_str(&path)
+        } else {
+            DerivationPath::from_key_str(&path)
+        }.map_err(|e| {
+            normalized.zeroize();
+            anyhow::anyhow!("Invalid {}: {}", path_var, e)
+        })?;
+
+        let mut seed = generate_seed_from_seed_phrase_and_passphrase(
+            &normalized,
+            passphrase.as_ref().map(|p| p.expose_secret().as_str()).unwrap_or(""),
+        );
+        normalized.zeroize();
+        let keypair = Keypair::from_seed_and_derivation_path(&seed, Some(derivation_path));
+        seed.zeroize();
+        keypair
+            .map(|keypair| keypair.to_bytes().to_vec())
+            .map_err(|e| anyhow::anyhow!("Cannot derive a keypair from the mnemonic: {}", e))
+    }
 }
 
 /// Secure wrapper for API keys
@@ -67,6 +208,13 @@ impl SecureApiKey {
         })
     }
     
+    /// Load API key from the configured secrets backend, falling back to the environment
+    pub fn load(var_name: &str) -> Result<Self> {
+        Ok(Self {
+            api_key: lookup_secret(var_name)?,
+        })
+    }
+    
     /// Get the API key for use (exposes it temporarily)
     pub fn expose_secret(&self) -> &str {
         self.api_key.expose_secret()
@@ -79,8 +227,42 @@ mod tests {
     
     #[test]
     fn test_secure_private_key_creation() {
-        // This test would need to set environment variables
-        // In a real test, you'd use a test harness
-        assert!(true); // Placeholder
+        let encoded = bs58::encode([7u8; 64]).into_string();
+        install_provider_secrets(HashMap::from([("TEST_SECURE_PRIVATE_KEY".to_string(), Secret::new(encoded.clone()))])).unwrap();
+
+        let key = SecurePrivateKey::load("TEST_SECURE_PRIVATE_KEY").unwrap();
+        assert_eq!(key.expose_secret(), encoded);
+        assert_eq!(key.to_bytes().unwrap(), vec![7u8; 64]);
+
+        assert!(SecurePrivateKey::load("TEST_SECURE_PRIVATE_KEY_MISSING").is_err());
+
+        // A solana-keygen file holds the same keypair as a JSON byte array
+        let path = std::env::temp_dir().join(format!("secure_credentials_test_{}.json", std::process::id()));
+        std::fs::write(&path, serde_json::to_string(&vec![7u8; 64]).unwrap()).unwrap();
+        install_secret_from_file("TEST_SECURE_KEYPAIR_FILE", &path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(SecurePrivateKey::load("TEST_SECURE_KEYPAIR_FILE").unwrap().to_bytes().unwrap(), vec![7u8; 64]);
+
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let install = |name: &str, value: &str| {
+            install_provider_secrets(HashMap::from([(name.to_string(), Secret::new(value.to_string()))])).unwrap();
+        };
+        let mnemonic_bytes = || SecurePrivateKey::load("TEST_SECURE_MNEMONIC").unwrap().to_bytes();
+        install("TEST_SECURE_MNEMONIC", phrase);
+        let default_path = mnemonic_bytes().unwrap();
+        install("TEST_SECURE_MNEMONIC_DERIVATION_PATH", "m/44'/501'/0'/0'");
+        assert_eq!(mnemonic_bytes().unwrap(), default_path);
+        install("TEST_SECURE_MNEMONIC_DERIVATION_PATH", "m/44'/501'/1'/0'");
+        let second_account = mnemonic_bytes().unwrap();
+        assert!(Keypair::try_from(&second_account[..]).is_ok());
+        assert_ne!(second_account, default_path);
+
+        // Wrong word counts, words outside the list and bad checksums are refused
+        install("TEST_SECURE_MNEMONIC", "abandon abandon about");
+        assert!(mnemonic_bytes().is_err());
+        install("TEST_SECURE_MNEMONIC", &phrase.replace("about", "abuot"));
+        assert!(mnemonic_bytes().unwrap_err().to_string().contains("unknown word"));
+        install("TEST_SECURE_MNEMONIC", &phrase.replace("about", "abandon"));
+        assert!(mnemonic_bytes().unwrap_err().to_string().contains("checksum"));
     }
 }
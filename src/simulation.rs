@@ -0,0 +1,87 @@
+use solana_sdk::{instruction::InstructionError, pubkey::Pubkey, transaction::{Transaction, TransactionError}};
+use std::str::FromStr;
+
+use crate::create_token::SYSTEM_PROGRAM_ID;
+
+/// pump.fun program errors a launch can run into, by Anchor error number
+pub const PUMP_ERRORS: &[(u32, &str)] = &[
+    (6000, "the signer is not authorized for this instruction"),
+    (6001, "the program is already initialized"),
+    (6002, "the dev buy needs more SOL than the slippage allows; raise --dev-buy-slippage"),
+    (6003, "the sell returns less SOL than the slippage allows"),
+    (6004, "the mint does not match the bonding curve"),
+    (6005, "the bonding curve is complete and has migrated"),
+    (6006, "the bonding curve has not completed yet"),
+    (6007, "the pump.fun global account is not initialized on this cluster"),
+];
+
+/// Anchor's `Error Code: X. Error Number: N. Error Message: M.` log line
+fn anchor_error(logs: &[String]) -> Option<(String, u32, String)> {
+    logs.iter().find_map(|line| {
+        let rest = &line[line.find("Error Code: ")? + "Error Code: ".len()..];
+        let (code, rest) = rest.split_once(". Error Number: ")?;
+        let (number, message) = rest.split_once(". Error Message: ")?;
+        Some((code.to_string(), number.trim().parse().ok()?, message.trim_end_matches('.').to_string()))
+    })
+}
+
+/// Human-readable reason a simulated launch failed, naming the failing instruction's program
+pub fn explain_failure(transaction: &Transaction, error: &TransactionError, logs: &[String], pump_program: &Pubkey) -> String {
+    let message = &transaction.message;
+    match error {
+        TransactionError::InstructionError(index, instruction_error) => {
+            let program = message.instructions.get(*index as usize)
+                .and_then(|ix| message.account_keys.get(ix.program_id_index as usize))
+                .copied()
+                .unwrap_or_default();
+            let system_program = Pubkey::from_str(SYSTEM_PROGRAM_ID).unwrap();
+            let reason = match instruction_error {
+                InstructionError::Custom(code) if program == *pump_program => PUMP_ERRORS.iter()
+                    .find(|(number, _)| number == code)
+                    .map(|(_, reason)| reason.to_string())
+                    .or_else(|| anchor_error(logs).map(|(name, _, reason)| format!("{} ({})", reason, name)))
+                    .unwrap_or_else(|| format!("pump.fun error {}", code)),
+                InstructionError::Custom(0) if program == system_program => "the account already exists (was this mint address used before?)".to_string(),
+                InstructionError::Custom(1) if program == system_program => "the payer does not have enough SOL".to_string(),
+                InstructionError::Custom(code) => anchor_error(logs)
+                    .map(|(name, _, reason)| format!("{} ({})", reason, name))
+                    .unwrap_or_else(|| format!("custom program error {}", code)),
+                other => format!("{:?}", other),
+            };
+            format!("instruction {} ({}) failed: {}", index, program, reason)
+        }
+        TransactionError::AccountNotFound => "the payer account does not exist; fund the wallet first".to_string(),
+        TransactionError::InsufficientFundsForFee => "the payer cannot cover the transaction fee".to_string(),
+        TransactionError::InsufficientFundsForRent { account_index } => format!("account {} would be left below rent exemption",
+            message.account_keys.get(*account_index as usize).map(|key| key.to_string()).unwrap_or_else(|| account_index.to_string())),
+        TransactionError::BlockhashNotFound => "the blockhash expired before the simulation".to_string(),
+        other => format!("{:?}", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::funding::system_transfer_instruction;
+    use solana_sdk::instruction::Instruction;
+
+    #[test]
+    fn test_explain_failure() {
+        let (payer, pump) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let transaction = Transaction::new_with_payer(&[
+            system_transfer_instruction(&payer, &Pubkey::new_unique(), 1),
+            Instruction::new_with_bytes(pump, &[], Vec::new()),
+        ], Some(&payer));
+        let explain = |error| explain_failure(&transaction, &error, &[], &pump);
+
+        assert!(explain(TransactionError::InstructionError(1, InstructionError::Custom(6002))).contains("--dev-buy-slippage"));
+        assert!(explain(TransactionError::InstructionError(0, InstructionError::Custom(1))).contains("not have enough SOL"));
+        assert!(explain(TransactionError::InstructionError(1, InstructionError::Custom(6099))).ends_with("pump.fun error 6099"));
+
+        let logs = vec!["Program log: AnchorError occurred. Error Code: Overflow. Error Number: 6024. Error Message: Overflow.".to_string()];
+        assert_eq!(anchor_error(&logs), Some(("Overflow".to_string(), 6024, "Overflow".to_string())));
+        let error = TransactionError::InstructionError(1, InstructionError::Custom(6024));
+        assert!(explain_failure(&transaction, &error, &logs, &pump).ends_with("Overflow (Overflow)"));
+        assert!(explain(TransactionError::AccountNotFound).contains("fund the wallet"));
+    }
+}
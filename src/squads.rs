@@ -0,0 +1,298 @@
+use anyhow::Result;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    message::Message,
+    pubkey::Pubkey,
+};
+use std::str::FromStr;
+
+use crate::create_token::SYSTEM_PROGRAM_ID;
+use crate::pump_accounts::AccountReader;
+
+// Squads v4 program and seeds
+pub const SQUADS_PROGRAM_ID: &str = "SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf";
+const SEED_PREFIX: &[u8] = b"multisig";
+const SEED_VAULT: &[u8] = b"vault";
+const SEED_TRANSACTION: &[u8] = b"transaction";
+const SEED_PROPOSAL: &[u8] = b"proposal";
+const SEED_EPHEMERAL_SIGNER: &[u8] = b"ephemeral_signer";
+
+// Anchor discriminators (sha256("global:<name>") / sha256("account:<name>"), first 8 bytes)
+const VAULT_TRANSACTION_CREATE_DISCRIMINATOR: &[u8] = &[48, 250, 78, 168, 208, 226, 218, 211];
+const PROPOSAL_CREATE_DISCRIMINATOR: &[u8] = &[220, 60, 73, 224, 30, 108, 79, 159];
+const PROPOSAL_APPROVE_DISCRIMINATOR: &[u8] = &[144, 37, 164, 136, 188, 216, 42, 248];
+const MULTISIG_ACCOUNT_DISCRIMINATOR: &[u8] = &[224, 116, 121, 186, 68, 161, 79, 236];
+const PROPOSAL_ACCOUNT_DISCRIMINATOR: &[u8] = &[26, 94, 189, 187, 116, 136, 53, 33];
+
+/// Squads multisig whose vault acts as payer and creator of a launch
+#[derive(Debug, Clone)]
+pub struct SquadsConfig {
+    pub multisig: Pubkey,
+    pub vault_index: u8,
+    pub program_id: Pubkey,
+}
+
+impl SquadsConfig {
+    pub fn new(multisig: Pubkey, vault_index: u8) -> Self {
+        Self {
+            multisig,
+            vault_index,
+            program_id: Pubkey::from_str(SQUADS_PROGRAM_ID).unwrap(),
+        }
+    }
+
+    pub fn vault_pda(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[SEED_PREFIX, self.multisig.as_ref(), SEED_VAULT, &[self.vault_index]],
+            &self.program_id,
+        ).0
+    }
+
+    pub fn transaction_pda(&self, transaction_index: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[SEED_PREFIX, self.multisig.as_ref(), SEED_TRANSACTION, &transaction_index.to_le_bytes()],
+            &self.program_id,
+        ).0
+    }
+
+    pub fn proposal_pda(&self, transaction_index: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[SEED_PREFIX, self.multisig.as_ref(), SEED_TRANSACTION, &transaction_index.to_le_bytes(), SEED_PROPOSAL],
+            &self.program_id,
+        ).0
+    }
+
+    /// PDA that signs for the vault transaction at execution time (used as the mint)
+    pub fn ephemeral_signer_pda(&self, transaction_pda: &Pubkey, signer_index: u8) -> Pubkey {
+        Pubkey::find_program_address(
+            &[SEED_PREFIX, transaction_pda.as_ref(), SEED_EPHEMERAL_SIGNER, &[signer_index]],
+            &self.program_id,
+        ).0
+    }
+
+    /// `vault_transaction_create`: store `message` for later execution by the vault
+    pub fn vault_transaction_create_instruction(
+        &self,
+        transaction_index: u64,
+        creator: &Pubkey,
+        ephemeral_signers: u8,
+        message: Vec<u8>,
+        memo: Option<&str>,
+    ) -> Instruction {
+        let mut data = VAULT_TRANSACTION_CREATE_DISCRIMINATOR.to_vec();
+        data.push(self.vault_index);
+        data.push(ephemeral_signers);
+        data.extend_from_slice(&(message.len() as u32).to_le_bytes());
+        data.extend_from_slice(&message);
+        push_option_string(&mut data, memo);
+
+        Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(self.multisig, false),
+                AccountMeta::new(self.transaction_pda(transaction_index), false),
+                AccountMeta::new_readonly(*creator, true),
+                AccountMeta::new(*creator, true), // rent_payer
+                AccountMeta::new_readonly(Pubkey::from_str(SYSTEM_PROGRAM_ID).unwrap(), false),
+            ],
+            data,
+        }
+    }
+
+    /// `proposal_create`: open an active proposal for the transaction
+    pub fn proposal_create_instruction(&self, transaction_index: u64, creator: &Pubkey) -> Instruction {
+        let mut data = PROPOSAL_CREATE_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&transaction_index.to_le_bytes());
+        data.push(0); // draft: false
+
+        Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(self.multisig, false),
+                AccountMeta::new(self.proposal_pda(transaction_index), false),
+                AccountMeta::new_readonly(*creator, true),
+                AccountMeta::new(*creator, true), // rent_payer
+                AccountMeta::new_readonly(Pubkey::from_str(SYSTEM_PROGRAM_ID).unwrap(), false),
+            ],
+            data,
+        }
+    }
+
+    /// `proposal_approve`: cast an approval vote as `member`
+    pub fn proposal_approve_instruction(&self, transaction_index: u64, member: &Pubkey) -> Instruction {
+        let mut data = PROPOSAL_APPROVE_DISCRIMINATOR.to_vec();
+        push_option_string(&mut data, None);
+
+        Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(self.multisig, false),
+                AccountMeta::new(*member, true),
+                AccountMeta::new(self.proposal_pda(transaction_index), false),
+            ],
+            data,
+        }
+    }
+}
+
+fn push_option_string(data: &mut Vec<u8>, value: Option<&str>) {
+    match value {
+        Some(value) => {
+            data.push(1);
+            data.extend_from_slice(&(value.len() as u32).to_le_bytes());
+            data.extend_from_slice(value.as_bytes());
+        }
+        None => data.push(0),
+    }
+}
+
+/// Serialize instructions into Squads' compact `TransactionMessage` format, with `vault` as payer
+pub fn compile_vault_message(instructions: &[Instruction], vault: &Pubkey) -> Result<Vec<u8>> {
+    let message = Message::new(instructions, Some(vault));
+    let header = message.header;
+    let num_keys = message.account_keys.len();
+    if num_keys > u8::MAX as usize {
+        return Err(anyhow::anyhow!("Too many accounts for a Squads vault transaction: {}", num_keys));
+    }
+
+    let mut data = vec![
+        header.num_required_signatures,
+        header.num_required_signatures - header.num_readonly_signed_accounts,
+        (num_keys - header.num_required_signatures as usize - header.num_readonly_unsigned_accounts as usize) as u8,
+        num_keys as u8,
+    ];
+    for key in &message.account_keys {
+        data.extend_from_slice(key.as_ref());
+    }
+
+    data.push(message.instructions.len() as u8);
+    for instruction in &message.instructions {
+        data.push(instruction.program_id_index);
+        data.push(instruction.accounts.len() as u8);
+        data.extend_from_slice(&instruction.accounts);
+        data.extend_from_slice(&(instruction.data.len() as u16).to_le_bytes());
+        data.extend_from_slice(&instruction.data);
+    }
+
+    data.push(0); // no address table lookups
+    Ok(data)
+}
+
+/// Decoded subset of the Squads `Multisig` account
+#[derive(Debug, Clone)]
+pub struct MultisigAccount {
+    pub threshold: u16,
+    pub time_lock: u32,
+    pub transaction_index: u64,
+    pub members: Vec<Pubkey>,
+}
+
+impl MultisigAccount {
+    pub fn from_account_data(data: &[u8]) -> Result<Self> {
+        let mut reader = AccountReader::new(data, MULTISIG_ACCOUNT_DISCRIMINATOR, "Squads Multisig")?;
+        let _create_key = reader.read_pubkey()?;
+        let _config_authority = reader.read_pubkey()?;
+        let threshold = reader.read_u16()?;
+        let time_lock = reader.read_u32()?;
+        let transaction_index = reader.read_u64()?;
+        let _stale_transaction_index = reader.read_u64()?;
+        if reader.read_u8()? == 1 {
+            let _rent_collector = reader.read_pubkey()?;
+        }
+        let _bump = reader.read_u8()?;
+
+        let member_count = reader.read_u32()? as usize;
+        let mut members = Vec::with_capacity(member_count);
+        for _ in 0..member_count {
+            members.push(reader.read_pubkey()?);
+            let _permissions = reader.read_u8()?;
+        }
+
+        Ok(Self { threshold, time_lock, transaction_index, members })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProposalStatus {
+    Draft,
+    Active,
+    Rejected,
+    Approved,
+    Executing,
+    Executed,
+    Cancelled,
+}
+
+impl ProposalStatus {
+    /// No further state changes are expected
+    pub fn is_final(&self) -> bool {
+        matches!(self, ProposalStatus::Executed | ProposalStatus::Rejected | ProposalStatus::Cancelled)
+    }
+}
+
+/// Decoded Squads `Proposal` account
+#[derive(Debug, Clone)]
+pub struct ProposalAccount {
+    pub transaction_index: u64,
+    pub status: ProposalStatus,
+    pub approved: Vec<Pubkey>,
+    pub rejected: Vec<Pubkey>,
+    pub cancelled: Vec<Pubkey>,
+}
+
+impl ProposalAccount {
+    pub fn from_account_data(data: &[u8]) -> Result<Self> {
+        let mut reader = AccountReader::new(data, PROPOSAL_ACCOUNT_DISCRIMINATOR, "Squads Proposal")?;
+        let _multisig = reader.read_pubkey()?;
+        let transaction_index = reader.read_u64()?;
+
+        let status = match reader.read_u8()? {
+            0 => ProposalStatus::Draft,
+            1 => ProposalStatus::Active,
+            2 => ProposalStatus::Rejected,
+            3 => ProposalStatus::Approved,
+            4 => ProposalStatus::Executing,
+            5 => ProposalStatus::Executed,
+            6 => ProposalStatus::Cancelled,
+            other => return Err(anyhow::anyhow!("Unknown proposal status variant {}", other)),
+        };
+        if status != ProposalStatus::Executing {
+            let _timestamp = reader.read_i64()?;
+        }
+        let _bump = reader.read_u8()?;
+
+        Ok(Self {
+            transaction_index,
+            status,
+            approved: reader.read_pubkey_vec()?,
+            rejected: reader.read_pubkey_vec()?,
+            cancelled: reader.read_pubkey_vec()?,
+        })
+    }
+}
+
+pub async fn fetch_multisig(rpc: &RpcClient, multisig: &Pubkey) -> Result<MultisigAccount> {
+    let data = rpc.get_account_data(multisig).await
+        .map_err(|e| anyhow::anyhow!("Failed to read Squads multisig {}: {}", multisig, e))?;
+    MultisigAccount::from_account_data(&data)
+}
+
+pub async fn fetch_proposal(rpc: &RpcClient, config: &SquadsConfig, transaction_index: u64) -> Result<ProposalAccount> {
+    let proposal = config.proposal_pda(transaction_index);
+    let data = rpc.get_account_data(&proposal).await
+        .map_err(|e| anyhow::anyhow!("Failed to read Squads proposal {}: {}", proposal, e))?;
+    ProposalAccount::from_account_data(&data)
+}
+
+/// A launch proposed to a Squads multisig, awaiting approval and execution
+#[derive(Debug, Clone)]
+pub struct SquadsProposal {
+    pub signature: solana_sdk::signature::Signature,
+    pub transaction_index: u64,
+    pub transaction: Pubkey,
+    pub proposal: Pubkey,
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub metadata_uri: String,
+}
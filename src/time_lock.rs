@@ -0,0 +1,150 @@
+use anyhow::Result;
+use log::info;
+use std::env;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// NTP server the local clock is checked against unless `NTP_SERVER` is set
+pub const DEFAULT_NTP_SERVER: &str = "pool.ntp.org:123";
+/// Largest local clock error tolerated for a time-locked launch unless `MAX_CLOCK_SKEW_SECS` is set
+pub const DEFAULT_MAX_CLOCK_SKEW: Duration = Duration::from_secs(2);
+const NTP_TIMEOUT: Duration = Duration::from_secs(3);
+/// Seconds between the NTP epoch (1900) and the Unix epoch
+const NTP_UNIX_OFFSET: u64 = 2_208_988_800;
+
+/// Parse a not-before time: Unix seconds or RFC 3339 (`2025-06-01T17:00:00Z`, `2025-06-01T19:00:00+02:00`)
+pub fn parse_timestamp(value: &str) -> Result<SystemTime> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Ok(UNIX_EPOCH + Duration::from_secs(seconds));
+    }
+    let invalid = || anyhow::anyhow!("Invalid time '{}' (expected Unix seconds or RFC 3339, e.g. 2025-06-01T17:00:00Z)", value);
+    let field = |range: std::ops::Range<usize>| value.get(range).and_then(|v| v.parse::<i64>().ok()).ok_or_else(invalid);
+    if value.len() < 20 || !matches!(value.as_bytes()[10], b'T' | b't' | b' ') {
+        return Err(invalid());
+    }
+    let (year, month, day) = (field(0..4)?, field(5..7)?, field(8..10)?);
+    let (hour, minute, second) = (field(11..13)?, field(14..16)?, field(17..19)?);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+        return Err(invalid());
+    }
+
+    // Skip fractional seconds, then read the UTC offset
+    let rest = value[19..].trim_start_matches(|c: char| c == '.' || c.is_ascii_digit());
+    let offset_seconds = match rest {
+        "Z" | "z" => 0,
+        offset if offset.len() == 6 && offset.as_bytes()[3] == b':' => {
+            let sign = match offset.as_bytes()[0] {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return Err(invalid()),
+            };
+            let hours = offset[1..3].parse::<i64>().map_err(|_| invalid())?;
+            let minutes = offset[4..6].parse::<i64>().map_err(|_| invalid())?;
+            sign * (hours * 3600 + minutes * 60)
+        }
+        _ => return Err(invalid()),
+    };
+
+    let seconds = days_from_civil(year, month, day) * 86_400 + hour * 3600 + minute * 60 + second - offset_seconds;
+    u64::try_from(seconds).map(|s| UNIX_EPOCH + Duration::from_secs(s)).map_err(|_| invalid())
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// Current time according to an SNTP query of `server`
+pub async fn ntp_time(server: &str) -> Result<SystemTime> {
+    let socket = tokio::net::UdpSocket::bind("0.0.0.0:0").await?;
+    socket.connect(server).await?;
+    let mut request = [0u8; 48];
+    request[0] = 0x1B; // LI 0, version 3, client mode
+    socket.send(&request).await?;
+
+    let mut response = [0u8; 48];
+    let received = tokio::time::timeout(NTP_TIMEOUT, socket.recv(&mut response)).await
+        .map_err(|_| anyhow::anyhow!("No reply from NTP server {} within {}s", server, NTP_TIMEOUT.as_secs()))??;
+    if received < 48 {
+        return Err(anyhow::anyhow!("Short NTP reply from {}", server));
+    }
+    // Transmit timestamp: 32-bit seconds since 1900 and a 32-bit fraction
+    let seconds = u32::from_be_bytes(response[40..44].try_into().unwrap()) as u64;
+    let fraction = u32::from_be_bytes(response[44..48].try_into().unwrap()) as u64;
+    let unix = seconds.checked_sub(NTP_UNIX_OFFSET)
+        .ok_or_else(|| anyhow::anyhow!("NTP server {} returned an invalid time", server))?;
+    Ok(UNIX_EPOCH + Duration::from_secs(unix) + Duration::from_nanos((fraction * 1_000_000_000) >> 32))
+}
+
+/// Refuse when `not_before` has not passed on the local clock, or when the local clock cannot be trusted:
+/// `ntp` is unavailable or differs from it by more than `max_skew`
+pub fn check_not_before(not_before: SystemTime, local: SystemTime, ntp: Result<SystemTime>, max_skew: Duration) -> Result<()> {
+    if let Ok(remaining) = not_before.duration_since(local) {
+        if !remaining.is_zero() {
+            return Err(anyhow::anyhow!(
+                "Launch is time-locked until {} (Unix), {}s from now; refusing to send early",
+                unix_seconds(not_before), remaining.as_secs().max(1)
+            ));
+        }
+    }
+    let ntp = ntp.map_err(|e| anyhow::anyhow!("Cannot verify the local clock for a time-locked launch: {}", e))?;
+    let skew = ntp.duration_since(local).or_else(|_| local.duration_since(ntp)).unwrap_or_default();
+    if skew > max_skew {
+        return Err(anyhow::anyhow!(
+            "Local clock is {:.1}s off NTP time (max {}s); refusing time-locked launch until the clock is fixed",
+            skew.as_secs_f64(), max_skew.as_secs()
+        ));
+    }
+    if ntp < not_before {
+        return Err(anyhow::anyhow!("Launch is time-locked until {} (Unix) by NTP time; refusing to send early", unix_seconds(not_before)));
+    }
+    Ok(())
+}
+
+/// Enforce `not_before` against the local clock, sanity-checked with `NTP_SERVER`
+pub async fn enforce_not_before(not_before: SystemTime) -> Result<()> {
+    let server = env::var("NTP_SERVER").unwrap_or_else(|_| DEFAULT_NTP_SERVER.to_string());
+    let max_skew = match env::var("MAX_CLOCK_SKEW_SECS") {
+        Ok(value) => Duration::from_secs(value.trim().parse()
+            .map_err(|_| anyhow::anyhow!("MAX_CLOCK_SKEW_SECS must be a whole number of seconds, got '{}'", value))?),
+        Err(_) => DEFAULT_MAX_CLOCK_SKEW,
+    };
+    let local = SystemTime::now();
+    // Only ask NTP once the local clock says the lock has passed
+    let ntp = if local >= not_before { ntp_time(&server).await } else { Err(anyhow::anyhow!("not queried")) };
+    check_not_before(not_before, local, ntp, max_skew)?;
+    info!("Time lock {} (Unix) has passed; local clock agrees with {}", unix_seconds(not_before), server);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_lock() {
+        let at = |s: u64| UNIX_EPOCH + Duration::from_secs(s);
+        assert_eq!(parse_timestamp("1700000000").unwrap(), at(1_700_000_000));
+        assert_eq!(parse_timestamp("2023-11-14T22:13:20Z").unwrap(), at(1_700_000_000));
+        assert_eq!(parse_timestamp("2023-11-15T00:13:20.500+02:00").unwrap(), at(1_700_000_000));
+        assert!(parse_timestamp("2023-13-01T00:00:00Z").is_err());
+        assert!(parse_timestamp("tomorrow").is_err());
+
+        let skew = Duration::from_secs(2);
+        let lock = at(1_000);
+        assert!(check_not_before(lock, at(999), Ok(at(999)), skew).unwrap_err().to_string().contains("1s from now"));
+        assert!(check_not_before(lock, at(1_001), Ok(at(1_001)), skew).is_ok());
+        assert!(check_not_before(lock, at(1_001), Ok(at(1_010)), skew).unwrap_err().to_string().contains("off NTP"));
+        assert!(check_not_before(lock, at(1_001), Ok(at(999)), skew).is_err());
+        assert!(check_not_before(lock, at(1_001), Err(anyhow::anyhow!("timeout")), skew).is_err());
+    }
+}
@@ -0,0 +1,360 @@
+use anyhow::Result;
+use serde::Serialize;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use std::{env, str::FromStr};
+
+use crate::create_token::{ProgramConfig, ProgramVersion, SYSTEM_PROGRAM_ID, get_associated_token_address};
+use crate::pump_accounts::{BondingCurveAccount, GlobalAccount};
+
+// Instruction discriminators from the IDL
+const BUY_INSTRUCTION_DISCRIMINATOR: &[u8] = &[102, 6, 61, 18, 1, 218, 235, 234];
+const SELL_INSTRUCTION_DISCRIMINATOR: &[u8] = &[51, 230, 133, 164, 1, 127, 131, 173];
+// AssociatedTokenAccountInstruction::CreateIdempotent
+const CREATE_ATA_IDEMPOTENT: u8 = 1;
+/// Slippage tolerance of trading commands unless given explicitly
+pub const DEFAULT_SLIPPAGE_BPS: u64 = 500;
+/// How far a simulated buy may fill above its quoted price unless `BUY_PRICE_TOLERANCE_BPS` is set
+pub const DEFAULT_PRICE_TOLERANCE_BPS: u64 = 300;
+
+fn token_program_id() -> Pubkey {
+    Pubkey::new_from_array(spl_token::ID.to_bytes())
+}
+
+fn associated_token_program_id() -> Pubkey {
+    Pubkey::new_from_array(spl_associated_token_account::ID.to_bytes())
+}
+
+/// Everything a trade against an existing mint depends on, read from chain when the trade is built
+#[derive(Debug, Clone)]
+pub struct CurveState {
+    pub mint: Pubkey,
+    pub curve: BondingCurveAccount,
+    pub global: GlobalAccount,
+    pub version: ProgramVersion,
+    pub fee_recipient: Pubkey,
+}
+
+impl CurveState {
+    /// Creator whose vault receives creator fees, required by creator-vault program versions
+    pub fn creator(&self) -> Result<Option<Pubkey>> {
+        match (self.version, self.curve.creator) {
+            (ProgramVersion::Legacy, _) => Ok(None),
+            (ProgramVersion::CreatorVault, Some(creator)) => Ok(Some(creator)),
+            (ProgramVersion::CreatorVault, None) => Err(anyhow::anyhow!(
+                "Bonding curve of {} has no creator; set PUMP_PROGRAM_VERSION=legacy if the program predates creator vaults", self.mint
+            )),
+        }
+    }
+
+    fn ensure_tradable(&self) -> Result<()> {
+        if self.curve.complete {
+            return Err(anyhow::anyhow!("Bonding curve of {} is complete; the token has migrated off pump.fun", self.mint));
+        }
+        Ok(())
+    }
+}
+
+/// Where a trade executes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Venue {
+    /// The token's pump.fun bonding curve
+    BondingCurve,
+    /// The PumpSwap pool the token migrated into once its curve completed
+    PumpSwap,
+    /// Jupiter aggregator route (opt-in fallback, see `JUPITER_FALLBACK`)
+    Jupiter,
+}
+
+/// A buy of `token_amount` raw tokens paying at most `max_sol_cost` lamports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuyOrder {
+    pub venue: Venue,
+    pub sol_lamports: u64,
+    pub token_amount: u64,
+    pub max_sol_cost: u64,
+}
+
+impl BuyOrder {
+    /// Buy quoted at `token_amount` for `sol_lamports`, allowing the price to move by `slippage_bps`
+    pub fn new(venue: Venue, sol_lamports: u64, token_amount: u64, slippage_bps: u64) -> Self {
+        Self { venue, sol_lamports, token_amount, max_sol_cost: with_slippage(sol_lamports, slippage_bps as i64) }
+    }
+
+    /// Quote spending `sol_lamports` on the curve, allowing the price to move by `slippage_bps`
+    pub fn quote(state: &CurveState, sol_lamports: u64, slippage_bps: u64) -> Result<Self> {
+        state.ensure_tradable()?;
+        let token_amount = state.curve.quote_buy(sol_lamports, state.global.total_fee_basis_points());
+        if token_amount == 0 {
+            return Err(anyhow::anyhow!("{} lamports buys no tokens of {}", sol_lamports, state.mint));
+        }
+        Ok(Self::new(Venue::BondingCurve, sol_lamports, token_amount, slippage_bps))
+    }
+}
+
+/// A sell of `token_amount` raw tokens receiving at least `min_sol_output` lamports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SellOrder {
+    pub venue: Venue,
+    pub token_amount: u64,
+    pub expected_sol: u64,
+    pub min_sol_output: u64,
+}
+
+impl SellOrder {
+    /// Sell quoted at `expected_sol` for `token_amount`, allowing the price to move by `slippage_bps`
+    pub fn new(venue: Venue, token_amount: u64, expected_sol: u64, slippage_bps: u64) -> Self {
+        Self { venue, token_amount, expected_sol, min_sol_output: with_slippage(expected_sol, -(slippage_bps as i64)) }
+    }
+
+    /// Quote selling `token_amount` raw tokens to the curve, allowing the price to move by `slippage_bps`
+    pub fn quote(state: &CurveState, token_amount: u64, slippage_bps: u64) -> Result<Self> {
+        state.ensure_tradable()?;
+        if token_amount == 0 {
+            return Err(anyhow::anyhow!("Nothing to sell"));
+        }
+        let expected_sol = state.curve.quote_sell(token_amount, state.global.total_fee_basis_points());
+        Ok(Self::new(Venue::BondingCurve, token_amount, expected_sol, slippage_bps))
+    }
+}
+
+/// How much of the wallet's token balance a sell covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SellAmount {
+    /// Raw token amount
+    Tokens(u64),
+    All,
+}
+
+impl SellAmount {
+    /// Raw tokens to sell out of a wallet holding `balance`
+    pub fn resolve(self, balance: u64) -> Result<u64> {
+        match self {
+            _ if balance == 0 => Err(anyhow::anyhow!("The wallet holds none of this token")),
+            Self::All => Ok(balance),
+            Self::Tokens(0) => Err(anyhow::anyhow!("Nothing to sell")),
+            Self::Tokens(amount) if amount > balance => {
+                Err(anyhow::anyhow!("Cannot sell {} raw tokens, the wallet holds {}", amount, balance))
+            }
+            Self::Tokens(amount) => Ok(amount),
+        }
+    }
+}
+
+/// Tolerance for `check_simulated_fill`, from `BUY_PRICE_TOLERANCE_BPS`
+pub fn price_tolerance_from_env() -> Result<u64> {
+    match env::var("BUY_PRICE_TOLERANCE_BPS") {
+        Ok(value) => value.trim().parse()
+            .map_err(|_| anyhow::anyhow!("BUY_PRICE_TOLERANCE_BPS must be a whole number of basis points, got '{}'", value)),
+        Err(_) => Ok(DEFAULT_PRICE_TOLERANCE_BPS),
+    }
+}
+
+/// What a simulated buy actually did: raw tokens received for lamports spent (rent excluded)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimulatedFill {
+    pub token_amount: u64,
+    pub sol_lamports: u64,
+}
+
+impl SimulatedFill {
+    /// Basis points the realized price per token is above `order`'s quoted price (negative when cheaper)
+    pub fn price_deviation_bps(&self, order: &BuyOrder) -> Option<i64> {
+        if self.token_amount == 0 || order.sol_lamports == 0 {
+            return None;
+        }
+        // (fill sol / fill tokens) / (quoted sol / quoted tokens), in basis points
+        let ratio = self.sol_lamports as i128 * order.token_amount as i128 * 10_000
+            / (self.token_amount as i128 * order.sol_lamports as i128);
+        Some((ratio - 10_000) as i64)
+    }
+}
+
+/// Abort a buy whose simulation against the latest state fills more than `tolerance_bps` above its quote,
+/// e.g. because the quote went stale or the buy is being sandwiched
+pub fn check_simulated_fill(order: &BuyOrder, fill: &SimulatedFill, tolerance_bps: u64) -> Result<()> {
+    let deviation = fill.price_deviation_bps(order)
+        .ok_or_else(|| anyhow::anyhow!("Simulated buy received no tokens"))?;
+    if deviation > tolerance_bps as i64 {
+        return Err(anyhow::anyhow!(
+            "Simulated buy fills {:.2}% above the quote ({} tokens for {} lamports, quoted {} for {}); tolerance is {:.2}%",
+            deviation as f64 / 100.0, fill.token_amount, fill.sol_lamports, order.token_amount, order.sol_lamports,
+            tolerance_bps as f64 / 100.0
+        ));
+    }
+    Ok(())
+}
+
+/// `amount` moved by `bps` basis points (negative to lower it)
+fn with_slippage(amount: u64, bps: i64) -> u64 {
+    (amount as i128 * (10_000 + bps as i128) / 10_000).clamp(0, u64::MAX as i128) as u64
+}
+
+/// Associated token program `CreateIdempotent`: creates `owner`'s token account for `mint` unless it exists
+pub fn create_associated_token_account_idempotent(payer: &Pubkey, owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: associated_token_program_id(),
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(get_associated_token_address(owner, mint), false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(Pubkey::from_str(SYSTEM_PROGRAM_ID).unwrap(), false),
+            AccountMeta::new_readonly(token_program_id(), false),
+        ],
+        data: vec![CREATE_ATA_IDEMPOTENT],
+    }
+}
+
+fn trade_data(discriminator: &[u8], amount: u64, sol_limit: u64) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&sol_limit.to_le_bytes());
+    data
+}
+
+/// pump.fun `buy` of `order` on `state`'s curve, paid by `user`
+pub fn buy_instruction(program: &ProgramConfig, state: &CurveState, user: &Pubkey, order: &BuyOrder) -> Result<Instruction> {
+    let mint = &state.mint;
+    // Legacy programs pass the rent sysvar where newer ones take the creator vault
+    let creator_or_rent = match state.creator()? {
+        Some(creator) => AccountMeta::new(program.creator_vault_pda(&creator), false),
+        None => AccountMeta::new_readonly(solana_sdk::sysvar::rent::ID, false),
+    };
+    Ok(Instruction {
+        program_id: program.program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(program.global_pda(), false),
+            AccountMeta::new(state.fee_recipient, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(program.bonding_curve_pda(mint), false),
+            AccountMeta::new(program.associated_bonding_curve(mint), false),
+            AccountMeta::new(get_associated_token_address(user, mint), false),
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(Pubkey::from_str(SYSTEM_PROGRAM_ID).unwrap(), false),
+            AccountMeta::new_readonly(token_program_id(), false),
+            creator_or_rent,
+            AccountMeta::new_readonly(program.event_authority_pda(), false),
+            AccountMeta::new_readonly(program.program_id, false),
+        ],
+        data: trade_data(BUY_INSTRUCTION_DISCRIMINATOR, order.token_amount, order.max_sol_cost),
+    })
+}
+
+/// pump.fun `sell` of `order` to `state`'s curve, signed by `user`
+pub fn sell_instruction(program: &ProgramConfig, state: &CurveState, user: &Pubkey, order: &SellOrder) -> Result<Instruction> {
+    let mint = &state.mint;
+    // Creator-vault programs take the creator vault where legacy ones take the associated token program
+    let creator_or_ata_program = match state.creator()? {
+        Some(creator) => AccountMeta::new(program.creator_vault_pda(&creator), false),
+        None => AccountMeta::new_readonly(associated_token_program_id(), false),
+    };
+    Ok(Instruction {
+        program_id: program.program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(program.global_pda(), false),
+            AccountMeta::new(state.fee_recipient, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(program.bonding_curve_pda(mint), false),
+            AccountMeta::new(program.associated_bonding_curve(mint), false),
+            AccountMeta::new(get_associated_token_address(user, mint), false),
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(Pubkey::from_str(SYSTEM_PROGRAM_ID).unwrap(), false),
+            creator_or_ata_program,
+            AccountMeta::new_readonly(token_program_id(), false),
+            AccountMeta::new_readonly(program.event_authority_pda(), false),
+            AccountMeta::new_readonly(program.program_id, false),
+        ],
+        data: trade_data(SELL_INSTRUCTION_DISCRIMINATOR, order.token_amount, order.min_sol_output),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve_state(version: ProgramVersion, creator: Option<Pubkey>) -> CurveState {
+        CurveState {
+            mint: Pubkey::new_unique(),
+            curve: BondingCurveAccount {
+                virtual_token_reserves: 1_073_000_000_000_000,
+                virtual_sol_reserves: 30_000_000_000,
+                real_token_reserves: 793_100_000_000_000,
+                real_sol_reserves: 1_000_000_000,
+                token_total_supply: 1_000_000_000_000_000,
+                complete: false,
+                creator,
+            },
+            global: GlobalAccount {
+                initialized: true,
+                authority: Pubkey::default(),
+                fee_recipient: Pubkey::default(),
+                initial_virtual_token_reserves: 1_073_000_000_000_000,
+                initial_virtual_sol_reserves: 30_000_000_000,
+                initial_real_token_reserves: 793_100_000_000_000,
+                token_total_supply: 1_000_000_000_000_000,
+                fee_basis_points: 95,
+                withdraw_authority: Pubkey::default(),
+                enable_migrate: true,
+                pool_migration_fee: None,
+                creator_fee_basis_points: Some(5),
+                fee_recipients: Vec::new(),
+            },
+            version,
+            fee_recipient: Pubkey::new_unique(),
+        }
+    }
+
+    #[test]
+    fn test_orders_and_instruction_layout() {
+        let creator = Pubkey::new_unique();
+        let state = curve_state(ProgramVersion::CreatorVault, Some(creator));
+        let program = ProgramConfig::default();
+        let user = Pubkey::new_unique();
+
+        let buy = BuyOrder::quote(&state, 1_000_000_000, 500).unwrap();
+        assert_eq!(buy.token_amount, 34_277_831_558_567);
+        let fresh = CurveState { curve: state.global.initial_curve(Some(creator)), ..state.clone() };
+        assert_eq!(BuyOrder::quote(&fresh, 1_000_000_000, 500).unwrap(), buy);
+        assert_eq!(buy.max_sol_cost, 1_050_000_000);
+        let ix = buy_instruction(&program, &state, &user, &buy).unwrap();
+        assert_eq!(&ix.data[..8], BUY_INSTRUCTION_DISCRIMINATOR);
+        assert_eq!(u64::from_le_bytes(ix.data[8..16].try_into().unwrap()), buy.token_amount);
+        assert_eq!(ix.accounts[9].pubkey, program.creator_vault_pda(&creator));
+
+        let sell = SellOrder::quote(&state, buy.token_amount, 100).unwrap();
+        assert!(sell.expected_sol < 1_000_000_000);
+        assert_eq!(sell.min_sol_output, sell.expected_sol * 9_900 / 10_000);
+        let ix = sell_instruction(&program, &state, &user, &sell).unwrap();
+        assert_eq!(ix.accounts[8].pubkey, program.creator_vault_pda(&creator));
+        assert_eq!(u64::from_le_bytes(ix.data[16..24].try_into().unwrap()), sell.min_sol_output);
+        assert_eq!(SellAmount::All.resolve(buy.token_amount).unwrap(), buy.token_amount);
+        assert!(SellAmount::Tokens(buy.token_amount + 1).resolve(buy.token_amount).is_err());
+        assert!(SellAmount::All.resolve(0).is_err());
+
+        assert!(buy_instruction(&program, &curve_state(ProgramVersion::CreatorVault, None), &user, &buy).is_err());
+        let legacy = buy_instruction(&program, &curve_state(ProgramVersion::Legacy, None), &user, &buy).unwrap();
+        assert_eq!(legacy.accounts[9].pubkey, solana_sdk::sysvar::rent::ID);
+    }
+
+    #[test]
+    fn test_simulated_fill_check() {
+        let order = BuyOrder::new(Venue::BondingCurve, 1_000_000_000, 30_000_000_000, 500);
+        let exact = SimulatedFill { token_amount: 30_000_000_000, sol_lamports: 1_000_000_000 };
+        assert_eq!(exact.price_deviation_bps(&order), Some(0));
+        assert!(check_simulated_fill(&order, &exact, 0).is_ok());
+
+        // 4% more SOL for the same tokens
+        let stale = SimulatedFill { token_amount: 30_000_000_000, sol_lamports: 1_040_000_000 };
+        assert_eq!(stale.price_deviation_bps(&order), Some(400));
+        assert!(check_simulated_fill(&order, &stale, 300).is_err());
+        assert!(check_simulated_fill(&order, &stale, 500).is_ok());
+
+        let cheaper = SimulatedFill { token_amount: 31_000_000_000, sol_lamports: 1_000_000_000 };
+        assert!(cheaper.price_deviation_bps(&order).unwrap() < 0);
+        assert!(check_simulated_fill(&order, &SimulatedFill { token_amount: 0, sol_lamports: 1 }, 300).is_err());
+    }
+}
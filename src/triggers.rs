@@ -0,0 +1,398 @@
+use anyhow::Result;
+use hmac::{Hmac, Mac};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+
+use crate::discord_bot::{self, DiscordConfig};
+use crate::health::{is_ready, HealthChecker};
+use crate::read_only::{ensure_writable, handle_admin_request, is_read_only};
+use crate::secure_credentials::SecureApiKey;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Draft directory unless `DRAFTS_DIR` is set
+pub const DEFAULT_DRAFTS_DIR: &str = "data/drafts";
+/// Trigger definitions unless `TRIGGERS_FILE` is set
+pub const DEFAULT_TRIGGERS_FILE: &str = "data/triggers.json";
+pub const TIMESTAMP_HEADER: &str = "x-trigger-timestamp";
+pub const SIGNATURE_HEADER: &str = "x-trigger-signature";
+/// Signed requests older (or newer) than this are rejected as replays
+pub(crate) const MAX_REQUEST_AGE_SECS: u64 = 300;
+const MAX_REQUEST_BYTES: usize = 64 * 1024;
+
+/// A prepared launch, stored as `<DRAFTS_DIR>/<name>.json`; unset fields use the normal defaults
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LaunchDraft {
+    pub symbol: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub image_url: Option<String>,
+    pub twitter: Option<String>,
+    pub telegram: Option<String>,
+    pub website: Option<String>,
+    /// Registered wallet name or keypair file paying for the launch
+    pub wallet: Option<String>,
+}
+
+impl LaunchDraft {
+    pub fn load(name: &str) -> Result<Self> {
+        if name.contains(['/', '\\']) || name.starts_with('.') {
+            return Err(anyhow::anyhow!("Invalid draft name '{}'", name));
+        }
+        let dir = PathBuf::from(std::env::var("DRAFTS_DIR").unwrap_or_else(|_| DEFAULT_DRAFTS_DIR.to_string()));
+        let path = dir.join(format!("{}.json", name));
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|e| anyhow::anyhow!("Failed to read draft {}: {}", path.display(), e))?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Launch command-line flags equivalent to this draft
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        for (flag, value) in [
+            ("--symbol", &self.symbol),
+            ("--name", &self.name),
+            ("--description", &self.description),
+            ("--image", &self.image),
+            ("--image-url", &self.image_url),
+            ("--twitter", &self.twitter),
+            ("--telegram", &self.telegram),
+            ("--website", &self.website),
+            ("--wallet", &self.wallet),
+        ] {
+            if let Some(value) = value {
+                args.push(format!("{}={}", flag, value));
+            }
+        }
+        args
+    }
+
+    fn field_mut(&mut self, field: &str) -> Option<&mut Option<String>> {
+        match field {
+            "symbol" => Some(&mut self.symbol),
+            "name" => Some(&mut self.name),
+            "description" => Some(&mut self.description),
+            "image_url" => Some(&mut self.image_url),
+            "twitter" => Some(&mut self.twitter),
+            "telegram" => Some(&mut self.telegram),
+            "website" => Some(&mut self.website),
+            _ => None,
+        }
+    }
+}
+
+/// An inbound webhook that fires a draft, filling draft fields from the payload
+#[derive(Debug, Clone, Deserialize)]
+pub struct Trigger {
+    /// Path segment: requests go to `POST /triggers/<name>`
+    pub name: String,
+    pub draft: String,
+    /// Environment variable / secrets backend key holding the HMAC secret
+    pub secret: String,
+    /// Draft field -> JSON pointer into the payload, e.g. `"description": "/tweet/text"`
+    #[serde(default)]
+    pub fields: HashMap<String, String>,
+    /// Fire on every request instead of only the first
+    #[serde(default)]
+    pub repeat: bool,
+}
+
+impl Trigger {
+    /// Draft with the mapped payload fields applied
+    pub fn apply(&self, mut draft: LaunchDraft, payload: &serde_json::Value) -> Result<LaunchDraft> {
+        for (field, pointer) in &self.fields {
+            let value = payload.pointer(pointer)
+                .ok_or_else(|| anyhow::anyhow!("Payload has no {} for field {}", pointer, field))?;
+            let value = match value {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            *draft.field_mut(field).ok_or_else(|| anyhow::anyhow!("Trigger {} maps unknown draft field '{}'", self.name, field))? = Some(value);
+        }
+        Ok(draft)
+    }
+}
+
+/// Trigger definitions from `TRIGGERS_FILE`
+pub fn load_triggers() -> Result<Vec<Trigger>> {
+    let path = std::env::var("TRIGGERS_FILE").unwrap_or_else(|_| DEFAULT_TRIGGERS_FILE.to_string());
+    let contents = std::fs::read_to_string(Path::new(&path))
+        .map_err(|e| anyhow::anyhow!("Failed to read triggers file {}: {}", path, e))?;
+    let triggers: Vec<Trigger> = serde_json::from_str(&contents)?;
+    for trigger in &triggers {
+        if let Some(field) = trigger.fields.keys().find(|f| LaunchDraft::default().field_mut(f).is_none()) {
+            return Err(anyhow::anyhow!("Trigger {} maps unknown draft field '{}'", trigger.name, field));
+        }
+    }
+    Ok(triggers)
+}
+
+/// Hex HMAC-SHA256 of `<timestamp>.<body>`, the value senders put in `X-Trigger-Signature: sha256=<hex>`
+pub fn sign_request(secret: &[u8], timestamp: u64, body: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(format!("{}.", timestamp).as_bytes());
+    mac.update(body);
+    mac.finalize().into_bytes().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Check the signature headers of a request against `secret` at time `now`
+pub fn verify_request(secret: &[u8], timestamp: Option<&str>, signature: Option<&str>, body: &[u8], now: u64) -> Result<()> {
+    let timestamp: u64 = timestamp.and_then(|t| t.trim().parse().ok())
+        .ok_or_else(|| anyhow::anyhow!("Missing or invalid {} header", TIMESTAMP_HEADER))?;
+    if now.abs_diff(timestamp) > MAX_REQUEST_AGE_SECS {
+        return Err(anyhow::anyhow!("Request timestamp is outside the {}s window", MAX_REQUEST_AGE_SECS));
+    }
+    let hex = signature.and_then(|s| s.trim().strip_prefix("sha256="))
+        .ok_or_else(|| anyhow::anyhow!("Missing or invalid {} header", SIGNATURE_HEADER))?;
+    let expected: Vec<u8> = (0..hex.len()).step_by(2)
+        .map(|i| hex.get(i..i + 2).and_then(|byte| u8::from_str_radix(byte, 16).ok()))
+        .collect::<Option<_>>()
+        .ok_or_else(|| anyhow::anyhow!("Signature is not hex"))?;
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(format!("{}.", timestamp).as_bytes());
+    mac.update(body);
+    mac.verify_slice(&expected).map_err(|_| anyhow::anyhow!("Signature does not match"))
+}
+
+/// A draft fired by a trigger, ready to launch
+#[derive(Debug, Clone)]
+pub struct FiredTrigger {
+    pub trigger: String,
+    pub draft: LaunchDraft,
+}
+
+pub(crate) struct HttpRequest {
+    pub(crate) method: String,
+    pub(crate) path: String,
+    /// Lowercased names
+    pub(crate) headers: HashMap<String, String>,
+    pub(crate) body: Vec<u8>,
+}
+
+pub(crate) async fn read_request(stream: &mut TcpStream) -> Result<HttpRequest> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Err(anyhow::anyhow!("Connection closed mid-request"));
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if let Some(end) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end;
+        }
+        if buffer.len() > MAX_REQUEST_BYTES {
+            return Err(anyhow::anyhow!("Request headers too large"));
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let (method, path) = (request_line.next().unwrap_or_default().to_string(), request_line.next().unwrap_or_default().to_string());
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+        .collect();
+
+    let length: usize = headers.get("content-length").and_then(|l| l.parse().ok()).unwrap_or(0);
+    if length > MAX_REQUEST_BYTES {
+        return Err(anyhow::anyhow!("Request body too large"));
+    }
+    let mut body = buffer[header_end + 4..].to_vec();
+    while body.len() < length {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Err(anyhow::anyhow!("Connection closed mid-body"));
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+    body.truncate(length);
+    Ok(HttpRequest { method, path, headers, body })
+}
+
+pub(crate) async fn respond(stream: &mut TcpStream, status: &str, body: &serde_json::Value) {
+    let body = body.to_string();
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status, body.len(), body
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+}
+
+struct TriggerServer {
+    triggers: HashMap<String, Trigger>,
+    fired: Mutex<HashSet<String>>,
+    queue: mpsc::Sender<FiredTrigger>,
+    discord: Option<DiscordConfig>,
+    health: HealthChecker,
+}
+
+impl TriggerServer {
+    /// Status line and JSON body for one request
+    async fn handle(&self, request: HttpRequest) -> (&'static str, serde_json::Value) {
+        match request.path.as_str() {
+            "/discord/interactions" => return self.handle_discord(request).await,
+            // Liveness: answering at all means the accept loop is running
+            "/healthz" => return ("200 OK", serde_json::json!({ "status": "ok", "read_only": is_read_only() })),
+            "/admin/read-only" => return handle_admin_request(&request.method, &request.headers, &request.body),
+            "/readyz" => return self.handle_ready().await,
+            _ => {}
+        }
+        let (status, message) = self.handle_trigger(request).await;
+        (status, serde_json::json!({ "message": message }))
+    }
+
+    /// 503 until the RPC endpoint answers, the payer is funded and the vanity pool can supply a mint
+    async fn handle_ready(&self) -> (&'static str, serde_json::Value) {
+        let checks = self.health.check().await;
+        let failed: Vec<String> = checks.iter().filter(|c| !c.ok).map(|c| format!("{}: {}", c.name, c.detail)).collect();
+        if is_ready(&checks) {
+            ("200 OK", serde_json::json!({ "status": "ready", "checks": checks }))
+        } else {
+            ("503 Service Unavailable", serde_json::json!({ "status": "not ready", "message": failed.join("; "), "checks": checks }))
+        }
+    }
+
+    /// Slash commands, signed by Discord with the application's ed25519 key
+    async fn handle_discord(&self, request: HttpRequest) -> (&'static str, serde_json::Value) {
+        let Some(config) = &self.discord else {
+            return ("404 Not Found", serde_json::json!({ "message": "Discord bot not configured" }));
+        };
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        if let Err(e) = discord_bot::verify_interaction(
+            &config.public_key,
+            request.headers.get(discord_bot::TIMESTAMP_HEADER).map(String::as_str),
+            request.headers.get(discord_bot::SIGNATURE_HEADER).map(String::as_str),
+            &request.body,
+            now,
+        ) {
+            return ("401 Unauthorized", serde_json::json!({ "message": e.to_string() }));
+        }
+        match serde_json::from_slice(&request.body) {
+            Ok(interaction) => ("200 OK", discord_bot::handle_interaction(config, &interaction, &self.queue).await),
+            Err(e) => ("400 Bad Request", serde_json::json!({ "message": format!("Payload is not JSON: {}", e) })),
+        }
+    }
+
+    /// Status line and message for a webhook trigger; queues the launch when the trigger fires
+    async fn handle_trigger(&self, request: HttpRequest) -> (&'static str, String) {
+        let Some(name) = request.path.strip_prefix("/triggers/") else {
+            return ("404 Not Found", "Unknown path".to_string());
+        };
+        let Some(trigger) = self.triggers.get(name) else {
+            return ("404 Not Found", format!("Unknown trigger '{}'", name));
+        };
+        if request.method != "POST" {
+            return ("405 Method Not Allowed", "Use POST".to_string());
+        }
+        if let Err(e) = ensure_writable("fire triggers") {
+            return ("503 Service Unavailable", e.to_string());
+        }
+
+        let secret = match SecureApiKey::load(&trigger.secret) {
+            Ok(secret) => secret,
+            Err(e) => {
+                warn!("Trigger {} has no secret: {}", trigger.name, e);
+                return ("500 Internal Server Error", "Trigger secret not configured".to_string());
+            }
+        };
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        if let Err(e) = verify_request(
+            secret.expose_secret().as_bytes(),
+            request.headers.get(TIMESTAMP_HEADER).map(String::as_str),
+            request.headers.get(SIGNATURE_HEADER).map(String::as_str),
+            &request.body,
+            now,
+        ) {
+            warn!("Rejected request for trigger {}: {}", trigger.name, e);
+            return ("401 Unauthorized", e.to_string());
+        }
+
+        let payload: serde_json::Value = match serde_json::from_slice(&request.body) {
+            Ok(payload) => payload,
+            Err(e) => return ("400 Bad Request", format!("Payload is not JSON: {}", e)),
+        };
+        let draft = match LaunchDraft::load(&trigger.draft).and_then(|draft| trigger.apply(draft, &payload)) {
+            Ok(draft) => draft,
+            Err(e) => return ("422 Unprocessable Entity", e.to_string()),
+        };
+        // Checked and recorded together so two simultaneous events cannot both fire a one-shot trigger
+        if !trigger.repeat && !self.fired.lock().unwrap().insert(trigger.name.clone()) {
+            return ("409 Conflict", format!("Trigger {} already fired", trigger.name));
+        }
+        if self.queue.send(FiredTrigger { trigger: trigger.name.clone(), draft }).await.is_err() {
+            return ("503 Service Unavailable", "Launch queue closed".to_string());
+        }
+        info!("Trigger {} fired draft {}", trigger.name, trigger.draft);
+        ("202 Accepted", format!("Launch of draft {} queued", trigger.draft))
+    }
+}
+
+/// Accept signed webhook requests (and Discord interactions, when configured) on `listen_addr`
+/// and queue the drafts they fire; `/healthz` and `/readyz` serve liveness and readiness probes
+pub async fn serve_triggers(listen_addr: &str, triggers: Vec<Trigger>, discord: Option<DiscordConfig>, health: HealthChecker, queue: mpsc::Sender<FiredTrigger>) -> Result<()> {
+    let listener = TcpListener::bind(listen_addr).await?;
+    info!("Listening for {} trigger(s) on {}{}", triggers.len(), listen_addr,
+          if discord.is_some() { " (Discord interactions at /discord/interactions)" } else { "" });
+    let server = Arc::new(TriggerServer {
+        triggers: triggers.into_iter().map(|t| (t.name.clone(), t)).collect(),
+        fired: Mutex::new(HashSet::new()),
+        queue,
+        discord,
+        health,
+    });
+    loop {
+        let (mut stream, peer) = listener.accept().await?;
+        let server = server.clone();
+        tokio::spawn(async move {
+            let (status, body) = match read_request(&mut stream).await {
+                Ok(request) => server.handle(request).await,
+                Err(e) => ("400 Bad Request", serde_json::json!({ "message": e.to_string() })),
+            };
+            if !status.starts_with('2') {
+                info!("{} from {}: {}", status, peer, body["message"]);
+            }
+            respond(&mut stream, status, &body).await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trigger_signature_and_mapping() {
+        let body = br#"{"tweet":{"text":"Moon cat is here","likes":42}}"#;
+        let signature = format!("sha256={}", sign_request(b"secret", 1_700_000_000, body));
+        assert!(verify_request(b"secret", Some("1700000000"), Some(&signature), body, 1_700_000_100).is_ok());
+        assert!(verify_request(b"other", Some("1700000000"), Some(&signature), body, 1_700_000_100).is_err());
+        assert!(verify_request(b"secret", Some("1700000000"), Some(&signature), b"{}", 1_700_000_100).is_err());
+        assert!(verify_request(b"secret", Some("1700000000"), Some(&signature), body, 1_700_001_000).is_err());
+        assert!(verify_request(b"secret", None, Some(&signature), body, 1_700_000_100).is_err());
+
+        let trigger = Trigger {
+            name: "tweet".to_string(),
+            draft: "moon".to_string(),
+            secret: "TRIGGER_TWEET_SECRET".to_string(),
+            fields: HashMap::from([("description".to_string(), "/tweet/text".to_string())]),
+            repeat: false,
+        };
+        let draft = LaunchDraft { symbol: Some("MCAT".to_string()), ..Default::default() };
+        let payload: serde_json::Value = serde_json::from_slice(body).unwrap();
+        let fired = trigger.apply(draft.clone(), &payload).unwrap();
+        assert_eq!(fired.to_args(), vec!["--symbol=MCAT".to_string(), "--description=Moon cat is here".to_string()]);
+        assert!(trigger.apply(draft, &serde_json::json!({})).is_err());
+    }
+}
@@ -0,0 +1,60 @@
+use anyhow::Result;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use solana_sdk::{pubkey::Pubkey, transaction::Transaction};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+/// Wire encoding of a transaction as accepted by `sendTransaction` with base64 encoding
+pub fn encode_transaction_base64(transaction: &Transaction) -> Result<String> {
+    let bytes = bincode::serialize(transaction)?;
+    Ok(BASE64.encode(bytes))
+}
+
+/// Human-readable listing of signatures, accounts and instructions.
+///
+/// `labels` maps known addresses to names (e.g. "bonding_curve") to make the listing easier to read.
+pub fn describe_transaction(transaction: &Transaction, labels: &HashMap<Pubkey, String>) -> String {
+    let message = &transaction.message;
+    let label_for = |key: &Pubkey| labels.get(key).map(|l| format!(" ({})", l)).unwrap_or_default();
+    let mut out = String::new();
+
+    let _ = writeln!(out, "Recent blockhash: {}", message.recent_blockhash);
+    let _ = writeln!(out, "Signatures:");
+    for (index, signature) in transaction.signatures.iter().enumerate() {
+        let signer = message.account_keys.get(index).map(|k| k.to_string()).unwrap_or_default();
+        let _ = writeln!(out, "  [{}] {} by {}", index, signature, signer);
+    }
+
+    let _ = writeln!(out, "Accounts:");
+    for (index, key) in message.account_keys.iter().enumerate() {
+        let signer = if message.is_signer(index) { "signer" } else { "" };
+        let writable = if message.is_maybe_writable(index, None) { "writable" } else { "readonly" };
+        let _ = writeln!(out, "  [{}] {} {} {}{}", index, key, writable, signer, label_for(key));
+    }
+
+    let _ = writeln!(out, "Instructions:");
+    for (index, instruction) in message.instructions.iter().enumerate() {
+        let program = message.account_keys[instruction.program_id_index as usize];
+        let _ = writeln!(out, "  #{} program {}{}", index, program, label_for(&program));
+        for account_index in &instruction.accounts {
+            let key = message.account_keys[*account_index as usize];
+            let _ = writeln!(out, "      account {}{}", key, label_for(&key));
+        }
+        let data_hex: String = instruction.data.iter().map(|b| format!("{:02x}", b)).collect();
+        let _ = writeln!(out, "      data ({} bytes) {}", instruction.data.len(), data_hex);
+    }
+
+    out
+}
+
+/// Write the signed transaction (base64 plus decoded listing) to `path` for offline analysis or replay
+pub fn dump_transaction(path: &str, transaction: &Transaction, labels: &HashMap<Pubkey, String>) -> Result<()> {
+    let mut contents = String::new();
+    let _ = writeln!(contents, "# Signed transaction (base64, wire format)");
+    let _ = writeln!(contents, "{}", encode_transaction_base64(transaction)?);
+    let _ = writeln!(contents);
+    let _ = writeln!(contents, "# Decoded");
+    contents.push_str(&describe_transaction(transaction, labels));
+    std::fs::write(path, contents)?;
+    Ok(())
+}
@@ -0,0 +1,296 @@
+use anyhow::Result;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use solana_client::client_error::{ClientError, Result as ClientResult};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcSendTransactionConfig;
+use solana_client::rpc_request::RpcError;
+use solana_commitment_config::{CommitmentConfig, CommitmentLevel};
+use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::Transaction};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Journal location unless `TX_JOURNAL_PATH` is set
+pub const DEFAULT_TX_JOURNAL_PATH: &str = "data/sent_transactions.jsonl";
+
+static JOURNAL_LOCK: Mutex<()> = Mutex::new(());
+
+/// One state change of a sent transaction; the latest line for a key wins
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JournalEntry {
+    /// Identifies the logical launch, so a re-run maps to the same entry
+    pub key: String,
+    /// Wallet that paid for the launch; absent in journals written before it was recorded
+    #[serde(default)]
+    pub payer: Option<String>,
+    pub signature: String,
+    pub mint: Option<String>,
+    pub last_valid_block_height: u64,
+    /// "sent", "confirmed" or "failed"
+    pub status: String,
+    pub timestamp: u64,
+    /// 1-based launch attempt; later attempts use a new mint after a hard failure
+    #[serde(default = "first_attempt")]
+    pub attempt: u32,
+}
+
+fn first_attempt() -> u32 {
+    1
+}
+
+/// What to do about a launch that may already have been sent
+#[derive(Debug, Clone, PartialEq)]
+pub enum PriorSubmission {
+    /// Nothing in flight; build a new transaction
+    None,
+    /// The earlier transaction landed
+    Landed { signature: Signature, mint: Option<Pubkey> },
+    /// The earlier transaction is still valid and may land; wait for it instead of resending
+    InFlight { signature: Signature, mint: Option<Pubkey>, last_valid_block_height: u64 },
+}
+
+/// Append-only record of sent transactions, used to avoid sending a second competing launch
+pub struct TxJournal {
+    path: String,
+}
+
+impl TxJournal {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Journal at `TX_JOURNAL_PATH`, or the default location
+    pub fn from_env() -> Self {
+        Self::new(std::env::var("TX_JOURNAL_PATH").unwrap_or_else(|_| DEFAULT_TX_JOURNAL_PATH.to_string()))
+    }
+
+    /// Stable key for a launch from the payer and token fields
+    pub fn launch_key(payer: &Pubkey, name: &str, symbol: &str, description: &str) -> String {
+        let digest = Sha256::new()
+            .chain_update(payer.as_ref())
+            .chain_update(name.as_bytes()).chain_update([0])
+            .chain_update(symbol.as_bytes()).chain_update([0])
+            .chain_update(description.as_bytes())
+            .finalize();
+        digest.iter().take(16).map(|b| format!("{:02x}", b)).collect()
+    }
+
+    pub fn record(&self, key: &str, payer: &Pubkey, signature: &Signature, mint: Option<&Pubkey>, last_valid_block_height: u64, status: &str) -> Result<()> {
+        self.record_attempt(key, payer, signature, mint, last_valid_block_height, status, 1)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_attempt(&self, key: &str, payer: &Pubkey, signature: &Signature, mint: Option<&Pubkey>, last_valid_block_height: u64, status: &str, attempt: u32) -> Result<()> {
+        let _guard = JOURNAL_LOCK.lock().unwrap();
+        let entry = JournalEntry {
+            key: key.to_string(),
+            payer: Some(payer.to_string()),
+            signature: signature.to_string(),
+            mint: mint.map(|m| m.to_string()),
+            last_valid_block_height,
+            status: status.to_string(),
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            attempt,
+        };
+        if let Some(parent) = Path::new(&self.path).parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+        file.sync_all()?;
+        Ok(())
+    }
+
+    /// Latest entry recorded for `key`
+    pub fn latest(&self, key: &str) -> Result<Option<JournalEntry>> {
+        if !Path::new(&self.path).exists() {
+            return Ok(None);
+        }
+        let mut latest = None;
+        for line in BufReader::new(File::open(&self.path)?).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<JournalEntry>(&line) {
+                Ok(entry) if entry.key == key => latest = Some(entry),
+                Ok(_) => {}
+                Err(e) => warn!("Skipping unreadable journal line in {}: {}", self.path, e),
+            }
+        }
+        Ok(latest)
+    }
+
+    /// Mints of every launch recorded as confirmed, oldest first
+    pub fn launched_mints(&self) -> Result<Vec<Pubkey>> {
+        if !Path::new(&self.path).exists() {
+            return Ok(Vec::new());
+        }
+        let mut mints: Vec<Pubkey> = Vec::new();
+        for line in BufReader::new(File::open(&self.path)?).lines() {
+            let Ok(entry) = serde_json::from_str::<JournalEntry>(&line?) else { continue };
+            if entry.status != "confirmed" {
+                continue;
+            }
+            if let Some(mint) = entry.mint.as_deref().and_then(|m| Pubkey::from_str(m).ok()) {
+                if !mints.contains(&mint) {
+                    mints.push(mint);
+                }
+            }
+        }
+        Ok(mints)
+    }
+
+    /// Unix times of the confirmed launches paid for by `payer`, oldest first
+    pub fn launch_times(&self, payer: &Pubkey) -> Result<Vec<u64>> {
+        if !Path::new(&self.path).exists() {
+            return Ok(Vec::new());
+        }
+        let payer = payer.to_string();
+        let mut launches: Vec<(String, u64)> = Vec::new();
+        for line in BufReader::new(File::open(&self.path)?).lines() {
+            let Ok(entry) = serde_json::from_str::<JournalEntry>(&line?) else { continue };
+            if entry.status == "confirmed" && entry.payer.as_deref() == Some(payer.as_str())
+                && !launches.iter().any(|(signature, _)| *signature == entry.signature) {
+                launches.push((entry.signature, entry.timestamp));
+            }
+        }
+        Ok(launches.into_iter().map(|(_, timestamp)| timestamp).collect())
+    }
+
+    /// Check the chain for an earlier submission of `key`
+    pub async fn prior_submission(&self, rpc: &RpcClient, key: &str) -> Result<PriorSubmission> {
+        let Some(entry) = self.latest(key)? else {
+            return Ok(PriorSubmission::None);
+        };
+        if entry.status == "failed" {
+            return Ok(PriorSubmission::None);
+        }
+        let signature = Signature::from_str(&entry.signature)?;
+        let mint = entry.mint.as_deref().map(Pubkey::from_str).transpose()?;
+        if entry.status == "confirmed" {
+            return Ok(PriorSubmission::Landed { signature, mint });
+        }
+
+        // The full history, not just the recent status cache: the launch may have landed long ago
+        let status = rpc.get_signature_statuses_with_history(&[signature]).await?.value.into_iter().next().flatten();
+        match status.map(|status| status.err) {
+            Some(None) => Ok(PriorSubmission::Landed { signature, mint }),
+            Some(Some(e)) => {
+                info!("Earlier transaction {} failed on-chain ({}); sending a new one", signature, e);
+                Ok(PriorSubmission::None)
+            }
+            None if rpc.get_block_height().await? <= entry.last_valid_block_height => {
+                Ok(PriorSubmission::InFlight { signature, mint, last_valid_block_height: entry.last_valid_block_height })
+            }
+            // A node without the history may not know the signature, but the mint it created is there
+            None if mint_exists(rpc, mint.as_ref()).await? => {
+                info!("Earlier transaction {} is unknown to the RPC node but its mint exists; treating it as landed", signature);
+                Ok(PriorSubmission::Landed { signature, mint })
+            }
+            None => {
+                info!("Earlier transaction {} expired without landing; sending a new one", signature);
+                Ok(PriorSubmission::None)
+            }
+        }
+    }
+}
+
+/// Whether the journaled mint of a launch exists on-chain
+async fn mint_exists(rpc: &RpcClient, mint: Option<&Pubkey>) -> Result<bool> {
+    let Some(mint) = mint else {
+        return Ok(false);
+    };
+    Ok(rpc.get_account_with_commitment(mint, CommitmentConfig::confirmed()).await?.value.is_some())
+}
+
+/// Poll until `signature` reaches `commitment` or its blockhash expires. Returns whether it landed successfully.
+pub async fn wait_for_signature(rpc: &RpcClient, signature: &Signature, last_valid_block_height: u64, commitment: CommitmentConfig) -> Result<bool> {
+    loop {
+        match rpc.get_signature_status_with_commitment(signature, commitment).await? {
+            Some(Ok(())) => return Ok(true),
+            Some(Err(e)) => {
+                warn!("Transaction {} failed: {}", signature, e);
+                return Ok(false);
+            }
+            // Already landed, just not at the requested level yet; expiry no longer matters
+            None if rpc.get_signature_status_with_commitment(signature, CommitmentConfig::processed()).await?.is_some() => {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+            None if rpc.get_block_height().await? > last_valid_block_height => return Ok(false),
+            None => tokio::time::sleep(Duration::from_secs(2)).await,
+        }
+    }
+}
+
+/// Send `transaction` and wait until it reaches `commitment`.
+///
+/// Like `RpcClient::send_and_confirm_transaction`, a transaction error (rejected in preflight or
+/// failed on-chain) is returned as such, so callers can tell it apart from an expired transaction.
+pub async fn send_and_confirm(rpc: &RpcClient, transaction: &Transaction, commitment: CommitmentConfig) -> ClientResult<Signature> {
+    rpc.send_transaction_with_config(transaction, send_config(commitment)).await?;
+    confirm_sent(rpc, transaction, commitment).await
+}
+
+/// Send settings for a transaction that must reach `commitment`
+pub fn send_config(commitment: CommitmentConfig) -> RpcSendTransactionConfig {
+    // Simulating against finalized state would use a stale bank; confirmed is what the RPC client defaults to
+    let preflight = match commitment.commitment {
+        CommitmentLevel::Processed => CommitmentLevel::Processed,
+        _ => CommitmentLevel::Confirmed,
+    };
+    RpcSendTransactionConfig { preflight_commitment: Some(preflight), ..Default::default() }
+}
+
+/// Wait until the already sent `transaction` reaches `commitment`, or fail once its blockhash expires
+pub async fn confirm_sent(rpc: &RpcClient, transaction: &Transaction, commitment: CommitmentConfig) -> ClientResult<Signature> {
+    let signature = transaction.signatures[0];
+    loop {
+        match rpc.get_signature_status_with_commitment(&signature, commitment).await? {
+            Some(Ok(())) => return Ok(signature),
+            Some(Err(e)) => return Err(e.into()),
+            None if rpc.get_signature_status_with_commitment(&signature, CommitmentConfig::processed()).await?.is_some() => {}
+            None if !rpc.is_blockhash_valid(&transaction.message.recent_blockhash, CommitmentConfig::processed()).await? => {
+                return Err(ClientError::from(RpcError::ForUser(format!("transaction {} expired before reaching {:?}", signature, commitment.commitment))));
+            }
+            None => {}
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_latest_entry_wins() {
+        let path = std::env::temp_dir().join(format!("tx_journal_test_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let journal = TxJournal::new(path.to_string_lossy().to_string());
+        let key = TxJournal::launch_key(&Pubkey::default(), "Token", "TKN", "desc");
+        assert_ne!(key, TxJournal::launch_key(&Pubkey::default(), "Token", "TKN2", "desc"));
+
+        let signature = Signature::default();
+        let payer = Pubkey::default();
+        journal.record(&key, &payer, &signature, None, 100, "sent").unwrap();
+        journal.record("other", &payer, &signature, None, 100, "sent").unwrap();
+        journal.record(&key, &payer, &signature, None, 100, "confirmed").unwrap();
+        assert_eq!(journal.latest(&key).unwrap().unwrap().status, "confirmed");
+        // A confirmed launch is never re-checked, so even an unreachable node reports it landed
+        let rpc = RpcClient::new("http://127.0.0.1:1".to_string());
+        assert_eq!(journal.prior_submission(&rpc, &key).await.unwrap(), PriorSubmission::Landed { signature, mint: None });
+        assert_eq!(journal.launch_times(&payer).unwrap().len(), 1);
+        assert!(journal.launch_times(&Pubkey::new_unique()).unwrap().is_empty());
+        journal.record_attempt(&key, &payer, &signature, None, 100, "failed", 2).unwrap();
+        assert_eq!(journal.latest(&key).unwrap().unwrap().attempt, 2);
+        assert!(journal.latest("missing").unwrap().is_none());
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,100 @@
+use anyhow::Result;
+use solana_sdk::{
+    instruction::Instruction,
+    message::Message,
+    pubkey::Pubkey,
+};
+
+/// Maximum serialized transaction size accepted by the network (IPv6 MTU minus headers)
+pub const PACKET_DATA_SIZE: usize = 1232;
+
+const SIGNATURE_SIZE: usize = 64;
+
+/// An instruction tagged with a human-readable description of what it is for
+#[derive(Debug, Clone)]
+pub struct TransactionComponent {
+    pub label: String,
+    pub instruction: Instruction,
+}
+
+impl TransactionComponent {
+    pub fn new(label: impl Into<String>, instruction: Instruction) -> Self {
+        Self { label: label.into(), instruction }
+    }
+}
+
+fn short_vec_len(len: usize) -> usize {
+    match len {
+        0..=0x7f => 1,
+        0x80..=0x3fff => 2,
+        _ => 3,
+    }
+}
+
+/// Exact serialized size of a legacy transaction containing `instructions`, once fully signed
+pub fn serialized_transaction_size(instructions: &[Instruction], payer: &Pubkey) -> usize {
+    let message = Message::new(instructions, Some(payer));
+    let num_signatures = message.header.num_required_signatures as usize;
+    short_vec_len(num_signatures) + num_signatures * SIGNATURE_SIZE + message.serialize().len()
+}
+
+/// Check the transaction built from `components` fits in a packet.
+///
+/// On failure the error lists how many bytes each component contributes, largest first,
+/// so the caller knows exactly what to trim. Returns the serialized size on success.
+pub fn validate_transaction_size(components: &[TransactionComponent], payer: &Pubkey) -> Result<usize> {
+    let instructions: Vec<Instruction> = components.iter().map(|c| c.instruction.clone()).collect();
+    let total = serialized_transaction_size(&instructions, payer);
+    if total <= PACKET_DATA_SIZE {
+        return Ok(total);
+    }
+
+    // Marginal cost of each component: size with it minus size without it
+    let mut breakdown: Vec<(usize, &str)> = components
+        .iter()
+        .enumerate()
+        .map(|(index, component)| {
+            let without: Vec<Instruction> = instructions
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != index)
+                .map(|(_, ix)| ix.clone())
+                .collect();
+            (total - serialized_transaction_size(&without, payer), component.label.as_str())
+        })
+        .collect();
+    breakdown.sort_by_key(|(bytes, _)| std::cmp::Reverse(*bytes));
+
+    let details = breakdown
+        .iter()
+        .map(|(bytes, label)| format!("{}: {} bytes", label, bytes))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Err(anyhow::anyhow!(
+        "Transaction too large: {} bytes exceeds the {} byte limit by {} bytes. Trim one of [{}]",
+        total, PACKET_DATA_SIZE, total - PACKET_DATA_SIZE, details
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::instruction::AccountMeta;
+
+    #[test]
+    fn test_oversized_transaction_reports_components() {
+        let payer = Pubkey::new_unique();
+        let small = Instruction::new_with_bytes(Pubkey::new_unique(), &[1; 10], vec![AccountMeta::new(payer, true)]);
+        let large = Instruction::new_with_bytes(Pubkey::new_unique(), &[2; 1200], vec![]);
+
+        let size = validate_transaction_size(&[TransactionComponent::new("small", small.clone())], &payer).unwrap();
+        assert_eq!(size, serialized_transaction_size(std::slice::from_ref(&small), &payer));
+
+        let err = validate_transaction_size(
+            &[TransactionComponent::new("small", small), TransactionComponent::new("large", large)],
+            &payer,
+        ).unwrap_err().to_string();
+        assert!(err.contains("Trim one of [large:"), "{}", err);
+    }
+}
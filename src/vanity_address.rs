@@ -1,5 +1,6 @@
 use std::sync::{Arc, Mutex, Condvar, atomic::{AtomicBool, AtomicU64, Ordering}, OnceLock};
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
 use std::thread;
 use std::time::{Duration, Instant};
 use solana_sdk::{
@@ -8,13 +9,23 @@ use solana_sdk::{
     signer::Signer,
 };
 use anyhow::Result;
-use log::{info, error};
-use rayon::prelude::*;
+use log::{info, warn, error};
+use sha2::{Digest, Sha256};
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
+use crate::progress::progress;
+use crate::vanity_stats::StatsFile;
+use crate::vanity_store::VanityStore;
+use crate::vanity_worker::{WorkerClient, WorkerRequest};
+
 // Constants
+/// Pool size for the default four-character pattern
 pub const TARGET_VANITY_COUNT: usize = 10;
-pub const VANITY_SUFFIX: &str = "pump";
+/// Suffix ground when neither `VANITY_PREFIX` nor `VANITY_SUFFIX` is set
+pub const DEFAULT_VANITY_SUFFIX: &str = "pump";
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+/// Rough single-core grinding rate, used for wait estimates until the pool has timed a real search
+const ASSUMED_KEYPAIRS_PER_SEC_PER_THREAD: f64 = 40_000.0;
 
 #[derive(Debug, Clone)]
 pub struct VanityAddress {
@@ -64,6 +75,198 @@ impl SecureKeypair {
     }
 }
 
+/// What a vanity address must start and/or end with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VanityPattern {
+    prefix: String,
+    suffix: String,
+    ignore_case: bool,
+}
+
+impl Default for VanityPattern {
+    fn default() -> Self {
+        Self { prefix: String::new(), suffix: DEFAULT_VANITY_SUFFIX.to_string(), ignore_case: false }
+    }
+}
+
+impl VanityPattern {
+    /// Fails unless at least one part is set and every character can appear in a base58 address
+    pub fn new(prefix: &str, suffix: &str, ignore_case: bool) -> Result<Self> {
+        if prefix.is_empty() && suffix.is_empty() {
+            return Err(anyhow::anyhow!("a vanity pattern needs a prefix, a suffix or both"));
+        }
+        for c in prefix.chars().chain(suffix.chars()) {
+            let valid = |c: char| BASE58_ALPHABET.contains(c);
+            if !valid(c) && !(ignore_case && (valid(c.to_ascii_lowercase()) || valid(c.to_ascii_uppercase()))) {
+                return Err(anyhow::anyhow!("'{}' never appears in a base58 address (0, O, I and l are excluded)", c));
+            }
+        }
+        Ok(Self { prefix: prefix.to_string(), suffix: suffix.to_string(), ignore_case })
+    }
+
+    /// Pattern from `VANITY_PREFIX`, `VANITY_SUFFIX` and `VANITY_IGNORE_CASE`, with `prefix`
+    /// and `suffix` (e.g. from flags) taking precedence; `pump` when no part is set anywhere
+    pub fn resolve(prefix: Option<String>, suffix: Option<String>, ignore_case: bool) -> Result<Self> {
+        let read = |var: &str| std::env::var(var).ok().map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+        let prefix = prefix.or_else(|| read("VANITY_PREFIX"));
+        let suffix = suffix.or_else(|| read("VANITY_SUFFIX"));
+        let ignore_case = ignore_case || read("VANITY_IGNORE_CASE").is_some_and(|v| v.eq_ignore_ascii_case("true"));
+        match (prefix, suffix) {
+            (None, None) => Ok(Self { ignore_case, ..Self::default() }),
+            (prefix, suffix) => Self::new(prefix.as_deref().unwrap_or(""), suffix.as_deref().unwrap_or(""), ignore_case),
+        }
+    }
+
+    /// Pattern from the environment; an invalid one is logged and replaced by the default
+    pub fn from_env() -> Self {
+        Self::resolve(None, None, false).unwrap_or_else(|e| {
+            error!("Invalid vanity pattern ({}); grinding the default '{}' suffix", e, DEFAULT_VANITY_SUFFIX);
+            Self::default()
+        })
+    }
+
+    pub fn matches(&self, address: &str) -> bool {
+        let (prefix, suffix) = (self.prefix.len(), self.suffix.len());
+        if address.len() < prefix + suffix {
+            return false;
+        }
+        let (start, end) = (&address[..prefix], &address[address.len() - suffix..]);
+        if self.ignore_case {
+            start.eq_ignore_ascii_case(&self.prefix) && end.eq_ignore_ascii_case(&self.suffix)
+        } else {
+            start == self.prefix && end == self.suffix
+        }
+    }
+
+    /// Expected keypairs per match: 58 per character, 29 for letters whose case is ignored
+    pub fn expected_attempts(&self) -> f64 {
+        self.prefix.chars().chain(self.suffix.chars())
+            .map(|c| {
+                let both_cases = self.ignore_case && c.is_ascii_alphabetic()
+                    && BASE58_ALPHABET.contains(c.to_ascii_lowercase()) && BASE58_ALPHABET.contains(c.to_ascii_uppercase());
+                if both_cases { 29.0 } else { 58.0 }
+            })
+            .product()
+    }
+
+    /// Length of a case-sensitive pattern that is as hard to grind
+    pub fn difficulty(&self) -> usize {
+        self.expected_attempts().log(58.0).round() as usize
+    }
+}
+
+/// `pump` for a suffix, `abc*` for a prefix, `abc*pump` for both; `VANITY_POOL_SIZES` keys use this form
+impl fmt::Display for VanityPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.prefix.is_empty(), self.suffix.is_empty()) {
+            (true, _) => write!(f, "{}", self.suffix),
+            (false, true) => write!(f, "{}*", self.prefix),
+            (false, false) => write!(f, "{}*{}", self.prefix, self.suffix),
+        }
+    }
+}
+
+/// Outcome of asking the pool for an address
+#[allow(clippy::large_enum_variant)] // returned once per launch, never stored
+pub enum VanityAvailability {
+    Available(GeneratedVanityAddress),
+    /// Pool is empty; generation is running and expects its next address in about this long
+    GeneratingEta(Duration),
+    /// Pool is empty and nothing is generating
+    Disabled,
+}
+
+/// Expected time to grind `pattern` on `num_threads` threads before any search has been timed
+pub fn expected_search_time(pattern: &VanityPattern, num_threads: usize) -> Duration {
+    Duration::from_secs_f64(pattern.expected_attempts() / (ASSUMED_KEYPAIRS_PER_SEC_PER_THREAD * num_threads.max(1) as f64))
+}
+
+/// Attempts a grinding thread makes between checks for a stop request or a match on another
+/// thread; about 6ms at the assumed rate
+const GRIND_CHUNK: u64 = 256;
+/// How often a running search checks whether it should stop
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(5);
+/// Progress logs every 30s unless `VANITY_PROGRESS_SECS` is set (0 turns them off)
+pub const DEFAULT_VANITY_PROGRESS_SECS: u64 = 30;
+/// Attempts each grinding thread counts locally before adding them to the shared total unless `VANITY_COUNTER_FLUSH` is set
+pub const DEFAULT_VANITY_COUNTER_FLUSH: u64 = 10_000;
+
+/// Generation is restarted after this many minutes without progress unless `VANITY_WATCHDOG_MINUTES` is set (0 turns the watchdog off)
+pub const DEFAULT_VANITY_WATCHDOG_MINUTES: u64 = 10;
+
+/// How grinding threads report their attempts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GrindSettings {
+    /// `None` disables progress logging
+    pub progress_interval: Option<Duration>,
+    pub flush_every: u64,
+    /// Restart background generation when its thread dies or its attempt count stalls this long
+    pub watchdog: Option<Duration>,
+}
+
+impl GrindSettings {
+    pub fn from_env() -> Self {
+        let read = |var: &str, default: u64| std::env::var(var).ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .unwrap_or(default);
+        let progress_secs = read("VANITY_PROGRESS_SECS", DEFAULT_VANITY_PROGRESS_SECS);
+        Self {
+            progress_interval: (progress_secs > 0).then(|| Duration::from_secs(progress_secs)),
+            flush_every: read("VANITY_COUNTER_FLUSH", DEFAULT_VANITY_COUNTER_FLUSH).max(1),
+            watchdog: match read("VANITY_WATCHDOG_MINUTES", DEFAULT_VANITY_WATCHDOG_MINUTES) {
+                0 => None,
+                minutes => Some(Duration::from_secs(minutes * 60)),
+            },
+        }
+    }
+}
+
+/// Where vanity search gets candidate keypairs from
+pub trait KeypairSource: Send + Sync {
+    fn next_keypair(&self) -> Keypair;
+
+    /// Whether candidates may be drawn from several threads; sequential sources find matches in a fixed order
+    fn parallel(&self) -> bool {
+        true
+    }
+}
+
+/// Fresh keypairs from the OS random number generator
+pub struct OsKeypairSource;
+
+impl KeypairSource for OsKeypairSource {
+    fn next_keypair(&self) -> Keypair {
+        Keypair::new()
+    }
+}
+
+/// Deterministic keypairs derived from a seed, for tests only: anyone who knows the seed knows the keys
+pub struct SeededKeypairSource {
+    seed: u64,
+    counter: AtomicU64,
+}
+
+impl SeededKeypairSource {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, counter: AtomicU64::new(0) }
+    }
+}
+
+impl KeypairSource for SeededKeypairSource {
+    fn next_keypair(&self) -> Keypair {
+        let n = self.counter.fetch_add(1, Ordering::Relaxed);
+        let secret = Sha256::new()
+            .chain_update(self.seed.to_le_bytes())
+            .chain_update(n.to_le_bytes())
+            .finalize();
+        Keypair::new_from_array(secret.into())
+    }
+
+    fn parallel(&self) -> bool {
+        false
+    }
+}
+
 /// Generated vanity address with secure private key storage
 pub struct GeneratedVanityAddress {
     pub keypair: SecureKeypair,
@@ -77,19 +280,93 @@ pub struct VanityAddressPool {
     refill_cvar: Arc<Condvar>,
     is_generating: Arc<AtomicBool>,
     generation_thread: Arc<Mutex<Option<thread::JoinHandle<()>>>>,
+    /// Grinding happens in a separate worker process; the pool only forwards requests to it
+    worker: Option<WorkerClient>,
+    source: Arc<dyn KeypairSource>,
+    pattern: VanityPattern,
+    target_count: usize,
+    /// Moving average of recent search times in milliseconds, 0 until the first address is found
+    avg_search_ms: Arc<AtomicU64>,
+    /// Attempts across all searches; the watchdog restarts generation when it stops moving
+    attempts: Arc<AtomicU64>,
+    /// Run of the current generation thread; threads from an older run stop at their next check
+    epoch: Arc<AtomicU64>,
+    watchdog: Option<Duration>,
+    /// Encrypted copy of the pool on disk, so a restart does not lose minutes of grinding
+    store: Option<Arc<VanityStore>>,
+    /// Lifetime attempts and matches, kept in `VANITY_STATS_FILE`
+    stats: Option<Arc<StatsFile>>,
+}
+
+impl Default for VanityAddressPool {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl VanityAddressPool {
     pub fn new() -> Self {
+        Self::with_pattern(VanityPattern::from_env())
+    }
+
+    /// Pool grinding `pattern`, starting with the matching addresses kept in the `VANITY_STORE_SECRET` store
+    pub fn with_pattern(pattern: VanityPattern) -> Self {
+        let pool = Self { stats: Some(Arc::new(StatsFile::from_env())), ..Self::unstored(pattern) };
+        let store = match VanityStore::from_env() {
+            Ok(Some(store)) => Arc::new(store),
+            Ok(None) => return pool,
+            Err(e) => {
+                error!("Vanity store disabled: {}", e);
+                return pool;
+            }
+        };
+        match store.load() {
+            Ok(stored) => {
+                let total = stored.len();
+                let matching: VecDeque<_> = stored.into_iter().filter(|addr| pool.pattern.matches(&addr.address.to_string())).collect();
+                info!("Loaded {} stored vanity address(es) from {} ({} kept for other patterns)", matching.len(), store.path().display(), total - matching.len());
+                *pool.generated_addresses.lock().unwrap() = matching;
+            }
+            Err(e) => error!("Failed to load vanity store {}: {}", store.path().display(), e),
+        }
+        Self { store: Some(store), ..pool }
+    }
+
+    fn unstored(pattern: VanityPattern) -> Self {
         info!("Creating new VanityAddressPool");
         Self {
             generated_addresses: Arc::new(Mutex::new(VecDeque::new())),
             refill_cvar: Arc::new(Condvar::new()),
             is_generating: Arc::new(AtomicBool::new(false)),
             generation_thread: Arc::new(Mutex::new(None)),
+            worker: None,
+            source: Arc::new(OsKeypairSource),
+            target_count: PoolSizePolicy::from_env().target_for(&pattern),
+            pattern,
+            avg_search_ms: Arc::new(AtomicU64::new(0)),
+            attempts: Arc::new(AtomicU64::new(0)),
+            epoch: Arc::new(AtomicU64::new(0)),
+            watchdog: GrindSettings::from_env().watchdog,
+            store: None,
+            stats: None,
         }
     }
 
+    /// Pool grinding `pattern` from `source` up to `target_count` addresses; with a
+    /// `SeededKeypairSource` and a short pattern, tests get matches instantly and reproducibly
+    pub fn with_source(source: Arc<dyn KeypairSource>, pattern: VanityPattern, target_count: usize) -> Self {
+        Self { source, target_count, ..Self::unstored(pattern) }
+    }
+
+    /// Pool backed by a vanity worker process instead of an in-process generation thread
+    pub fn with_worker(worker: WorkerClient) -> Self {
+        Self { worker: Some(worker), ..Self::unstored(VanityPattern::from_env()) }
+    }
+
+    pub fn pattern(&self) -> &VanityPattern {
+        &self.pattern
+    }
+
     /// Get vanity address pool status (now only for generated addresses)
     pub fn get_vanity_status(&self) -> (bool, usize) {
         (self.has_generated_addresses(), self.generated_addresses_count())
@@ -102,6 +379,10 @@ impl VanityAddressPool {
 
     /// Start background generation of vanity addresses
     pub fn start_background_generation(&self) -> Result<()> {
+        if self.worker.is_some() {
+            info!("Vanity generation runs in the worker process");
+            return Ok(());
+        }
         if self.is_generating.load(Ordering::SeqCst) {
             info!("Background vanity generation already running");
             return Ok(());
@@ -111,98 +392,168 @@ impl VanityAddressPool {
         let is_generating = Arc::clone(&self.is_generating);
         let generation_thread = Arc::clone(&self.generation_thread);
         let refill_cvar = Arc::clone(&self.refill_cvar);
+        let source = Arc::clone(&self.source);
+        let pattern = self.pattern.clone();
+        let target_count = self.target_count;
+        let avg_search_ms = Arc::clone(&self.avg_search_ms);
+        let attempts = Arc::clone(&self.attempts);
+        let epoch = Arc::clone(&self.epoch);
+        let store = self.store.clone();
+        let stats = self.stats.clone();
 
         is_generating.store(true, Ordering::SeqCst);
 
-        let handle = thread::spawn(move || {
-            info!("Starting background vanity address generation for suffix: '{}'", VANITY_SUFFIX);
-            info!("Target count: {} addresses", TARGET_VANITY_COUNT);
-            
-            // Initialize rayon thread pool
-            let num_threads = num_cpus::get();
-            info!("Using {} CPU threads for parallel generation", num_threads);
-            rayon::ThreadPoolBuilder::new()
-                .num_threads(num_threads)
-                .build_global()
-                .ok();
-
-            let mut total_attempts = 0u64;
-            let start_time = Instant::now();
-
-            let mut last_status_time = Instant::now();
-            let status_interval = Duration::from_secs(30); // Log status every 30 seconds
-
-            while is_generating.load(Ordering::SeqCst) {
-                // Determine whether we should generate a new address.
-                // If the pool is at or above target, wait until consumption or periodic timeout.
-                let mut current_count;
-                {
-                    let mut pool = generated_addresses.lock().unwrap();
-                    while pool.len() >= TARGET_VANITY_COUNT && is_generating.load(Ordering::SeqCst) {
+        // Each generation thread runs under an epoch; bumping it retires a stuck thread, which
+        // then never touches the pool or the running flag again
+        let spawn_generator = {
+            let (generated_addresses, is_generating, refill_cvar, attempts, epoch) =
+                (generated_addresses.clone(), is_generating.clone(), refill_cvar.clone(), attempts.clone(), epoch.clone());
+            move |own_epoch: u64| {
+                let (generated_addresses, is_generating, refill_cvar, source, pattern, avg_search_ms, attempts, epoch, store, stats) = (
+                    generated_addresses.clone(), is_generating.clone(), refill_cvar.clone(), source.clone(),
+                    pattern.clone(), avg_search_ms.clone(), attempts.clone(), epoch.clone(), store.clone(), stats.clone(),
+                );
+                thread::spawn(move || {
+                    let current = || is_generating.load(Ordering::SeqCst) && epoch.load(Ordering::SeqCst) == own_epoch;
+                    info!("Starting background vanity address generation for pattern: '{}'", pattern);
+                    info!("Target count: {} addresses", target_count);
+
+                    let num_threads = num_cpus::get();
+                    info!("Using {} CPU threads for parallel generation", num_threads);
+
+                    let mut total_attempts = 0u64;
+                    let start_time = Instant::now();
+
+                    let mut last_status_time = Instant::now();
+                    let status_interval = GrindSettings::from_env().progress_interval.unwrap_or(Duration::MAX);
+
+                    while current() {
+                        // Determine whether we should generate a new address.
+                        // If the pool is at or above target, wait until consumption or periodic timeout.
+                        let current_count = loop {
+                            let pool = generated_addresses.lock().unwrap();
+                            if pool.len() < target_count || !current() {
+                                break pool.len();
+                            }
+                            let (pool, _) = refill_cvar.wait_timeout(pool, Duration::from_secs(5)).unwrap();
+                            let still_full = pool.len() >= target_count;
+                            drop(pool);
+                            // Logged with the lock released so consumers are never kept waiting on it
+                            if still_full && last_status_time.elapsed() >= status_interval {
+                                info!("Vanity pool full ({}). Waiting for consumption...", target_count);
+                                last_status_time = Instant::now();
+                            }
+                        };
+
+                        if !current() {
+                            break;
+                        }
+
+                        // Log status every VANITY_PROGRESS_SECS
                         if last_status_time.elapsed() >= status_interval {
-                            info!("Vanity pool full ({}). Waiting for consumption...", TARGET_VANITY_COUNT);
+                            info!("Vanity generation status: {} addresses generated, {} remaining, {} total attempts",
+                                  current_count, target_count - current_count, total_attempts);
                             last_status_time = Instant::now();
                         }
-                        let (p, _) = refill_cvar.wait_timeout(pool, Duration::from_secs(5)).unwrap();
-                        pool = p;
-                    }
 
-                    if !is_generating.load(Ordering::SeqCst) {
-                        break;
+                        info!("🔍 Generating vanity address #{} (current pool: {})", current_count + 1, current_count);
+
+                        // Generate one vanity address
+                        let stop_requested = || !current();
+                        let (counted_before, searched) = (attempts.load(Ordering::Relaxed), Instant::now());
+                        let search = Self::search_counting(source.as_ref(), &pattern, num_threads, &stop_requested, &attempts);
+                        // Stopped searches count too: their attempts are part of what the pattern costs
+                        if let Some(stats) = &stats {
+                            let made = attempts.load(Ordering::Relaxed) - counted_before;
+                            if let Err(e) = stats.record(&pattern.to_string(), made, searched.elapsed(), matches!(search, Ok(Some(_)))) {
+                                error!("Failed to update vanity stats {}: {}", stats.path().display(), e);
+                            }
+                        }
+                        if let Ok(Some(result)) = search {
+                            total_attempts += result.attempts;
+                            let elapsed_ms = result.elapsed.as_millis() as u64;
+                            let avg = match avg_search_ms.load(Ordering::Relaxed) {
+                                0 => elapsed_ms.max(1),
+                                avg => ((avg * 3 + elapsed_ms) / 4).max(1),
+                            };
+                            avg_search_ms.store(avg, Ordering::Relaxed);
+                            let pubkey_str = result.keypair.pubkey().to_string();
+                            if let Some(store) = &store {
+                                if let Err(e) = store.add(&result.keypair) {
+                                    error!("Failed to persist vanity address {}: {}", pubkey_str, e);
+                                }
+                            }
+
+                            // Create secure keypair wrapper
+                            let secure_keypair = SecureKeypair::new(result.keypair);
+                            let address = secure_keypair.pubkey();
+
+                            let generated_addr = GeneratedVanityAddress {
+                                keypair: secure_keypair,
+                                seed: format!("vanity_{}", current_count),
+                                address,
+                            };
+
+                            generated_addresses.lock().unwrap().push_back(generated_addr);
+                            info!("Generated vanity address #{}: {}", current_count + 1, pubkey_str);
+                            info!("    Attempts: {}, Time: {:?}, Total attempts so far: {}",
+                                  result.attempts, result.elapsed, total_attempts);
+                        } else if search.is_err() {
+                            error!("Failed to generate vanity address");
+                        }
                     }
 
-                    current_count = pool.len();
-                }
-
-                // Log status every 30 seconds
-                if last_status_time.elapsed() >= status_interval {
-                    info!("Vanity generation status: {} addresses generated, {} remaining, {} total attempts", 
-                          current_count, TARGET_VANITY_COUNT - current_count, total_attempts);
-                    last_status_time = Instant::now();
-                }
-
-                info!("🔍 Generating vanity address #{} (current pool: {})", current_count + 1, current_count);
-                
-                // Generate one vanity address
-                if let Ok(result) = Self::find_vanity_address_with_suffix(VANITY_SUFFIX, num_threads) {
-                    total_attempts += result.attempts;
-                    let pubkey_str = result.keypair.pubkey().to_string();
-                    
-                    // Create secure keypair wrapper
-                    let secure_keypair = SecureKeypair::new(result.keypair);
-                    let address = secure_keypair.pubkey();
-                    
-                    let generated_addr = GeneratedVanityAddress {
-                        keypair: secure_keypair,
-                        seed: format!("vanity_{}", current_count),
-                        address,
-                    };
+                    let total_time = start_time.elapsed();
+                    info!("Background vanity address generation completed");
+                    info!("    Total time: {:?}", total_time);
+                    info!("    Total attempts: {}", total_attempts);
+                    info!("    Rate: {:.2} attempts/second", total_attempts as f64 / total_time.as_secs_f64());
 
-                    {
-                        let mut pool = generated_addresses.lock().unwrap();
-                        pool.push_back(generated_addr);
-                        info!("Generated vanity address #{}: {}", current_count + 1, pubkey_str);
-                        info!("    Attempts: {}, Time: {:?}, Total attempts so far: {}", 
-                              result.attempts, result.elapsed, total_attempts);
-                        // Removed private key logging for security
+                    // A retired thread must not mark its replacement as stopped
+                    if epoch.load(Ordering::SeqCst) == own_epoch {
+                        is_generating.store(false, Ordering::SeqCst);
                     }
-                } else {
-                    error!("Failed to generate vanity address");
-                }
+                })
             }
-
-            let total_time = start_time.elapsed();
-            info!("Background vanity address generation completed");
-            info!("    Total time: {:?}", total_time);
-            info!("    Total attempts: {}", total_attempts);
-            info!("    Rate: {:.2} attempts/second", total_attempts as f64 / total_time.as_secs_f64());
-
-            is_generating.store(false, Ordering::SeqCst);
-        });
-
-        {
-            let mut thread_guard = generation_thread.lock().unwrap();
-            *thread_guard = Some(handle);
+        };
+
+        let mut run_epoch = epoch.fetch_add(1, Ordering::SeqCst) + 1;
+        *generation_thread.lock().unwrap() = Some(spawn_generator(run_epoch));
+
+        if let Some(stall_limit) = self.watchdog {
+            let check_interval = (stall_limit / 4).min(Duration::from_secs(1));
+            thread::spawn(move || {
+                let mut last_attempts = attempts.load(Ordering::Relaxed);
+                let mut last_progress = Instant::now();
+                loop {
+                    thread::sleep(check_interval);
+                    // Ends with the run it watches: on stop, or when a new run takes over
+                    if !is_generating.load(Ordering::SeqCst) || epoch.load(Ordering::SeqCst) != run_epoch {
+                        break;
+                    }
+                    let exited = generation_thread.lock().unwrap().as_ref().is_some_and(|handle| handle.is_finished());
+                    let full = generated_addresses.lock().unwrap().len() >= target_count;
+                    let current_attempts = attempts.load(Ordering::Relaxed);
+                    // A full pool is idle by design, not stalled
+                    if current_attempts != last_attempts || full {
+                        last_attempts = current_attempts;
+                        last_progress = Instant::now();
+                    }
+                    let reason = if exited {
+                        "generation thread exited unexpectedly".to_string()
+                    } else if last_progress.elapsed() >= stall_limit {
+                        format!("no grinding progress for {:?}", last_progress.elapsed())
+                    } else {
+                        continue;
+                    };
+                    warn!("Vanity watchdog: {}; restarting generation", reason);
+                    progress("vanity_generator_restarted", serde_json::json!({ "reason": reason }));
+                    run_epoch = epoch.fetch_add(1, Ordering::SeqCst) + 1;
+                    // A stuck thread cannot be joined; its handle is dropped and it is left detached
+                    *generation_thread.lock().unwrap() = Some(spawn_generator(run_epoch));
+                    last_progress = Instant::now();
+                }
+            });
         }
 
         info!("Background vanity address generation started successfully");
@@ -227,8 +578,24 @@ impl VanityAddressPool {
         info!("Background vanity address generation stopped");
     }
 
-    /// Get a generated vanity address for token creation
-    pub fn get_generated_vanity_address(&self) -> Option<GeneratedVanityAddress> {
+    /// Get a generated vanity address for token creation, or how long until one is expected
+    pub fn get_generated_vanity_address(&self) -> VanityAvailability {
+        if let Some(worker) = &self.worker {
+            return match worker.take() {
+                Ok(VanityAvailability::Available(addr)) => {
+                    info!("Using vanity address from worker: {}", addr.address);
+                    VanityAvailability::Available(addr)
+                }
+                Ok(other) => {
+                    info!("No generated vanity addresses available from worker");
+                    other
+                }
+                Err(e) => {
+                    error!("Failed to take vanity address from worker: {}", e);
+                    VanityAvailability::Disabled
+                }
+            };
+        }
         let mut pool = self.generated_addresses.lock().unwrap();
         let remaining_count = pool.len();
         let result = pool.pop_front();
@@ -237,84 +604,247 @@ impl VanityAddressPool {
             self.refill_cvar.notify_one();
         }
         
-        if let Some(ref addr) = result {
-            info!("Using generated vanity address: {}", addr.address);
-            info!("Remaining addresses in pool: {}", remaining_count - 1);
-        } else {
-            info!("No generated vanity addresses available in pool");
+        drop(pool);
+
+        match result {
+            Some(addr) => {
+                // Removed before it is used, so a crash mid-launch can never hand the same mint out twice
+                if let Some(store) = &self.store {
+                    if let Err(e) = store.remove(&addr.address) {
+                        error!("Failed to remove vanity address {} from the store: {}", addr.address, e);
+                    }
+                }
+                info!("Using generated vanity address: {}", addr.address);
+                info!("Remaining addresses in pool: {}", remaining_count - 1);
+                VanityAvailability::Available(addr)
+            }
+            None => {
+                info!("No generated vanity addresses available in pool");
+                match self.estimated_wait() {
+                    Some(eta) => VanityAvailability::GeneratingEta(eta),
+                    None => VanityAvailability::Disabled,
+                }
+            }
         }
-        
-        result
+    }
+
+    /// Expected time until generation finds its next address, or None when it is not running.
+    /// Searches are memoryless, so this does not shrink while one is in progress.
+    pub fn estimated_wait(&self) -> Option<Duration> {
+        if let Some(worker) = &self.worker {
+            return worker.request(&WorkerRequest::Status).ok()
+                .filter(|status| status.generating)
+                .map(|status| Duration::from_millis(status.eta_ms.unwrap_or(0)));
+        }
+        if !self.is_generation_running() {
+            return None;
+        }
+        Some(match self.avg_search_ms.load(Ordering::Relaxed) {
+            0 => expected_search_time(&self.pattern, num_cpus::get()),
+            ms => Duration::from_millis(ms),
+        })
     }
 
     /// Check if we have generated vanity addresses available
     pub fn has_generated_addresses(&self) -> bool {
+        if self.worker.is_some() {
+            return self.generated_addresses_count() > 0;
+        }
         let pool = self.generated_addresses.lock().unwrap();
         !pool.is_empty()
     }
 
     /// Get count of generated vanity addresses
     pub fn generated_addresses_count(&self) -> usize {
+        if let Some(worker) = &self.worker {
+            return worker.request(&WorkerRequest::Status).map(|s| s.count).unwrap_or(0);
+        }
         let pool = self.generated_addresses.lock().unwrap();
         pool.len()
     }
 
     /// Check if background generation is running
     pub fn is_generation_running(&self) -> bool {
+        if let Some(worker) = &self.worker {
+            return worker.request(&WorkerRequest::Status).is_ok_and(|s| s.generating);
+        }
         self.is_generating.load(Ordering::SeqCst)
     }
 
-    /// Searches for a Solana keypair whose public key ends with the given suffix.
-    fn find_vanity_address_with_suffix(suffix: &str, num_threads: usize) -> Result<VanityResult> {
-        let found = AtomicBool::new(false);
-        let attempts = AtomicU64::new(0);
-        let start_time = Instant::now();
-        let result = Arc::new(Mutex::new(None::<Keypair>));
-        let mut last_progress_time = Instant::now();
-        let progress_interval = Duration::from_secs(30);
+    /// Add an address found elsewhere (e.g. by a remote grinder); false if it is already pooled
+    pub fn add_generated_address(&self, addr: GeneratedVanityAddress) -> bool {
+        let mut pool = self.generated_addresses.lock().unwrap();
+        if pool.iter().any(|existing| existing.address == addr.address) {
+            return false;
+        }
+        if let Some(store) = &self.store {
+            if let Err(e) = store.add(addr.keypair.keypair()) {
+                error!("Failed to persist vanity address {}: {}", addr.address, e);
+            }
+        }
+        pool.push_back(addr);
+        true
+    }
 
-        rayon::ThreadPoolBuilder::new().num_threads(num_threads).build_global().ok();
+    /// Searches for a Solana keypair whose public key matches `pattern`
+    pub fn find_vanity_address(pattern: &VanityPattern, num_threads: usize) -> Result<VanityResult> {
+        Self::find_vanity_address_from_source(&OsKeypairSource, pattern, num_threads)
+    }
 
-        while !found.load(Ordering::SeqCst) {
-            let result_clone = Arc::clone(&result);
-            (0..100_000).into_par_iter().for_each(|_| {
-                if found.load(Ordering::SeqCst) {
-                    return;
+    /// Searches `source` for a keypair whose public key matches `pattern`
+    pub fn find_vanity_address_from_source(source: &dyn KeypairSource, pattern: &VanityPattern, num_threads: usize) -> Result<VanityResult> {
+        let result = Self::search_until(source, pattern, num_threads, &|| false)?;
+        Ok(result.expect("an uncancellable search only returns with a match"))
+    }
+
+    /// Like [`Self::find_vanity_address_from_source`], but gives up within milliseconds of
+    /// `should_stop` returning true; `None` means the search was stopped
+    pub fn search_until(source: &dyn KeypairSource, pattern: &VanityPattern, num_threads: usize, should_stop: &(dyn Fn() -> bool + Sync)) -> Result<Option<VanityResult>> {
+        Self::search_counting(source, pattern, num_threads, should_stop, &AtomicU64::new(0))
+    }
+
+    /// [`Self::search_until`], also adding attempts to `counter` as they are made
+    fn search_counting(source: &dyn KeypairSource, pattern: &VanityPattern, num_threads: usize, should_stop: &(dyn Fn() -> bool + Sync), counter: &AtomicU64) -> Result<Option<VanityResult>> {
+        let start_time = Instant::now();
+        if !source.parallel() {
+            let mut attempts = 0u64;
+            loop {
+                for made in 1..=GRIND_CHUNK {
+                    let keypair = source.next_keypair();
+                    attempts += 1;
+                    if pattern.matches(&keypair.pubkey().to_string()) {
+                        counter.fetch_add(made, Ordering::Relaxed);
+                        return Ok(Some(VanityResult { keypair, elapsed: start_time.elapsed(), attempts }));
+                    }
                 }
-                let keypair = Keypair::new();
-                let pubkey_str = keypair.pubkey().to_string();
-                attempts.fetch_add(1, Ordering::Relaxed);
-                if pubkey_str.ends_with(suffix) {
-                    found.store(true, Ordering::SeqCst);
-                    let mut result_guard = result_clone.lock().unwrap();
-                    *result_guard = Some(keypair);
+                counter.fetch_add(GRIND_CHUNK, Ordering::Relaxed);
+                if should_stop() {
+                    return Ok(None);
                 }
-            });
-            
-            // Log progress every 30 seconds during the search
-            if last_progress_time.elapsed() >= progress_interval {
-                let current_attempts = attempts.load(Ordering::Relaxed);
-                let elapsed = start_time.elapsed();
-                let rate = current_attempts as f64 / elapsed.as_secs_f64();
-                info!("🔍 Still searching for '{}' suffix... {} attempts in {:?} ({:.0} attempts/sec)", 
-                      suffix, current_attempts, elapsed, rate);
-                last_progress_time = Instant::now();
             }
         }
 
-        let keypair = result.lock().unwrap().take().expect("Keypair should be found");
-        Ok(VanityResult {
+        // Set once a thread finds a match or the search is stopped
+        let done = AtomicBool::new(false);
+        let attempts = counter;
+        let counted_before = attempts.load(Ordering::Relaxed);
+        let result = Mutex::new(None::<Keypair>);
+        let finished = Condvar::new();
+        let settings = GrindSettings::from_env();
+
+        thread::scope(|scope| {
+            for _ in 0..num_threads.max(1) {
+                scope.spawn(|| {
+                    // Counted locally and flushed in batches: a shared increment per attempt
+                    // makes every core fight over one cache line
+                    let mut local_attempts = 0u64;
+                    // Flags are only checked between chunks, which bounds how long a stop or a
+                    // match elsewhere goes unnoticed
+                    'search: while !done.load(Ordering::Relaxed) {
+                        for _ in 0..GRIND_CHUNK {
+                            let keypair = source.next_keypair();
+                            local_attempts += 1;
+                            if pattern.matches(&keypair.pubkey().to_string()) {
+                                if !done.swap(true, Ordering::SeqCst) {
+                                    *result.lock().unwrap() = Some(keypair);
+                                    finished.notify_all();
+                                }
+                                break 'search;
+                            }
+                        }
+                        if local_attempts >= settings.flush_every {
+                            attempts.fetch_add(local_attempts, Ordering::Relaxed);
+                            local_attempts = 0;
+                        }
+                    }
+                    attempts.fetch_add(local_attempts, Ordering::Relaxed);
+                });
+            }
+
+            // Stop requests and progress are handled here only, never while a worker holds anything
+            let mut last_progress_time = Instant::now();
+            let mut guard = result.lock().unwrap();
+            while guard.is_none() {
+                if should_stop() {
+                    done.store(true, Ordering::SeqCst);
+                    break;
+                }
+                guard = finished.wait_timeout(guard, STOP_CHECK_INTERVAL).unwrap().0;
+                if settings.progress_interval.is_some_and(|interval| last_progress_time.elapsed() >= interval) {
+                    drop(guard);
+                    let current_attempts = attempts.load(Ordering::Relaxed) - counted_before;
+                    let elapsed = start_time.elapsed();
+                    info!("🔍 Still searching for '{}'... {} attempts in {:?} ({:.0} attempts/sec)",
+                          pattern, current_attempts, elapsed, current_attempts as f64 / elapsed.as_secs_f64());
+                    last_progress_time = Instant::now();
+                    guard = result.lock().unwrap();
+                }
+            }
+        });
+
+        Ok(result.into_inner().unwrap().map(|keypair| VanityResult {
             keypair,
             elapsed: start_time.elapsed(),
-            attempts: attempts.load(Ordering::Relaxed),
+            attempts: attempts.load(Ordering::Relaxed) - counted_before,
+        }))
+    }
+}
+
+/// How many addresses to keep pooled per vanity pattern
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PoolSizePolicy {
+    /// Sizes set in `VANITY_POOL_SIZES`; other patterns are sized by difficulty
+    pub overrides: HashMap<String, usize>,
+}
+
+impl PoolSizePolicy {
+    /// Parse `pattern=size` pairs, e.g. `pump=20,abcde=2`
+    pub fn parse(value: &str) -> Result<Self> {
+        let mut overrides = HashMap::new();
+        for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (pattern, size) = entry.split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("expected <pattern>=<size>, got '{}'", entry))?;
+            let size = size.trim().parse::<usize>()
+                .map_err(|_| anyhow::anyhow!("pool size for '{}' must be a whole number, got '{}'", pattern.trim(), size.trim()))?;
+            overrides.insert(pattern.trim().to_string(), size);
+        }
+        Ok(Self { overrides })
+    }
+
+    /// Policy from `VANITY_POOL_SIZES`; an invalid value is logged and ignored
+    pub fn from_env() -> Self {
+        let Ok(value) = std::env::var("VANITY_POOL_SIZES") else {
+            return Self::default();
+        };
+        Self::parse(&value).unwrap_or_else(|e| {
+            error!("Invalid VANITY_POOL_SIZES ({}); sizing vanity pools by pattern difficulty", e);
+            Self::default()
         })
     }
 
+    pub fn target_for(&self, pattern: &VanityPattern) -> usize {
+        self.overrides.get(&pattern.to_string()).copied().unwrap_or_else(|| default_pool_size(pattern))
+    }
+}
+
+/// Keep many of the cheap patterns and few of the expensive ones: each extra character
+/// makes a match 58 times rarer
+pub fn default_pool_size(pattern: &VanityPattern) -> usize {
+    match pattern.difficulty() {
+        0..=3 => 2 * TARGET_VANITY_COUNT,
+        4 => TARGET_VANITY_COUNT,
+        5 => 2,
+        _ => 1,
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct VanityConfig {
     pub enabled: bool,
+    /// Grind in a separate worker process (`VANITY_MODE=worker`) instead of a thread
+    pub worker: bool,
+    pub pattern: VanityPattern,
+    pub pool_sizes: PoolSizePolicy,
 }
 
 impl VanityConfig {
@@ -325,22 +855,27 @@ impl VanityConfig {
         
         println!("DEBUG: Vanity configuration loaded");
 
-        Self { enabled }
+        let worker = std::env::var("VANITY_MODE").is_ok_and(|mode| mode.eq_ignore_ascii_case("worker"));
+        Self { enabled, worker, pattern: VanityPattern::from_env(), pool_sizes: PoolSizePolicy::from_env() }
     }
 }
 
 // Global vanity address pool singleton
 static GLOBAL_VANITY_POOL: OnceLock<Arc<VanityAddressPool>> = OnceLock::new();
 
-/// Initialize the global vanity address pool
-pub fn init_global_vanity_pool() -> Result<()> {
+/// Initialize the global vanity address pool grinding `pattern`; a worker process grinds its own configured pattern
+pub fn init_global_vanity_pool(pattern: VanityPattern) -> Result<()> {
     if GLOBAL_VANITY_POOL.get().is_some() {
         info!("🔄 Global vanity pool already initialized");
         return Ok(());
     }
 
     info!("🚀 Initializing global vanity address pool");
-    let pool = Arc::new(VanityAddressPool::new());
+    let pool = if VanityConfig::from_env().worker {
+        Arc::new(VanityAddressPool::with_worker(WorkerClient::from_env()?))
+    } else {
+        Arc::new(VanityAddressPool::with_pattern(pattern))
+    };
     
     // Start background generation immediately
     if let Err(e) = pool.start_background_generation() {
@@ -368,3 +903,93 @@ pub fn get_global_vanity_status() -> (bool, usize, bool) {
         (false, 0, false)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded_pool_fills_and_drains() {
+        let one = VanityPattern::new("", "1", false).unwrap();
+        let first = VanityAddressPool::find_vanity_address_from_source(&SeededKeypairSource::new(7), &one, 1).unwrap();
+        let again = VanityAddressPool::find_vanity_address_from_source(&SeededKeypairSource::new(7), &one, 1).unwrap();
+        assert_eq!(first.keypair.pubkey(), again.keypair.pubkey());
+        assert_eq!(first.attempts, again.attempts);
+        assert!(first.keypair.pubkey().to_string().ends_with('1'));
+        let parallel = VanityAddressPool::find_vanity_address(&one, 4).unwrap();
+        assert!(parallel.keypair.pubkey().to_string().ends_with('1'));
+        assert!(parallel.attempts >= 1);
+
+        // "0" is not base58, so only the stop request can end these searches
+        let never = VanityPattern { prefix: String::new(), suffix: "0".to_string(), ignore_case: false };
+        for source in [&OsKeypairSource as &dyn KeypairSource, &SeededKeypairSource::new(7)] {
+            let started = Instant::now();
+            let stop = || started.elapsed() > Duration::from_millis(50);
+            assert!(VanityAddressPool::search_until(source, &never, 2, &stop).unwrap().is_none());
+            assert!(started.elapsed() < Duration::from_secs(1), "stop request took too long");
+        }
+
+        let pool = VanityAddressPool::with_source(Arc::new(SeededKeypairSource::new(7)), one.clone(), 2);
+        pool.start_background_generation().unwrap();
+        let started = Instant::now();
+        while pool.generated_addresses_count() < 2 {
+            assert!(started.elapsed() < Duration::from_secs(10), "pool did not fill");
+            thread::sleep(Duration::from_millis(10));
+        }
+        // Full pool stays at its target until an address is taken
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(pool.generated_addresses_count(), 2);
+        pool.stop_background_generation();
+
+        let VanityAvailability::Available(addr) = pool.get_generated_vanity_address() else { panic!("expected an address") };
+        assert_eq!(addr.address, first.keypair.pubkey());
+        assert!(matches!(pool.get_generated_vanity_address(), VanityAvailability::Available(_)));
+        assert!(matches!(pool.get_generated_vanity_address(), VanityAvailability::Disabled));
+
+        pool.start_background_generation().unwrap();
+        let expected = expected_search_time(&one, num_cpus::get());
+        match pool.estimated_wait() {
+            Some(eta) => assert!(eta > Duration::ZERO && eta != expected, "estimate should come from timed searches"),
+            None => panic!("generation is running"),
+        }
+        pool.stop_background_generation();
+        assert!(pool.estimated_wait().is_none());
+
+        // The watchdog replaces a generation thread that panicked
+        struct CrashOnce(AtomicBool, SeededKeypairSource);
+        impl KeypairSource for CrashOnce {
+            fn next_keypair(&self) -> Keypair {
+                assert!(self.0.swap(true, Ordering::SeqCst), "grinder crashed");
+                self.1.next_keypair()
+            }
+            fn parallel(&self) -> bool {
+                false
+            }
+        }
+        let source = CrashOnce(AtomicBool::new(false), SeededKeypairSource::new(7));
+        let pool = VanityAddressPool { watchdog: Some(Duration::from_millis(200)), ..VanityAddressPool::with_source(Arc::new(source), one.clone(), 1) };
+        pool.start_background_generation().unwrap();
+        let started = Instant::now();
+        while pool.generated_addresses_count() < 1 {
+            assert!(started.elapsed() < Duration::from_secs(10), "watchdog did not restart generation");
+            thread::sleep(Duration::from_millis(10));
+        }
+        pool.stop_background_generation();
+
+        let policy = PoolSizePolicy::parse("pump=20, abcde=2, ab*cd=3").unwrap();
+        assert_eq!(policy.target_for(&VanityPattern::default()), 20);
+        assert_eq!(policy.target_for(&VanityPattern::new("ab", "cd", false).unwrap()), 3);
+        assert_eq!(policy.target_for(&VanityPattern::new("", "abcdef", false).unwrap()), 1);
+        assert_eq!(PoolSizePolicy::default().target_for(&VanityPattern::default()), TARGET_VANITY_COUNT);
+        assert!(PoolSizePolicy::parse("pump").is_err());
+
+        let either = VanityPattern::new("Ab", "PUMP", true).unwrap();
+        assert!(either.matches("abXYZpump") && either.matches("ABXYZPuMp"));
+        assert!(!either.matches("abXYZpum") && !either.matches("Ab"));
+        assert!(VanityPattern::new("", "pOmp", false).is_err());
+        assert!(VanityPattern::new("l", "", true).is_ok());
+        assert!(VanityPattern::new("", "", false).is_err());
+        assert_eq!(VanityPattern::new("Ab", "", false).unwrap().to_string(), "Ab*");
+        assert_eq!(VanityPattern { ignore_case: true, ..VanityPattern::default() }.difficulty(), 3);
+    }
+}
@@ -0,0 +1,252 @@
+use anyhow::Result;
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use std::collections::HashMap;
+use std::env;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::keypair_cipher::{KeypairCipher, SealedKeypair};
+use crate::vanity_address::{GeneratedVanityAddress, PoolSizePolicy, SecureKeypair, VanityAddressPool, VanityPattern};
+use crate::vanity_stats::StatsFile;
+use crate::vanity_worker::{WorkerRequest, WorkerResponse};
+
+/// Submissions older (or further in the future) than this are rejected as replays
+pub const MAX_SUBMISSION_AGE_SECS: u64 = 300;
+/// Longest request line accepted from a remote grinder
+const MAX_REQUEST_BYTES: u64 = 64 * 1024;
+
+/// Shared key of a grinding fleet (`VANITY_FLEET_SECRET`), used to encrypt and authenticate submissions
+pub struct FleetKey {
+    cipher: KeypairCipher,
+}
+
+/// A found keypair, encrypted for the central pool
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Submission {
+    pub worker: String,
+    pub timestamp: u64,
+    /// Base64 ChaCha20-Poly1305 nonce and ciphertext; worker and timestamp are authenticated as associated data
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+impl FleetKey {
+    /// Fleet key from 32 random bytes as hex or base64
+    pub fn new(secret: &str) -> Result<Self> {
+        Ok(Self { cipher: KeypairCipher::from_key("VANITY_FLEET_SECRET", secret)? })
+    }
+
+    pub fn from_env() -> Result<Self> {
+        let secret = env::var("VANITY_FLEET_SECRET")
+            .map_err(|_| anyhow::anyhow!("VANITY_FLEET_SECRET must be set for distributed grinding"))?;
+        Self::new(secret.trim())
+    }
+
+    fn associated_data(worker: &str, timestamp: u64) -> Vec<u8> {
+        format!("{}\n{}", worker, timestamp).into_bytes()
+    }
+
+    pub fn seal(&self, worker: &str, keypair: &Keypair) -> Result<Submission> {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let sealed = self.cipher.seal(keypair, &Self::associated_data(worker, timestamp))?;
+        Ok(Submission { worker: worker.to_string(), timestamp, nonce: sealed.nonce, ciphertext: sealed.ciphertext })
+    }
+
+    /// Decrypt and authenticate `submission`, rejecting stale ones
+    pub fn open(&self, submission: &Submission, now: u64) -> Result<Keypair> {
+        if now.abs_diff(submission.timestamp) > MAX_SUBMISSION_AGE_SECS {
+            return Err(anyhow::anyhow!("Submission timestamp is outside the {}s window", MAX_SUBMISSION_AGE_SECS));
+        }
+        let sealed = SealedKeypair { nonce: submission.nonce.clone(), ciphertext: submission.ciphertext.clone() };
+        self.cipher.open(&sealed, &Self::associated_data(&submission.worker, submission.timestamp))
+            .map_err(|e| anyhow::anyhow!("Submission from '{}': {}", submission.worker, e))
+    }
+}
+
+/// Addresses accepted while their submissions could still be replayed, so a mint taken from the
+/// pool is not put back by a captured submission
+#[derive(Default)]
+pub struct AcceptedAddresses(Mutex<HashMap<Pubkey, u64>>);
+
+impl AcceptedAddresses {
+    /// Record `address` as accepted at `now`; false when it already was within the replay window
+    pub fn insert(&self, address: Pubkey, now: u64) -> bool {
+        let mut accepted = self.0.lock().unwrap();
+        // A submission stays valid until its timestamp is a window in the past, and it may be a window ahead
+        accepted.retain(|_, at| now.saturating_sub(*at) <= 2 * MAX_SUBMISSION_AGE_SECS);
+        if accepted.contains_key(&address) {
+            return false;
+        }
+        accepted.insert(address, now);
+        true
+    }
+}
+
+/// Validate a submission and add its keypair to `pool`
+pub fn accept_submission(pool: &VanityAddressPool, key: &FleetKey, accepted: &AcceptedAddresses, submission: &Submission) -> Result<()> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let keypair = key.open(submission, now)?;
+    let address = keypair.pubkey();
+    if !pool.pattern().matches(&address.to_string()) {
+        return Err(anyhow::anyhow!("{} does not match '{}'", address, pool.pattern()));
+    }
+    if !accepted.insert(address, now) {
+        return Err(anyhow::anyhow!("{} was already submitted", address));
+    }
+    let added = pool.add_generated_address(GeneratedVanityAddress {
+        keypair: SecureKeypair::new(keypair),
+        seed: format!("fleet_{}", submission.worker),
+        address,
+    });
+    if !added {
+        return Err(anyhow::anyhow!("{} was already submitted", address));
+    }
+    info!("Accepted vanity address {} from worker '{}'", address, submission.worker);
+    Ok(())
+}
+
+/// Accept submissions from remote grinders on `listen_addr`. Only `submit` and `status`
+/// are served over the network; keypairs never leave the pool this way.
+pub fn serve_submissions(listen_addr: &str, pool: Arc<VanityAddressPool>, key: Arc<FleetKey>) -> Result<()> {
+    let listener = TcpListener::bind(listen_addr)?;
+    info!("Accepting remote vanity submissions on {}", listen_addr);
+    let accepted = Arc::new(AcceptedAddresses::default());
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                warn!("Failed to accept fleet connection: {}", e);
+                continue;
+            }
+        };
+        let (pool, key, accepted) = (Arc::clone(&pool), Arc::clone(&key), Arc::clone(&accepted));
+        std::thread::spawn(move || {
+            let peer = stream.peer_addr().map(|a| a.to_string()).unwrap_or_default();
+            let _ = stream.set_read_timeout(Some(Duration::from_secs(30)));
+            let mut writer = match stream.try_clone() {
+                Ok(writer) => writer,
+                Err(e) => return warn!("Failed to clone fleet connection: {}", e),
+            };
+            let mut reader = BufReader::new(stream);
+            loop {
+                let mut line = String::new();
+                match reader.by_ref().take(MAX_REQUEST_BYTES).read_line(&mut line) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) if !line.ends_with('\n') && line.len() as u64 >= MAX_REQUEST_BYTES => {
+                        warn!("Closing fleet connection from {}: request larger than {} bytes", peer, MAX_REQUEST_BYTES);
+                        break;
+                    }
+                    Ok(_) => {}
+                }
+                let mut response = WorkerResponse::default();
+                match serde_json::from_str::<WorkerRequest>(&line) {
+                    Ok(WorkerRequest::Submit(submission)) => {
+                        if let Err(e) = accept_submission(&pool, &key, &accepted, &submission) {
+                            warn!("Rejected submission from {} ({}): {}", submission.worker, peer, e);
+                            response.error = Some(e.to_string());
+                        }
+                    }
+                    Ok(WorkerRequest::Status) => {}
+                    Ok(_) => response.error = Some("only submit and status are allowed over the network".to_string()),
+                    Err(e) => response.error = Some(format!("invalid request: {}", e)),
+                }
+                response.count = pool.generated_addresses_count();
+                response.generating = pool.is_generation_running();
+                let Ok(reply) = serde_json::to_string(&response) else { break };
+                if writeln!(writer, "{}", reply).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    Ok(())
+}
+
+fn fleet_request(central: &str, request: &WorkerRequest) -> Result<WorkerResponse> {
+    let mut stream = TcpStream::connect(central)?;
+    stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+    writeln!(stream, "{}", serde_json::to_string(request)?)?;
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+    Ok(serde_json::from_str(&line)?)
+}
+
+/// Grind on this machine and submit every match to the central pool at `central` (host:port).
+/// Pauses while the central pool is full.
+pub fn run_remote_grinder(central: &str, worker: &str, key: &FleetKey) -> Result<()> {
+    let num_threads = num_cpus::get();
+    // Must match the central pool's pattern, or every submission is rejected
+    let pattern = VanityPattern::from_env();
+    let target = PoolSizePolicy::from_env().target_for(&pattern);
+    let stats = StatsFile::from_env();
+    info!("Grinding '{}' addresses for {} as worker '{}' ({} threads)", pattern, central, worker, num_threads);
+    loop {
+        match fleet_request(central, &WorkerRequest::Status) {
+            Ok(status) if status.count >= target => {
+                info!("Central pool is full ({}); waiting", status.count);
+                std::thread::sleep(Duration::from_secs(30));
+                continue;
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Central pool at {} unreachable: {}", central, e),
+        }
+
+        let result = VanityAddressPool::find_vanity_address(&pattern, num_threads)?;
+        let address = result.keypair.pubkey();
+        info!("Found {} after {} attempts in {:?}", address, result.attempts, result.elapsed);
+        if let Err(e) = stats.record(&pattern.to_string(), result.attempts, result.elapsed, true) {
+            warn!("Failed to update vanity stats {}: {}", stats.path().display(), e);
+        }
+
+        // Keep the keypair until it is delivered; a lost match costs minutes of grinding.
+        // Reseal on every attempt so retries stay inside the timestamp window.
+        let mut delay = Duration::from_secs(5);
+        loop {
+            match fleet_request(central, &WorkerRequest::Submit(key.seal(worker, &result.keypair)?)) {
+                Ok(response) => {
+                    match response.error {
+                        Some(e) => error!("Central pool rejected {}: {}", address, e),
+                        None => info!("Submitted {} (central pool: {})", address, response.count),
+                    }
+                    break;
+                }
+                Err(e) => {
+                    warn!("Failed to submit {}: {}; retrying in {}s", address, e, delay.as_secs());
+                    std::thread::sleep(delay);
+                    delay = (delay * 2).min(Duration::from_secs(300));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open() {
+        let key = FleetKey::new(&"a5".repeat(32)).unwrap();
+        let keypair = Keypair::new();
+        let submission = key.seal("box-1", &keypair).unwrap();
+        let now = submission.timestamp;
+        assert_eq!(key.open(&submission, now).unwrap().pubkey(), keypair.pubkey());
+
+        assert!(key.open(&submission, now + MAX_SUBMISSION_AGE_SECS + 1).is_err());
+        assert!(FleetKey::new(&"5a".repeat(32)).unwrap().open(&submission, now).is_err());
+        let relabeled = Submission { worker: "box-2".to_string(), ..submission };
+        assert!(key.open(&relabeled, now).is_err());
+        assert!(FleetKey::new("correct horse battery staple").is_err());
+
+        // A replayed submission is refused even after its mint left the pool
+        let accepted = AcceptedAddresses::default();
+        assert!(accepted.insert(keypair.pubkey(), now));
+        assert!(!accepted.insert(keypair.pubkey(), now + MAX_SUBMISSION_AGE_SECS));
+        assert!(accepted.insert(Keypair::new().pubkey(), now + 3 * MAX_SUBMISSION_AGE_SECS));
+        assert!(accepted.insert(keypair.pubkey(), now + 3 * MAX_SUBMISSION_AGE_SECS));
+    }
+}
@@ -0,0 +1,145 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Stats location unless `VANITY_STATS_FILE` is set
+pub const DEFAULT_VANITY_STATS_FILE: &str = "data/vanity-stats.json";
+
+/// Cumulative grinding work for one pattern
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PatternStats {
+    pub attempts: u64,
+    pub found: u64,
+    pub grind_secs: f64,
+}
+
+impl PatternStats {
+    /// Average attempts paid per address, `None` before the first match
+    pub fn attempts_per_address(&self) -> Option<f64> {
+        (self.found > 0).then(|| self.attempts as f64 / self.found as f64)
+    }
+}
+
+/// Lifetime grinder statistics, keyed by pattern so a policy change does not skew the numbers
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct GrinderStats {
+    /// Unix time of the first recorded search
+    pub since: u64,
+    pub patterns: BTreeMap<String, PatternStats>,
+}
+
+impl GrinderStats {
+    /// Add one search, whether it found a match or was stopped
+    pub fn record(&mut self, pattern: &str, attempts: u64, elapsed: Duration, found: bool) {
+        if self.since == 0 {
+            self.since = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        }
+        let stats = self.patterns.entry(pattern.to_string()).or_default();
+        stats.attempts += attempts;
+        stats.found += found as u64;
+        stats.grind_secs += elapsed.as_secs_f64();
+    }
+
+    pub fn total(&self) -> PatternStats {
+        self.patterns.values().fold(PatternStats::default(), |total, stats| PatternStats {
+            attempts: total.attempts + stats.attempts,
+            found: total.found + stats.found,
+            grind_secs: total.grind_secs + stats.grind_secs,
+        })
+    }
+
+    /// Prometheus text exposition format, one series per pattern
+    pub fn to_exposition(&self) -> String {
+        let mut text = String::new();
+        let mut counter = |name: &str, help: &str, value: fn(&PatternStats) -> String| {
+            let _ = writeln!(text, "# HELP {} {}\n# TYPE {} counter", name, help, name);
+            for (pattern, stats) in &self.patterns {
+                let _ = writeln!(text, "{}{{pattern=\"{}\"}} {}", name, pattern.replace('\\', "\\\\").replace('"', "\\\""), value(stats));
+            }
+        };
+        counter("pumpfun_vanity_attempts_total", "Keypairs generated while grinding", |s| s.attempts.to_string());
+        counter("pumpfun_vanity_found_total", "Vanity addresses found", |s| s.found.to_string());
+        counter("pumpfun_vanity_grind_seconds_total", "Wall time spent grinding", |s| format!("{:.3}", s.grind_secs));
+        text
+    }
+}
+
+/// Grinder statistics kept on disk across restarts
+pub struct StatsFile {
+    path: PathBuf,
+    /// Serializes read-modify-write cycles within this process
+    lock: Mutex<()>,
+}
+
+impl StatsFile {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), lock: Mutex::new(()) }
+    }
+
+    /// Stats at `VANITY_STATS_FILE`
+    pub fn from_env() -> Self {
+        Self::new(std::env::var("VANITY_STATS_FILE").unwrap_or_else(|_| DEFAULT_VANITY_STATS_FILE.to_string()))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Empty stats when nothing was recorded yet
+    pub fn load(&self) -> Result<GrinderStats> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map_err(|e| anyhow::anyhow!("Corrupt vanity stats {}: {}", self.path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(GrinderStats::default()),
+            Err(e) => Err(anyhow::anyhow!("Cannot read vanity stats {}: {}", self.path.display(), e)),
+        }
+    }
+
+    /// Add one search to the file; re-reading first keeps counts from other processes on this machine
+    pub fn record(&self, pattern: &str, attempts: u64, elapsed: Duration, found: bool) -> Result<()> {
+        let _guard = self.lock.lock().unwrap();
+        let mut stats = self.load()?;
+        stats.record(pattern, attempts, elapsed, found);
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        // Replace the file in one rename, so a crash never leaves half-written counters
+        let tmp = self.path.with_extension("json.tmp");
+        let mut file = std::fs::File::create(&tmp)?;
+        file.write_all(serde_json::to_string_pretty(&stats)?.as_bytes())?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_accumulate_across_loads() {
+        let path = std::env::temp_dir().join(format!("vanity_stats_test_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let file = StatsFile::new(&path);
+        assert_eq!(file.load().unwrap(), GrinderStats::default());
+
+        file.record("pump", 1_000, Duration::from_secs(2), true).unwrap();
+        file.record("pump", 500, Duration::from_secs(1), false).unwrap();
+        StatsFile::new(&path).record("ab*", 300, Duration::from_millis(500), true).unwrap();
+
+        let stats = file.load().unwrap();
+        assert!(stats.since > 0);
+        assert_eq!(stats.patterns["pump"], PatternStats { attempts: 1_500, found: 1, grind_secs: 3.0 });
+        assert_eq!(stats.patterns["pump"].attempts_per_address(), Some(1_500.0));
+        assert_eq!(stats.total().found, 2);
+        assert_eq!(PatternStats::default().attempts_per_address(), None);
+        assert!(stats.to_exposition().contains("pumpfun_vanity_attempts_total{pattern=\"ab*\"} 300\n"));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
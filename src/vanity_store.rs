@@ -0,0 +1,157 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::keypair_cipher::{KeypairCipher, SealedKeypair};
+use crate::vanity_address::{GeneratedVanityAddress, SecureKeypair};
+
+/// Store location unless `VANITY_STORE_FILE` is set
+pub const DEFAULT_VANITY_STORE_FILE: &str = "data/vanity-store.json";
+
+/// One keypair, encrypted with its address as associated data so entries cannot be swapped
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct StoredAddress {
+    address: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Generated vanity keypairs kept on disk across restarts, each encrypted with ChaCha20-Poly1305
+pub struct VanityStore {
+    path: PathBuf,
+    cipher: KeypairCipher,
+    /// Serializes read-modify-write cycles within this process
+    lock: Mutex<()>,
+}
+
+impl VanityStore {
+    pub fn new(path: impl Into<PathBuf>, cipher: KeypairCipher) -> Self {
+        Self { path: path.into(), cipher, lock: Mutex::new(()) }
+    }
+
+    /// Store at `VANITY_STORE_FILE` keyed by `VANITY_STORE_SECRET`; `None` when no secret is set
+    pub fn from_env() -> Result<Option<Self>> {
+        let Ok(secret) = std::env::var("VANITY_STORE_SECRET") else {
+            return Ok(None);
+        };
+        let path = std::env::var("VANITY_STORE_FILE").unwrap_or_else(|_| DEFAULT_VANITY_STORE_FILE.to_string());
+        Ok(Some(Self::new(path, KeypairCipher::from_key("VANITY_STORE_SECRET", &secret)?)))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read(&self) -> Result<Vec<StoredAddress>> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map_err(|e| anyhow::anyhow!("Corrupt vanity store {}: {}", self.path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(anyhow::anyhow!("Cannot read vanity store {}: {}", self.path.display(), e)),
+        }
+    }
+
+    /// Replace the file in one rename, so a crash leaves either the old or the new contents
+    fn write(&self, entries: &[StoredAddress]) -> Result<()> {
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(&tmp)?;
+        file.write_all(serde_json::to_string_pretty(entries)?.as_bytes())?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+
+    fn seal(&self, keypair: &Keypair) -> Result<StoredAddress> {
+        let address = keypair.pubkey().to_string();
+        let sealed = self.cipher.seal(keypair, address.as_bytes())?;
+        Ok(StoredAddress { address, nonce: sealed.nonce, ciphertext: sealed.ciphertext })
+    }
+
+    fn open(&self, entry: &StoredAddress) -> Result<Keypair> {
+        let sealed = SealedKeypair { nonce: entry.nonce.clone(), ciphertext: entry.ciphertext.clone() };
+        let keypair = self.cipher.open(&sealed, entry.address.as_bytes())
+            .map_err(|e| anyhow::anyhow!("Stored keypair {}: {}", entry.address, e))?;
+        if keypair.pubkey().to_string() != entry.address {
+            return Err(anyhow::anyhow!("Stored keypair does not match address {}", entry.address));
+        }
+        Ok(keypair)
+    }
+
+    /// Every stored address; fails on the first entry that does not decrypt
+    pub fn load(&self) -> Result<Vec<GeneratedVanityAddress>> {
+        let _guard = self.lock.lock().unwrap();
+        self.read()?.iter().map(|entry| {
+            let keypair = self.open(entry)?;
+            Ok(GeneratedVanityAddress {
+                address: keypair.pubkey(),
+                seed: "stored".to_string(),
+                keypair: SecureKeypair::new(keypair),
+            })
+        }).collect()
+    }
+
+    /// Persist `keypair`; storing an address twice keeps one entry
+    pub fn add(&self, keypair: &Keypair) -> Result<()> {
+        let _guard = self.lock.lock().unwrap();
+        let mut entries = self.read()?;
+        let address = keypair.pubkey().to_string();
+        if entries.iter().any(|entry| entry.address == address) {
+            return Ok(());
+        }
+        entries.push(self.seal(keypair)?);
+        self.write(&entries)
+    }
+
+    /// Forget `address` once it has been handed to a launch
+    pub fn remove(&self, address: &Pubkey) -> Result<()> {
+        let _guard = self.lock.lock().unwrap();
+        let mut entries = self.read()?;
+        let address = address.to_string();
+        let before = entries.len();
+        entries.retain(|entry| entry.address != address);
+        if entries.len() != before {
+            self.write(&entries)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_round_trip() {
+        let path = std::env::temp_dir().join(format!("vanity_store_test_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let key = |byte: &str| KeypairCipher::from_key("VANITY_STORE_SECRET", &byte.repeat(32)).unwrap();
+        let store = VanityStore::new(&path, key("c3"));
+        assert!(store.load().unwrap().is_empty());
+
+        let (first, second) = (Keypair::new(), Keypair::new());
+        store.add(&first).unwrap();
+        store.add(&second).unwrap();
+        store.add(&first).unwrap();
+        let loaded: Vec<Pubkey> = store.load().unwrap().iter().map(|a| a.address).collect();
+        assert_eq!(loaded, vec![first.pubkey(), second.pubkey()]);
+        assert!(!std::fs::read_to_string(&path).unwrap().contains(&bs58::encode(first.to_bytes()).into_string()));
+
+        store.remove(&first.pubkey()).unwrap();
+        assert_eq!(store.load().unwrap()[0].keypair.pubkey(), second.pubkey());
+        assert!(VanityStore::new(&path, key("3c")).load().is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,250 @@
+use anyhow::Result;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{signature::Keypair, signer::Signer};
+use std::env;
+use std::io::{BufRead, BufReader, Write};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::vanity_address::{GeneratedVanityAddress, SecureKeypair, VanityAddressPool, VanityAvailability};
+use crate::vanity_fleet::{serve_submissions, FleetKey, Submission};
+
+/// Worker socket location unless `VANITY_WORKER_SOCKET` is set
+pub const DEFAULT_WORKER_SOCKET: &str = "data/vanity-worker.sock";
+const SPAWN_TIMEOUT_SECS: u64 = 10;
+/// Output of a worker spawned by the launcher
+const WORKER_LOG_PATH: &str = "data/vanity-worker.log";
+
+/// Request sent by the launcher to a vanity worker, one JSON object per line
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum WorkerRequest {
+    /// Pop one generated address from the worker's pool
+    Take,
+    Status,
+    /// Encrypted keypair from a remote grinder; only accepted on the fleet listener
+    Submit(Submission),
+}
+
+/// Worker reply, one JSON object per line
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct WorkerResponse {
+    /// Base58 64-byte keypair of the taken address, if one was available
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keypair: Option<String>,
+    pub count: usize,
+    pub generating: bool,
+    /// Expected milliseconds until the worker finds its next address, while generating
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eta_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+pub fn worker_socket_path() -> String {
+    env::var("VANITY_WORKER_SOCKET").unwrap_or_else(|_| DEFAULT_WORKER_SOCKET.to_string())
+}
+
+/// Answer one request against `pool`
+pub fn handle_request(pool: &VanityAddressPool, request: &WorkerRequest) -> WorkerResponse {
+    let keypair = match request {
+        WorkerRequest::Take => match pool.get_generated_vanity_address() {
+            VanityAvailability::Available(addr) => Some(bs58::encode(addr.keypair.keypair().to_bytes()).into_string()),
+            _ => None,
+        },
+        WorkerRequest::Status => None,
+        WorkerRequest::Submit(_) => return WorkerResponse {
+            error: Some("submissions are only accepted on the fleet listener".to_string()),
+            ..Default::default()
+        },
+    };
+    WorkerResponse {
+        keypair,
+        count: pool.generated_addresses_count(),
+        generating: pool.is_generation_running(),
+        eta_ms: pool.estimated_wait().map(|eta| eta.as_millis() as u64),
+        error: None,
+    }
+}
+
+/// Decode a keypair handed over by a worker into a pool entry
+pub fn decode_worker_keypair(encoded: &str) -> Result<GeneratedVanityAddress> {
+    let bytes = bs58::decode(encoded).into_vec()?;
+    let keypair = Keypair::try_from(&bytes[..])
+        .map_err(|e| anyhow::anyhow!("Worker sent an invalid keypair: {}", e))?;
+    let address = keypair.pubkey();
+    Ok(GeneratedVanityAddress {
+        keypair: SecureKeypair::new(keypair),
+        seed: "vanity_worker".to_string(),
+        address,
+    })
+}
+
+/// Grind vanity addresses in this process and serve them on `socket_path` until killed.
+/// With `fleet_listen`, remote grinders can also submit addresses over TCP.
+#[cfg(unix)]
+pub fn run_worker(socket_path: &str, fleet_listen: Option<&str>) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::Path;
+
+    if Path::new(socket_path).exists() {
+        if UnixStream::connect(socket_path).is_ok() {
+            return Err(anyhow::anyhow!("A vanity worker is already listening on {}", socket_path));
+        }
+        std::fs::remove_file(socket_path)?;
+    }
+    if let Some(parent) = Path::new(socket_path).parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let listener = UnixListener::bind(socket_path)?;
+    // Keypairs cross this socket; only our user may connect
+    std::fs::set_permissions(socket_path, std::fs::Permissions::from_mode(0o600))?;
+
+    let pool = Arc::new(VanityAddressPool::new());
+    pool.start_background_generation()?;
+    info!("Vanity worker listening on {}", socket_path);
+
+    if let Some(listen_addr) = fleet_listen {
+        let key = Arc::new(FleetKey::from_env()?);
+        let (pool, listen_addr) = (Arc::clone(&pool), listen_addr.to_string());
+        std::thread::spawn(move || {
+            if let Err(e) = serve_submissions(&listen_addr, pool, key) {
+                warn!("Fleet listener on {} stopped: {}", listen_addr, e);
+            }
+        });
+    }
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                warn!("Failed to accept worker connection: {}", e);
+                continue;
+            }
+        };
+        let pool = Arc::clone(&pool);
+        std::thread::spawn(move || {
+            let mut writer = match stream.try_clone() {
+                Ok(writer) => writer,
+                Err(e) => return warn!("Failed to clone worker connection: {}", e),
+            };
+            for line in BufReader::new(stream).lines() {
+                let Ok(line) = line else { break };
+                let response = match serde_json::from_str::<WorkerRequest>(&line) {
+                    Ok(request) => handle_request(&pool, &request),
+                    Err(e) => WorkerResponse { error: Some(format!("invalid request: {}", e)), ..Default::default() },
+                };
+                let Ok(reply) = serde_json::to_string(&response) else { break };
+                if writeln!(writer, "{}", reply).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn run_worker(_socket_path: &str, _fleet_listen: Option<&str>) -> Result<()> {
+    Err(anyhow::anyhow!("The vanity worker is only supported on unix"))
+}
+
+/// Connection details for a vanity worker process
+#[derive(Debug, Clone)]
+pub struct WorkerClient {
+    socket_path: String,
+}
+
+impl WorkerClient {
+    pub fn new(socket_path: impl Into<String>) -> Self {
+        Self { socket_path: socket_path.into() }
+    }
+
+    /// Client for the worker at `VANITY_WORKER_SOCKET`, spawning `vanity-worker` in the
+    /// background when none is running and `VANITY_WORKER_SPAWN` is not `false`
+    pub fn from_env() -> Result<Self> {
+        let client = Self::new(worker_socket_path());
+        if client.request(&WorkerRequest::Status).is_ok() {
+            return Ok(client);
+        }
+        if env::var("VANITY_WORKER_SPAWN").is_ok_and(|v| v.eq_ignore_ascii_case("false")) {
+            return Err(anyhow::anyhow!("No vanity worker listening on {}", client.socket_path));
+        }
+
+        info!("Spawning vanity worker on {} (log: {})", client.socket_path, WORKER_LOG_PATH);
+        let log = std::fs::OpenOptions::new().create(true).append(true).open(WORKER_LOG_PATH)?;
+        let mut command = std::process::Command::new(env::current_exe()?);
+        command.args(["vanity-worker", "--socket", &client.socket_path])
+            .stdin(std::process::Stdio::null())
+            .stdout(log.try_clone()?)
+            .stderr(log);
+        // Own process group, so stopping the launcher (e.g. Ctrl-C) leaves the worker grinding
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut command, 0);
+        command.spawn()?;
+        let started = Instant::now();
+        while client.request(&WorkerRequest::Status).is_err() {
+            if started.elapsed() > Duration::from_secs(SPAWN_TIMEOUT_SECS) {
+                return Err(anyhow::anyhow!("Vanity worker did not start listening on {}", client.socket_path));
+            }
+            std::thread::sleep(Duration::from_millis(200));
+        }
+        Ok(client)
+    }
+
+    #[cfg(unix)]
+    pub fn request(&self, request: &WorkerRequest) -> Result<WorkerResponse> {
+        let mut stream = std::os::unix::net::UnixStream::connect(&self.socket_path)?;
+        stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+        writeln!(stream, "{}", serde_json::to_string(request)?)?;
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line)?;
+        let response: WorkerResponse = serde_json::from_str(&line)?;
+        if let Some(error) = &response.error {
+            return Err(anyhow::anyhow!("Vanity worker error: {}", error));
+        }
+        Ok(response)
+    }
+
+    #[cfg(not(unix))]
+    pub fn request(&self, _request: &WorkerRequest) -> Result<WorkerResponse> {
+        Err(anyhow::anyhow!("The vanity worker is only supported on unix"))
+    }
+
+    /// Take one generated address from the worker, if it has any
+    pub fn take(&self) -> Result<VanityAvailability> {
+        let response = self.request(&WorkerRequest::Take)?;
+        Ok(match response.keypair {
+            Some(encoded) => VanityAvailability::Available(decode_worker_keypair(&encoded)?),
+            None if response.generating => VanityAvailability::GeneratingEta(Duration::from_millis(response.eta_ms.unwrap_or(0))),
+            None => VanityAvailability::Disabled,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protocol_round_trip() {
+        assert_eq!(serde_json::to_string(&WorkerRequest::Take).unwrap(), r#"{"op":"take"}"#);
+
+        let keypair = Keypair::new();
+        let response = WorkerResponse {
+            keypair: Some(bs58::encode(keypair.to_bytes()).into_string()),
+            count: 3,
+            generating: true,
+            eta_ms: None,
+            error: None,
+        };
+        let decoded: WorkerResponse = serde_json::from_str(&serde_json::to_string(&response).unwrap()).unwrap();
+        assert_eq!(decoded, response);
+        assert_eq!(decode_worker_keypair(decoded.keypair.as_deref().unwrap()).unwrap().address, keypair.pubkey());
+
+        let pool = VanityAddressPool::new();
+        assert_eq!(handle_request(&pool, &WorkerRequest::Take), WorkerResponse::default());
+    }
+}
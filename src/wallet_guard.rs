@@ -0,0 +1,105 @@
+use anyhow::Result;
+use log::{info, warn};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_response::{RpcConfirmedTransactionStatusWithSignature, TransactionConfirmationStatus};
+use solana_sdk::pubkey::Pubkey;
+use std::env;
+use std::time::{Duration, Instant};
+
+/// Recent payer signatures inspected for unsettled activity
+const GUARD_LOOKBACK: usize = 20;
+/// Seconds abort mode waits for unsettled transactions unless `WALLET_GUARD_WAIT_SECS` is set
+pub const DEFAULT_GUARD_WAIT_SECS: u64 = 30;
+
+/// What to do when the payer has transactions that have not settled yet, selected by `WALLET_ACTIVITY_GUARD`
+#[derive(Debug, Clone, PartialEq)]
+pub enum WalletGuard {
+    Off,
+    Warn,
+    /// Wait up to `wait` for the transactions to finalize, then refuse to launch
+    Abort { wait: Duration },
+}
+
+impl WalletGuard {
+    pub fn from_env() -> Result<Self> {
+        let mode = env::var("WALLET_ACTIVITY_GUARD").unwrap_or_else(|_| "off".to_string());
+        match mode.trim().to_lowercase().as_str() {
+            "off" | "" => Ok(Self::Off),
+            "warn" => Ok(Self::Warn),
+            "abort" => {
+                let secs = env::var("WALLET_GUARD_WAIT_SECS").ok()
+                    .and_then(|v| v.trim().parse().ok())
+                    .unwrap_or(DEFAULT_GUARD_WAIT_SECS);
+                Ok(Self::Abort { wait: Duration::from_secs(secs) })
+            }
+            other => Err(anyhow::anyhow!("Unknown WALLET_ACTIVITY_GUARD '{}' (expected off, warn or abort)", other)),
+        }
+    }
+
+    /// Check `wallet` for transactions that could still change its balance before the launch lands
+    pub async fn check(&self, rpc: &RpcClient, wallet: &Pubkey) -> Result<()> {
+        let wait = match self {
+            Self::Off => return Ok(()),
+            Self::Warn => Duration::ZERO,
+            Self::Abort { wait } => *wait,
+        };
+        let started = Instant::now();
+        loop {
+            let history = rpc.get_signatures_for_address_with_config(wallet, GetConfirmedSignaturesForAddress2Config {
+                limit: Some(GUARD_LOOKBACK),
+                ..Default::default()
+            }).await?;
+            let pending = unsettled_signatures(&history);
+            if pending.is_empty() {
+                return Ok(());
+            }
+
+            let summary = format!("{} has {} unsettled transaction(s): {}", wallet, pending.len(), pending.join(", "));
+            match self {
+                Self::Warn => {
+                    warn!("{}", summary);
+                    return Ok(());
+                }
+                _ if started.elapsed() >= wait => {
+                    return Err(anyhow::anyhow!("{}; refusing to launch while the wallet is in use (WALLET_ACTIVITY_GUARD=abort)", summary));
+                }
+                _ => {
+                    info!("{}; waiting for them to finalize", summary);
+                    tokio::time::sleep(Duration::from_secs(2)).await;
+                }
+            }
+        }
+    }
+}
+
+/// Signatures that are not finalized yet (successful or not), newest first
+pub fn unsettled_signatures(history: &[RpcConfirmedTransactionStatusWithSignature]) -> Vec<&str> {
+    history.iter()
+        .filter(|s| s.confirmation_status != Some(TransactionConfirmationStatus::Finalized))
+        .map(|s| s.signature.as_str())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsettled_signatures() {
+        let entry = |signature: &str, status| RpcConfirmedTransactionStatusWithSignature {
+            signature: signature.to_string(),
+            slot: 1,
+            err: None,
+            memo: None,
+            block_time: None,
+            confirmation_status: status,
+        };
+        let history = vec![
+            entry("pending", Some(TransactionConfirmationStatus::Confirmed)),
+            entry("done", Some(TransactionConfirmationStatus::Finalized)),
+            entry("unknown", None),
+        ];
+        assert_eq!(unsettled_signatures(&history), vec!["pending", "unknown"]);
+    }
+}
@@ -0,0 +1,209 @@
+use anyhow::Result;
+use log::warn;
+use serde::Serialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::env;
+use std::fmt::Write as _;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::buyers::{fetch_transaction_json, transaction_account_keys};
+use crate::watcher::DEFAULT_FRONTEND_API_URL;
+
+const SIGNATURE_PAGE_LIMIT: usize = 1_000;
+const CREATIONS_PAGE_SIZE: usize = 50;
+/// Recent transactions whose post-balances make up the balance history
+pub const BALANCE_SAMPLES: usize = 10;
+/// Newest transactions listed as recent activity
+pub const RECENT_ACTIVITY: usize = 10;
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+/// The wallet's SOL balance after one of its transactions
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BalancePoint {
+    pub slot: u64,
+    /// Unix seconds, when the RPC node knows the block time
+    pub timestamp: Option<i64>,
+    pub sol: f64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Activity {
+    pub signature: String,
+    pub slot: u64,
+    pub timestamp: Option<i64>,
+    pub success: bool,
+}
+
+/// What a wallet's on-chain history says about its credibility as a creator
+#[derive(Debug, Clone, Serialize)]
+pub struct WalletReport {
+    pub wallet: String,
+    pub balance_sol: f64,
+    /// Transactions seen, up to the signature limit
+    pub transaction_count: usize,
+    /// False when the signature limit was hit, so the wallet is older and busier than reported
+    pub history_complete: bool,
+    pub first_activity: Option<i64>,
+    pub age_days: Option<f64>,
+    pub transactions_last_7d: usize,
+    /// Coins created on pump.fun, when the frontend API answers
+    pub pump_creations: Option<usize>,
+    /// Oldest first
+    pub balance_history: Vec<BalancePoint>,
+    /// Newest first
+    pub recent_activity: Vec<Activity>,
+}
+
+/// Up to `max_signatures` signatures for `wallet`, newest first, and whether that is the full history
+pub async fn fetch_recent_signatures(rpc: &RpcClient, wallet: &Pubkey, max_signatures: usize) -> Result<(Vec<RpcConfirmedTransactionStatusWithSignature>, bool)> {
+    let mut signatures = Vec::new();
+    let mut before = None;
+    loop {
+        let page = rpc.get_signatures_for_address_with_config(wallet, GetConfirmedSignaturesForAddress2Config {
+            before,
+            limit: Some(SIGNATURE_PAGE_LIMIT),
+            ..Default::default()
+        }).await?;
+        let done = page.len() < SIGNATURE_PAGE_LIMIT;
+        before = page.last().map(|s| Signature::from_str(&s.signature)).transpose()?;
+        signatures.extend(page);
+        if done {
+            return Ok((signatures, true));
+        }
+        if signatures.len() >= max_signatures {
+            signatures.truncate(max_signatures);
+            return Ok((signatures, false));
+        }
+    }
+}
+
+/// Number of coins `wallet` created on pump.fun, from the frontend API
+pub async fn count_pump_creations(wallet: &Pubkey) -> Result<usize> {
+    let api_url = env::var("PUMP_FRONTEND_API_URL").unwrap_or_else(|_| DEFAULT_FRONTEND_API_URL.to_string());
+    let client = reqwest::Client::builder().timeout(Duration::from_secs(10)).build()?;
+    let mut count = 0;
+    loop {
+        let page: serde_json::Value = client
+            .get(format!("{}/coins/user-created-coins/{}?offset={}&limit={}&includeNsfw=true",
+                         api_url, wallet, count, CREATIONS_PAGE_SIZE))
+            .send().await?.error_for_status()?.json().await?;
+        // Older API versions return a bare array, newer ones wrap it
+        let fetched = page.as_array().or_else(|| page["coins"].as_array()).map(Vec::len).unwrap_or(0);
+        count += fetched;
+        if fetched < CREATIONS_PAGE_SIZE {
+            return Ok(count);
+        }
+    }
+}
+
+/// `wallet`'s balance after a json-encoded transaction
+pub fn balance_point(tx: &serde_json::Value, wallet: &str) -> Option<BalancePoint> {
+    let index = transaction_account_keys(tx).iter().position(|k| k == wallet)?;
+    Some(BalancePoint {
+        slot: tx["slot"].as_u64()?,
+        timestamp: tx["blockTime"].as_i64(),
+        sol: tx["meta"]["postBalances"][index].as_u64()? as f64 / LAMPORTS_PER_SOL,
+    })
+}
+
+/// Build the report for `wallet`, reading at most `max_signatures` transactions
+pub async fn build_report(rpc: &RpcClient, wallet: &Pubkey, max_signatures: usize) -> Result<WalletReport> {
+    let balance_sol = rpc.get_balance(wallet).await? as f64 / LAMPORTS_PER_SOL;
+    let (signatures, history_complete) = fetch_recent_signatures(rpc, wallet, max_signatures).await?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+
+    // Only the oldest signature dates the wallet, and only when the whole history was read
+    let first_activity = signatures.last().and_then(|s| s.block_time).filter(|_| history_complete);
+    let transactions_last_7d = signatures.iter()
+        .filter(|s| s.block_time.is_some_and(|t| now - t <= 7 * 86_400))
+        .count();
+
+    let mut balance_history = Vec::new();
+    for status in signatures.iter().take(BALANCE_SAMPLES) {
+        match fetch_transaction_json(rpc, &status.signature).await {
+            Ok(tx) => balance_history.extend(balance_point(&tx, &wallet.to_string())),
+            Err(e) => warn!("Skipping transaction {} in balance history: {}", status.signature, e),
+        }
+    }
+    balance_history.reverse();
+
+    let pump_creations = match count_pump_creations(wallet).await {
+        Ok(count) => Some(count),
+        Err(e) => {
+            warn!("Could not count pump.fun creations of {}: {}", wallet, e);
+            None
+        }
+    };
+
+    Ok(WalletReport {
+        wallet: wallet.to_string(),
+        balance_sol,
+        transaction_count: signatures.len(),
+        history_complete,
+        first_activity,
+        age_days: first_activity.map(|first| (now - first).max(0) as f64 / 86_400.0),
+        transactions_last_7d,
+        pump_creations,
+        balance_history,
+        recent_activity: signatures.iter().take(RECENT_ACTIVITY).map(|s| Activity {
+            signature: s.signature.clone(),
+            slot: s.slot,
+            timestamp: s.block_time,
+            success: s.err.is_none(),
+        }).collect(),
+    })
+}
+
+impl WalletReport {
+    pub fn to_text(&self) -> String {
+        let mut text = format!("Wallet {}\n", self.wallet);
+        let _ = writeln!(text, "  Balance:           {:.4} SOL", self.balance_sol);
+        let _ = match (self.age_days, self.history_complete) {
+            (Some(days), _) => writeln!(text, "  Age:               {:.0} days", days),
+            (None, false) => writeln!(text, "  Age:               older than the last {} transactions", self.transaction_count),
+            (None, true) => writeln!(text, "  Age:               no activity"),
+        };
+        let more = if self.history_complete { "" } else { "+" };
+        let _ = writeln!(text, "  Transactions:      {}{} ({} in the last 7 days)", self.transaction_count, more, self.transactions_last_7d);
+        let _ = match self.pump_creations {
+            Some(count) => writeln!(text, "  pump.fun creations: {}", count),
+            None => writeln!(text, "  pump.fun creations: unknown"),
+        };
+        if !self.balance_history.is_empty() {
+            let _ = writeln!(text, "  Balance history:");
+            for point in &self.balance_history {
+                let _ = writeln!(text, "    slot {:<12} {:>12.4} SOL", point.slot, point.sol);
+            }
+        }
+        if !self.recent_activity.is_empty() {
+            let _ = writeln!(text, "  Recent activity:");
+            for activity in &self.recent_activity {
+                let status = if activity.success { "ok" } else { "failed" };
+                let _ = writeln!(text, "    slot {:<12} {:<6} {}", activity.slot, status, activity.signature);
+            }
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_balance_point() {
+        let wallet = Pubkey::new_unique().to_string();
+        let tx = serde_json::json!({
+            "slot": 42,
+            "blockTime": 1_700_000_000,
+            "transaction": { "message": { "accountKeys": ["payer", wallet] } },
+            "meta": { "preBalances": [5_000_000_000u64, 0], "postBalances": [3_499_995_000u64, 1_500_000_000u64] }
+        });
+        assert_eq!(balance_point(&tx, &wallet), Some(BalancePoint { slot: 42, timestamp: Some(1_700_000_000), sol: 1.5 }));
+        assert_eq!(balance_point(&tx, "missing"), None);
+    }
+}
@@ -0,0 +1,152 @@
+use solana_sdk::signature::Keypair;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use zeroize::Zeroize;
+
+use crate::config_check::{ConfigError, ConfigSource, PRIVATE_KEY_HINT};
+use crate::secure_credentials::SecurePrivateKey;
+
+/// Payer selected with `--wallet`: a registered name or a keypair file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WalletSpec {
+    /// Private key registered as `WALLET_<NAME>` in the environment or secrets backend
+    Named(String),
+    /// solana-keygen JSON keypair file, or a file holding the base58 private key
+    File(PathBuf),
+}
+
+impl FromStr for WalletSpec {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(anyhow::anyhow!("Wallet must be a registered name or a keypair file path"));
+        }
+        if s.contains(['/', '\\']) || s.ends_with(".json") || Path::new(s).is_file() {
+            Ok(Self::File(PathBuf::from(s)))
+        } else {
+            Ok(Self::Named(s.to_string()))
+        }
+    }
+}
+
+impl WalletSpec {
+    /// Environment variable holding a named wallet's private key
+    pub fn env_var(name: &str) -> String {
+        format!("WALLET_{}", name.to_uppercase().replace('-', "_"))
+    }
+
+    pub fn load(&self) -> Result<Keypair, ConfigError> {
+        match self {
+            Self::Named(name) => {
+                let var_name = Self::env_var(name);
+                let key = SecurePrivateKey::load(&var_name)
+                    .map_err(|_| ConfigError::missing(&var_name, &format!("register wallet '{}' by setting {}", name, var_name)))?;
+                let bytes = key.to_bytes().map_err(|e| ConfigError::invalid(&var_name, e))?;
+                Keypair::try_from(&bytes[..])
+                    .map_err(|_| ConfigError::invalid(&var_name, format!("expected a 64-byte keypair, got {} bytes", bytes.len())))
+            }
+            Self::File(path) => read_keypair_file(path).map_err(|reason| ConfigError::Invalid {
+                key: "wallet".to_string(),
+                source: ConfigSource::Flag("--wallet".to_string()),
+                reason: format!("{}: {}", path.display(), reason),
+            }),
+        }
+    }
+}
+
+/// Keypair of `wallet`, or of `PRIVATE_KEY` when `None`
+pub fn load_wallet(wallet: Option<&WalletSpec>) -> Result<Keypair, ConfigError> {
+    if let Some(wallet) = wallet {
+        return wallet.load();
+    }
+    let key = SecurePrivateKey::load("PRIVATE_KEY")
+        .map_err(|_| ConfigError::missing("PRIVATE_KEY", PRIVATE_KEY_HINT))?;
+    let bytes = key.to_bytes().map_err(|e| ConfigError::invalid("PRIVATE_KEY", e))?;
+    Keypair::try_from(&bytes[..])
+        .map_err(|_| ConfigError::invalid("PRIVATE_KEY", format!("expected a 64-byte keypair, got {} bytes", bytes.len())))
+}
+
+/// Directory `new-wallet` writes keypair files to unless `WALLETS_DIR` is set
+pub const DEFAULT_WALLETS_DIR: &str = "data/wallets";
+
+/// Generate a keypair and save it as `<dir>/<name>.json` (solana-keygen format, owner-only),
+/// never overwriting an existing file
+pub fn create_wallet_file(dir: &Path, name: &str) -> anyhow::Result<(Keypair, PathBuf)> {
+    let keypair = Keypair::new();
+    let path = write_keypair_file(dir, name, &keypair)?;
+    Ok((keypair, path))
+}
+
+/// Save `keypair` as `<dir>/<name>.json` like [`create_wallet_file`]
+pub fn write_keypair_file(dir: &Path, name: &str, keypair: &Keypair) -> anyhow::Result<PathBuf> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(anyhow::anyhow!("Wallet name '{}' may only contain letters, digits, '-' and '_'", name));
+    }
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{}.json", name));
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&path)
+        .map_err(|e| anyhow::anyhow!("Cannot create {}: {}", path.display(), e))?;
+
+    let mut contents = serde_json::to_string(&keypair.to_bytes().to_vec())?;
+    let written = file.write_all(contents.as_bytes()).and_then(|_| file.sync_all());
+    contents.zeroize();
+    written?;
+    Ok(path)
+}
+
+/// Read a keypair file, zeroing the file contents once decoded
+fn read_keypair_file(path: &Path) -> Result<Keypair, String> {
+    let mut contents = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let trimmed = contents.trim();
+    let decoded = if trimmed.starts_with('[') {
+        serde_json::from_str::<Vec<u8>>(trimmed).map_err(|e| format!("invalid keypair JSON: {}", e))
+    } else {
+        bs58::decode(trimmed).into_vec().map_err(|e| format!("invalid base58 private key: {}", e))
+    };
+    contents.zeroize();
+    let mut bytes = decoded?;
+    let keypair = Keypair::try_from(&bytes[..])
+        .map_err(|_| format!("expected a 64-byte keypair, got {} bytes", bytes.len()));
+    bytes.zeroize();
+    keypair
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signer::Signer;
+
+    #[test]
+    fn test_wallet_spec_and_keypair_file() {
+        assert_eq!("treasury".parse::<WalletSpec>().unwrap(), WalletSpec::Named("treasury".to_string()));
+        assert_eq!("keys/dev.json".parse::<WalletSpec>().unwrap(), WalletSpec::File(PathBuf::from("keys/dev.json")));
+        assert_eq!(WalletSpec::env_var("alt-creator"), "WALLET_ALT_CREATOR");
+
+        let keypair = Keypair::new();
+        let path = std::env::temp_dir().join(format!("wallet_test_{}.json", std::process::id()));
+        std::fs::write(&path, serde_json::to_string(&keypair.to_bytes().to_vec()).unwrap()).unwrap();
+        let loaded = WalletSpec::File(path.clone()).load().unwrap();
+        assert_eq!(loaded.pubkey(), keypair.pubkey());
+
+        std::fs::write(&path, "[1, 2, 3]").unwrap();
+        assert!(WalletSpec::File(path.clone()).load().is_err());
+        std::fs::remove_file(&path).ok();
+
+        let dir = std::env::temp_dir().join(format!("wallet_dir_test_{}", std::process::id()));
+        let (created, created_path) = create_wallet_file(&dir, "fresh").unwrap();
+        assert_eq!(WalletSpec::File(created_path).load().unwrap().pubkey(), created.pubkey());
+        assert!(create_wallet_file(&dir, "fresh").is_err());
+        assert!(create_wallet_file(&dir, "../escape").is_err());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,114 @@
+use anyhow::Result;
+use log::{info, warn};
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::io::{BufRead, Write};
+use std::sync::{Arc, Mutex};
+
+/// How much a warning should worry the operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningSeverity {
+    /// Worth knowing, nothing to act on
+    Info,
+    /// Probably unintended; review before sending
+    Warning,
+    /// Likely to cost money or reputation if sent as is
+    Critical,
+}
+
+impl WarningSeverity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Critical => "CRITICAL",
+        }
+    }
+}
+
+/// One finding from the launch pipeline
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LaunchWarning {
+    pub severity: WarningSeverity,
+    /// Stable identifier for tooling, e.g. `image_large`
+    pub code: String,
+    pub message: String,
+}
+
+/// Warnings collected across a launch; clones share the same list
+#[derive(Debug, Clone, Default)]
+pub struct LaunchWarnings(Arc<Mutex<Vec<LaunchWarning>>>);
+
+impl LaunchWarnings {
+    /// Record a warning and log it right away
+    pub fn push(&self, severity: WarningSeverity, code: &str, message: impl Into<String>) {
+        let message = message.into();
+        match severity {
+            WarningSeverity::Info => info!("{}", message),
+            _ => warn!("{}", message),
+        }
+        let mut warnings = self.0.lock().unwrap();
+        // The same finding from a retried step is only reported once
+        let warning = LaunchWarning { severity, code: code.to_string(), message };
+        if !warnings.contains(&warning) {
+            warnings.push(warning);
+        }
+    }
+
+    /// All warnings, most severe first
+    pub fn list(&self) -> Vec<LaunchWarning> {
+        let mut warnings = self.0.lock().unwrap().clone();
+        warnings.sort_by_key(|warning| Reverse(warning.severity));
+        warnings
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.lock().unwrap().is_empty()
+    }
+
+    /// One line per warning, most severe first
+    pub fn summary(&self) -> String {
+        self.list().iter()
+            .map(|w| format!("[{}] {}: {}", w.severity.label(), w.code, w.message))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Show the collected warnings and ask on stdin whether to send the launch
+pub fn confirm_launch(warnings: &LaunchWarnings, description: &str) -> Result<()> {
+    let mut stderr = std::io::stderr().lock();
+    if warnings.is_empty() {
+        writeln!(stderr, "No warnings.")?;
+    } else {
+        writeln!(stderr, "Warnings:\n{}", warnings.summary())?;
+    }
+    write!(stderr, "Send {}? [y/N] ", description)?;
+    stderr.flush()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    if matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!("Launch cancelled at the confirmation prompt"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launch_warnings() {
+        let warnings = LaunchWarnings::default();
+        let shared = warnings.clone();
+        shared.push(WarningSeverity::Info, "skip_preflight", "not checked");
+        shared.push(WarningSeverity::Critical, "priority_fee_high", "10 SOL fee");
+        shared.push(WarningSeverity::Critical, "priority_fee_high", "10 SOL fee");
+        let list = warnings.list();
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].code, "priority_fee_high");
+        assert_eq!(warnings.summary(), "[CRITICAL] priority_fee_high: 10 SOL fee\n[info] skip_preflight: not checked");
+    }
+}
@@ -0,0 +1,127 @@
+use anyhow::Result;
+use log::{info, warn};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::notifications::{Notification, Notifier};
+
+/// pump.fun frontend API unless `PUMP_FRONTEND_API_URL` is set
+pub const DEFAULT_FRONTEND_API_URL: &str = "https://frontend-api-v3.pump.fun";
+const KING_OF_THE_HILL_PATH: &str = "/coins/king-of-the-hill?includeNsfw=true";
+/// Listing treated as "trending" unless `PUMP_TRENDING_PATH` is set
+const DEFAULT_TRENDING_PATH: &str = "/coins?offset=0&limit=50&sort=market_cap&order=DESC&includeNsfw=true";
+
+/// Where a watched mint currently ranks on pump.fun
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MintStatus {
+    pub king_of_the_hill: bool,
+    pub trending: bool,
+}
+
+/// Polls pump.fun's KOTH and trending listings and notifies when watched mints enter or leave them
+pub struct Watcher {
+    mints: Vec<Pubkey>,
+    notifier: Notifier,
+    api_url: String,
+    trending_path: String,
+    interval: Duration,
+}
+
+impl Watcher {
+    pub fn new(mints: Vec<Pubkey>, notifier: Notifier, interval: Duration) -> Self {
+        Self {
+            mints,
+            notifier,
+            api_url: env::var("PUMP_FRONTEND_API_URL").unwrap_or_else(|_| DEFAULT_FRONTEND_API_URL.to_string()),
+            trending_path: env::var("PUMP_TRENDING_PATH").unwrap_or_else(|_| DEFAULT_TRENDING_PATH.to_string()),
+            interval,
+        }
+    }
+
+    /// Current KOTH mint and trending set
+    async fn poll(&self, client: &reqwest::Client) -> Result<(Option<Pubkey>, HashSet<Pubkey>)> {
+        let koth: serde_json::Value = client.get(format!("{}{}", self.api_url, KING_OF_THE_HILL_PATH))
+            .send().await?.error_for_status()?.json().await?;
+        let king = koth["mint"].as_str().and_then(|m| Pubkey::from_str(m).ok());
+
+        let trending: serde_json::Value = client.get(format!("{}{}", self.api_url, self.trending_path))
+            .send().await?.error_for_status()?.json().await?;
+        let trending = trending.as_array()
+            .map(|coins| coins.iter().filter_map(|c| c["mint"].as_str().and_then(|m| Pubkey::from_str(m).ok())).collect())
+            .unwrap_or_default();
+
+        Ok((king, trending))
+    }
+
+    /// Poll until `duration` elapses (forever when `None`)
+    pub async fn run(&self, duration: Option<Duration>) -> Result<()> {
+        let client = reqwest::Client::builder().timeout(Duration::from_secs(10)).build()?;
+        let started = std::time::Instant::now();
+        let mut statuses: HashMap<Pubkey, MintStatus> = HashMap::new();
+        info!("Watching {} mint(s) for KOTH/trending every {}s", self.mints.len(), self.interval.as_secs());
+
+        loop {
+            match self.poll(&client).await {
+                Ok((king, trending)) => {
+                    for mint in &self.mints {
+                        let now = MintStatus {
+                            king_of_the_hill: king == Some(*mint),
+                            trending: trending.contains(mint),
+                        };
+                        let before = statuses.insert(*mint, now).unwrap_or_default();
+                        for notification in status_changes(mint, before, now) {
+                            self.notifier.notify(notification).await;
+                        }
+                    }
+                }
+                Err(e) => warn!("Failed to poll pump.fun listings: {}", e),
+            }
+
+            if duration.is_some_and(|d| started.elapsed() >= d) {
+                return Ok(());
+            }
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+}
+
+/// Notifications for transitions between two statuses of `mint`
+pub fn status_changes(mint: &Pubkey, before: MintStatus, now: MintStatus) -> Vec<Notification> {
+    let mut notifications = Vec::new();
+    let mut push = |kind: &str, message: String| {
+        notifications.push(Notification::new(kind, Some(mint.to_string()), message));
+    };
+    match (before.king_of_the_hill, now.king_of_the_hill) {
+        (false, true) => push("king_of_the_hill", format!("{} is now King of the Hill", mint)),
+        (true, false) => push("king_of_the_hill_lost", format!("{} lost King of the Hill", mint)),
+        _ => {}
+    }
+    match (before.trending, now.trending) {
+        (false, true) => push("trending", format!("{} is trending on pump.fun", mint)),
+        (true, false) => push("trending_lost", format!("{} dropped off trending", mint)),
+        _ => {}
+    }
+    notifications
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_changes() {
+        let mint = Pubkey::new_unique();
+        let koth = MintStatus { king_of_the_hill: true, trending: true };
+        let kinds = |v: Vec<Notification>| v.into_iter().map(|n| n.kind).collect::<Vec<_>>();
+
+        assert_eq!(kinds(status_changes(&mint, MintStatus::default(), koth)), vec!["king_of_the_hill", "trending"]);
+        assert!(status_changes(&mint, koth, koth).is_empty());
+        assert_eq!(
+            kinds(status_changes(&mint, koth, MintStatus { king_of_the_hill: false, trending: true })),
+            vec!["king_of_the_hill_lost"]
+        );
+    }
+}